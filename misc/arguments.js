@@ -0,0 +1,61 @@
+// `arguments` has every argument, including the ones without a parameter
+function count() {
+    return arguments.length;
+}
+console.log(count(), count(1), count(1, "two", 3));
+
+function sum() {
+    let total = 0;
+    for (let i = 0; i < arguments.length; i++) {
+        total += arguments[i];
+    }
+    return total;
+}
+console.log(sum(1, 2, 3, 4));
+
+// The named parameters are still bound, and missing ones are undefined
+function describe(first, second) {
+    console.log(first, second, arguments.length, arguments[2]);
+}
+describe("a");
+describe("a", "b", "c");
+
+// Arrow functions use the `arguments` of the function around them
+function outer(a, b) {
+    const inner = () => arguments[0] * 10 + arguments[1];
+    return inner(7, 8);
+}
+console.log(outer(1, 2));
+
+const expression = function () {
+    return arguments[1];
+};
+console.log(expression("x", "y"));
+
+class Counter {
+    constructor() {
+        this.count = arguments.length;
+    }
+    add() {
+        this.count += arguments.length;
+        return this;
+    }
+}
+console.log(new Counter(1, 2).add(3, 4, 5).count);
+
+function* each() {
+    for (let i = 0; i < arguments.length; i++) {
+        yield arguments[i];
+    }
+}
+for (const item of each("p", "q")) {
+    console.log(item);
+}
+
+// Only the arguments are enumerable, not the length
+const all = (function () {
+    return arguments;
+})(1, 2);
+for (const key in all) {
+    console.log(key, all[key]);
+}
@@ -0,0 +1,57 @@
+// Arrays are printed as strings, like `[1,2]`
+const show = (array) => "[" + array + "]";
+
+const numbers = [1, 2, 3, 4, 5, 6];
+console.log(show(numbers.map((n) => n * n)));
+console.log(show(numbers.filter((n) => n % 2 === 0)));
+console.log(numbers.reduce((sum, n) => sum + n, 0), numbers.reduce((a, b) => a * b));
+console.log(numbers.some((n) => n > 5), numbers.some((n) => n > 6));
+console.log(numbers.every((n) => n > 0), numbers.every((n) => n < 6));
+console.log(numbers.find((n) => n > 3), numbers.find((n) => n > 10));
+
+// The callbacks get the index and the array too
+console.log(show(numbers.map((n, i) => n * i)));
+console.log(show(["a", "b", "c"].map((letter, i, array) => letter + i + array.length)));
+console.log(["x", "y"].reduce((text, letter, i) => text + i + letter, ">"));
+
+const seen = [];
+numbers.forEach(function (n, i) {
+    seen.push(i + ":" + n);
+});
+console.log(show(seen), numbers.forEach((n) => n) === undefined);
+
+// `this` for the callback
+const scaler = {
+    factor: 10,
+    scale(values) {
+        return values.map(function (value) {
+            return value * this.factor;
+        }, this);
+    },
+};
+console.log(show(scaler.scale([1, 2, 3])));
+
+// Holes are skipped, except by `find`
+const sparse = [1, 2, 3];
+delete sparse[1];
+console.log(sparse.map((n) => n * 2).length, 1 in sparse.map((n) => n * 2));
+console.log(sparse.filter(() => true).length, sparse.find((n) => n === undefined));
+
+// Chaining
+const words = ["apple", "kiwi", "banana", "fig", "cherry"];
+const result = words
+    .filter((word) => word.length > 3)
+    .map((word) => word.toUpperCase())
+    .reduce((joined, word) => joined + word.charAt(0), "");
+console.log(result);
+
+class Item {
+    constructor(name, price) {
+        this.name = name;
+        this.price = price;
+    }
+}
+const items = [new Item("pen", 2), new Item("book", 12), new Item("bag", 30)];
+console.log(items.reduce((total, item) => total + item.price, 0));
+console.log(items.find((item) => item.price > 10).name);
+console.log(items.every((item) => item instanceof Item));
@@ -0,0 +1,28 @@
+function undefinedless() {
+    return null;
+}
+
+const values = [1, 2, 3, 4, 5];
+const [a, , b, ...rest] = values;
+console.log(a, b, rest.length, rest[0], rest[1]);
+
+const [first = 10, second = 20, third = 30, , , sixth = 60] = [undefinedless(), 2];
+console.log(first, second, third, sixth);
+
+let [x, [y, z = 9], { w }] = [1, [2], { w: 3 }];
+x += y;
+console.log(x, y, z, w);
+
+const [...copy] = values;
+copy[0] = 100;
+console.log(copy[0], values[0], copy.length);
+
+const [head, ...[next, after]] = values;
+console.log(head, next, after);
+
+function swap(pair) {
+    const [left, right] = pair;
+    return [right, left];
+}
+const [p, q] = swap([7, 8]);
+console.log(p, q);
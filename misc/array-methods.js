@@ -0,0 +1,38 @@
+const values = [1, "two", null, undefined, 3.5, [4, 5], true];
+console.log(values.join(), values.join(" | "), values.join(""));
+console.log([].join("-") === "", [null].join() === "", ["solo"].join("-"));
+
+// `slice` doesn't change the array, negative positions count from the end
+const letters = ["a", "b", "c", "d", "e"];
+console.log(letters.slice(1, 3).join(), letters.slice(-2).join(), letters.slice().join());
+console.log(letters.slice(3, 1).length, letters.slice(-100, 2).join(), letters.length);
+
+// `concat` spreads arrays, but not the arrays inside them
+const joined = [1, 2].concat(3, [4, 5], [[6, 7]]);
+console.log(joined.length, joined.join(";"), joined[5].length);
+
+console.log(letters.indexOf("c"), letters.indexOf("z"), letters.indexOf("a", 1));
+console.log(letters.indexOf("e", -1), [0 / 0].indexOf(0 / 0), [1, "1"].indexOf("1"));
+console.log(letters.includes("d"), letters.includes("d", 4), [0 / 0].includes(0 / 0));
+
+const reversed = [1, 2, 3].reverse();
+console.log(reversed.join(), reversed.reverse() === reversed, reversed.join());
+
+console.log([0, 0, 0, 0].fill(7).join());
+console.log([1, 2, 3, 4, 5].fill(0, 1, 3).join(), [1, 2, 3, 4].fill(9, -2).join());
+
+// Holes are empty strings when joined, and are kept by `slice` and `concat`
+const sparse = [1, 2, 3];
+delete sparse[1];
+console.log(sparse.join("-"), 1 in sparse.slice(), 1 in sparse.concat([]));
+console.log(sparse.includes(undefined), sparse.indexOf(undefined));
+
+// Formatting a table
+const rows = [
+    ["name", "qty"],
+    ["apple", 3],
+    ["pear", 12],
+];
+for (const row of rows) {
+    console.log(row.map((cell) => ("" + cell).padStart(6)).join(" |"));
+}
@@ -0,0 +1,44 @@
+// Arrays are printed as strings, like `[1,2]`
+const show = (array) => "[" + array + "]";
+
+const list = [1, 2, 3];
+console.log(list.push(4), list.push(5, 6), list.length, show(list));
+console.log(list.pop(), list.pop(), list.length, show(list));
+console.log(list.shift(), list.length, show(list));
+console.log(list.unshift(0), list.unshift(-2, -1), show(list));
+
+const empty = [];
+console.log(empty.pop() === undefined, empty.shift() === undefined, empty.length);
+
+// `splice` returns what it removes
+const letters = ["a", "b", "c", "d", "e"];
+console.log(show(letters.splice(1, 2)), show(letters));
+console.log(show(letters.splice(1, 0, "x", "y")), show(letters));
+console.log(show(letters.splice(-2)), show(letters));
+console.log(show(letters.splice(1, 1, "z")), show(letters));
+console.log(show(letters.splice(0, 99)), letters.length);
+console.log(show(letters.splice()), letters.splice(5, 1).length);
+
+// A queue and a stack built on them
+const queue = [];
+for (let i = 0; i < 5; i++) {
+    queue.push(i * i);
+}
+let total = 0;
+while (queue.length > 0) {
+    total += queue.shift();
+}
+console.log(total);
+
+const stack = [];
+for (const c of "hello") {
+    stack.push(c);
+}
+let reversed = "";
+while (stack.length) {
+    reversed += stack.pop();
+}
+console.log(reversed, "push" in stack);
+for (const key in [7, 8]) {
+    console.log(key);
+}
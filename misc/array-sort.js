@@ -0,0 +1,36 @@
+// Without a comparator, the elements are sorted by their strings
+console.log([3, 1, 10, 2, 21].sort().join());
+console.log(["pear", "Apple", "banana", "apple"].sort().join());
+console.log([true, false, "t", null, 5].sort().join());
+
+// With one, its sign decides the order
+const numbers = [3, 1, 10, 2, 21, -4, 0.5];
+console.log(numbers.sort((a, b) => a - b).join());
+console.log(numbers.sort((a, b) => b - a).join());
+console.log(numbers.sort((a, b) => a - b) === numbers);
+
+// The sort is stable, equal elements keep their order
+const people = [
+    { name: "Ann", age: 30 },
+    { name: "Bob", age: 25 },
+    { name: "Cid", age: 30 },
+    { name: "Dee", age: 25 },
+    { name: "Eve", age: 35 },
+];
+people.sort((a, b) => a.age - b.age);
+console.log(people.map((person) => person.name + person.age).join(" "));
+people.sort((a, b) => (a.name < b.name ? 1 : a.name > b.name ? -1 : 0));
+console.log(people.map((person) => person.name).join(" "));
+
+// `undefined` goes last, the holes after it
+const sparse = [5, undefined, 1, 4, undefined, 2];
+delete sparse[3];
+sparse.sort();
+console.log(sparse.length, sparse.join("-"), 3 in sparse, 5 in sparse, sparse[3] === undefined);
+
+// A comparator that returns NaN or isn't consistent doesn't break the sort
+console.log([3, 2, 1].sort(() => 0 / 0).join(), [1, 2, 3, 4, 5].sort(() => 1).length);
+
+const words = ["delta", "alpha", "charlie", "bravo"];
+console.log(words.sort((a, b) => a.length - b.length || (a < b ? -1 : 1)).join());
+console.log([].sort().length, [1].sort((a, b) => a - b).join());
@@ -0,0 +1,20 @@
+console.log(5 & 3, 5 | 3, 5 ^ 3, ~5);
+console.log(-1 & 255, -1 | 0, -1 ^ 0, 0xff00 & 0x0ff0);
+console.log(4294967295 | 0, 4294967296 | 0, 2147483648 | 0, -2147483649 | 0);
+console.log(3.9 | 0, -3.9 | 0, 1e21 | 0, "12" & 10, "abc" | 0, true ^ 1, null | 5);
+console.log(~~-7.5, ~4294967295);
+
+function hash(values) {
+    let h = 17;
+    for (const value of values) {
+        h = (h * 31) ^ value;
+        h = h & 0x7fffffff;
+    }
+    return h;
+}
+console.log(hash([1, 2, 3, 1000, 99999]));
+
+const READ = 1;
+const WRITE = 2;
+const flags = READ | WRITE;
+console.log((flags & WRITE) === WRITE, (flags & 4) === 4);
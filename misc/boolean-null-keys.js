@@ -0,0 +1,12 @@
+const obj = {};
+obj[true] = 1;
+obj[null] = 2;
+console.log(obj[`true`]);
+console.log(obj[`null`]);
+console.log(obj[true] + obj[null]);
+
+const arr = [10, 20];
+arr[false] = 3;
+console.log(arr[`false`]);
+console.log(arr[`1`]);
+console.log(arr.length);
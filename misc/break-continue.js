@@ -0,0 +1,59 @@
+// `continue` still runs the update of a `for` loop
+let odd = 0;
+for (let i = 0; i < 10; i++) {
+    if ((i / 2 | 0) < i / 2) {
+        odd = odd + 1;
+        continue;
+    }
+    if (8 < i) {
+        break;
+    }
+}
+console.log(`odd numbers ${odd}`);
+
+// and the test of a do-while loop
+let n = 0;
+do {
+    n++;
+    if (n < 3) {
+        continue;
+    }
+    console.log(`do-while at ${n}`);
+} while (n < 5);
+
+// Labeled jumps out of nested loops and switches
+outer: for (let row = 0; row < 4; row++) {
+    for (const col of [0, 1, 2, 3]) {
+        switch (col) {
+            case 1:
+                continue;
+            case 2:
+                if (row < 2) {
+                    continue outer;
+                }
+                break outer;
+        }
+        console.log(`cell ${row} ${col}`);
+    }
+}
+
+let steps = 0;
+while (true) {
+    steps++;
+    inner: {
+        if (steps < 3) {
+            break inner;
+        }
+        break;
+    }
+    console.log(`step ${steps}`);
+}
+console.log(`stopped after ${steps}`);
+
+first: second: for (const key in { a: 1, b: 2 }) {
+    for (;;) {
+        if (key in { b: 0 }) break second;
+        console.log(`key ${key}`);
+        continue first;
+    }
+}
@@ -0,0 +1,58 @@
+const scale = 2;
+
+class Vector {
+    constructor(x, y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    add(other) {
+        return new Vector(this.x + other.x, this.y + other.y);
+    }
+}
+
+class Body {
+    mass = 1;
+    steps;
+
+    constructor(x, y, vx, vy) {
+        this.x = x;
+        this.y = y;
+        this.vx = vx;
+        this.vy = vy;
+        this.steps = 0;
+    }
+
+    advance(dt) {
+        this.x += this.vx * dt * scale;
+        this.y += this.vy * dt * scale;
+        this.steps = this.steps + 1;
+        return this;
+    }
+
+    energy() {
+        return 0.5 * this.mass * (this.vx * this.vx + this.vy * this.vy);
+    }
+}
+
+class Counter {
+    count = 0;
+
+    increment() {
+        this.count = this.count + 1;
+        return this.count;
+    }
+}
+
+const body = new Body(1, 2, 3, 4);
+body.advance(0.5).advance(0.25);
+console.log(body.x, body.y, body.steps, body.mass);
+console.log(body.energy());
+console.log(`energy` in body, `mass` in body);
+
+const counter = new Counter();
+counter.increment();
+console.log(counter.increment(), new Counter().increment());
+
+const sum = new Vector(1, 2).add(new Vector(3, 4)).add(new Vector(scale, scale));
+console.log(sum.x, sum.y);
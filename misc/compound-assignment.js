@@ -0,0 +1,60 @@
+let x = 10;
+x += 5;
+x -= 3;
+x *= 4;
+x /= 8;
+x %= 4;
+x **= 3;
+console.log(x);
+
+let bits = 0b1100;
+bits |= 1;
+bits &= 0b1101;
+bits ^= 0b0100;
+bits <<= 3;
+bits >>= 1;
+console.log(bits);
+let unsigned = -8;
+unsigned >>>= 28;
+console.log(unsigned);
+
+const point = { x: 1, y: 2 };
+point.x += 10;
+point.y *= 3;
+point.y -= 1;
+point.x <<= 2;
+console.log(point.x, point.y);
+
+const values = [1, 2, 3];
+for (let i = 0; i < values.length; i++) {
+    values[i] *= 10;
+    values[i] |= 1;
+}
+console.log(values[0], values[1], values[2]);
+
+const log = [];
+function pick(object, name) {
+    log[log.length] = name;
+    return object;
+}
+function key(name) {
+    log[log.length] = name;
+    return name;
+}
+const counters = { a: 1, b: 2 };
+pick(counters, "object")[key("a")] += 100;
+console.log(counters.a, log.length, log[0], log[1]);
+
+class Account {
+    constructor() {
+        this.balance = 0;
+    }
+    deposit(amount) {
+        this.balance += amount;
+        this.balance %= 1000;
+        return this.balance;
+    }
+}
+const account = new Account();
+account.deposit(600);
+console.log(account.deposit(700));
@@ -0,0 +1,18 @@
+const a = 3;
+const b = 5;
+console.log(a < b ? a : b, b < a ? a : b);
+
+const sign = (x) => (x < 0 ? -1 : 0 < x ? 1 : 0);
+console.log(sign(-4), sign(0), sign(7));
+
+const label = (n) => `${n} item${n === 1 ? `` : `s`}`;
+console.log(label(1), label(2));
+
+const state = { count: 0 };
+const bump = () => {
+    state.count = state.count + 1;
+    return state.count;
+};
+console.log(true ? 1 : bump(), false ? 1 : bump(), state.count);
+console.log((a < b ? { value: a } : { value: b }).value);
+console.log(null ? 1 : 2, 0 ? 1 : 2);
@@ -0,0 +1,57 @@
+console.log([1, 2, 3], { a: 1, b: "two", "c-d": [true, null, undefined] });
+console.log({ nested: { deeper: { deepest: { tooDeep: 1 } } }, list: [[1, [2, [3, [4]]]]] });
+console.log([], {}, [[]], [{}], -0, [-0], "top level string", ["nested 'string'"]);
+console.log({ quotes: "it's \"quoted\"", escapes: "tab\tnewline\n", 1: "numeric key" });
+
+const long = [];
+for (let i = 0; i < 30; i++) {
+  long.push(i * 7);
+}
+console.log(long);
+const many = [];
+for (let i = 0; i < 120; i++) {
+  many.push(i);
+}
+console.log(many);
+const words = [];
+for (let i = 0; i < 10; i++) {
+  words.push("word" + i);
+}
+console.log(words);
+
+const sparse = [1, 2, 3, 4, 5];
+delete sparse[1];
+delete sparse[2];
+delete sparse[4];
+console.log(sparse);
+
+const wide = {
+  first: "a fairly long string value",
+  second: "another fairly long string value",
+  third: [1, 2, 3],
+};
+console.log(wide);
+console.log({ text: "a very long string that goes on and on and on\nand continues past the line break" });
+
+const cyclic = { name: "cycle", child: { back: null } };
+cyclic.child.back = cyclic;
+cyclic.self = cyclic;
+console.log(cyclic);
+
+console.log(() => 1, /ab+c/gi, new Date(86400000));
+
+console.log("%s is %d years and %i days, %f%%", "Bob", 42, 3.7, 1.5, "extra");
+console.log("%j and %O", { a: [1] }, { b: { c: {} } });
+console.log("%c styled", "color: red");
+console.log("%s", { a: { b: 1 } }, "%s");
+console.log("100%", 5);
+
+console.info("info goes to stdout");
+console.debug("so does debug");
+console.error("errors go to stderr");
+console.warn("as do warnings");
+console.assert(true, "not printed");
+console.assert(false, "printed to stderr", { detail: 1 });
+
+console.time("timer");
+console.timeEnd("missing");
@@ -0,0 +1,45 @@
+function Person(name, age) {
+  this.name = name;
+  this.age = age;
+}
+Person.prototype.greet = function () {
+  return `${this.name} is ${this.age}`;
+};
+const ada = new Person("Ada", 36);
+console.log(ada.greet(), ada instanceof Person, Person.name, Object.keys(ada));
+
+function Check() {
+  return { constructed: new.target !== undefined };
+}
+console.log(new Check().constructed, Check().constructed);
+
+function Counter() {
+  this.count = 0;
+  const increment = () => {
+    this.count += 1;
+    return new.target === Counter;
+  };
+  console.log(increment(), increment());
+}
+console.log(new Counter().count);
+
+class Shape {
+  constructor() {
+    this.kind = new.target.name;
+  }
+}
+class Square extends Shape {}
+class Circle extends Shape {
+  constructor() {
+    super();
+    this.round = new.target === Circle;
+  }
+}
+console.log(new Shape().kind, new Square().kind, new Circle().kind, new Circle().round);
+console.log(Shape, Square.name, Circle);
+
+const arrow = () => 1;
+const named = function () {};
+const object = { method() {}, arrow: () => {} };
+console.log(arrow.name, named.name, object.method.name, object.arrow.name);
+console.log(arrow, named, object.method, arrow.prototype);
@@ -0,0 +1,49 @@
+const start = Date.now();
+console.log(start > 1600000000000);
+
+const epoch = new Date(0);
+console.log(epoch.getTime() + " " + epoch.toISOString());
+console.log(epoch.getFullYear() + " " + epoch.getMonth() + " " + epoch.getDate() + " " + epoch.getDay());
+
+const leap = new Date("2024-02-29T13:45:30.250Z");
+console.log(leap.toISOString());
+console.log([leap.getUTCFullYear(), leap.getUTCMonth(), leap.getUTCDate(), leap.getUTCDay()].join());
+console.log([leap.getUTCHours(), leap.getUTCMinutes(), leap.getUTCSeconds(), leap.getUTCMilliseconds()].join());
+console.log(leap.toUTCString());
+console.log(JSON.stringify({ leap: leap }));
+
+const later = new Date(Date.UTC(2024, 2, 1));
+console.log((later - leap) / 1000);
+console.log(later > leap, later.getTime() === Date.UTC(2024, 2, 1));
+console.log(new Date(later).getTime() === later.getTime());
+console.log(later.valueOf() + 1);
+
+console.log(new Date(Date.UTC(2023, 13, 32, 25)).toISOString());
+console.log(new Date(Date.UTC(99, 0)).toISOString());
+console.log(new Date(Date.UTC(-1, 0)).toISOString());
+console.log(new Date(8.64e15).toISOString());
+console.log(Date.parse("2000-01-01") + " " + Date.parse("2000-01-01T00:00:00+01:00"));
+console.log(Date.parse("not a date") + " " + Date.parse("2000-13-01"));
+
+const moved = new Date(0);
+moved.setFullYear(2001, 5, 15);
+moved.setHours(23);
+moved.setMinutes(59, 58);
+console.log(moved.toISOString());
+console.log(moved.setTime(1000) + " " + moved.toISOString());
+moved.setMonth(14);
+console.log(moved.toISOString());
+
+const invalid = new Date(NaN);
+console.log(invalid.getTime() + " " + invalid.getFullYear() + " " + JSON.stringify([invalid]));
+console.log("" + invalid);
+console.log("" + new Date(-62198755200000));
+console.log(new Date(-62198755200000).toISOString());
+console.log(epoch.getTimezoneOffset());
+console.log(leap);
+
+const end = Date.now();
+console.log(end - start >= 0);
+
+const date = new Date(Date.UTC(2020, 1, 29));
+console.log(date.getUTCDate());
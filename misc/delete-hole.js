@@ -0,0 +1,6 @@
+const arr = [1, 2, 3];
+delete arr[1];
+console.log(arr.length);
+console.log(1 in arr);
+console.log(2 in arr);
+console.log(arr[1]);
@@ -0,0 +1,36 @@
+function undefinedless() {
+    return null;
+}
+
+let a = 1;
+let b = 2;
+[a, b] = [b, a];
+console.log(a, b);
+
+let x = 0;
+let y = 0;
+({ x, y: b } = { x: 5, y: 6 });
+console.log(x, b);
+
+let first;
+let rest;
+[first = 10, , ...rest] = [undefinedless(), 2, 3, 4];
+console.log(first, rest.length, rest[0]);
+
+const target = { list: [0, 0], name: null };
+[target.list[0], target.list[1]] = [7, 8];
+({ value: target.name = 42 } = {});
+console.log(target.list[0], target.list[1], target.name);
+
+let deep;
+let other = 3;
+({ outer: [deep, { other = 9 }] } = { outer: [1, {}] });
+console.log(deep, other);
+
+const key = `k`;
+let found;
+({ [key]: found } = { k: `yes` });
+console.log(found);
+
+const result = ([a, b] = [20, 30]);
+console.log(a, b, result.length);
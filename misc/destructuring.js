@@ -0,0 +1,35 @@
+const point = { x: 1, y: 2, z: 3 };
+const { x, y: renamed } = point;
+console.log(x, renamed);
+
+const { w = 10, z = 20 } = point;
+console.log(w, z);
+
+const config = { size: { width: 4, height: 5 }, depth: null };
+const {
+    size: { width, height = 9 },
+    depth = 7,
+} = config;
+console.log(width, height, depth);
+
+let { x: first, missing = x + renamed } = point;
+first += 1;
+console.log(first, missing);
+
+const key = `y`;
+const { [key]: computed } = point;
+console.log(computed);
+
+function area(rect) {
+    const { width, height } = rect;
+    return width * height;
+}
+console.log(area({ width: 3, height: 4 }));
+
+let calls = { count: 0 };
+function next() {
+    calls.count += 1;
+    return { value: calls.count };
+}
+const { value } = next();
+console.log(value, calls.count);
@@ -0,0 +1,15 @@
+// Empty statements and `debugger` do nothing when no debugger is attached
+;
+let total = 0;;
+for (let i = 0; i < 5; i++);
+for (let i = 0; i < 5; i++) {
+    total += i;
+    debugger;
+}
+if (total > 5);
+else {
+    console.log("unreachable");
+}
+while (total > 100);
+debugger;
+console.log(total);
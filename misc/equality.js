@@ -0,0 +1,21 @@
+function NaN_value() {
+    return 0 / 0;
+}
+function undefined_value() {
+    return;
+}
+
+console.log(1 === 1, 1 === "1", 1 !== "1", "a" !== "a");
+console.log(NaN_value() === NaN_value(), NaN_value() == NaN_value(), NaN_value() != NaN_value());
+console.log(0 === -0, null === undefined_value(), null == undefined_value());
+console.log(null == 0, undefined_value() == 0, null == false, null != null);
+console.log(1 == "1", 0 == "", "  12  " == 12, "0x10" == 16, "1e3" == 1000, "inf" == 1);
+console.log(true == 1, false == 0, true == "1", false == "", true == 2);
+console.log([1] == 1, [1, 2] == "1,2", [] == "", [] == 0, [] == false);
+console.log({} == "[object Object]");
+
+const object = { a: 1 };
+const same = object;
+console.log(object == same, object === same, object == { a: 1 }, object != { a: 1 });
+console.log("abc" == "abc", "abc" != "abd");
+console.log(+"  42 ", +"", +"0b101", +"0o17", +"12px", +[7]);
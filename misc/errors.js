@@ -0,0 +1,70 @@
+const plain = new Error("something broke");
+console.log(plain.message, plain.name, `${plain}`, "" + plain, Object.keys(plain));
+console.log(plain instanceof Error, plain instanceof TypeError, plain.stack.split("\n")[0]);
+
+const empty = new RangeError();
+console.log(`[${empty.message}]`, empty.name, `${empty}`, empty instanceof Error);
+
+function parse(text) {
+  if (text === "") {
+    throw new TypeError(`cannot parse ${text.length} characters`);
+  }
+  return text.length;
+}
+
+try {
+  parse("");
+} catch (error) {
+  console.log(error instanceof TypeError, error instanceof RangeError, error instanceof Error);
+  console.log(error.name, error.message, `${error}`);
+}
+
+class ValidationError extends Error {
+  constructor(field, message) {
+    super(message);
+    this.name = "ValidationError";
+    this.field = field;
+  }
+
+  describe() {
+    return `${this.field}: ${this.message}`;
+  }
+}
+
+function validate(user) {
+  if (user.age < 0) {
+    throw new ValidationError("age", "must not be negative");
+  }
+  return true;
+}
+
+for (const user of [{ age: 3 }, { age: -1 }]) {
+  try {
+    console.log(validate(user));
+  } catch (error) {
+    console.log(error instanceof ValidationError, error instanceof Error, error.describe());
+    console.log(`${error}`, Object.keys(error), error.stack.split("\n")[0]);
+  }
+}
+
+const wrapped = new Error("request failed", { cause: plain });
+console.log(wrapped.cause === plain, new Error("no cause", {}).cause, "cause" in wrapped);
+
+const renamed = new Error("custom");
+renamed.name = "";
+const unnamed = new TypeError("");
+console.log(`${renamed}`, `${unnamed}`, renamed.toString());
+
+Promise.reject(new RangeError("out of range")).catch((error) => {
+  console.log("rejected with", error.name, error.message);
+});
+
+try {
+  try {
+    throw new Error("inner");
+  } finally {
+    console.log("cleanup");
+  }
+} catch (error) {
+  console.log(`rethrown ${error.message}`);
+}
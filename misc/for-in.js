@@ -0,0 +1,16 @@
+const point = { x: 1, y: 2 };
+point[10] = 10;
+point[2] = 20;
+for (const key in point) {
+    console.log(`${key}: ${point[key]}`);
+}
+
+const list = [5, 6, 7];
+delete list[1];
+let last = -1;
+for (last in list) {
+    console.log(`index ${last}`);
+}
+console.log(`last index ${last}`);
+
+for (let i in `abc`) console.log(i);
@@ -0,0 +1,24 @@
+const primes = [2, 3, 5];
+let sum = 0;
+for (const prime of primes) {
+    sum = sum + prime;
+}
+console.log(`sum of primes ${sum}`);
+
+const grow = [1];
+for (let value of grow) {
+    if (value < 4) {
+        grow[value] = value + 1;
+    }
+    console.log(`value ${value}`);
+}
+
+const holes = [1, 2, 3];
+delete holes[1];
+for (const element of holes) console.log(element);
+
+let letter = 0;
+for (letter of `héllo`) {
+    console.log(letter);
+}
+console.log(`last letter ${letter}`);
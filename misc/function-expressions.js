@@ -0,0 +1,48 @@
+const double = function (x) {
+    return x * 2;
+};
+const square = (x) => x * x;
+const add = (a, b) => {
+    const sum = a + b;
+    return sum;
+};
+console.log(double(3), square(4), add(5, 6));
+
+const offset = 10;
+const shift = (x) => x + offset;
+console.log(shift(1));
+
+const makeAdder = (n) => (x) => x + n;
+const addThree = makeAdder(3);
+console.log(addThree(4), makeAdder(1)(1));
+
+const apply = (f, value) => f(value);
+console.log(apply(square, 9), apply((x) => -x, 2));
+
+console.log((function () {
+    return 42;
+})());
+
+const missing = (a, b) => b;
+console.log(missing(1));
+
+const point = {
+    x: 3,
+    scaled: (factor) => factor * 3,
+};
+console.log(point.scaled(2));
+
+class Counter {
+    count = 0;
+
+    adder() {
+        return (n) => {
+            this.count = this.count + n;
+            return this.count;
+        };
+    }
+}
+const counter = new Counter();
+const addToCounter = counter.adder();
+addToCounter(2);
+console.log(addToCounter(3), counter.count);
@@ -0,0 +1,139 @@
+function* count(from, to) {
+    for (let i = from; i <= to; i++) {
+        yield i;
+    }
+}
+
+for (const n of count(1, 4)) {
+    console.log(n);
+}
+console.log(`${[...count(5, 8)]}`);
+
+function* naturals() {
+    let n = 0;
+    while (true) {
+        yield n;
+        n += 1;
+    }
+}
+
+function* take(iterable, limit) {
+    if (limit <= 0) {
+        return;
+    }
+    let taken = 0;
+    for (const item of iterable) {
+        yield item;
+        taken += 1;
+        if (taken >= limit) {
+            break;
+        }
+    }
+}
+
+function* map(iterable, f) {
+    for (const item of iterable) {
+        yield f(item);
+    }
+}
+
+console.log(`${[...take(map(naturals(), (n) => n * n), 5)]}`);
+console.log([...take(naturals(), 0)].length);
+
+function* conversation() {
+    const name = yield "What's your name?";
+    const hobby = yield `Hello, ${name}! What's your hobby?`;
+    return `${name} likes ${hobby}`;
+}
+const talk = conversation();
+console.log(talk.next().value);
+console.log(talk.next("Ada").value);
+const last = talk.next("math");
+console.log(last.value, last.done);
+const after = talk.next();
+console.log(after.value === void 0, after.done);
+
+function* fibonacci() {
+    let a = 0;
+    let b = 1;
+    while (true) {
+        yield a;
+        [a, b] = [b, a + b];
+    }
+}
+const fib = fibonacci();
+const first = [];
+for (let i = 0; i < 10; i++) {
+    first[i] = fib.next().value;
+}
+console.log(`${first}`);
+
+function* labeled() {
+    outer: for (let i = 0; i < 3; i++) {
+        for (let j = 0; j < 3; j++) {
+            if (j === 2) {
+                continue outer;
+            }
+            if (i === 2) {
+                break outer;
+            }
+            yield `${i}${j}`;
+        }
+    }
+    yield "end";
+}
+console.log(`${[...labeled()]}`);
+
+function* keys(object) {
+    for (const key in object) {
+        yield key;
+    }
+}
+console.log(`${[...keys({ a: 1, b: 2, 3: 0 })]}`);
+
+const early = count(1, 10);
+console.log(early.next().value);
+console.log(early.return(42).value, early.next().done);
+
+const object = {
+    values: [3, 1, 2],
+    *sorted() {
+        let remaining = this.values.length;
+        let last = -1;
+        while (remaining > 0) {
+            let smallest = 1e9;
+            for (const value of this.values) {
+                if (value > last && value < smallest) {
+                    smallest = value;
+                }
+            }
+            last = smallest;
+            remaining -= 1;
+            yield smallest;
+        }
+    },
+};
+console.log(`${[...object.sorted()]}`);
+
+class Tree {
+    constructor(value, children) {
+        this.value = value;
+        this.children = children;
+    }
+    *values() {
+        yield this.value;
+        for (const child of this.children) {
+            for (const value of child.values()) {
+                yield value;
+            }
+        }
+    }
+}
+const tree = new Tree(1, [new Tree(2, [new Tree(3, [])]), new Tree(4, [])]);
+console.log(`${[...tree.values()]}`);
+
+const expression = function* () {
+    yield 1;
+    yield 2;
+};
+console.log(`${[...expression()]}`, `${expression()}`);
@@ -0,0 +1,29 @@
+// The globals of the language are values like any other
+console.log(undefined, NaN, Infinity, -Infinity);
+console.log(undefined === void 0, NaN === NaN, Infinity > 1e308);
+
+function describe(value) {
+    if (value === undefined) {
+        return "missing";
+    }
+    return "got " + value;
+}
+console.log(describe(), describe(1));
+
+const point = { x: 1 };
+console.log(point.y === undefined, point.x !== undefined);
+console.log(0 / 0, 1 / 0 === Infinity, -1 / 0 === -Infinity);
+
+// The global object has them as properties too
+console.log(globalThis.NaN, globalThis.Infinity, globalThis.undefined);
+console.log(globalThis === globalThis);
+globalThis.answer = 42;
+console.log(globalThis.answer);
+
+// A variable of the same name hides the global
+function shadow() {
+    const Infinity = 1;
+    const NaN = 2;
+    return Infinity + NaN;
+}
+console.log(shadow());
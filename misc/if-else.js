@@ -0,0 +1,19 @@
+function sign(x) {
+    if (x < 0) {
+        return -1;
+    } else if (0 < x) {
+        return 1;
+    } else {
+        return 0;
+    }
+}
+
+let positives = 0;
+for (let i = -2; i < 3; i++) {
+    if (0 < sign(i)) positives++;
+    if (sign(i) < 0) {
+    } else {
+        console.log(`${i} is not negative`);
+    }
+}
+console.log(sign(-5), sign(5), sign(0), positives);
@@ -0,0 +1,65 @@
+(function () {
+    console.log("inside a function");
+})();
+
+(() => {
+    console.log("inside an arrow function");
+})();
+
+let total = 0;
+(function (amount) {
+    total += amount;
+})(total + 5);
+((amount) => {
+    total *= amount;
+})(total);
+console.log(total);
+
+const sum = (function (a, b, ...rest) {
+    let result = a + b;
+    for (const value of rest) {
+        result += value;
+    }
+    return result;
+})(1, 2, 3, 4);
+console.log(sum);
+
+const square = ((x) => x * x)(7);
+console.log(square);
+
+const missing = (function (a, b) {
+    return b;
+})(1);
+console.log(missing === void 0);
+
+(function () {
+    const results = { runs: 0 };
+    function run(n) {
+        results.runs += 1;
+        let [a, b] = [0, 1];
+        for (let i = 0; i < n; i++) {
+            [a, b] = [b, a + b];
+        }
+        return a;
+    }
+    console.log(run(10), run(20), results.runs);
+})();
+
+const counter = {
+    count: 0,
+    bump() {
+        (() => {
+            this.count += 1;
+        })();
+        return this.count;
+    },
+};
+console.log(counter.bump(), counter.bump());
+
+let outer = 1;
+(function () {
+    (function () {
+        outer += 10;
+    })();
+})();
+console.log(outer);
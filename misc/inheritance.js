@@ -0,0 +1,120 @@
+class Shape {
+    #id;
+    sides = 0;
+
+    constructor(name) {
+        this.name = name;
+        this.#id = name === "circle" ? 1 : 2;
+    }
+
+    describe() {
+        return `${this.name} with ${this.sides} sides`;
+    }
+
+    get id() {
+        return this.#id;
+    }
+
+    static create(name) {
+        return new this(name);
+    }
+
+    static kind = "shape";
+}
+
+class Polygon extends Shape {
+    sides = 3;
+
+    constructor(name, sides) {
+        super(name);
+        if (sides !== void 0) {
+            this.sides = sides;
+        }
+    }
+
+    describe() {
+        return `polygon: ${super.describe()}`;
+    }
+
+    get area() {
+        return this.sides * 10;
+    }
+
+    set area(value) {
+        this.sides = value / 10;
+    }
+}
+
+class Square extends Polygon {
+    #side = 2;
+
+    constructor(side) {
+        super("square", 4);
+        this.#side = side;
+    }
+
+    describe() {
+        const parent = () => super.describe();
+        return `${parent()} of length ${this.#side}`;
+    }
+
+    get area() {
+        return this.#side * this.#side;
+    }
+
+    static create(side) {
+        return new Square(side);
+    }
+
+    static label() {
+        return `${super.create("base").name} ${this.kind}`;
+    }
+}
+
+class Triangle extends Polygon {}
+
+const shape = new Shape("circle");
+const polygon = new Polygon("hexagon", 6);
+const square = Square.create(5);
+const triangle = new Triangle("triangle");
+
+console.log(shape.describe());
+console.log(polygon.describe());
+console.log(square.describe());
+console.log(triangle.describe(), triangle.name);
+console.log(polygon.area, square.area, square.id, triangle.id);
+polygon.area = 80;
+console.log(polygon.sides, polygon.area);
+square.area = 100;
+console.log(square.area, square.sides);
+console.log(square instanceof Square, square instanceof Polygon, square instanceof Shape);
+console.log(polygon instanceof Square, shape instanceof Polygon, triangle instanceof Polygon);
+console.log(Square.label(), Triangle.kind, Polygon.create("made").describe());
+console.log("area" in square, "id" in shape);
+
+class Temperature {
+    #celsius = 0;
+
+    get fahrenheit() {
+        return this.#celsius * 9 / 5 + 32;
+    }
+
+    set fahrenheit(value) {
+        this.#celsius = (value - 32) * 5 / 9;
+    }
+
+    get #kelvin() {
+        return this.#celsius + 273;
+    }
+
+    kelvin() {
+        return this.#kelvin;
+    }
+
+}
+const temperature = new Temperature();
+temperature.fahrenheit = 212;
+console.log(temperature.fahrenheit, temperature.kelvin());
+for (const key in temperature) {
+    console.log(key);
+}
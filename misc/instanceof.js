@@ -0,0 +1,24 @@
+class Point {
+    constructor(x, y) {
+        this.x = x;
+        this.y = y;
+    }
+}
+
+class Empty {}
+
+const point = new Point(1, 2);
+const empty = new Empty();
+console.log(point instanceof Point);
+console.log(point instanceof Empty);
+console.log(empty instanceof Empty);
+console.log(empty instanceof Point);
+console.log({ x: 1 } instanceof Point);
+console.log(1 instanceof Point);
+console.log(null instanceof Empty);
+
+function check(value) {
+    return value instanceof Point ? "point" : "other";
+}
+console.log(check(point), check(empty));
+console.log(point instanceof function () {});
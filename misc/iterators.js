@@ -0,0 +1,108 @@
+const letters = ["a", "b"];
+const arrayIterator = letters[Symbol.iterator]();
+console.log(arrayIterator.next(), arrayIterator.next(), arrayIterator.next());
+console.log(arrayIterator[Symbol.iterator]() === arrayIterator, Symbol.iterator in letters);
+
+const stringIterator = "hi"[Symbol.iterator]();
+console.log(stringIterator.next().value, stringIterator.next().value, stringIterator.next().done);
+
+const prices = new Map([
+  ["apple", 1.5],
+  ["pear", 2],
+]);
+const mapIterator = prices[Symbol.iterator]();
+console.log(mapIterator.next().value, mapIterator.next().value, mapIterator.next());
+
+const tags = new Set(["new", "sale"]);
+const setIterator = tags[Symbol.iterator]();
+console.log(setIterator.next(), setIterator.next().value, setIterator.next().done);
+
+function range(start, end) {
+  return {
+    [Symbol.iterator]() {
+      let current = start;
+      return {
+        next() {
+          if (current < end) {
+            current += 1;
+            return { value: current - 1, done: false };
+          }
+          return { value: undefined, done: true };
+        },
+        return() {
+          console.log(`closed at ${current}`);
+          return { done: true };
+        },
+      };
+    },
+  };
+}
+
+for (const number of range(1, 4)) {
+  console.log("number", number);
+}
+console.log([...range(0, 5)], Math.max(...range(3, 7)));
+
+for (const number of range(10, 20)) {
+  if (number === 12) {
+    break;
+  }
+  console.log("until 12", number);
+}
+
+function firstOver(limit) {
+  for (const number of range(0, 100)) {
+    if (number > limit) {
+      return number;
+    }
+  }
+}
+console.log(firstOver(3));
+
+const countdown = {
+  from: 3,
+  *[Symbol.iterator]() {
+    for (let value = this.from; value > 0; value -= 1) {
+      yield value;
+    }
+  },
+};
+console.log([...countdown], new Set(countdown).size);
+
+const pairs = {
+  [Symbol.iterator]() {
+    return [
+      ["x", 1],
+      ["y", 2],
+    ][Symbol.iterator]();
+  },
+};
+console.log(new Map(pairs).get("y"), Object.fromEntries(pairs));
+
+function* numbers() {
+  yield 1;
+  yield 2;
+}
+const generator = numbers();
+console.log(generator[Symbol.iterator]() === generator, [...generator]);
+
+for (const value of [{}, { [Symbol.iterator]: 1 }, { [Symbol.iterator]: () => 1 }]) {
+  try {
+    for (const item of value) {
+      console.log(item);
+    }
+  } catch (error) {
+    console.log(error instanceof TypeError, error.name);
+  }
+}
+
+const broken = {
+  [Symbol.iterator]() {
+    return { next: () => 5 };
+  },
+};
+try {
+  console.log([...broken]);
+} catch (error) {
+  console.log(`${error}`);
+}
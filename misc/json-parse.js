@@ -0,0 +1,21 @@
+const parsed = JSON.parse(`{"a": [1, 2], "nested": {"deep": [{"value": 3.5}]}}`);
+console.log(parsed.a[1]);
+console.log(parsed.nested.deep[0].value);
+console.log(JSON.parse(`{"a":[1,2]}`).a[1]);
+
+const values = JSON.parse(` [true, false, null, -0.5e2, 0, "esc\\n\\u0041\\ud83d\\ude00", {}] `);
+console.log(values.join("|"));
+console.log(JSON.parse(`"text"`) + JSON.parse(`12E-1`));
+
+const revived = JSON.parse(`{"a": 1, "b": [1, 2, 3], "drop": 5, "c": {"d": 4}}`, function (key, value) {
+  console.log("visit '" + key + "'");
+  if (key === "drop") {
+    return undefined;
+  }
+  if (key === "d" || key === "1") {
+    return value * 100;
+  }
+  return value;
+});
+console.log(JSON.stringify(revived));
+console.log("drop" in revived);
@@ -0,0 +1,37 @@
+console.log(JSON.stringify({ b: 1, a: "two", 2: true, 1: null }));
+console.log(JSON.stringify([1, "a", undefined, () => 1, NaN, Infinity, -0]));
+console.log(JSON.stringify({ skipped: undefined, f: function () {}, kept: 0 }));
+console.log(JSON.stringify("quote \" backslash \\ newline \n tab \t control \u0001"));
+console.log(JSON.stringify(undefined));
+console.log(JSON.stringify(() => 1));
+console.log(JSON.stringify(null) + " " + JSON.stringify(false) + " " + JSON.stringify(1.5e21));
+console.log(JSON.stringify({}) + " " + JSON.stringify([]));
+
+const nested = { name: "x", list: [1, [2, 3], {}], inner: { deep: { value: 4 } }, empty: [] };
+console.log(JSON.stringify(nested, null, 2));
+console.log(JSON.stringify(nested, null, "--"));
+console.log(JSON.stringify([1, 2], null, 20));
+
+console.log(JSON.stringify(nested, ["name", "inner", "deep"]));
+console.log(
+  JSON.stringify(nested, function (key, value) {
+    if (key === "list") {
+      return undefined;
+    }
+    return key === "value" ? value * 10 : value;
+  }),
+);
+
+const withToJson = {
+  when: { toJSON: function (key) { return "serialized " + key; } },
+};
+console.log(JSON.stringify(withToJson));
+
+const later = { first: 1 };
+later.second = 2;
+delete later.first;
+later.first = 3;
+console.log(JSON.stringify(later));
+
+const round = JSON.parse(JSON.stringify(nested));
+console.log(round.inner.deep.value + " " + round.list[1][1]);
@@ -0,0 +1,21 @@
+console.log("double quoted");
+console.log('single quoted');
+console.log("quotes: \" ' \\ done");
+console.log('tab\tnew\nline');
+console.log("unicode: é \u{1F600} ü \x41");
+console.log("");
+console.log(true, false, null);
+
+const empty = "";
+const text = "text";
+console.log(empty ? "truthy" : "falsy");
+console.log(text ? "truthy" : "falsy");
+console.log(text === "text");
+console.log(text === 'other');
+
+const object = { key: "value", other: 'x' };
+console.log(object["key"]);
+console.log(object.other);
+console.log(`${"in"} a template`);
+console.log(null ?? "default");
+console.log(false || "fallback");
@@ -0,0 +1,39 @@
+let a = 0;
+a ||= 5;
+console.log(a);
+a ||= 10;
+console.log(a);
+a &&= 7;
+console.log(a);
+let zero = 0;
+zero &&= 7;
+console.log(zero);
+let missing = null;
+missing ??= "default";
+console.log(missing);
+missing ??= "other";
+console.log(missing);
+
+const calls = { count: 0 };
+function value(result) {
+    calls.count += 1;
+    return result;
+}
+let kept = "kept";
+kept ||= value("unused");
+kept ??= value("unused");
+console.log(kept, calls.count);
+
+const options = { retries: 0, name: "", timeout: null };
+options.retries ||= 3;
+options.name ||= "anonymous";
+options.timeout ??= 1000;
+options.timeout ??= 5;
+options.retries &&= options.retries * 2;
+console.log(options.retries, options.name, options.timeout);
+
+const cache = [];
+for (let i = 0; i < 3; i++) {
+    cache[i % 2] ??= value(i);
+}
+console.log(cache[0], cache[1], calls.count);
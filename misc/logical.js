@@ -0,0 +1,22 @@
+const state = { calls: 0 };
+const touch = (value) => {
+    state.calls = state.calls + 1;
+    return value;
+};
+
+console.log(1 && 2, 0 && 2, null && 2);
+console.log(1 || 2, 0 || 2, state.missing || null);
+console.log(null ?? 3, state.missing ?? 4, 0 ?? 5, false ?? 6);
+
+console.log(0 && touch(1), state.calls);
+console.log(1 || touch(1), state.calls);
+console.log(1 ?? touch(1), state.calls);
+console.log(1 && touch(7), state.calls);
+
+const options = { size: 0 };
+console.log(options.size ?? 10, options.size || 10, options.color ?? `red`);
+console.log((1 && 0) || (null ?? 8));
+
+const flag = true;
+flag && touch(1);
+console.log(state.calls);
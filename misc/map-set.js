@@ -0,0 +1,36 @@
+const point = { x: 1 };
+const map = new Map([
+  ["a", 1],
+  [point, "point"],
+]);
+map.set(NaN, "not a number").set(0, "zero").set("a", 10);
+console.log(map, map.size, map.get("a"), map.get(point), map.get({ x: 1 }));
+console.log(map.get(NaN), map.get(-0), map.has(point), map.has("b"), map.get("b"));
+console.log(map.delete("a"), map.delete("a"), map.size, map);
+map.set("a", 2);
+for (const [key, value] of map) {
+  console.log(key, value);
+}
+map.forEach((value, key) => console.log(`${value} <- ${key}`));
+console.log([...map.keys()], [...map.values()], [...map.entries()]);
+map.clear();
+console.log(map, map.size);
+
+const set = new Set([3, 1, 3, "3", point, point]);
+set.add(1).add(2);
+console.log(set, set.size, set.has(3), set.has("1"), set.has(point));
+console.log(set.delete(3), set.has(3), [...set], [...set.entries()]);
+set.forEach((value, same) => console.log(value === same));
+for (const value of new Set("hello")) {
+  console.log(value);
+}
+
+const visited = new Set([1]);
+for (const value of visited) {
+  if (value < 4) {
+    visited.add(value + 1);
+  }
+  visited.delete(value);
+}
+console.log(visited, { nested: { deeper: { map: new Map() } }, set: new Set([[1, 2]]) });
+console.log(Object.keys(new Map([[1, 2]])), `${new Set()}`, JSON.stringify(new Map([[1, 2]])));
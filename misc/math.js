@@ -0,0 +1,29 @@
+console.log(Math.PI, Math.E, Math.LN2, Math.LN10, Math.LOG2E, Math.LOG10E, Math.SQRT2, Math.SQRT1_2);
+
+console.log(Math.abs(-3.5), Math.abs("-2"), Math.abs(null), Math.abs());
+console.log(Math.floor(2.7), Math.floor(-2.1), Math.ceil(2.1), Math.ceil(-2.7));
+console.log(Math.round(2.5), Math.round(-2.5), Math.round(2.4999), Math.round(-0.4), Math.round(0.49999999999999994));
+console.log(Math.trunc(4.9), Math.trunc(-4.9), Math.sign(-7), Math.sign(0), Math.sign(-0), Math.sign("x"));
+console.log(Math.min(3, 1, 2), Math.max(3, 1, 2), Math.min(), Math.max(), Math.max(1, NaN, 3));
+console.log(Math.max(-0, 0), Math.min(0, -0), Math.max("7", 5));
+
+console.log(Math.pow(2, 10), Math.pow(2, 0.5), Math.pow(1, Infinity), Math.sqrt(16), Math.cbrt(27));
+console.log(Math.exp(1), Math.expm1(0), Math.log(Math.E), Math.log2(8), Math.log10(1000), Math.log1p(0));
+console.log(Math.sin(0), Math.cos(0), Math.tan(0), Math.atan2(1, 1), Math.atan(1), Math.asin(1), Math.acos(1));
+console.log(Math.sinh(0), Math.cosh(0), Math.tanh(0), Math.asinh(0), Math.acosh(1), Math.atanh(0));
+console.log(Math.hypot(3, 4), Math.hypot(), Math.hypot(1, 2, 2), Math.hypot(NaN, Infinity), Math.hypot(-5));
+console.log(Math.clz32(1), Math.clz32(0), Math.imul(3, 4), Math.imul(0xffffffff, 5), Math.fround(5.5), Math.fround(5.05));
+
+// `random` is in [0, 1)
+let inRange = true;
+for (let i = 0; i < 1000; i++) {
+    const value = Math.random();
+    inRange = inRange && value >= 0 && value < 1;
+}
+console.log(inRange, Math.random() !== Math.random());
+
+// The functions are values like any other
+const round = Math.round;
+console.log([1.4, 2.6, -3.5].map(round).join(), [4, 9, 16].map(Math.sqrt).join());
+console.log([3, 7, 1].reduce((a, b) => Math.max(a, b)));
+console.log("floor" in Math);
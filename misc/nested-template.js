@@ -0,0 +1,9 @@
+const x = 42;
+const name = `inner`;
+console.log(`outer ${`${name} ${x}`} and \`escaped\` \t tab`);
+console.log(`${x}`);
+
+for (const c of [true, false]) {
+  console.log(`a${c ? `b${x}` : 'd'}`);
+  console.log(`${c ? `${name}-${c ? `${x + 1}` : ""}` : `none`}!`);
+}
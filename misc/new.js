@@ -0,0 +1,37 @@
+class Point {
+    constructor(x, y) {
+        this.x = x;
+        this.y = y;
+    }
+}
+const point = new Point(1, 2);
+console.log(point.x, point.y);
+
+const Factory = function (value) {
+    return { value, doubled: value * 2 };
+};
+const made = new Factory(21);
+console.log(made.value, made.doubled);
+
+const Plain = function () {
+    return 42;
+};
+const plain = new Plain();
+console.log(plain === 42);
+
+const Greeter = function () {};
+Greeter.prototype = {
+    greet: (name) => `hello ${name}`,
+    kind: "greeter",
+};
+const greeter = new Greeter();
+console.log(greeter.greet("world"), greeter.kind);
+console.log(new Greeter().kind);
+
+const classes = { Point };
+const nested = new classes.Point(3, 4);
+console.log(nested.x + nested.y);
+
+class Empty {}
+const empty = new Empty;
+console.log(empty instanceof Empty);
@@ -0,0 +1,20 @@
+// Numbers are printed with the shortest digits that read back as the same number
+console.log(1, -1, 0.5, 123.456, 0.1 + 0.2, 1 / 3, -2 / 3);
+console.log(0, -0, 1 / 0, -1 / 0, 0 / 0);
+console.log("" + -0, `${-0}`, "" + [0, -0, 1.5]);
+
+// Large numbers switch to exponential notation from 1e21 on
+console.log(123456789012345680000, 1e20, 1e21, 1.5e21, 2 ** 70, -(10 ** 25));
+console.log(1.7976931348623157e308, 5e-324);
+
+// Small numbers switch to it below 1e-6
+console.log(0.000001, 0.0000015, 0.0000001, 1.25e-7, -3e-10);
+
+// Numbers as property keys use the same format
+const table = {};
+table[0.5] = "half";
+table[1e21] = "big";
+table[-0] = "zero";
+for (const key in table) {
+    console.log(key, table[key]);
+}
@@ -0,0 +1,21 @@
+console.log(Number.MAX_SAFE_INTEGER, Number.MIN_SAFE_INTEGER, Number.EPSILON, Number.MAX_VALUE, Number.MIN_VALUE);
+console.log(Number.POSITIVE_INFINITY, Number.NEGATIVE_INFINITY, Number.NaN);
+console.log(Number("42"), Number(""), Number("0x10"), Number(null), Number(), Number(true), Number("1e3"));
+console.log(Number.isInteger(5), Number.isInteger(5.5), Number.isInteger("5"), Number.isInteger(Infinity));
+console.log(Number.isSafeInteger(2 ** 53 - 1), Number.isSafeInteger(2 ** 53), Number.isNaN("abc"), Number.isNaN(NaN));
+console.log(Number.isFinite("12"), Number.isFinite(12), Number.parseFloat === parseFloat, Number.parseInt("12px"));
+
+console.log((255).toString(16), (255).toString(2), (-255).toString(36), (0.5).toString(2), (0.1).toString(3));
+console.log((3.75).toString(16), (1e21).toString(7), (123.456).toString(36), (NaN).toString(2), (2 ** 60).toString(2));
+console.log((42).toString(), (1e21).toString(), (-0).toString(16), (Math.PI).toString(8));
+
+console.log((1.005).toFixed(2), (1.45).toFixed(1), (0.5).toFixed(0), (2.5).toFixed(0), (-2.5).toFixed(0));
+console.log((1234.5678).toFixed(2), (0).toFixed(3), (-0.0001).toFixed(2), (1e21).toFixed(2), (99.99).toFixed(1));
+console.log((123.456).toFixed(), (0.000001).toFixed(7), (1.23e-10).toFixed(20), (NaN).toFixed(2));
+
+console.log((123456).toExponential(2), (0.00015).toExponential(1), (1.5).toExponential(), (0).toExponential(2));
+console.log((-9.99).toExponential(1), (1e-7).toExponential(), (5e-324).toExponential(3), (Infinity).toExponential(1));
+
+console.log((123.456).toPrecision(4), (0.00012345).toPrecision(2), (123456).toPrecision(2), (1.5).toPrecision());
+console.log((0).toPrecision(3), (-1.005).toPrecision(3), (1e-7).toPrecision(1), (999.99).toPrecision(3), (1e21).toPrecision(22));
+console.log((5).valueOf() + 1, (12.5).toFixed(1) + "!");
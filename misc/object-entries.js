@@ -0,0 +1,31 @@
+const object = { b: 1, a: "two", 10: "ten", 2: "two", c: [3] };
+object.later = true;
+delete object.b;
+object.b = "back";
+console.log(Object.keys(object));
+console.log(Object.values(object));
+console.log(Object.entries(object));
+
+console.log(Object.keys([5, 6, 7]), Object.keys("hey"), Object.keys(42), Object.values("ab"));
+const sparse = [1, 2, 3];
+delete sparse[1];
+console.log(Object.entries(sparse));
+
+const rebuilt = Object.fromEntries(Object.entries({ x: 1, y: 2 }).map((entry) => [entry[0] + entry[0], entry[1] * 10]));
+console.log(rebuilt);
+console.log(Object.fromEntries([["a", 1], [2, "b"], ["a", 3]]));
+
+function* pairs() {
+  yield ["first", 1];
+  yield ["second", 2];
+}
+console.log(Object.fromEntries(pairs()));
+
+let total = 0;
+for (const entry of Object.entries({ p: 1, q: 2, r: 3 })) {
+  const [key, value] = entry;
+  total += value;
+  console.log(key);
+}
+console.log(total);
+console.log(Object(object) === object, Object(), Object(null));
@@ -0,0 +1,26 @@
+const name = "dynamic";
+const object = {
+    "a-b": 1,
+    [name]: 2,
+    42: 3,
+    [`${name}-2`]: 4,
+    [1 + 2]: 5,
+    1.5: 6,
+    "with space": 7,
+};
+console.log(object["a-b"]);
+console.log(object.dynamic);
+console.log(object[42], object["42"]);
+console.log(object["dynamic-2"]);
+console.log(object[3]);
+console.log(object["1.5"]);
+console.log(object["with space"]);
+
+const step = { count: 0 };
+const keys = {
+    [(step.key = ++step.count, "k")]: (step.value = ++step.count, 1),
+};
+console.log(step.key, step.value, keys.k);
+
+const { "a-b": dashed, [name]: computed, 42: numbered } = object;
+console.log(dashed, computed, numbered);
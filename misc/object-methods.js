@@ -0,0 +1,34 @@
+const x = 1;
+const y = 2;
+const point = {
+    x,
+    y,
+    move(dx) {
+        return dx + 1;
+    },
+    "quoted method"() {
+        return "quoted";
+    },
+    [`computed${"Method"}`](a, b) {
+        return a * b;
+    },
+    arrow: (value) => value - 1,
+};
+console.log(point.x, point.y);
+console.log(point.move(3));
+console.log(point["quoted method"]());
+console.log(point.computedMethod(6, 7));
+console.log(point.arrow(10));
+
+function makeCounter(start) {
+    const state = { start };
+    return {
+        state,
+        next() {
+            state.start += 1;
+            return state.start;
+        },
+    };
+}
+const counter = makeCounter(5);
+console.log(counter.next(), counter.next(), counter.state.start);
@@ -0,0 +1,41 @@
+const target = { a: 1, b: 2 };
+const returned = Object.assign(target, { b: 3, c: 4 }, null, undefined, { d: [5] });
+console.log(returned === target, target);
+console.log(Object.assign({}, "hi", [7]));
+
+const frozen = Object.freeze({ x: 1, nested: { y: 2 } });
+frozen.x = 10;
+frozen.added = true;
+console.log(delete frozen.x, delete frozen.missing);
+frozen.nested.y = 20;
+console.log(frozen, Object.isFrozen(frozen), Object.isFrozen({}), Object.isFrozen(3));
+console.log(Object.freeze(5));
+
+const list = Object.freeze([1, 2]);
+list[0] = 5;
+list[2] = 3;
+console.log(list, delete list[0]);
+
+try {
+  Object.assign(frozen, { x: 2 });
+} catch (error) {
+  console.log("caught");
+}
+
+const animal = {
+  describe() {
+    return `${this.name} says ${this.sound}`;
+  },
+  sound: "...",
+};
+const dog = Object.create(animal);
+dog.name = "Rex";
+dog.sound = "woof";
+console.log(dog.describe(), Object.keys(dog), "describe" in dog);
+const blank = Object.create(animal);
+blank.name = "Blank";
+console.log(blank.describe());
+
+const bare = Object.create(null);
+bare.key = "value";
+console.log(Object.keys(bare), "toString" in bare);
@@ -0,0 +1,35 @@
+const state = { calls: 0 };
+const touch = (value) => {
+    state.calls = state.calls + 1;
+    return value;
+};
+
+const config = {
+    server: { port: 8080, hosts: [`a`, `b`] },
+    describe: (prefix) => `${prefix} config`,
+};
+const empty = null;
+
+console.log(config?.server?.port, config.client?.port, empty?.server.port);
+console.log(config.server.hosts?.[1], config.client?.hosts[touch(0)], state.calls);
+console.log(config.describe?.(`the`), config.missing?.(touch(1)), state.calls);
+
+const counter = {
+    count: 0,
+    increment: null,
+};
+console.log(counter.increment?.(), (counter?.count));
+
+class Box {
+    value = 5;
+
+    get() {
+        return this.value;
+    }
+}
+const item = new Box();
+const noItem = state.missing;
+console.log(item?.get(), noItem?.get(), item.get?.(), item.set?.(1));
+
+const call = (f) => f?.(2);
+console.log(call((x) => x * 3), call(null));
@@ -0,0 +1,20 @@
+console.log(parseInt("42"), parseInt("  -17px"), parseInt("+8"), parseInt("3.99"), parseInt("-0"));
+console.log(parseInt("0x1F"), parseInt("0X1f", 16), parseInt("0x1F", 10), parseInt("-0xff"));
+console.log(parseInt("ff", 16), parseInt("101", 2), parseInt("z", 36), parseInt("777", 8));
+console.log(parseInt("12", 1), parseInt("12", 37), parseInt("9", 8), parseInt("", 10));
+console.log(parseInt("abc"), parseInt(), parseInt(null, 36), parseInt("\n\t 12"));
+console.log(parseInt("123456789012345678901234567890"), parseInt(15.99), parseInt("1e3"));
+
+console.log(parseFloat("3.14"), parseFloat("  -2.5e3xyz"), parseFloat(".5"), parseFloat("-.5e-2"));
+console.log(parseFloat("1."), parseFloat("1.e2"), parseFloat("1e"), parseFloat("1e+"), parseFloat("+1.5E+2"));
+console.log(parseFloat("Infinity"), parseFloat("-Infinityx"), parseFloat("infinity"), parseFloat("."));
+console.log(parseFloat(""), parseFloat("-"), parseFloat("0x10"), parseFloat("  007"), parseFloat("-0"));
+
+console.log(isNaN(NaN), isNaN("abc"), isNaN("12"), isNaN(undefined), isNaN(null), isNaN(""));
+console.log(isFinite(1), isFinite("12"), isFinite(Infinity), isFinite(NaN), isFinite(null), isFinite("x"));
+
+{
+  const parseInt = (text) => "shadowed " + text;
+  console.log(parseInt("1"));
+}
+console.log(globalThis.parseFloat("2.5"));
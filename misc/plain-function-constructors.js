@@ -0,0 +1,25 @@
+function Empty() {}
+const empty = new Empty();
+console.log(empty instanceof Empty, Object.keys(empty).length);
+
+function makePair(first, second) {
+  return [first, second];
+}
+console.log(new makePair(1, 2), makePair(3, 4));
+
+function Primitive() {
+  return 5;
+}
+console.log(new Primitive() instanceof Primitive, Primitive());
+
+function Shape() {}
+Shape.prototype.describe = function () {
+  return "a shape";
+};
+console.log(new Shape().describe(), Object.getPrototypeOf(new Shape()) === Shape.prototype);
+
+function greet(name) {
+  return `hello ${name}`;
+}
+const callbacks = [greet];
+console.log(callbacks.map((callback) => callback("you")), greet.name);
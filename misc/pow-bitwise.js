@@ -0,0 +1,5 @@
+console.log((2 ** 4) | 1);
+console.log((2 ** 0.5) | 0);
+console.log(-(2.5 ** 2) | 0);
+console.log((2 ** 32 + 5) | 0);
+console.log((2 ** 31) | 0);
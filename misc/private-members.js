@@ -0,0 +1,78 @@
+class Counter {
+    #count = 0;
+    #step;
+    label = "counter";
+
+    constructor(step) {
+        this.#step = step;
+    }
+
+    #advance() {
+        this.#count += this.#step;
+        return this.#count;
+    }
+
+    increment() {
+        this.#advance();
+        this.#count++;
+        return this.#count;
+    }
+
+    get() {
+        return this.#count;
+    }
+
+    sameCount(other) {
+        return this.#count === other.#count;
+    }
+
+    countOf(other) {
+        return other?.#count;
+    }
+}
+
+class Other {
+    #count = 100;
+
+    count() {
+        return this.#count;
+    }
+
+    hasCount(value) {
+        return #count in value;
+    }
+}
+
+const counter = new Counter(2);
+console.log(counter.increment(), counter.increment(), counter.get());
+console.log(counter.label, counter.count, counter["#count"]);
+for (const key in counter) {
+    console.log(key);
+}
+
+const other = new Other();
+console.log(other.count(), other.hasCount(counter), other.hasCount(other), other.hasCount({}));
+
+const a = new Counter(1);
+const b = new Counter(1);
+a.increment();
+console.log(a.sameCount(b));
+b.increment();
+console.log(a.sameCount(b), a.countOf(b), a.countOf(null));
+
+class Outer {
+    #secret = "outer";
+
+    reveal() {
+        const outer = this;
+        class Inner {
+            #secret = "inner";
+
+            secret() {
+                return this.#secret;
+            }
+        }
+        return `${new Inner().secret()} ${outer.#secret}`;
+    }
+}
+console.log(new Outer().reveal());
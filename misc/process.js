@@ -0,0 +1,17 @@
+console.log(process.platform);
+console.log(process.env.PATH === undefined ? "no PATH" : "has PATH");
+console.log(process.env.JSRS_SURELY_UNSET_VARIABLE);
+console.log("PATH" in process.env);
+
+process.stdout.write("written ");
+process.stdout.write("without newlines");
+process.stdout.write("\n");
+console.log(process.stdout.write("returns "));
+process.stderr.write("to stderr\n");
+
+const exit = (code) => {
+  process.stdout.write("exiting with " + code);
+  process.exit(code);
+};
+exit(3);
+console.log("unreachable");
@@ -0,0 +1,53 @@
+const steps = [];
+setTimeout(() => console.log("timeout", steps), 0);
+Promise.resolve(1)
+  .then((value) => value + 1)
+  .then((value) => {
+    steps.push(value);
+    throw "failed";
+  })
+  .catch((reason) => `caught ${reason}`)
+  .finally(() => steps.push("finally"))
+  .then((value) => console.log(value, steps));
+
+queueMicrotask(() => console.log("microtask"));
+console.log("sync");
+
+const pending = new Promise(() => {});
+const fulfilled = Promise.resolve({ answer: 42 });
+const rejected = Promise.reject("no");
+rejected.catch(() => {});
+console.log(pending, fulfilled, rejected, Promise.resolve(fulfilled) === fulfilled);
+
+new Promise((resolve, reject) => {
+  resolve("first");
+  reject("ignored");
+  resolve("ignored too");
+}).then((value) => console.log(value));
+
+new Promise(() => {
+  throw "from executor";
+}).catch((reason) => console.log("rejected", reason));
+
+const delayed = (value, ms) => new Promise((resolve) => setTimeout(() => resolve(value), ms));
+Promise.all([delayed("a", 20), "b", Promise.resolve("c")]).then((values) => console.log("all", values));
+Promise.all([]).then((values) => console.log("empty", values));
+Promise.all([delayed(1, 10), Promise.reject("bad")]).catch((reason) => console.log("all failed", reason));
+Promise.race([delayed("slow", 30), delayed("fast", 5)]).then((winner) => console.log("race", winner));
+
+const thenable = {
+  then(resolve) {
+    resolve("thenable");
+  },
+};
+Promise.resolve(thenable).then((value) => console.log(value));
+
+Promise.resolve(1).then(() => console.log("tick 1")).then(() => console.log("tick 3"));
+Promise.resolve(2).then(() => console.log("tick 2")).then(() => console.log("tick 4"));
+
+const timer = setTimeout(() => console.log("cleared"), 5);
+clearTimeout(timer);
+setTimeout((a, b) => console.log("args", a + b), 15, 1, 2);
+Promise.resolve("kept")
+  .finally(() => delayed("ignored", 1))
+  .then((value) => console.log(value));
@@ -0,0 +1,57 @@
+const point = { x: 1 };
+Object.defineProperty(point, "y", { value: 2 });
+Object.defineProperty(point, "z", { value: 3, enumerable: true, writable: true });
+point.y = 20;
+point.z = 30;
+console.log(point, point.y, Object.keys(point), delete point.y, point.y);
+console.log(Object.getOwnPropertyDescriptor(point, "y"));
+console.log(Object.getOwnPropertyDescriptor(point, "x"));
+console.log(Object.getOwnPropertyDescriptor(point, "missing"));
+
+const temperature = { celsius: 20 };
+Object.defineProperty(temperature, "fahrenheit", {
+  get() {
+    return this.celsius * 1.8 + 32;
+  },
+  set(value) {
+    this.celsius = (value - 32) / 1.8;
+  },
+  enumerable: true,
+  configurable: true,
+});
+temperature.fahrenheit = 212;
+console.log(temperature.celsius, temperature.fahrenheit, temperature);
+const descriptor = Object.getOwnPropertyDescriptor(temperature, "fahrenheit");
+console.log("value" in descriptor, "get" in descriptor, descriptor.enumerable);
+
+const account = {
+  balance: 100,
+  get formatted() {
+    return `$${this.balance}`;
+  },
+  set formatted(text) {
+    this.balance = Number(text.slice(1));
+  },
+};
+account.formatted = "$250";
+console.log(account.balance, account.formatted, account, { ...account });
+
+const fixed = Object.defineProperties(
+  {},
+  {
+    a: { value: 1, enumerable: true },
+    b: { value: 2 },
+  }
+);
+console.log(fixed, fixed.b, Object.entries(fixed));
+Object.defineProperty(fixed, "a", { value: 1 });
+
+const child = Object.create(fixed, { own: { value: "mine", enumerable: true } });
+child.a = 5;
+console.log(child.a, child.own, Object.keys(child));
+
+const frozen = Object.freeze({ key: "value" });
+console.log(Object.getOwnPropertyDescriptor(frozen, "key"));
+const array = [1, 2];
+Object.defineProperty(array, 0, { value: "first" });
+console.log(array, Object.getOwnPropertyDescriptor(array, "length"));
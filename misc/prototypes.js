@@ -0,0 +1,38 @@
+const animal = {
+  describe() {
+    return `${this.name} says ${this.sound}`;
+  },
+  sound: "...",
+};
+const dog = Object.create(animal);
+dog.name = "Rex";
+animal.legs = 4;
+console.log(dog.describe(), dog.legs, "legs" in dog, dog);
+for (const key in dog) {
+  console.log(key);
+}
+console.log(Object.getPrototypeOf(dog) === animal);
+console.log(Object.getPrototypeOf({}) === Object.prototype);
+
+const bare = Object.create(null);
+console.log(bare, Object.getPrototypeOf(bare));
+bare.key = 1;
+Object.setPrototypeOf(bare, dog);
+console.log(bare.name, bare.sound, bare);
+try {
+  Object.setPrototypeOf(animal, bare);
+} catch (error) {
+  console.log("cyclic");
+}
+
+function Counter(start) {
+  this.count = start;
+}
+Counter.prototype.increment = function () {
+  this.count += 1;
+  return this.count;
+};
+const counter = new Counter(5);
+counter.increment();
+console.log(counter.increment(), counter.count, counter instanceof Counter, {} instanceof Counter);
+console.log(counter.constructor === Counter, Object.keys(Counter.prototype));
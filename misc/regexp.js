@@ -0,0 +1,66 @@
+const digits = /foo\d+/;
+console.log(digits.test(`foo123`));
+console.log(digits.test(`foo`));
+console.log(digits.test(`xxfoo7yy`));
+console.log(digits);
+console.log(digits.source);
+console.log(digits.flags);
+console.log(/a/gimsy.flags);
+
+const found = /(\w+)@(\w+)\.com/.exec(`mail alice@example.com now`);
+console.log(found[0]);
+console.log(found[1]);
+console.log(found[2]);
+console.log(found.index);
+console.log(found.length);
+console.log(/x/.exec(`abc`));
+
+console.log(/^hello$/i.test(`HeLLo`));
+console.log(/^a.c$/.test(`a\nc`));
+console.log(/^a.c$/s.test(`a\nc`));
+console.log(/^b$/m.test(`a\nb\nc`));
+console.log(/^b$/.test(`a\nb\nc`));
+console.log(/colou?r/.test(`color`));
+console.log(/^(ab)+$/.test(`ababab`));
+console.log(/^a{2,3}$/.test(`aaaa`));
+console.log(/^a{2,3}$/.test(`aaa`));
+console.log(/a{,2}/.test(`a{,2}`));
+console.log(/^[a-c]+[^a-c]$/.test(`abcabcd`));
+console.log(/^[\d\s-]+$/.test(`12 34-56`));
+console.log(/\bcat\b/.test(`concat`));
+console.log(/\bcat\b/.test(`a cat here`));
+console.log(/^(a|b|cd)*$/.test(`abcdab`));
+console.log(/(\w)\1/.exec(`abccd`)[0]);
+console.log(/<.+?>/.exec(`<a><b>`)[0]);
+console.log(/<.+>/.exec(`<a><b>`)[0]);
+console.log(/foo(?=bar)/.test(`foobar`));
+console.log(/foo(?!bar)/.test(`foobar`));
+console.log(/(?<year>\d{4})-(?<month>\d\d)/.exec(`on 2024-05`).groups.month);
+console.log(/(a)|(b)/.exec(`b`)[1]);
+console.log(/A\x42\//.test(`AB/`));
+console.log(/[.*+?]/.test(`?`));
+
+const global = /o/g;
+console.log(global.exec(`foo boo`).index);
+console.log(global.lastIndex);
+console.log(global.exec(`foo boo`).index);
+console.log(global.exec(`foo boo`).index);
+console.log(global.exec(`foo boo`).index);
+console.log(global.exec(`foo boo`));
+console.log(global.lastIndex);
+
+const sticky = /a/y;
+console.log(sticky.test(`aab`));
+console.log(sticky.test(`aab`));
+console.log(sticky.test(`aab`));
+console.log(sticky.lastIndex);
+
+function count(text) {
+    const word = /\w+/g;
+    let n = 0;
+    while (word.test(text)) {
+        n += 1;
+    }
+    return n;
+}
+console.log(count(`one two  three`));
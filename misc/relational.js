@@ -0,0 +1,21 @@
+function nan() {
+    return 0 / 0;
+}
+
+console.log(1 < 2, 2 < 1, 1 > 2, 2 > 1, 1 <= 1, 1 >= 1, 2 <= 1, 1 >= 2);
+console.log(nan() < 1, nan() > 1, nan() <= nan(), nan() >= 1, 1 >= nan());
+console.log("a" < "b", "b" > "a", "abc" < "abd", "ab" < "abc", "B" < "a", "a" <= "a");
+console.log("10" < "9", "10" < 9, 10 < "9", "10" >= "9");
+console.log("12px" < 5, "12px" >= 5, "" < 1, " 3 " > 2);
+console.log(null < 1, null >= 0, null <= 0, null > -1, true > false, true >= 1);
+console.log([2] > 1, [1, 2] < "1,3", [] < 1);
+console.log("\u{1F600}" > "￿", "é" > "z");
+
+for (let i = 5; i >= 0; i = i - 2) {
+    console.log(i);
+}
+let n = 0;
+while (n <= 3) {
+    n = n + 1;
+}
+console.log(n);
@@ -0,0 +1,21 @@
+function nan() {
+    return 0 / 0;
+}
+
+console.log(7 % 3, -7 % 3, 7 % -3, -7 % -3, 5.5 % 2, 7 % 0, 0 % 5, -0 % 5);
+console.log(2 ** 10, 2 ** -1, (-2) ** 3, 4 ** 0.5, 2 ** 0.5, nan() ** 0, 1 ** nan());
+console.log(2 ** 3 ** 2, (2 ** 3) ** 2, -(2 ** 2));
+
+let x = 17;
+x %= 5;
+console.log(x);
+x **= 3;
+console.log(x);
+
+let total = 0;
+for (let i = 0; i < 20; i++) {
+    if (i % 3 === 0) {
+        total += i ** 2;
+    }
+}
+console.log(total);
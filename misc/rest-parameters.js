@@ -0,0 +1,40 @@
+function sum(first, ...rest) {
+    let total = first;
+    for (const value of rest) {
+        total += value;
+    }
+    return total;
+}
+
+function count(...items) {
+    return items.length;
+}
+
+function pad(a, b) {
+    return b;
+}
+
+console.log(sum(1, 2, 3, 4));
+console.log(sum(5));
+console.log(count());
+console.log(count(1, 2, 3));
+console.log(pad(1));
+
+const join = (head, ...tail) => head + tail.length;
+console.log(join(1, 2, 3));
+
+const collect = function (...values) {
+    return values.length;
+};
+console.log(collect(7, 8));
+
+class Bag {
+    constructor(...items) {
+        this.items = items;
+    }
+    add(...more) {
+        return this.items.length + more.length;
+    }
+}
+const bag = new Bag(1, 2);
+console.log(bag.add(3, 4, 5));
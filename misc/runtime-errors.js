@@ -0,0 +1,67 @@
+function attempt(label, action) {
+  try {
+    console.log(label, action());
+  } catch (error) {
+    console.log(label, error instanceof TypeError, error instanceof RangeError, `${error}`);
+  }
+}
+
+// Node names the expression in these messages, which the generated program doesn't know
+function attemptCall(label, action) {
+  try {
+    console.log(label, action());
+  } catch (error) {
+    console.log(label, error instanceof TypeError, error.name);
+  }
+}
+
+const missing = undefined;
+const nothing = null;
+const config = { retries: 3 };
+attempt("read undefined", () => missing.length);
+attempt("read null", () => nothing.name);
+attempt("write undefined", () => {
+  missing.value = 1;
+});
+attemptCall("call number", () => config.retries());
+attemptCall("call missing", () => config.onRetry());
+attemptCall("call string", () => "text"());
+attemptCall("construct arrow", () => new (() => {})());
+
+class Point {
+  constructor(x) {
+    this.x = x;
+  }
+}
+attempt("class without new", () => Point(1));
+
+attempt("toFixed", () => (1.5).toFixed(200));
+attempt("repeat", () => "ab".repeat(-1));
+attempt("typed array", () => new Uint8Array(-1));
+attempt("keys of null", () => Object.keys(null));
+attempt("frozen", () => Object.defineProperty(Object.freeze({}), "key", { value: 1 }));
+
+try {
+  JSON.parse("{ broken");
+} catch (error) {
+  console.log(error instanceof SyntaxError, error instanceof Error, error.name);
+}
+
+try {
+  config.retries.toFixed(1).missing.deeper;
+} catch (error) {
+  console.log(error.name, error.message, error.stack.split("\n")[0]);
+}
+
+const primitive = true;
+console.log(primitive.anything, (5).missing, "text".missing);
+
+const list = [1, 2, 3];
+list[Infinity] = "infinite";
+console.log(list.length, list[Infinity]);
+
+const push = list.push;
+attemptCall("detached push", () => push(4));
+
+console.log("a1b22c".split(/\d/), "a1b22c".split(/(\d)+/), "a, b ,c".split(/\s*,\s*/, 2));
+console.log("abc".split(/(?:)/), "".split(/x/), "".split(/(?:)/), "AxBXc".split(/x/i));
@@ -0,0 +1,20 @@
+let a = 1;
+let b = 2;
+const c = (a += 1, b += 10, a + b);
+console.log(a, b, c);
+
+for (let i = 0, j = 10; i < j; i++, j--) {
+    console.log(i, j);
+}
+
+function next(counter) {
+    return (counter.calls += 1, counter.value * 2);
+}
+const counter = { calls: 0, value: 21 };
+console.log(next(counter), counter.calls);
+
+a = 0, b = 0;
+console.log(a, b);
+console.log((1, 2, "three"));
+const values = [(a++, a), (a++, a)];
+console.log(values[0], values[1]);
@@ -0,0 +1,43 @@
+function makeCounter() {
+  let count = 0;
+  return {
+    increment: () => {
+      count += 1;
+      return count;
+    },
+    current: () => count,
+  };
+}
+const counter = makeCounter();
+counter.increment();
+counter.increment();
+console.log(counter.increment(), counter.current(), makeCounter().current());
+
+let sum = 0;
+[1, 2, 3].forEach((x) => {
+  sum += x;
+});
+console.log(sum);
+
+const quadruple = (x) => double(double(x));
+console.log(quadruple(3));
+
+function double(x) {
+  return x * 2;
+}
+
+function factorial(n) {
+  return n <= 1 ? 1 : n * factorial(n - 1);
+}
+console.log(factorial(5));
+
+const callbacks = [];
+for (let i = 0; i < 3; i++) {
+  callbacks.push(() => i);
+}
+console.log(callbacks.map((callback) => callback()));
+
+let label = "before";
+const read = () => label;
+label = "after";
+console.log(read());
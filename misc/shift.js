@@ -0,0 +1,14 @@
+console.log(1 << 3, 1 << 31, 1 << 32, 1 << 33, 5 << -1);
+console.log(-16 >> 2, 16 >> 2, -1 >> 31, 1024 >> 33);
+console.log(-1 >>> 0, -16 >>> 2, 16 >>> 2, -1 >>> 31, 4294967296 >>> 0, 1 >>> 32);
+console.log(2.7 << 1.9, "8" >> "1", null << 2);
+
+function toHex(value) {
+    let digits = [];
+    for (let i = 28; i >= 0; i = i - 4) {
+        digits[digits.length] = (value >>> i) & 15;
+    }
+    return digits;
+}
+const nibbles = toHex(0xdeadbeef | 0);
+console.log(`${nibbles}`);
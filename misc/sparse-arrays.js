@@ -0,0 +1,10 @@
+const list = [1, 2, 3];
+list[1e9] = "far";
+console.log(list.length, list[1e9], 1e9 in list, list[5], Object.keys(list));
+console.log(Object.getOwnPropertyDescriptor(list, "length").value, Object.getOwnPropertyDescriptor(list, 1e9));
+const grid = [];
+grid[1500] = "sparse";
+grid[1000] = "dense";
+console.log(grid.length, grid[1500], grid[1000], Object.keys(grid).length);
+grid[1400] = "grown";
+console.log(grid.length, grid[1500], grid[1400], Object.keys(grid).length);
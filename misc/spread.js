@@ -0,0 +1,33 @@
+const xs = [1, 2, 3];
+const ys = [...xs, 4];
+console.log(`${ys}`, ys.length);
+console.log(`${[0, ...xs, ...ys, 5]}`);
+console.log(`${[...xs]}`, [...xs] === xs);
+console.log(`${[..."hey"]}`);
+console.log([...[]].length);
+
+const copy = [...xs];
+copy[0] = 100;
+console.log(xs[0], copy[0]);
+
+const base = { a: 1, b: 2 };
+const extended = { ...base, c: 3 };
+console.log(extended.a, extended.b, extended.c);
+
+const overridden = { a: 0, ...base, b: 20 };
+console.log(overridden.a, overridden.b);
+
+const fromArray = { ...["x", "y"] };
+console.log(fromArray[0], fromArray[1]);
+
+const fromNothing = { ...null, ...42, ...true, d: 4 };
+console.log(fromNothing.d);
+
+const nested = { inner: { value: 1 } };
+const shallow = { ...nested };
+shallow.inner.value = 2;
+console.log(nested.inner.value);
+
+for (const key in { ...base, ...{ z: 26 } }) {
+    console.log(key);
+}
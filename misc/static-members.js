@@ -0,0 +1,47 @@
+let created = 0;
+
+class Point {
+    static origin = new Point(0, 0);
+    static count = 0;
+
+    constructor(x, y) {
+        this.x = x;
+        this.y = y;
+    }
+
+    static create(x, y) {
+        this.count++;
+        return new this(x, y);
+    }
+
+    static {
+        created = this.count;
+        const label = `${this.origin.x},${this.origin.y}`;
+        this.label = label;
+    }
+
+    length() {
+        return Math.sqrt(this.x * this.x + this.y * this.y);
+    }
+}
+
+const p = Point.create(3, 4);
+Point.create(1, 1);
+console.log(p.length(), Point.count, created, Point.label);
+console.log(Point.origin.length(), p.create, p.count);
+
+class Registry {
+    static entries = [];
+    static size = this.entries.length;
+
+    static {
+        let total = 0;
+        for (let i = 1; i <= 3; i++) {
+            total += i;
+        }
+        this.total = total;
+    }
+
+    static double = () => this.total * 2;
+}
+console.log(Registry.size, Registry.total, Registry.double());
@@ -0,0 +1,43 @@
+// Either operand being a string makes `+` concatenate
+const i = 3;
+console.log("n = " + i);
+console.log(i + " apples");
+console.log("a" + "b" + 1 + 2);
+console.log(1 + 2 + "c");
+console.log("" + true + null + void 0);
+
+// Otherwise the operands are added as numbers
+console.log(true + 1, null + 5, true + true);
+console.log(void 0 + 1);
+
+// Arrays and objects are converted to their primitive values first
+console.log([1, 2] + [3]);
+console.log("list: " + [4, 5, 6]);
+console.log({} + "!");
+
+// An object can convert itself with `valueOf` or `toString`
+const money = {
+    valueOf() {
+        return 42;
+    },
+};
+console.log(money + 1, money + "!");
+
+class Point {
+    constructor(x, y) {
+        this.x = x;
+        this.y = y;
+    }
+    toString() {
+        return "(" + this.x + ", " + this.y + ")";
+    }
+}
+console.log("at " + new Point(1, 2));
+console.log(new Point(3, 4) + new Point(5, 6));
+console.log(new Point(1, 1) < new Point(1, 2), money == 42);
+
+let message = "count:";
+for (let k = 0; k < 3; k++) {
+    message += " " + k;
+}
+console.log(message);
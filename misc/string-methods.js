@@ -0,0 +1,31 @@
+const text = "Hello, World";
+console.log(text.length, "".length, "héllo".length, "😀".length);
+console.log(text[0], text[7], text[100] === undefined);
+console.log(text.charAt(4), text.charAt(-1) === "", text.charCodeAt(0), text.charCodeAt(99));
+
+// `slice` counts negative positions from the end, `substring` swaps its positions
+console.log(text.slice(7), text.slice(-5), text.slice(0, -7), text.slice(5, 2) === "");
+console.log(text.substring(7), text.substring(5, 0), text.substring(-3, 2));
+
+console.log(text.indexOf("o"), text.indexOf("o", 5), text.indexOf("xyz"), text.indexOf(""));
+console.log(text.includes("World"), text.includes("world"), text.includes("H", 1));
+
+const parts = "a,b,,c".split(",");
+console.log(parts.length, parts[0], parts[2] === "", parts[3]);
+console.log("abc".split("").length, "a b c".split(" ", 2).length, "abc".split()[0]);
+console.log("one--two--three".split("--")[2]);
+
+console.log(text.toUpperCase(), text.toLowerCase(), "straße".toUpperCase());
+console.log("[" + "  \t padded \n ".trim() + "]");
+console.log("ab".repeat(3), "x".repeat(0) === "");
+console.log("5".padStart(3, "0"), "abc".padStart(8, "12"), "abc".padStart(2), "7".padStart(4));
+
+// Numbers and other values are converted to strings first
+const format = (n) => ("" + n).padStart(6, ".");
+console.log(format(42), format(3.5));
+
+let result = "";
+for (const word of "the quick brown fox".split(" ")) {
+    result += word.charAt(0).toUpperCase() + word.slice(1) + " ";
+}
+console.log(result.trim());
@@ -0,0 +1,43 @@
+function describe(n) {
+    let text = `${n}:`;
+    switch (n) {
+        case 1:
+            text = `${text} one`;
+        case 2:
+            text = `${text} one-or-two`;
+            break;
+        default:
+            text = `${text} other`;
+        case 3:
+            text = `${text} three-or-other`;
+            break;
+        case 4:
+        case 5:
+            text = `${text} four-or-five`;
+    }
+    return text;
+}
+
+for (let i = 0; i < 7; i++) {
+    console.log(describe(i));
+}
+
+function last(n) {
+    switch (n) {
+        case 0:
+            return `zero`;
+        default:
+            return `not zero`;
+    }
+}
+console.log(last(0));
+console.log(last(-1));
+
+const same = [1];
+switch (same) {
+    case [1]:
+        console.log(`a copy is the same`);
+        break;
+    case same:
+        console.log(`only the same object is`);
+}
@@ -0,0 +1,25 @@
+const tag = Symbol("tag");
+const other = Symbol("tag");
+const anonymous = Symbol();
+console.log(tag, anonymous, tag === other, tag === tag, tag == other);
+console.log(tag.description, anonymous.description, tag.toString(), !!anonymous);
+
+const box = { name: "box", [tag]: 1 };
+box[other] = 2;
+box[tag] += 10;
+console.log(box, box[tag], box[other], tag in box, anonymous in box);
+console.log(Object.keys(box), JSON.stringify(box), { ...box });
+for (const key in box) {
+  console.log(key);
+}
+delete box[other];
+console.log(Object.getOwnPropertySymbols(box), Object.assign({}, box));
+
+const registered = Symbol.for("app");
+console.log(registered === Symbol.for("app"), registered === Symbol("app"));
+console.log(registered, Symbol.keyFor(registered), Symbol.keyFor(tag));
+
+const hidden = {};
+Object.defineProperty(hidden, tag, { value: "secret" });
+console.log(hidden, hidden[tag], Object.getOwnPropertySymbols(hidden).length);
+console.log(Symbol.iterator, Symbol.iterator === Symbol.iterator, Symbol.iterator.description);
@@ -0,0 +1,29 @@
+function tag(strings, ...values) {
+    let result = `${strings.length}:`;
+    for (let i = 0; i < values.length; i++) {
+        result = `${result}[${strings[i]}|${values[i]}]`;
+    }
+    return `${result}[${strings[strings.length - 1]}]`;
+}
+
+const a = 1;
+const b = 2;
+console.log(tag`a ${a} and b ${b}!`);
+console.log(tag`no substitutions`);
+console.log(tag`${a}${b}`);
+
+function raw(strings) {
+    return `${strings.raw[0]} vs ${strings[0]}`;
+}
+console.log(raw`line\nbreak`);
+console.log(raw`bad \unicode`);
+
+const upper = (strings, value) => `<${strings[0]}${value * 10}${strings[1]}>`;
+console.log(upper`x${a}y`);
+
+const helpers = {
+    wrap(strings, value) {
+        return `(${strings[0]}${value}${strings[1]})`;
+    },
+};
+console.log(helpers.wrap`left ${b} right`);
@@ -0,0 +1,18 @@
+const n = 3;
+const name = `world`;
+console.log(`n = ${n}`);
+console.log(`hello, ${name}!`);
+console.log(`${n}${n}`);
+console.log(`sum: ${n + 1.5}, product: ${n * 2}`);
+console.log(`nothing: ${null}, ${true}`);
+console.log(`list: ${[1, 2, 3]}`);
+console.log(`tab:\there\nnew line A \x42 \u{1F600} \` \${not} \\`);
+console.log(`outer ${`inner ${n}`} done`);
+console.log(`multi
+line`);
+console.log(`${n < 5 ? `small ${n}` : `big ${n}`}`);
+
+function greet(who) {
+    return `hi ${who}`;
+}
+console.log(greet(`${name}s`));
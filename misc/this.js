@@ -0,0 +1,67 @@
+"use strict";
+
+const counter = {
+    count: 0,
+    increment() {
+        this.count += 1;
+        return this;
+    },
+    incrementLater() {
+        return () => this.increment();
+    },
+};
+counter.increment().increment();
+console.log(counter.count);
+counter.incrementLater()();
+console.log(counter.count);
+
+const Point = function (x, y) {
+    this.x = x;
+    this.y = y;
+};
+Point.prototype = {
+    sum: function () {
+        return this.x + this.y;
+    },
+};
+const point = new Point(3, 4);
+console.log(point.x, point.y, point.sum());
+
+function plain() {
+    return this;
+}
+console.log(plain() === void 0);
+
+const detached = counter.increment;
+const other = { count: 10, increment: detached };
+other.increment();
+console.log(other.count, counter.count);
+
+class Box {
+    constructor(value) {
+        this.value = value;
+    }
+    map(f) {
+        return new Box(f(this.value));
+    }
+    unwrap() {
+        const read = () => this.value;
+        return read();
+    }
+}
+console.log(new Box(2).map((v) => v * 10).unwrap());
+
+const arrowOutside = {
+    name: "outer",
+    make() {
+        const inner = {
+            name: "inner",
+            get: () => this.name,
+            own() {
+                return this.name;
+            },
+        };
+        return [inner.get(), inner.own()];
+    },
+};
+console.log(`${arrowOutside.make()}`);
@@ -0,0 +1,68 @@
+function check(n) {
+    if (n < 0) {
+        throw `negative: ${n}`;
+    }
+    return n;
+}
+
+function safe(n) {
+    try {
+        return check(n);
+    } catch (error) {
+        console.log(`caught ${error}`);
+        return 0;
+    } finally {
+        console.log(`checked ${n}`);
+    }
+}
+console.log(safe(1));
+console.log(safe(-1));
+
+function overridden() {
+    try {
+        throw 1;
+    } finally {
+        return `finally wins`;
+    }
+}
+console.log(overridden());
+
+let count = 0;
+for (let i = 0; i < 5; i++) {
+    try {
+        if (2 < i) {
+            throw i;
+        }
+        count++;
+    } catch {
+        continue;
+    }
+    console.log(`no exception at ${i}`);
+}
+console.log(`count ${count}`);
+
+function rethrow() {
+    try {
+        try {
+            throw `inner`;
+        } finally {
+            console.log(`inner finally`);
+        }
+    } catch (error) {
+        console.log(`outer caught ${error}`);
+        try {
+            return `returned from nested try`;
+        } finally {
+            console.log(`nested finally`);
+        }
+    }
+}
+console.log(rethrow());
+
+let result = 0;
+try {
+    result = check(-5);
+} catch (error) {
+    result = error;
+}
+console.log(result);
@@ -0,0 +1,43 @@
+const positions = new Float64Array(4);
+positions[0] = 1.5;
+positions[1] = -0;
+positions[3] = "2.25";
+positions[4] = 10;
+console.log(positions, positions.length, positions[2], positions[4], positions["3"]);
+
+const ints = new Int32Array([1.9, -1.9, 2147483648, "7", true]);
+const bytes = new Uint8Array([255, 256, -1, 3.7]);
+console.log(ints, bytes, Int32Array.BYTES_PER_ELEMENT, Float64Array.BYTES_PER_ELEMENT);
+bytes[0] += 1;
+ints[1] *= 3;
+console.log(bytes[0], ints[1]);
+
+const filled = new Float64Array(6).fill(0.5).fill(2, 1, 3).fill(7, -1);
+console.log(filled, `${filled}`, Object.keys(new Int32Array(2)));
+
+const target = new Int32Array(6);
+target.set([1, 2, 3]);
+target.set(new Uint8Array([9, 8]), 4);
+console.log(target);
+
+const middle = target.subarray(1, 4);
+middle[0] = 100;
+middle.fill(-5, 1);
+console.log(middle, middle.length, target, target.subarray(-2), target.subarray(4, 2));
+target.set(target.subarray(0, 3), 3);
+console.log(target);
+
+let sum = 0;
+for (const value of new Float64Array([0.25, 0.5, 0.75])) {
+  sum += value;
+}
+console.log(sum, [...new Uint8Array([3, 2, 1])], new Float64Array(new Int32Array([4, 5])));
+console.log({ nested: { deeper: { array: new Uint8Array(1) } } }, new Uint8Array(0), new Float64Array(12));
+
+const velocities = new Float64Array(3 * 2);
+for (let step = 0; step < 10; step++) {
+  for (let i = 0; i < velocities.length; i++) {
+    velocities[i] += i * 0.1;
+  }
+}
+console.log(velocities);
@@ -0,0 +1,16 @@
+const a = 5;
+console.log(-a, -a, +a);
+console.log(!a, !!a, !0, !"", !"text", !null, ![]);
+console.log(~a, ~-1, ~0, ~~3.7, ~2147483648);
+console.log(void 0);
+console.log(void a);
+
+const counter = { calls: 0 };
+function touch(counter) {
+    counter.calls += 1;
+    return counter.calls;
+}
+console.log(void touch(counter), counter.calls);
+if (!(a < 3)) {
+    console.log("not less");
+}
@@ -0,0 +1,32 @@
+const counter = { count: 0 };
+console.log(counter.count++);
+console.log(counter.count);
+console.log(++counter.count);
+console.log(counter.count--);
+console.log(--counter.count);
+console.log(counter.count);
+
+const numbers = [10, 20, 30];
+let i = 1;
+console.log(numbers[i]--);
+console.log(++numbers[i + 1]);
+console.log(numbers[0]++, numbers[0]);
+console.log(`${numbers}`);
+
+const text = { value: "5" };
+console.log(text.value++);
+console.log(text.value);
+
+const evaluations = { count: 0 };
+const target = { n: 1 };
+function getTarget() {
+    evaluations.count += 1;
+    return target;
+}
+getTarget().n++;
+++getTarget()["n"];
+console.log(target.n, evaluations.count);
+
+const empty = {};
+empty.missing++;
+console.log(empty.missing);
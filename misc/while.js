@@ -0,0 +1,13 @@
+let total = 0;
+let i = 0;
+while (i < 5) {
+    total = total + i;
+    i++;
+}
+console.log(`while ran ${i} times, total ${total}`);
+
+let j = 10;
+do {
+    console.log(`do-while ran with ${j}`);
+    j++;
+} while (j < 3);
@@ -0,0 +1,8251 @@
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    iter, panic,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
+
+mod js_cell {
+    use std::{
+        cell::UnsafeCell,
+        marker::PhantomData,
+        ops::{Deref, DerefMut},
+        ptr::NonNull,
+    };
+
+    /// Implements RefCell like behaviour but without checking ownership rules during runtime.
+    ///
+    /// This may be completely invalid and may cause undefined behaviour,
+    /// so I may need to replace this with RefCell, if strange behaviour is found during runtime
+    pub struct JsCell<T> {
+        value: UnsafeCell<T>,
+    }
+    impl<T> JsCell<T> {
+        pub fn new(value: T) -> Self {
+            JsCell {
+                value: UnsafeCell::new(value),
+            }
+        }
+
+        #[inline]
+        pub fn borrow(&self) -> &T {
+            unsafe { &*self.value.get() }
+        }
+
+        #[inline]
+        pub fn borrow_mut<'a>(&'a self) -> RefMut<'a, T> {
+            let value = unsafe { NonNull::new_unchecked(self.value.get()) };
+            RefMut {
+                value,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    pub struct RefMut<'a, T: ?Sized> {
+        value: NonNull<T>,
+        marker: PhantomData<&'a T>,
+    }
+
+    impl<T: ?Sized> Deref for RefMut<'_, T> {
+        type Target = T;
+
+        #[inline]
+        fn deref(&self) -> &T {
+            // SAFETY: the value is accessible as long as we hold our borrow.
+            unsafe { self.value.as_ref() }
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for RefMut<'_, T> {
+        #[inline]
+        fn deref_mut(&mut self) -> &mut T {
+            // SAFETY: the value is accessible as long as we hold our borrow.
+            unsafe { self.value.as_mut() }
+        }
+    }
+}
+
+use js_cell::JsCell;
+
+#[derive(Clone)]
+pub struct ConsoleStruct {
+    pub log: JsValue,
+    pub error: JsValue,
+    pub warn: JsValue,
+    pub info: JsValue,
+    pub debug: JsValue,
+    pub assert: JsValue,
+    pub time: JsValue,
+    pub time_end: JsValue,
+    pub time_log: JsValue,
+}
+
+#[derive(Clone)]
+pub struct ProcessStruct {
+    pub argv: JsValue,
+    pub env: JsValue,
+    pub exit: JsValue,
+    pub platform: JsValue,
+    pub stdout: JsValue,
+    pub stderr: JsValue,
+}
+
+#[derive(Clone)]
+pub struct JsonStruct {
+    pub parse: JsValue,
+    pub stringify: JsValue,
+}
+
+thread_local! {
+    static CONSOLE_OBJ: ConsoleStruct = ConsoleStruct {
+        log: JsValue::new_function(Box::new(console::log)),
+        // Like in node, errors and warnings go to stderr, the rest to stdout
+        error: JsValue::new_function(Box::new(console::error)),
+        warn: JsValue::new_function(Box::new(console::error)),
+        info: JsValue::new_function(Box::new(console::log)),
+        debug: JsValue::new_function(Box::new(console::log)),
+        assert: JsValue::new_function(Box::new(console::assert)),
+        time: JsValue::new_function(Box::new(console::time)),
+        time_end: JsValue::new_function(Box::new(console::time_end)),
+        time_log: JsValue::new_function(Box::new(console::time_log)),
+    };
+
+    static PROCESS_OBJ: ProcessStruct = ProcessStruct {
+        argv: JsValue::new_array(
+            // We pretend as if the program is running on node, because nodejs scripts
+            // receive that as the first argument
+            iter::once(String::from("node")).chain(std::env::args())
+            .map(|a| JsValue::String(JsString::from(a))).collect::<Vec<_>>()
+        ),
+        env: JsValue::from_entries(
+            std::env::vars_os()
+                .map(|(key, value)| {
+                    let value = JsValue::String(JsString::from(value.to_string_lossy().as_ref()));
+                    (PropertyKey::from(key.to_string_lossy().as_ref()), value)
+                })
+                .collect()
+        ),
+        exit: JsValue::new_function(Box::new(|args| {
+            let code = match arg(args, 0) {
+                JsValue::Undefined => 0,
+                code => code.to_int32(),
+            };
+            // Unlike returning from `main`, exiting doesn't flush what `write` left unfinished
+            let _ = io::stdout().flush();
+            std::process::exit(code)
+        })),
+        // The names that node uses for the operating systems
+        platform: JsValue::from(match std::env::consts::OS {
+            "macos" => "darwin",
+            "windows" => "win32",
+            os => os,
+        }),
+        stdout: output_stream(|text| {
+            let _ = io::stdout().write_all(text.as_bytes());
+        }),
+        stderr: output_stream(|text| {
+            let _ = io::stderr().write_all(text.as_bytes());
+        }),
+    };
+
+    static JSON_OBJ: JsonStruct = JsonStruct {
+        parse: JsValue::new_function(Box::new(|args| {
+            json::parse(arg(args, 0).to_js_string().as_str(), &arg(args, 1))
+        })),
+        stringify: JsValue::new_function(Box::new(|args| {
+            json::stringify(arg(args, 0), &arg(args, 1), &arg(args, 2))
+        })),
+    };
+
+    /// The methods of every regular expression, which receive it as `this`
+    static REGEXP_METHODS: Rc<Methods> = Rc::new(Methods::from(HashMap::from([
+        (JsString::from("exec"), JsValue::new_method(Box::new(|this, args| {
+            regexp_exec(this, &arg(args, 0))
+        }))),
+        (JsString::from("test"), JsValue::new_method(Box::new(|this, args| {
+            JsValue::Boolean(!matches!(regexp_exec(this, &arg(args, 0)), JsValue::Null))
+        }))),
+    ])));
+
+    /// The methods of every array, which receive it as `this`
+    static ARRAY_METHODS: Rc<Methods> = Rc::new(Methods::from(HashMap::from([
+        (JsString::from("push"), JsValue::new_method(Box::new(array::push))),
+        (JsString::from("pop"), JsValue::new_method(Box::new(array::pop))),
+        (JsString::from("shift"), JsValue::new_method(Box::new(array::shift))),
+        (JsString::from("unshift"), JsValue::new_method(Box::new(array::unshift))),
+        (JsString::from("splice"), JsValue::new_method(Box::new(array::splice))),
+        (JsString::from("join"), JsValue::new_method(Box::new(array::join))),
+        (JsString::from("slice"), JsValue::new_method(Box::new(array::slice))),
+        (JsString::from("concat"), JsValue::new_method(Box::new(array::concat))),
+        (JsString::from("indexOf"), JsValue::new_method(Box::new(array::index_of))),
+        (JsString::from("includes"), JsValue::new_method(Box::new(array::includes))),
+        (JsString::from("reverse"), JsValue::new_method(Box::new(array::reverse))),
+        (JsString::from("fill"), JsValue::new_method(Box::new(array::fill))),
+        (JsString::from("sort"), JsValue::new_method(Box::new(array::sort))),
+        (JsString::from("forEach"), JsValue::new_method(Box::new(array::for_each))),
+        (JsString::from("map"), JsValue::new_method(Box::new(array::map))),
+        (JsString::from("filter"), JsValue::new_method(Box::new(array::filter))),
+        (JsString::from("some"), JsValue::new_method(Box::new(array::some))),
+        (JsString::from("every"), JsValue::new_method(Box::new(array::every))),
+        (JsString::from("find"), JsValue::new_method(Box::new(array::find))),
+        (JsString::from("reduce"), JsValue::new_method(Box::new(array::reduce))),
+    ])).with_iterator(|this, _| this.elements_iterator()));
+
+    /// The methods of every string, which receive it as `this`
+    static STRING_METHODS: Rc<Methods> = Rc::new(Methods::from(HashMap::from([
+        (JsString::from("charAt"), JsValue::new_method(Box::new(string::char_at))),
+        (JsString::from("charCodeAt"), JsValue::new_method(Box::new(string::char_code_at))),
+        (JsString::from("slice"), JsValue::new_method(Box::new(string::slice))),
+        (JsString::from("substring"), JsValue::new_method(Box::new(string::substring))),
+        (JsString::from("indexOf"), JsValue::new_method(Box::new(string::index_of))),
+        (JsString::from("includes"), JsValue::new_method(Box::new(string::includes))),
+        (JsString::from("split"), JsValue::new_method(Box::new(string::split))),
+        (JsString::from("toUpperCase"), JsValue::new_method(Box::new(string::to_upper_case))),
+        (JsString::from("toLowerCase"), JsValue::new_method(Box::new(string::to_lower_case))),
+        (JsString::from("trim"), JsValue::new_method(Box::new(string::trim))),
+        (JsString::from("repeat"), JsValue::new_method(Box::new(string::repeat))),
+        (JsString::from("padStart"), JsValue::new_method(Box::new(string::pad_start))),
+    ])).with_iterator(|this, _| this.elements_iterator()));
+
+    /// The methods of every generator object, which receive it as `this`
+    static GENERATOR_METHODS: Rc<Methods> = Rc::new(Methods::from(HashMap::from([
+        (JsString::from("next"), JsValue::new_method(Box::new(|this, args| {
+            match this.resume(arg(args, 0)) {
+                GeneratorStep::Yield(value) => iterator_result(value, false),
+                GeneratorStep::Return(value) => iterator_result(value, true),
+            }
+        }))),
+        (JsString::from("return"), JsValue::new_method(Box::new(|this, args| {
+            this.finish();
+            iterator_result(arg(args, 0), true)
+        }))),
+    ])).with_iterator(|this, _| this.clone()));
+
+    /// The global object, with the globals that aren't objects of their own as properties that
+    /// aren't enumerable
+    static GLOBAL_OBJ: JsValue = JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+        properties: PropertyMap::new(),
+        subtype: ObjectSubtype::RegularObject,
+        methods: Some(Rc::new(Methods::from(HashMap::from([
+            (JsString::from("NaN"), JsValue::Number(f64::NAN)),
+            (JsString::from("Infinity"), JsValue::Number(f64::INFINITY)),
+            (JsString::from("undefined"), JsValue::Undefined),
+            (JsString::from("parseInt"), JsValue::new_function(Box::new(number::parse_int))),
+            (JsString::from("parseFloat"), JsValue::new_function(Box::new(number::parse_float))),
+            (JsString::from("isNaN"), JsValue::new_function(Box::new(number::is_nan))),
+            (JsString::from("isFinite"), JsValue::new_function(Box::new(number::is_finite))),
+            (JsString::from("setTimeout"), JsValue::new_function(Box::new(set_timeout))),
+            (JsString::from("clearTimeout"), JsValue::new_function(Box::new(clear_timeout))),
+            (
+                JsString::from("queueMicrotask"),
+                JsValue::new_function(Box::new(queue_microtask_callback)),
+            ),
+        ])))),
+        prototype: None,
+        private: HashMap::new(),
+        frozen: false,
+    })));
+
+    /// The prototype of objects, which ends their prototype chain
+    static OBJECT_PROTOTYPE: JsObject = JsObject::new(JsCell::new(JsObjectContents {
+        properties: PropertyMap::new(),
+        subtype: ObjectSubtype::RegularObject,
+        methods: None,
+        prototype: None,
+        private: HashMap::new(),
+        frozen: false,
+    }));
+
+    static MATH_OBJ: JsValue = math::new_object();
+
+    static OBJECT_OBJ: JsValue = object::new_constructor();
+
+    static NUMBER_OBJ: JsValue = number::new_constructor();
+
+    /// The methods of every number, which receive it as `this`
+    static NUMBER_METHODS: Rc<Methods> = Rc::new(number::methods());
+
+    static SYMBOL_OBJ: JsValue = symbol::new_constructor();
+
+    /// The methods of every symbol, which receive it as `this`
+    static SYMBOL_METHODS: Rc<Methods> = Rc::new(symbol::methods());
+
+    static MAP_OBJ: JsValue = collection::new_constructor(false);
+
+    /// The methods of every map, which receive it as `this`
+    static MAP_METHODS: Rc<Methods> =
+        Rc::new(collection::map_methods().with_iterator(|this, _| this.elements_iterator()));
+
+    static SET_OBJ: JsValue = collection::new_constructor(true);
+
+    /// The methods of every set, which receive it as `this`
+    static SET_METHODS: Rc<Methods> =
+        Rc::new(collection::set_methods().with_iterator(|this, _| this.elements_iterator()));
+
+    static FLOAT64_ARRAY_OBJ: JsValue = typed_array::new_constructor(typed_array::Kind::Float64);
+
+    static INT32_ARRAY_OBJ: JsValue = typed_array::new_constructor(typed_array::Kind::Int32);
+
+    static UINT8_ARRAY_OBJ: JsValue = typed_array::new_constructor(typed_array::Kind::Uint8);
+
+    /// The methods of every typed array, which receive it as `this`
+    static TYPED_ARRAY_METHODS: Rc<Methods> =
+        Rc::new(typed_array::methods().with_iterator(|this, _| this.elements_iterator()));
+
+    static PROMISE_OBJ: JsValue = promise::new_constructor();
+
+    /// The methods of every promise, which receive it as `this`
+    static PROMISE_METHODS: Rc<Methods> = Rc::new(promise::methods());
+
+    static ERROR_OBJ: JsValue = error::new_class(error::Kind::Error);
+
+    static TYPE_ERROR_OBJ: JsValue = error::new_class(error::Kind::TypeError);
+
+    static RANGE_ERROR_OBJ: JsValue = error::new_class(error::Kind::RangeError);
+
+    static SYNTAX_ERROR_OBJ: JsValue = error::new_class(error::Kind::SyntaxError);
+
+    static DATE_OBJ: JsValue = date::new_constructor();
+
+    /// The methods of every date, which receive it as `this`
+    static DATE_METHODS: Rc<Methods> = Rc::new(date::methods());
+}
+
+pub fn console() -> ConsoleStruct {
+    CONSOLE_OBJ.with(|console| console.clone())
+}
+
+pub fn process() -> ProcessStruct {
+    PROCESS_OBJ.with(|process| process.clone())
+}
+
+/// An object with a `write` method, which writes the string of its argument as it is, without
+/// adding a newline
+fn output_stream(write: fn(&str)) -> JsValue {
+    let write = JsValue::new_function(Box::new(move |args| {
+        write(arg(args, 0).to_js_string().as_str());
+        JsValue::Boolean(true)
+    }));
+    JsValue::from_entries(vec![(PropertyKey::from("write"), write)])
+}
+
+pub fn math() -> JsValue {
+    MATH_OBJ.with(|math| math.clone())
+}
+
+// Unlike `math`, these are named after the constructors, as programs often have variables that
+// are named after the objects, which would shadow the functions in the generated code
+pub fn object_constructor() -> JsValue {
+    OBJECT_OBJ.with(|object| object.clone())
+}
+
+pub fn number_constructor() -> JsValue {
+    NUMBER_OBJ.with(|number| number.clone())
+}
+
+pub fn symbol_constructor() -> JsValue {
+    SYMBOL_OBJ.with(|symbol| symbol.clone())
+}
+
+pub fn map_constructor() -> JsValue {
+    MAP_OBJ.with(|map| map.clone())
+}
+
+pub fn set_constructor() -> JsValue {
+    SET_OBJ.with(|set| set.clone())
+}
+
+pub fn float64_array_constructor() -> JsValue {
+    FLOAT64_ARRAY_OBJ.with(|float64_array| float64_array.clone())
+}
+
+pub fn int32_array_constructor() -> JsValue {
+    INT32_ARRAY_OBJ.with(|int32_array| int32_array.clone())
+}
+
+pub fn uint8_array_constructor() -> JsValue {
+    UINT8_ARRAY_OBJ.with(|uint8_array| uint8_array.clone())
+}
+
+pub fn promise_constructor() -> JsValue {
+    PROMISE_OBJ.with(|promise| promise.clone())
+}
+
+pub fn error_constructor() -> JsValue {
+    ERROR_OBJ.with(|error| error.clone())
+}
+
+pub fn type_error_constructor() -> JsValue {
+    TYPE_ERROR_OBJ.with(|type_error| type_error.clone())
+}
+
+pub fn range_error_constructor() -> JsValue {
+    RANGE_ERROR_OBJ.with(|range_error| range_error.clone())
+}
+
+pub fn syntax_error_constructor() -> JsValue {
+    SYNTAX_ERROR_OBJ.with(|syntax_error| syntax_error.clone())
+}
+
+pub fn date_constructor() -> JsValue {
+    DATE_OBJ.with(|date| date.clone())
+}
+
+pub fn json() -> JsonStruct {
+    JSON_OBJ.with(|json| json.clone())
+}
+
+pub fn global_this() -> JsValue {
+    GLOBAL_OBJ.with(|global| global.clone())
+}
+
+/// The global variable, like `parseInt`, which is a member of `globalThis`
+pub fn global(name: &str) -> JsValue {
+    global_this().get_prop(JsValue::from(name))
+}
+
+mod console {
+    //! The members of `console`, which format their arguments like node does
+
+    use std::{
+        cell::RefCell,
+        collections::{hash_map::Entry, HashMap},
+        rc::Rc,
+        slice,
+        time::Instant,
+    };
+
+    use super::{
+        arg, collection, date, error, json, number, number_to_string, promise, JsObject, JsString,
+        JsValue, ObjectSubtype, PropertyKey,
+    };
+
+    thread_local! {
+        /// When each timer of `console.time` started, by its label
+        static TIMERS: RefCell<HashMap<String, Instant>> = RefCell::new(HashMap::new());
+    }
+
+    pub fn log(args: &[JsValue]) -> JsValue {
+        println!("{}", format(args));
+        JsValue::Undefined
+    }
+
+    pub fn error(args: &[JsValue]) -> JsValue {
+        eprintln!("{}", format(args));
+        JsValue::Undefined
+    }
+
+    /// Prints "Assertion failed" and the rest of the arguments to stderr if the first one is
+    /// falsy
+    pub fn assert(args: &[JsValue]) -> JsValue {
+        if arg(args, 0).falsy() {
+            let mut message = args[args.len().min(1)..].to_vec();
+            let heading = match message.first() {
+                Some(first) => format!("Assertion failed: {}", first.to_js_string().as_str()),
+                None => String::from("Assertion failed"),
+            };
+            match message.first_mut() {
+                Some(first) => *first = JsValue::String(JsString::from(heading)),
+                None => message.push(JsValue::String(JsString::from(heading))),
+            }
+            error(&message);
+        }
+        JsValue::Undefined
+    }
+
+    pub fn time(args: &[JsValue]) -> JsValue {
+        let label = label(args);
+        TIMERS.with(|timers| match timers.borrow_mut().entry(label) {
+            Entry::Occupied(entry) => {
+                let label = entry.key();
+                eprintln!("Warning: Label '{label}' already exists for console.time()");
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(Instant::now());
+            }
+        });
+        JsValue::Undefined
+    }
+
+    pub fn time_end(args: &[JsValue]) -> JsValue {
+        let label = label(args);
+        match TIMERS.with(|timers| timers.borrow_mut().remove(&label)) {
+            Some(start) => println!("{label}: {}", elapsed(start)),
+            None => eprintln!("Warning: No such label '{label}' for console.timeEnd()"),
+        }
+        JsValue::Undefined
+    }
+
+    /// Prints the time of the timer like `time_end`, followed by the rest of the arguments,
+    /// but keeps it running
+    pub fn time_log(args: &[JsValue]) -> JsValue {
+        let label = label(args);
+        match TIMERS.with(|timers| timers.borrow().get(&label).copied()) {
+            Some(start) => {
+                let mut line = format!("{label}: {}", elapsed(start));
+                for value in args.iter().skip(1) {
+                    line.push(' ');
+                    line.push_str(&inspect_top_level(value));
+                }
+                println!("{line}");
+            }
+            None => eprintln!("Warning: No such label '{label}' for console.timeLog()"),
+        }
+        JsValue::Undefined
+    }
+
+    fn label(args: &[JsValue]) -> String {
+        match arg(args, 0) {
+            JsValue::Undefined => String::from("default"),
+            label => label.to_js_string().as_str().to_string(),
+        }
+    }
+
+    /// The time since the start, like "1.234ms", "5.678s" or "2:03.456 (m:ss.mmm)"
+    fn elapsed(start: Instant) -> String {
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        if ms < 1000.0 {
+            let rounded = (ms * 1000.0).round() / 1000.0;
+            format!("{}ms", number_to_string(rounded))
+        } else if ms < 60_000.0 {
+            format!("{:.3}s", ms / 1000.0)
+        } else {
+            let minutes = (ms / 60_000.0).floor();
+            let seconds = (ms - minutes * 60_000.0) / 1000.0;
+            format!("{minutes}:{seconds:06.3} (m:ss.mmm)")
+        }
+    }
+
+    /// Joins the arguments with spaces, where strings are printed as they are, and the other
+    /// values like `Inspector` shows them.
+    ///
+    /// If there are more arguments after a first string, its `%s`, `%d`, `%i`, `%f`, `%j`,
+    /// `%o`, `%O` and `%c` placeholders are replaced with them, and `%%` with `%`.
+    pub fn format(args: &[JsValue]) -> String {
+        let mut parts = Vec::new();
+        let mut rest = args;
+        if let [JsValue::String(first), values @ ..] = args {
+            if !values.is_empty() {
+                let (text, used) = substitute(first.as_str(), values);
+                parts.push(text);
+                rest = &values[used..];
+            }
+        }
+        parts.extend(rest.iter().map(inspect_top_level));
+        parts.join(" ")
+    }
+
+    /// Replaces the placeholders of the text with the values, and returns how many of the
+    /// values it used
+    fn substitute(text: &str, values: &[JsValue]) -> (String, usize) {
+        let mut output = String::new();
+        let mut used = 0;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let Some(&next) = chars.peek().filter(|_| c == '%') else {
+                output.push(c);
+                continue;
+            };
+            if next == '%' {
+                chars.next();
+                output.push('%');
+                continue;
+            }
+            let Some(value) = values.get(used) else {
+                output.push(c);
+                continue;
+            };
+            let replacement = match next {
+                's' => match value {
+                    JsValue::Number(_) | JsValue::Symbol(_) => inspect_top_level(value),
+                    // Objects without a `toString` of their own are shown one level deep
+                    JsValue::Object(_)
+                        if value.lookup(&PropertyKey::from("toString")).is_none() =>
+                    {
+                        Inspector::new(0).inspect(value, 0)
+                    }
+                    _ => value.to_js_string().as_str().to_string(),
+                },
+                'd' => inspect_top_level(&value.to_number()),
+                'i' => inspect_top_level(&number::parse_int(slice::from_ref(value))),
+                'f' => inspect_top_level(&number::parse_float(slice::from_ref(value))),
+                'j' => json::stringify(value.clone(), &JsValue::Undefined, &JsValue::Undefined)
+                    .to_js_string()
+                    .as_str()
+                    .to_string(),
+                'o' => Inspector::new(4).inspect(value, 0),
+                'O' => Inspector::new(2).inspect(value, 0),
+                'c' => String::new(),
+                _ => {
+                    output.push(c);
+                    continue;
+                }
+            };
+            chars.next();
+            used += 1;
+            output.push_str(&replacement);
+        }
+        (output, used)
+    }
+
+    /// Strings as they are, and the other values like `inspect` shows them
+    fn inspect_top_level(value: &JsValue) -> String {
+        match value {
+            JsValue::String(string) => string.as_str().to_string(),
+            _ => Inspector::new(2).inspect(value, 0),
+        }
+    }
+
+    /// Shows values like `util.inspect` of node with its default options, e.g.
+    /// `{ a: [ 1, 2 ], b: 'text' }`, and breaks them into lines that are at most 80 characters
+    /// long.
+    ///
+    /// Unlike in node, instances of classes are shown without the name of their class, as
+    /// they don't keep it.
+    struct Inspector {
+        /// How many levels of objects are shown, the deeper ones are `[Object]` or `[Array]`
+        depth: usize,
+        indentation: usize,
+        /// The objects that are being shown, to detect cycles
+        seen: Vec<JsObject>,
+        /// The objects that are referred to by a cycle, where the index is the number that
+        /// the references show
+        circular: Vec<JsObject>,
+        /// The depth of the object that was shown most recently
+        current_depth: usize,
+    }
+
+    const BREAK_LENGTH: usize = 80;
+
+    /// How many levels of objects the innermost line may combine
+    const COMPACT: usize = 3;
+
+    const MAX_ARRAY_LENGTH: usize = 100;
+
+    impl Inspector {
+        fn new(depth: usize) -> Self {
+            Inspector {
+                depth,
+                indentation: 0,
+                seen: Vec::new(),
+                circular: Vec::new(),
+                current_depth: 0,
+            }
+        }
+
+        fn inspect(&mut self, value: &JsValue, depth: usize) -> String {
+            match value {
+                JsValue::Null => String::from("null"),
+                JsValue::Undefined => String::from("undefined"),
+                JsValue::Boolean(boolean) => boolean.to_string(),
+                JsValue::Number(num) if *num == 0.0 && num.is_sign_negative() => String::from("-0"),
+                JsValue::Number(num) => number_to_string(*num),
+                JsValue::String(string) => self.inspect_string(string.as_str()),
+                JsValue::Symbol(symbol) => symbol.to_string(),
+                JsValue::Object(obj) => {
+                    if self.seen.iter().any(|seen| Rc::ptr_eq(seen, obj)) {
+                        let index = match self.circular.iter().position(|o| Rc::ptr_eq(o, obj)) {
+                            Some(index) => index,
+                            None => {
+                                self.circular.push(obj.clone());
+                                self.circular.len() - 1
+                            }
+                        };
+                        return format!("[Circular *{}]", index + 1);
+                    }
+                    self.inspect_object(value, obj, depth)
+                }
+            }
+        }
+
+        /// A quoted string, which is split into lines if it's too long
+        fn inspect_string(&self, string: &str) -> String {
+            let len = string.chars().count();
+            if len > 16 && len + self.indentation + 4 > BREAK_LENGTH {
+                let separator = format!(" +\n{}", " ".repeat(self.indentation + 2));
+                return string
+                    .split_inclusive('\n')
+                    .map(quote)
+                    .collect::<Vec<_>>()
+                    .join(&separator);
+            }
+            quote(string)
+        }
+
+        fn inspect_object(&mut self, value: &JsValue, obj: &JsObject, depth: usize) -> String {
+            let is_array = matches!(
+                obj.borrow().subtype,
+                ObjectSubtype::Array(_) | ObjectSubtype::TypedArray(_)
+            );
+            let keys: Vec<PropertyKey> = match obj.borrow().subtype {
+                ObjectSubtype::RegExp(_) => Vec::new(),
+                _ => value
+                    .own_enumerable_keys()
+                    .into_iter()
+                    .filter(|key| !is_array || key.to_array_index().is_none())
+                    .chain(value.own_enumerable_symbols())
+                    .map(|key| key.to_property_key())
+                    .collect(),
+            };
+            let (mut base, braces) = match &obj.borrow().subtype {
+                ObjectSubtype::Array(elements) if elements.is_empty() && keys.is_empty() => {
+                    return String::from("[]")
+                }
+                ObjectSubtype::Array(_) => (String::new(), ["[", "]"]),
+                ObjectSubtype::TypedArray(typed) if typed.is_empty() && keys.is_empty() => {
+                    return format!("{}(0) []", typed.kind().name())
+                }
+                ObjectSubtype::TypedArray(typed) => (
+                    format!("{}({})", typed.kind().name(), typed.len()),
+                    ["[", "]"],
+                ),
+                ObjectSubtype::Function(..) | ObjectSubtype::Method(..) => {
+                    let name = match value.function_name() {
+                        Some("") | None => String::from(" (anonymous)"),
+                        Some(name) => format!(": {name}"),
+                    };
+                    (format!("[Function{name}]"), ["{", "}"])
+                }
+                ObjectSubtype::Class { parent, .. } => {
+                    let name = value.function_name().filter(|name| !name.is_empty());
+                    let mut base = format!("[class {}", name.unwrap_or("(anonymous)"));
+                    if let Some(parent) = parent.as_ref().and_then(JsValue::function_name) {
+                        base.push_str(&format!(" extends {parent}"));
+                    }
+                    (base + "]", ["{", "}"])
+                }
+                ObjectSubtype::RegExp(regexp) => {
+                    return format!("/{}/{}", regexp.source, regexp.flags)
+                }
+                ObjectSubtype::Date(time) if time.is_nan() => {
+                    (String::from("Invalid Date"), ["{", "}"])
+                }
+                ObjectSubtype::Date(_) => {
+                    let iso = date::to_iso_string(value, &[]);
+                    (iso.to_js_string().as_str().to_string(), ["{", "}"])
+                }
+                ObjectSubtype::Generator(_) => (String::new(), ["Object [Generator] {", "}"]),
+                ObjectSubtype::Map(entries) if entries.is_empty() && keys.is_empty() => {
+                    return String::from("Map(0) {}")
+                }
+                ObjectSubtype::Set(entries) if entries.is_empty() && keys.is_empty() => {
+                    return String::from("Set(0) {}")
+                }
+                ObjectSubtype::Map(entries) => (format!("Map({})", entries.len()), ["{", "}"]),
+                ObjectSubtype::Set(entries) => (format!("Set({})", entries.len()), ["{", "}"]),
+                ObjectSubtype::Promise(_) => (String::from("Promise"), ["{", "}"]),
+                ObjectSubtype::RegularObject if error::is_error(value) => {
+                    (error::inspect(value), ["{", "}"])
+                }
+                ObjectSubtype::RegularObject
+                    if obj.borrow().prototype.is_none() && obj.borrow().methods.is_none() =>
+                {
+                    (String::new(), ["[Object: null prototype] {", "}"])
+                }
+                ObjectSubtype::RegularObject | ObjectSubtype::Accessor { .. } => {
+                    (String::new(), ["{", "}"])
+                }
+            };
+            let has_entries = collection::is_collection(value) || promise::is_promise(value);
+            if keys.is_empty() && !is_array && !has_entries {
+                return if base.is_empty() {
+                    format!("{}{}", braces[0], braces[1])
+                } else {
+                    base
+                };
+            }
+            if depth > self.depth {
+                let name = match &obj.borrow().subtype {
+                    ObjectSubtype::Array(_) => "Array",
+                    ObjectSubtype::TypedArray(typed) => typed.kind().name(),
+                    ObjectSubtype::Map(_) => "Map",
+                    ObjectSubtype::Set(_) => "Set",
+                    ObjectSubtype::Promise(_) => "Promise",
+                    _ => "Object",
+                };
+                return format!("[{name}]");
+            }
+            let depth = depth + 1;
+            self.seen.push(obj.clone());
+            self.current_depth = depth;
+            let mut output = Vec::new();
+            let mut all_numbers = true;
+            if is_array {
+                let elements = match &obj.borrow().subtype {
+                    ObjectSubtype::Array(elements) => elements.clone(),
+                    ObjectSubtype::TypedArray(typed) => {
+                        let values = typed.values().into_iter();
+                        values.map(|num| Some(JsValue::Number(num))).collect()
+                    }
+                    _ => unreachable!(),
+                };
+                all_numbers = elements
+                    .iter()
+                    .all(|element| matches!(element, Some(JsValue::Number(_))));
+                output = self.inspect_elements(&elements, depth);
+            }
+            if has_entries {
+                output = self.inspect_entries(obj, depth);
+            }
+            for key in keys {
+                let property = obj.borrow().properties.get(&key).cloned();
+                let shown = match property.as_ref().and_then(JsValue::accessor) {
+                    Some((JsValue::Undefined, _)) => String::from("[Setter]"),
+                    Some((_, JsValue::Undefined)) => String::from("[Getter]"),
+                    Some(_) => String::from("[Getter/Setter]"),
+                    None => {
+                        let property = value.get_prop(key.to_value());
+                        self.indentation += 2;
+                        let shown = self.inspect(&property, depth);
+                        self.indentation -= 2;
+                        shown
+                    }
+                };
+                let key = match &key {
+                    PropertyKey::String(key) => key_name(key.as_str()),
+                    PropertyKey::Symbol(symbol) => format!("[{symbol}]"),
+                };
+                output.push(format!("{key}: {shown}"));
+            }
+            if let Some(index) = self.circular.iter().position(|o| Rc::ptr_eq(o, obj)) {
+                let reference = format!("<ref *{}>", index + 1);
+                base = if base.is_empty() {
+                    reference
+                } else {
+                    format!("{reference} {base}")
+                };
+            }
+            self.seen.pop();
+            let entries = output.len();
+            if is_array && entries > 6 {
+                output = self.group_elements(output, all_numbers);
+            }
+            self.reduce_to_single_string(output, entries, &base, braces, depth)
+        }
+
+        /// The elements of an array, where consecutive holes are shown together
+        fn inspect_elements(&mut self, elements: &[Option<JsValue>], depth: usize) -> Vec<String> {
+            let mut output = Vec::new();
+            let mut index = 0;
+            while index < elements.len() && output.len() < MAX_ARRAY_LENGTH {
+                match &elements[index] {
+                    Some(element) => {
+                        self.indentation += 2;
+                        output.push(self.inspect(element, depth));
+                        self.indentation -= 2;
+                        index += 1;
+                    }
+                    None => {
+                        let holes = elements[index..]
+                            .iter()
+                            .take_while(|element| element.is_none())
+                            .count();
+                        let ending = if holes > 1 { "s" } else { "" };
+                        output.push(format!("<{holes} empty item{ending}>"));
+                        index += holes;
+                    }
+                }
+            }
+            if index < elements.len() {
+                let remaining = elements.len() - index;
+                let ending = if remaining > 1 { "s" } else { "" };
+                output.push(format!("... {remaining} more item{ending}"));
+            }
+            output
+        }
+
+        /// The entries of a map as `key => value`, the values of a set, or the state of a
+        /// promise
+        fn inspect_entries(&mut self, obj: &JsObject, depth: usize) -> Vec<String> {
+            let (entries, is_set) = match &obj.borrow().subtype {
+                ObjectSubtype::Map(entries) => (entries.iter().cloned().collect::<Vec<_>>(), false),
+                ObjectSubtype::Set(entries) => (entries.iter().cloned().collect(), true),
+                ObjectSubtype::Promise(promise) => {
+                    return vec![match &promise.state {
+                        promise::State::Pending => String::from("<pending>"),
+                        promise::State::Fulfilled(value) => {
+                            self.indentation += 2;
+                            let shown = self.inspect(value, depth);
+                            self.indentation -= 2;
+                            shown
+                        }
+                        promise::State::Rejected(reason) => {
+                            self.indentation += 2;
+                            let shown = self.inspect(reason, depth);
+                            self.indentation -= 2;
+                            format!("<rejected> {shown}")
+                        }
+                    }]
+                }
+                _ => unreachable!(),
+            };
+            self.indentation += 2;
+            let output = entries
+                .iter()
+                .map(|(key, value)| match is_set {
+                    true => self.inspect(value, depth),
+                    false => format!(
+                        "{} => {}",
+                        self.inspect(key, depth),
+                        self.inspect(value, depth)
+                    ),
+                })
+                .collect();
+            self.indentation -= 2;
+            output
+        }
+
+        /// Arranges many short elements in columns, aligned to the right if they're all
+        /// numbers
+        fn group_elements(&self, output: Vec<String>, all_numbers: bool) -> Vec<String> {
+            const SEPARATOR_SPACE: usize = 2;
+            let has_more = output.last().is_some_and(|last| last.starts_with("... "));
+            let len = output.len() - usize::from(has_more);
+            let lengths: Vec<usize> = output[..len].iter().map(|s| s.chars().count()).collect();
+            let total_length: usize = lengths.iter().map(|len| len + SEPARATOR_SPACE).sum();
+            let max_length = lengths.iter().copied().max().unwrap_or(0);
+            let actual_max = max_length + SEPARATOR_SPACE;
+            if actual_max * 3 + self.indentation >= BREAK_LENGTH
+                || (total_length as f64 / actual_max as f64 <= 5.0 && max_length > 6)
+            {
+                return output;
+            }
+            let average_bias =
+                (actual_max as f64 - total_length as f64 / output.len() as f64).sqrt();
+            let biased_max = (actual_max as f64 - 3.0 - average_bias).max(1.0);
+            let columns = ((2.5 * biased_max * len as f64).sqrt() / biased_max)
+                .round()
+                .min(((BREAK_LENGTH - self.indentation) / actual_max) as f64)
+                .min((COMPACT * 4) as f64)
+                .min(15.0) as usize;
+            if columns <= 1 {
+                return output;
+            }
+            let column_widths: Vec<usize> = (0..columns)
+                .map(|column| {
+                    let widest = lengths.iter().skip(column).step_by(columns).max();
+                    widest.copied().unwrap_or(0) + SEPARATOR_SPACE
+                })
+                .collect();
+            let mut grouped: Vec<String> = output[..len]
+                .chunks(columns)
+                .map(|row| {
+                    let mut line = String::new();
+                    for (column, entry) in row.iter().enumerate() {
+                        let is_last = column == row.len() - 1;
+                        let entry = if is_last {
+                            entry.clone()
+                        } else {
+                            format!("{entry}, ")
+                        };
+                        let width =
+                            column_widths[column] - if is_last { SEPARATOR_SPACE } else { 0 };
+                        let padding = " ".repeat(width.saturating_sub(entry.chars().count()));
+                        if all_numbers {
+                            line.push_str(&padding);
+                            line.push_str(&entry);
+                        } else {
+                            line.push_str(&entry);
+                            if !is_last {
+                                line.push_str(&padding);
+                            }
+                        }
+                    }
+                    line
+                })
+                .collect();
+            if has_more {
+                grouped.push(output[len].clone());
+            }
+            grouped
+        }
+
+        /// Puts the entries on a single line if they fit and don't nest too deep, or else each
+        /// on a line of its own
+        fn reduce_to_single_string(
+            &self,
+            output: Vec<String>,
+            entries: usize,
+            base: &str,
+            braces: [&str; 2],
+            depth: usize,
+        ) -> String {
+            let start = output.len() + self.indentation + braces[0].len() + base.len() + 10;
+            let base = if base.is_empty() {
+                String::new()
+            } else {
+                format!("{base} ")
+            };
+            if self.current_depth - depth < COMPACT && entries == output.len() {
+                let mut total_length = output.len() + start;
+                let fits = total_length + output.len() <= BREAK_LENGTH
+                    && output.iter().all(|entry| {
+                        total_length += entry.chars().count();
+                        total_length <= BREAK_LENGTH
+                    });
+                let joined = output.join(", ");
+                if fits && !joined.contains('\n') {
+                    return format!("{base}{} {joined} {}", braces[0], braces[1]);
+                }
+            }
+            let indentation = format!("\n{}", " ".repeat(self.indentation));
+            format!(
+                "{base}{}{indentation}  {}{indentation}{}",
+                braces[0],
+                output.join(&format!(",{indentation}  ")),
+                braces[1]
+            )
+        }
+    }
+
+    /// The key of a property, quoted if it's not an identifier
+    fn key_name(key: &str) -> String {
+        let mut chars = key.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_identifier {
+            key.to_string()
+        } else {
+            quote(key)
+        }
+    }
+
+    /// The string in single quotes, or in double quotes or backticks if it contains single
+    /// quotes and those don't appear in it
+    fn quote(string: &str) -> String {
+        let quote = if !string.contains('\'') {
+            '\''
+        } else if !string.contains('"') {
+            '"'
+        } else if !string.contains('`') && !string.contains("${") {
+            '`'
+        } else {
+            '\''
+        };
+        let mut result = String::from(quote);
+        for c in string.chars() {
+            match c {
+                '\u{8}' => result.push_str("\\b"),
+                '\t' => result.push_str("\\t"),
+                '\n' => result.push_str("\\n"),
+                '\u{c}' => result.push_str("\\f"),
+                '\r' => result.push_str("\\r"),
+                '\\' => result.push_str("\\\\"),
+                c if c == quote => {
+                    result.push('\\');
+                    result.push(c);
+                }
+                c if c < ' ' || ('\u{7f}'..='\u{9f}').contains(&c) => {
+                    result.push_str(&format!("\\x{:02X}", c as u32))
+                }
+                c => result.push(c),
+            }
+        }
+        result.push(quote);
+        result
+    }
+}
+
+mod json {
+    use super::{
+        number_to_string, syntax_error, type_error, JsObject, JsString, JsValue, ObjectSubtype,
+        PropertyKey,
+    };
+    use std::{iter::Peekable, rc::Rc, str::Chars};
+
+    /// Serializes the value to JSON text, or returns `undefined` if the value has no JSON
+    /// representation, like a function.
+    ///
+    /// The `replacer` is either a function that receives the holder object as `this` and each
+    /// key and value, and returns the value to serialize instead, or an array of the keys to
+    /// serialize. The `space` is the number of spaces or the string to indent with, up to 10.
+    pub fn stringify(value: JsValue, replacer: &JsValue, space: &JsValue) -> JsValue {
+        let allow_list = match replacer {
+            JsValue::Object(obj) if matches!(obj.borrow().subtype, ObjectSubtype::Array(_)) => {
+                let mut keys: Vec<JsString> = Vec::new();
+                for element in replacer.iter_elements() {
+                    if let JsValue::String(_) | JsValue::Number(_) = element {
+                        let key = element.to_js_string();
+                        if !keys.contains(&key) {
+                            keys.push(key);
+                        }
+                    }
+                }
+                Some(keys)
+            }
+            _ => None,
+        };
+        let gap = match space {
+            JsValue::Number(_) => {
+                " ".repeat(space.to_integer_or_infinity().clamp(0.0, 10.0) as usize)
+            }
+            JsValue::String(string) => string.as_str().chars().take(10).collect(),
+            _ => String::new(),
+        };
+        let mut serializer = Serializer {
+            replacer: replacer.is_callable().then(|| replacer.clone()),
+            allow_list,
+            gap,
+            indent: String::new(),
+            stack: Vec::new(),
+        };
+        let wrapper = JsValue::from_entries(vec![(PropertyKey::from(""), value)]);
+        match serializer.serialize_property(&wrapper, JsString::from("")) {
+            Some(text) => JsValue::String(JsString::from(text)),
+            None => JsValue::Undefined,
+        }
+    }
+
+    struct Serializer {
+        replacer: Option<JsValue>,
+        /// The keys of the objects to serialize, if the replacer is an array
+        allow_list: Option<Vec<JsString>>,
+        gap: String,
+        indent: String,
+        /// The objects and arrays that are being serialized, to detect cycles
+        stack: Vec<JsObject>,
+    }
+
+    impl Serializer {
+        /// The JSON text of the property of the holder, or `None` if it's left out
+        fn serialize_property(&mut self, holder: &JsValue, key: JsString) -> Option<String> {
+            let mut value = holder.get_prop(JsValue::String(key.clone()));
+            if let JsValue::Object(_) = value {
+                let to_json = value.get_prop(JsValue::String(JsString::from("toJSON")));
+                if to_json.is_callable() {
+                    value = to_json.call_with_this(&value, &[JsValue::String(key.clone())]);
+                }
+            }
+            if let Some(replacer) = &self.replacer {
+                value = replacer.call_with_this(holder, &[JsValue::String(key), value]);
+            }
+            match &value {
+                JsValue::Null => Some(String::from("null")),
+                JsValue::Boolean(boolean) => Some(boolean.to_string()),
+                JsValue::Number(num) if num.is_finite() => Some(number_to_string(*num)),
+                JsValue::Number(_) => Some(String::from("null")),
+                JsValue::String(string) => Some(quote(string.as_str())),
+                JsValue::Object(_) if value.is_callable() => None,
+                JsValue::Object(obj) => {
+                    if self.stack.iter().any(|ancestor| Rc::ptr_eq(ancestor, obj)) {
+                        type_error("Converting circular structure to JSON");
+                    }
+                    self.stack.push(obj.clone());
+                    let is_array = matches!(obj.borrow().subtype, ObjectSubtype::Array(_));
+                    let text = if is_array {
+                        self.serialize_array(&value)
+                    } else {
+                        self.serialize_object(&value)
+                    };
+                    self.stack.pop();
+                    Some(text)
+                }
+                // Like functions, symbols have no JSON representation
+                JsValue::Undefined | JsValue::Symbol(_) => None,
+            }
+        }
+
+        fn serialize_object(&mut self, object: &JsValue) -> String {
+            let keys = match &self.allow_list {
+                Some(keys) => keys.clone(),
+                None => object
+                    .own_enumerable_keys()
+                    .iter()
+                    .map(JsValue::to_js_string)
+                    .collect(),
+            };
+            let separator = if self.gap.is_empty() { ":" } else { ": " };
+            let outer_indent = self.indent.clone();
+            self.indent.push_str(&self.gap);
+            let members: Vec<_> = keys
+                .into_iter()
+                .filter_map(|key| {
+                    let quoted_key = quote(key.as_str());
+                    let value = self.serialize_property(object, key)?;
+                    Some(format!("{quoted_key}{separator}{value}"))
+                })
+                .collect();
+            let text = self.wrap('{', members, '}', &outer_indent);
+            self.indent = outer_indent;
+            text
+        }
+
+        fn serialize_array(&mut self, array: &JsValue) -> String {
+            let len = array.iter_elements().count();
+            let outer_indent = self.indent.clone();
+            self.indent.push_str(&self.gap);
+            let elements: Vec<_> = (0..len)
+                .map(|index| {
+                    let key = JsString::from(index.to_string());
+                    let value = self.serialize_property(array, key);
+                    value.unwrap_or_else(|| String::from("null"))
+                })
+                .collect();
+            let text = self.wrap('[', elements, ']', &outer_indent);
+            self.indent = outer_indent;
+            text
+        }
+
+        /// Puts each part on its own line if there's a gap to indent with
+        fn wrap(&self, open: char, parts: Vec<String>, close: char, outer_indent: &str) -> String {
+            if parts.is_empty() {
+                format!("{open}{close}")
+            } else if self.gap.is_empty() {
+                format!("{open}{}{close}", parts.join(","))
+            } else {
+                let separator = format!(",\n{}", self.indent);
+                let parts = parts.join(&separator);
+                format!("{open}\n{}{parts}\n{outer_indent}{close}", self.indent)
+            }
+        }
+    }
+
+    /// The string as a JSON string literal
+    fn quote(string: &str) -> String {
+        let mut result = String::from('"');
+        for c in string.chars() {
+            match c {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\u{8}' => result.push_str("\\b"),
+                '\u{c}' => result.push_str("\\f"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+                c => result.push(c),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    /// Parses JSON text into the same object and array representations that
+    /// object and array literals produce, so the result can be used like any other value.
+    ///
+    /// If the `reviver` is a function, it's called with each key and value, from the innermost
+    /// ones out, with the object or array that holds them as `this`, and the value is replaced
+    /// with what it returns, or deleted if that's `undefined`.
+    pub fn parse(text: &str, reviver: &JsValue) -> JsValue {
+        let mut parser = Parser {
+            chars: text.chars().peekable(),
+        };
+        let value = parser.parse_value();
+        parser.skip_whitespace();
+        if let Some(c) = parser.chars.next() {
+            parser.unexpected(Some(c));
+        }
+        if !reviver.is_callable() {
+            return value;
+        }
+        let root = JsValue::from_entries(vec![(PropertyKey::from(""), value)]);
+        revive(&root, JsValue::String(JsString::from("")), reviver)
+    }
+
+    fn revive(holder: &JsValue, key: JsValue, reviver: &JsValue) -> JsValue {
+        let value = holder.get_prop(key.clone());
+        if let JsValue::Object(_) = value {
+            for inner_key in value.own_enumerable_keys() {
+                match revive(&value, inner_key.clone(), reviver) {
+                    JsValue::Undefined => {
+                        value.delete_prop(inner_key);
+                    }
+                    revived => value.set_prop(inner_key, revived),
+                }
+            }
+        }
+        reviver.call_with_this(holder, &[key, value])
+    }
+
+    struct Parser<'a> {
+        chars: Peekable<Chars<'a>>,
+    }
+
+    impl Parser<'_> {
+        fn skip_whitespace(&mut self) {
+            while let Some(' ' | '\t' | '\n' | '\r') = self.chars.peek() {
+                self.chars.next();
+            }
+        }
+
+        fn unexpected(&self, c: Option<char>) -> ! {
+            match c {
+                Some(c) => syntax_error(format_args!("Unexpected token '{c}' in JSON")),
+                None => syntax_error("Unexpected end of JSON input"),
+            }
+        }
+
+        fn expect(&mut self, expected: char) {
+            match self.chars.next() {
+                Some(c) if c == expected => (),
+                c => self.unexpected(c),
+            }
+        }
+
+        fn expect_word(&mut self, word: &str) {
+            for expected in word.chars() {
+                self.expect(expected);
+            }
+        }
+
+        fn parse_value(&mut self) -> JsValue {
+            self.skip_whitespace();
+            match self.chars.peek().copied() {
+                Some('{') => self.parse_object(),
+                Some('[') => self.parse_array(),
+                Some('"') => JsValue::String(self.parse_string()),
+                Some('t') => {
+                    self.expect_word("true");
+                    JsValue::Boolean(true)
+                }
+                Some('f') => {
+                    self.expect_word("false");
+                    JsValue::Boolean(false)
+                }
+                Some('n') => {
+                    self.expect_word("null");
+                    JsValue::Null
+                }
+                Some('-' | '0'..='9') => self.parse_number(),
+                c => self.unexpected(c),
+            }
+        }
+
+        fn parse_object(&mut self) -> JsValue {
+            self.expect('{');
+            let object = JsValue::from_entries(Vec::new());
+            self.skip_whitespace();
+            if self.chars.peek() == Some(&'}') {
+                self.chars.next();
+                return object;
+            }
+            loop {
+                self.skip_whitespace();
+                if self.chars.peek() != Some(&'"') {
+                    self.unexpected(self.chars.clone().next());
+                }
+                let key = self.parse_string();
+                self.skip_whitespace();
+                self.expect(':');
+                let value = self.parse_value();
+                object.set_prop(JsValue::String(key), value);
+                self.skip_whitespace();
+                match self.chars.next() {
+                    Some(',') => continue,
+                    Some('}') => return object,
+                    c => self.unexpected(c),
+                }
+            }
+        }
+
+        fn parse_array(&mut self) -> JsValue {
+            self.expect('[');
+            let mut elements = Vec::new();
+            self.skip_whitespace();
+            if self.chars.peek() == Some(&']') {
+                self.chars.next();
+                return JsValue::new_array(elements);
+            }
+            loop {
+                elements.push(self.parse_value());
+                self.skip_whitespace();
+                match self.chars.next() {
+                    Some(',') => continue,
+                    Some(']') => return JsValue::new_array(elements),
+                    c => self.unexpected(c),
+                }
+            }
+        }
+
+        fn parse_string(&mut self) -> JsString {
+            self.expect('"');
+            let mut result = String::new();
+            loop {
+                match self.chars.next() {
+                    Some('"') => return JsString::from(result),
+                    Some('\\') => match self.chars.next() {
+                        Some('"') => result.push('"'),
+                        Some('\\') => result.push('\\'),
+                        Some('/') => result.push('/'),
+                        Some('b') => result.push('\u{8}'),
+                        Some('f') => result.push('\u{c}'),
+                        Some('n') => result.push('\n'),
+                        Some('r') => result.push('\r'),
+                        Some('t') => result.push('\t'),
+                        Some('u') => {
+                            let unit = self.parse_hex4();
+                            // Surrogate pairs are encoded as two consecutive escapes
+                            let code_point = if (0xD800..0xDC00).contains(&unit) {
+                                self.expect_word("\\u");
+                                let low = self.parse_hex4();
+                                0x10000
+                                    + ((unit - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                unit
+                            };
+                            result.push(char::from_u32(code_point).unwrap_or('\u{FFFD}'));
+                        }
+                        c => self.unexpected(c),
+                    },
+                    Some(c) if (c as u32) < 0x20 => self.unexpected(Some(c)),
+                    Some(c) => result.push(c),
+                    None => self.unexpected(None),
+                }
+            }
+        }
+
+        fn parse_hex4(&mut self) -> u32 {
+            let mut value = 0;
+            for _ in 0..4 {
+                let c = self.chars.next();
+                match c.and_then(|c| c.to_digit(16)) {
+                    Some(digit) => value = value * 16 + digit,
+                    None => self.unexpected(c),
+                }
+            }
+            value
+        }
+
+        /// Parses a number, which unlike in JS can't have leading zeros, a leading `+` or `.`,
+        /// or a trailing `.`
+        fn parse_number(&mut self) -> JsValue {
+            let mut text = String::new();
+            if self.chars.peek() == Some(&'-') {
+                text.push('-');
+                self.chars.next();
+            }
+            if self.chars.peek() == Some(&'0') {
+                text.push('0');
+                self.chars.next();
+            } else {
+                self.take_digits(&mut text);
+            }
+            if self.chars.peek() == Some(&'.') {
+                text.push('.');
+                self.chars.next();
+                self.take_digits(&mut text);
+            }
+            if let Some(c @ ('e' | 'E')) = self.chars.peek().copied() {
+                text.push(c);
+                self.chars.next();
+                if let Some(sign @ ('+' | '-')) = self.chars.peek().copied() {
+                    text.push(sign);
+                    self.chars.next();
+                }
+                self.take_digits(&mut text);
+            }
+            JsValue::Number(text.parse().unwrap())
+        }
+
+        /// Moves at least one digit to the end of the text
+        fn take_digits(&mut self, text: &mut String) {
+            match self.chars.peek().copied() {
+                Some(c) if c.is_ascii_digit() => (),
+                c => self.unexpected(c),
+            }
+            while let Some(c) = self.chars.peek().copied().filter(char::is_ascii_digit) {
+                text.push(c);
+                self.chars.next();
+            }
+        }
+    }
+}
+
+mod regexp {
+    use std::{iter::Peekable, str::Chars};
+
+    use super::syntax_error;
+
+    /// A compiled regular expression with the syntax and matching semantics of JS, which is
+    /// matched by backtracking over the characters of the input. It isn't the `regex` crate, as
+    /// that has no backreferences or lookahead, and the inlined runtime can't have dependencies.
+    pub struct RegExp {
+        pub source: String,
+        pub flags: Flags,
+        node: Node,
+        /// The names of the capturing groups, by their index, `None` for unnamed ones
+        group_names: Vec<Option<String>>,
+    }
+
+    #[derive(Default)]
+    pub struct Flags {
+        pub has_indices: bool,
+        pub global: bool,
+        pub ignore_case: bool,
+        pub multiline: bool,
+        pub dot_all: bool,
+        pub unicode: bool,
+        pub unicode_sets: bool,
+        pub sticky: bool,
+    }
+
+    impl Flags {
+        fn parse(flags: &str) -> Flags {
+            let mut result = Flags::default();
+            for flag in flags.chars() {
+                let seen = match flag {
+                    'd' => &mut result.has_indices,
+                    'g' => &mut result.global,
+                    'i' => &mut result.ignore_case,
+                    'm' => &mut result.multiline,
+                    's' => &mut result.dot_all,
+                    'u' => &mut result.unicode,
+                    'v' => &mut result.unicode_sets,
+                    'y' => &mut result.sticky,
+                    _ => syntax_error(format_args!(
+                        "Invalid flags supplied to RegExp constructor '{flags}'"
+                    )),
+                };
+                if *seen {
+                    syntax_error(format_args!(
+                        "Invalid flags supplied to RegExp constructor '{flags}'"
+                    ));
+                }
+                *seen = true;
+            }
+            result
+        }
+    }
+
+    /// The flags in the order that the `flags` property lists them
+    impl std::fmt::Display for Flags {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let flags = [
+                (self.has_indices, 'd'),
+                (self.global, 'g'),
+                (self.ignore_case, 'i'),
+                (self.multiline, 'm'),
+                (self.dot_all, 's'),
+                (self.unicode, 'u'),
+                (self.unicode_sets, 'v'),
+                (self.sticky, 'y'),
+            ];
+            for (set, flag) in flags {
+                if set {
+                    write!(f, "{flag}")?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    enum Node {
+        Empty,
+        Char(char),
+        /// `.`
+        Any,
+        Class {
+            items: Vec<ClassItem>,
+            negated: bool,
+        },
+        /// `^`
+        Start,
+        /// `$`
+        End,
+        /// `\b`, or `\B` if it's negated
+        WordBoundary {
+            negated: bool,
+        },
+        /// A group, which captures what it matched if it has an index
+        Group {
+            node: Box<Node>,
+            index: Option<usize>,
+        },
+        /// `(?=...)`, or `(?!...)` if it's negated
+        Lookahead {
+            node: Box<Node>,
+            negated: bool,
+        },
+        BackReference(usize),
+        /// A back reference to a named group, which is resolved once all groups are known
+        NamedBackReference(String),
+        Concat(Vec<Node>),
+        Alternation(Vec<Node>),
+        Repeat {
+            node: Box<Node>,
+            min: usize,
+            max: Option<usize>,
+            greedy: bool,
+        },
+    }
+
+    enum ClassItem {
+        Range(char, char),
+        Digit { negated: bool },
+        Word { negated: bool },
+        Space { negated: bool },
+    }
+
+    impl ClassItem {
+        fn matches(&self, c: char, ignore_case: bool) -> bool {
+            match *self {
+                ClassItem::Range(start, end) => {
+                    let in_range = |c: char| (start..=end).contains(&c);
+                    in_range(c)
+                        || (ignore_case
+                            && (c.to_lowercase().any(in_range) || c.to_uppercase().any(in_range)))
+                }
+                ClassItem::Digit { negated } => c.is_ascii_digit() != negated,
+                ClassItem::Word { negated } => is_word_char(c) != negated,
+                ClassItem::Space { negated } => is_space(c) != negated,
+            }
+        }
+    }
+
+    fn is_word_char(c: char) -> bool {
+        c.is_ascii_alphanumeric() || c == '_'
+    }
+
+    /// The whitespace and line terminators that `\s` matches
+    fn is_space(c: char) -> bool {
+        c.is_whitespace() || c == '\u{FEFF}'
+    }
+
+    fn is_line_terminator(c: char) -> bool {
+        matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+    }
+
+    fn chars_equal(a: char, b: char, ignore_case: bool) -> bool {
+        a == b || (ignore_case && a.to_lowercase().eq(b.to_lowercase()))
+    }
+
+    impl RegExp {
+        /// Panics with a `SyntaxError` if the pattern or the flags are invalid, like the
+        /// `RegExp` constructor throws one.
+        pub fn new(source: &str, flags: &str) -> RegExp {
+            let flags = Flags::parse(flags);
+            let mut parser = Parser {
+                source,
+                chars: source.chars().peekable(),
+                group_names: Vec::new(),
+            };
+            let mut node = parser.parse_alternation();
+            if parser.chars.next().is_some() {
+                parser.error("Unmatched ')'");
+            }
+            parser.resolve_names(&mut node);
+            RegExp {
+                source: source.to_string(),
+                flags,
+                node,
+                group_names: parser.group_names,
+            }
+        }
+
+        /// The names of the groups, where the group at index 0 is the whole match
+        pub fn group_names(&self) -> impl Iterator<Item = Option<&str>> {
+            std::iter::once(None).chain(self.group_names.iter().map(Option::as_deref))
+        }
+
+        /// Matches the pattern starting exactly at `start`, returning the ranges of the
+        /// characters that the groups captured, where the group at index 0 is the whole match.
+        pub fn match_at(
+            &self,
+            input: &[char],
+            start: usize,
+        ) -> Option<Vec<Option<(usize, usize)>>> {
+            let mut matcher = Matcher {
+                input,
+                flags: &self.flags,
+                captures: vec![None; self.group_names.len() + 1],
+            };
+            let mut end = None;
+            let matched = matcher.match_node(&self.node, start, &mut |_, pos| {
+                end = Some(pos);
+                true
+            });
+            if !matched {
+                return None;
+            }
+            matcher.captures[0] = Some((start, end.unwrap()));
+            Some(matcher.captures)
+        }
+    }
+
+    struct Parser<'a> {
+        source: &'a str,
+        chars: Peekable<Chars<'a>>,
+        group_names: Vec<Option<String>>,
+    }
+
+    impl Parser<'_> {
+        fn error(&self, message: &str) -> ! {
+            syntax_error(format_args!(
+                "Invalid regular expression: /{}/: {message}",
+                self.source
+            ))
+        }
+
+        fn eat(&mut self, c: char) -> bool {
+            self.chars.next_if_eq(&c).is_some()
+        }
+
+        fn parse_alternation(&mut self) -> Node {
+            let mut alternatives = vec![self.parse_concat()];
+            while self.eat('|') {
+                alternatives.push(self.parse_concat());
+            }
+            match alternatives.len() {
+                1 => alternatives.pop().unwrap(),
+                _ => Node::Alternation(alternatives),
+            }
+        }
+
+        fn parse_concat(&mut self) -> Node {
+            let mut nodes = Vec::new();
+            while let Some(&c) = self.chars.peek() {
+                if c == '|' || c == ')' {
+                    break;
+                }
+                let atom = self.parse_atom();
+                nodes.push(self.parse_quantifier(atom));
+            }
+            match nodes.len() {
+                0 => Node::Empty,
+                1 => nodes.pop().unwrap(),
+                _ => Node::Concat(nodes),
+            }
+        }
+
+        fn parse_quantifier(&mut self, atom: Node) -> Node {
+            let (min, max) = match self.chars.peek() {
+                Some('{') => match self.parse_braces() {
+                    Some(bounds) => bounds,
+                    None => return atom,
+                },
+                Some('*' | '+' | '?') => match self.chars.next() {
+                    Some('*') => (0, None),
+                    Some('+') => (1, None),
+                    _ => (0, Some(1)),
+                },
+                _ => return atom,
+            };
+            if matches!(
+                atom,
+                Node::Start | Node::End | Node::WordBoundary { .. } | Node::Lookahead { .. }
+            ) {
+                self.error("Nothing to repeat");
+            }
+            if max.is_some_and(|max| max < min) {
+                self.error("numbers out of order in {} quantifier");
+            }
+            let greedy = !self.eat('?');
+            Node::Repeat {
+                node: Box::new(atom),
+                min,
+                max,
+                greedy,
+            }
+        }
+
+        /// Parses `{n}`, `{n,}` or `{n,m}`. A brace that doesn't start one of them is a literal
+        /// character, which is left where it is.
+        fn parse_braces(&mut self) -> Option<(usize, Option<usize>)> {
+            let mut lookahead = self.chars.clone();
+            lookahead.next();
+            let number = |chars: &mut Peekable<Chars>| {
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                digits.parse::<usize>().ok()
+            };
+            let min = number(&mut lookahead)?;
+            let max = match lookahead.next_if_eq(&',') {
+                Some(_) => number(&mut lookahead),
+                None => Some(min),
+            };
+            lookahead.next_if_eq(&'}')?;
+            self.chars = lookahead;
+            Some((min, max))
+        }
+
+        fn parse_atom(&mut self) -> Node {
+            match self.chars.next().unwrap() {
+                '^' => Node::Start,
+                '$' => Node::End,
+                '.' => Node::Any,
+                '(' => self.parse_group(),
+                '[' => self.parse_class(),
+                '\\' => self.parse_escape(),
+                c @ ('*' | '+' | '?') => self.error(&format!("Nothing to repeat at '{c}'")),
+                c => Node::Char(c),
+            }
+        }
+
+        fn parse_group(&mut self) -> Node {
+            let node = if self.eat('?') {
+                match self.chars.next() {
+                    Some(':') => self.parse_alternation(),
+                    Some(c @ ('=' | '!')) => Node::Lookahead {
+                        node: Box::new(self.parse_alternation()),
+                        negated: c == '!',
+                    },
+                    Some('<') if matches!(self.chars.peek(), Some('=' | '!')) => {
+                        self.error("Lookbehind assertions aren't supported")
+                    }
+                    Some('<') => {
+                        let name = self.parse_group_name();
+                        self.group_names.push(Some(name));
+                        let index = self.group_names.len();
+                        Node::Group {
+                            node: Box::new(self.parse_alternation()),
+                            index: Some(index),
+                        }
+                    }
+                    _ => self.error("Invalid group"),
+                }
+            } else {
+                self.group_names.push(None);
+                let index = self.group_names.len();
+                Node::Group {
+                    node: Box::new(self.parse_alternation()),
+                    index: Some(index),
+                }
+            };
+            if !self.eat(')') {
+                self.error("Unterminated group");
+            }
+            match node {
+                Node::Group { .. } | Node::Lookahead { .. } => node,
+                node => Node::Group {
+                    node: Box::new(node),
+                    index: None,
+                },
+            }
+        }
+
+        /// The name of a named group or back reference, after its `<`
+        fn parse_group_name(&mut self) -> String {
+            let mut name = String::new();
+            loop {
+                match self.chars.next() {
+                    Some('>') if !name.is_empty() => return name,
+                    Some(c) if c == '$' || is_word_char(c) || !c.is_ascii() => name.push(c),
+                    _ => self.error("Invalid capture group name"),
+                }
+            }
+        }
+
+        fn parse_class(&mut self) -> Node {
+            let negated = self.eat('^');
+            let mut items = Vec::new();
+            loop {
+                let start = match self.chars.next() {
+                    Some(']') => break,
+                    Some('\\') => match self.parse_class_escape() {
+                        Ok(c) => c,
+                        Err(item) => {
+                            items.push(item);
+                            continue;
+                        }
+                    },
+                    Some(c) => c,
+                    None => self.error("Unterminated character class"),
+                };
+                let mut lookahead = self.chars.clone();
+                let is_range =
+                    lookahead.next() == Some('-') && !matches!(lookahead.peek(), Some(']') | None);
+                if !is_range {
+                    items.push(ClassItem::Range(start, start));
+                    continue;
+                }
+                self.chars.next();
+                let end = match self.chars.next() {
+                    Some('\\') => match self.parse_class_escape() {
+                        Ok(c) => c,
+                        // Like `[a-\d]`, where the `-` is literal
+                        Err(item) => {
+                            items.extend([
+                                ClassItem::Range(start, start),
+                                ClassItem::Range('-', '-'),
+                                item,
+                            ]);
+                            continue;
+                        }
+                    },
+                    Some(c) => c,
+                    None => self.error("Unterminated character class"),
+                };
+                if end < start {
+                    self.error("Range out of order in character class");
+                }
+                items.push(ClassItem::Range(start, end));
+            }
+            Node::Class { items, negated }
+        }
+
+        /// An escape in a class, which is either a character or a class of its own
+        fn parse_class_escape(&mut self) -> Result<char, ClassItem> {
+            match self.chars.peek() {
+                Some('b') => {
+                    self.chars.next();
+                    Ok('\u{8}')
+                }
+                Some('-') => {
+                    self.chars.next();
+                    Ok('-')
+                }
+                _ => match self.parse_escape() {
+                    Node::Char(c) => Ok(c),
+                    Node::Class { mut items, .. } => Err(items.pop().unwrap()),
+                    _ => self.error("Invalid class escape"),
+                },
+            }
+        }
+
+        fn parse_escape(&mut self) -> Node {
+            let class = |item| Node::Class {
+                items: vec![item],
+                negated: false,
+            };
+            let c = match self.chars.next() {
+                Some(c) => c,
+                None => self.error("\\ at end of pattern"),
+            };
+            let c = match c {
+                'd' | 'D' => return class(ClassItem::Digit { negated: c == 'D' }),
+                'w' | 'W' => return class(ClassItem::Word { negated: c == 'W' }),
+                's' | 'S' => return class(ClassItem::Space { negated: c == 'S' }),
+                'b' | 'B' => return Node::WordBoundary { negated: c == 'B' },
+                '1'..='9' => {
+                    let mut digits = String::from(c);
+                    while let Some(digit) = self.chars.next_if(char::is_ascii_digit) {
+                        digits.push(digit);
+                    }
+                    return Node::BackReference(digits.parse().unwrap());
+                }
+                'k' if self.eat('<') => return Node::NamedBackReference(self.parse_group_name()),
+                '0' => '\0',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'v' => '\u{b}',
+                'f' => '\u{c}',
+                'c' => match self.chars.next_if(char::is_ascii_alphabetic) {
+                    Some(letter) => char::from(letter as u8 % 32),
+                    None => '\\',
+                },
+                'x' => self.parse_hex(2).unwrap_or('x'),
+                'u' if self.eat('{') => {
+                    let mut digits = String::new();
+                    while let Some(digit) = self.chars.next_if(char::is_ascii_hexdigit) {
+                        digits.push(digit);
+                    }
+                    let c = u32::from_str_radix(&digits, 16)
+                        .ok()
+                        .and_then(char::from_u32);
+                    match c {
+                        Some(c) if self.eat('}') => c,
+                        _ => self.error("Invalid Unicode escape"),
+                    }
+                }
+                'u' => self.parse_hex(4).unwrap_or('u'),
+                c => c,
+            };
+            Node::Char(c)
+        }
+
+        /// A character of `digits` hexadecimal digits, or `None` if there aren't that many,
+        /// which leaves them where they are
+        fn parse_hex(&mut self, digits: usize) -> Option<char> {
+            let mut lookahead = self.chars.clone();
+            let mut value = 0;
+            for _ in 0..digits {
+                value = value * 16 + lookahead.next()?.to_digit(16)?;
+            }
+            self.chars = lookahead;
+            // Lone surrogates can't be represented, they match nothing
+            Some(char::from_u32(value).unwrap_or('\u{FFFD}'))
+        }
+
+        fn resolve_names(&self, node: &mut Node) {
+            match node {
+                Node::NamedBackReference(name) => {
+                    let index = self
+                        .group_names
+                        .iter()
+                        .position(|group| group.as_deref() == Some(name.as_str()));
+                    match index {
+                        Some(index) => *node = Node::BackReference(index + 1),
+                        None => self.error("Invalid named capture referenced"),
+                    }
+                }
+                Node::Group { node, .. }
+                | Node::Lookahead { node, .. }
+                | Node::Repeat { node, .. } => self.resolve_names(node),
+                Node::Concat(nodes) | Node::Alternation(nodes) => {
+                    nodes.iter_mut().for_each(|node| self.resolve_names(node))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    type Captures = Vec<Option<(usize, usize)>>;
+
+    struct Matcher<'a> {
+        input: &'a [char],
+        flags: &'a Flags,
+        captures: Captures,
+    }
+
+    /// What comes after a node, which is called with the position where the node's match
+    /// ended and tells whether the rest of the pattern matched from there
+    type Continuation<'k, 'a> = dyn FnMut(&mut Matcher<'a>, usize) -> bool + 'k;
+
+    impl<'a> Matcher<'a> {
+        fn match_node(&mut self, node: &Node, pos: usize, k: &mut Continuation<'_, 'a>) -> bool {
+            let input = self.input;
+            let ignore_case = self.flags.ignore_case;
+            match node {
+                Node::Empty => k(self, pos),
+                Node::Char(c) => match input.get(pos) {
+                    Some(&next) if chars_equal(next, *c, ignore_case) => k(self, pos + 1),
+                    _ => false,
+                },
+                Node::Any => match input.get(pos) {
+                    Some(&next) if self.flags.dot_all || !is_line_terminator(next) => {
+                        k(self, pos + 1)
+                    }
+                    _ => false,
+                },
+                Node::Class { items, negated } => match input.get(pos) {
+                    Some(&next)
+                        if items.iter().any(|item| item.matches(next, ignore_case)) != *negated =>
+                    {
+                        k(self, pos + 1)
+                    }
+                    _ => false,
+                },
+                Node::Start => {
+                    let at_start =
+                        pos == 0 || (self.flags.multiline && is_line_terminator(input[pos - 1]));
+                    at_start && k(self, pos)
+                }
+                Node::End => {
+                    let at_end = pos == input.len()
+                        || (self.flags.multiline && is_line_terminator(input[pos]));
+                    at_end && k(self, pos)
+                }
+                Node::WordBoundary { negated } => {
+                    let before = pos > 0 && is_word_char(input[pos - 1]);
+                    let after = pos < input.len() && is_word_char(input[pos]);
+                    ((before != after) != *negated) && k(self, pos)
+                }
+                Node::Group { node, index: None } => self.match_node(node, pos, k),
+                Node::Group {
+                    node,
+                    index: Some(index),
+                } => {
+                    let index = *index;
+                    self.match_node(node, pos, &mut |matcher, end| {
+                        let previous = matcher.captures[index].replace((pos, end));
+                        if k(matcher, end) {
+                            return true;
+                        }
+                        matcher.captures[index] = previous;
+                        false
+                    })
+                }
+                Node::Lookahead { node, negated } => {
+                    let saved = self.captures.clone();
+                    let matched = self.match_node(node, pos, &mut |_, _| true);
+                    // Only a positive lookahead keeps what it captured, which is undone if the
+                    // rest of the pattern doesn't match
+                    if matched != *negated && k(self, pos) {
+                        return true;
+                    }
+                    self.captures = saved;
+                    false
+                }
+                Node::BackReference(index) => {
+                    let Some(&Some((start, end))) = self.captures.get(*index) else {
+                        // A group that didn't take part in the match matches the empty string
+                        return k(self, pos);
+                    };
+                    let len = end - start;
+                    let matches = pos + len <= input.len()
+                        && (0..len)
+                            .all(|i| chars_equal(input[start + i], input[pos + i], ignore_case));
+                    matches && k(self, pos + len)
+                }
+                Node::NamedBackReference(_) => unreachable!(),
+                Node::Concat(nodes) => self.match_sequence(nodes, pos, k),
+                Node::Alternation(alternatives) => alternatives
+                    .iter()
+                    .any(|alternative| self.match_node(alternative, pos, k)),
+                Node::Repeat {
+                    node,
+                    min,
+                    max,
+                    greedy,
+                } => self.match_repeat(node, *min, *max, *greedy, 0, pos, k),
+            }
+        }
+
+        fn match_sequence(
+            &mut self,
+            nodes: &[Node],
+            pos: usize,
+            k: &mut Continuation<'_, 'a>,
+        ) -> bool {
+            match nodes {
+                [] => k(self, pos),
+                [node, rest @ ..] => self.match_node(node, pos, &mut |matcher, pos| {
+                    matcher.match_sequence(rest, pos, k)
+                }),
+            }
+        }
+
+        /// Matches the rest of the repetitions after `count` of them matched.
+        ///
+        /// Once the minimum is reached, a repetition that matches the empty string ends the
+        /// loop, like in JS, so that it doesn't go on forever.
+        #[allow(clippy::too_many_arguments)]
+        fn match_repeat(
+            &mut self,
+            node: &Node,
+            min: usize,
+            max: Option<usize>,
+            greedy: bool,
+            count: usize,
+            pos: usize,
+            k: &mut Continuation<'_, 'a>,
+        ) -> bool {
+            let again = |matcher: &mut Self, k: &mut Continuation<'_, 'a>| {
+                matcher.match_node(node, pos, &mut |matcher, end| {
+                    (count < min || end != pos)
+                        && matcher.match_repeat(node, min, max, greedy, count + 1, end, k)
+                })
+            };
+            if count < min {
+                return again(self, k);
+            }
+            if max == Some(count) {
+                return k(self, pos);
+            }
+            match greedy {
+                true => again(self, k) || k(self, pos),
+                false => k(self, pos) || again(self, k),
+            }
+        }
+    }
+}
+
+mod string {
+    //! The methods of strings, which work on UTF-16 code units like in JS
+
+    use super::{
+        arg, is_js_whitespace, range_error, regexp::RegExp, relative_index, type_error, JsString,
+        JsValue, ObjectSubtype,
+    };
+
+    fn units(value: &JsValue) -> Vec<u16> {
+        value.to_js_string().as_str().encode_utf16().collect()
+    }
+
+    /// Lone surrogates can't be represented, they become replacement characters
+    fn from_units(units: &[u16]) -> JsValue {
+        JsValue::String(JsString::from(String::from_utf16_lossy(units)))
+    }
+
+    /// The position of the argument, clamped to the string
+    fn clamped_index(position: &JsValue, len: usize) -> usize {
+        position.to_integer_or_infinity().clamp(0.0, len as f64) as usize
+    }
+
+    /// The index of the first occurrence of `search` in `units` from `start` on
+    fn find(units: &[u16], search: &[u16], start: usize) -> Option<usize> {
+        (start..=units.len().checked_sub(search.len())?)
+            .find(|&index| units[index..].starts_with(search))
+    }
+
+    pub fn char_at(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let position = arg(args, 0).to_integer_or_infinity();
+        match position >= 0.0 && position < units.len() as f64 {
+            true => from_units(&units[position as usize..][..1]),
+            false => JsValue::from(""),
+        }
+    }
+
+    pub fn char_code_at(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let position = arg(args, 0).to_integer_or_infinity();
+        match position >= 0.0 && position < units.len() as f64 {
+            true => JsValue::Number(units[position as usize] as f64),
+            false => JsValue::Number(f64::NAN),
+        }
+    }
+
+    pub fn slice(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let start = relative_index(&arg(args, 0), units.len(), 0);
+        let end = relative_index(&arg(args, 1), units.len(), units.len());
+        from_units(&units[start..end.max(start)])
+    }
+
+    /// Unlike `slice`, negative positions are 0, and the positions are swapped if the end
+    /// comes first
+    pub fn substring(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let start = clamped_index(&arg(args, 0), units.len());
+        let end = match arg(args, 1) {
+            JsValue::Undefined => units.len(),
+            end => clamped_index(&end, units.len()),
+        };
+        from_units(&units[start.min(end)..start.max(end)])
+    }
+
+    pub fn index_of(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let start = clamped_index(&arg(args, 1), units.len());
+        match find(&units, &self::units(&arg(args, 0)), start) {
+            Some(index) => JsValue::from(index),
+            None => JsValue::Number(-1.0),
+        }
+    }
+
+    pub fn includes(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let search = arg(args, 0);
+        if let JsValue::Object(obj) = &search {
+            if let ObjectSubtype::RegExp(_) = obj.borrow().subtype {
+                type_error(
+                    "First argument to String.prototype.includes must not be a regular expression",
+                );
+            }
+        }
+        let units = units(this);
+        let start = clamped_index(&arg(args, 1), units.len());
+        JsValue::Boolean(find(&units, &self::units(&search), start).is_some())
+    }
+
+    /// Splits at every occurrence of the separator, into at most `limit` strings. An empty
+    /// separator splits the string into its code units.
+    pub fn split(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let limit = match arg(args, 1) {
+            JsValue::Undefined => u32::MAX,
+            limit => limit.to_uint32(),
+        } as usize;
+        let separator = match arg(args, 0) {
+            JsValue::Undefined => return JsValue::new_array(vec![from_units(&units)]),
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::RegExp(regexp) => {
+                    return JsValue::new_array(split_by_regexp(this, regexp, limit))
+                }
+                _ => self::units(&arg(args, 0)),
+            },
+            separator => self::units(&separator),
+        };
+        let mut parts = Vec::new();
+        if separator.is_empty() {
+            parts.extend(units.chunks(1).map(from_units));
+        } else {
+            let mut start = 0;
+            while let Some(index) = find(&units, &separator, start) {
+                parts.push(from_units(&units[start..index]));
+                start = index + separator.len();
+            }
+            parts.push(from_units(&units[start..]));
+        }
+        parts.truncate(limit);
+        JsValue::new_array(parts)
+    }
+
+    /// Splits at every match of the regular expression, where the strings that its groups
+    /// captured are placed between the parts, like `@@split` of `RegExp` does. A match that is
+    /// empty where the last part ends doesn't split the string.
+    fn split_by_regexp(this: &JsValue, regexp: &RegExp, limit: usize) -> Vec<JsValue> {
+        let string = this.to_js_string();
+        let chars = string.as_str().chars().collect::<Vec<_>>();
+        let part = |start: usize, end: usize| {
+            JsValue::String(JsString::from(chars[start..end].iter().collect::<String>()))
+        };
+        let mut parts = Vec::new();
+        if limit == 0 {
+            return parts;
+        }
+        if chars.is_empty() {
+            if regexp.match_at(&chars, 0).is_none() {
+                parts.push(part(0, 0));
+            }
+            return parts;
+        }
+        let mut last_end = 0;
+        let mut position = 0;
+        while position < chars.len() {
+            let Some(captures) = regexp.match_at(&chars, position) else {
+                position += 1;
+                continue;
+            };
+            let (_, end) = captures[0].unwrap();
+            if end == last_end {
+                position += 1;
+                continue;
+            }
+            parts.push(part(last_end, position));
+            for range in &captures[1..] {
+                if parts.len() == limit {
+                    return parts;
+                }
+                parts.push(range.map_or(JsValue::Undefined, |(start, end)| part(start, end)));
+            }
+            if parts.len() == limit {
+                return parts;
+            }
+            last_end = end;
+            position = end;
+        }
+        parts.push(part(last_end, chars.len()));
+        parts
+    }
+
+    pub fn to_upper_case(this: &JsValue, _: &[JsValue]) -> JsValue {
+        JsValue::String(JsString::from(this.to_js_string().as_str().to_uppercase()))
+    }
+
+    pub fn to_lower_case(this: &JsValue, _: &[JsValue]) -> JsValue {
+        JsValue::String(JsString::from(this.to_js_string().as_str().to_lowercase()))
+    }
+
+    pub fn trim(this: &JsValue, _: &[JsValue]) -> JsValue {
+        JsValue::from(this.to_js_string().as_str().trim_matches(is_js_whitespace))
+    }
+
+    pub fn repeat(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let count = arg(args, 0).to_integer_or_infinity();
+        if count < 0.0 || count.is_infinite() {
+            range_error(format_args!(
+                "Invalid count value: {}",
+                arg(args, 0).to_js_string().as_str()
+            ));
+        }
+        let string = this.to_js_string();
+        JsValue::String(JsString::from(string.as_str().repeat(count as usize)))
+    }
+
+    /// Pads the start with as much of the repeated `fillString` as it takes to reach the
+    /// `maxLength`
+    pub fn pad_start(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let units = units(this);
+        let max_length = arg(args, 0).to_integer_or_infinity();
+        let fill = match arg(args, 1) {
+            JsValue::Undefined => vec![b' ' as u16],
+            fill => self::units(&fill),
+        };
+        if max_length <= units.len() as f64 || fill.is_empty() {
+            return this.clone();
+        }
+        let padding = max_length as usize - units.len();
+        let mut padded: Vec<_> = fill.iter().copied().cycle().take(padding).collect();
+        padded.extend(units);
+        from_units(&padded)
+    }
+}
+
+mod math {
+    //! The `Math` object, whose functions convert their arguments to numbers
+
+    use std::{
+        cell::Cell,
+        collections::{hash_map::RandomState, HashMap},
+        f64::consts,
+        hash::{BuildHasher, Hasher},
+        rc::Rc,
+    };
+
+    use super::{
+        arg, JsCell, JsObject, JsObjectContents, JsString, JsValue, Methods, ObjectSubtype,
+        PropertyMap,
+    };
+
+    thread_local! {
+        /// The state of the random number generator, seeded from the randomness that the
+        /// standard library uses for its hash maps
+        static RANDOM_STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+    }
+
+    fn number(value: &JsValue) -> f64 {
+        match value.to_number() {
+            JsValue::Number(num) => num,
+            _ => unreachable!(),
+        }
+    }
+
+    fn unary(f: fn(f64) -> f64) -> JsValue {
+        JsValue::new_function(Box::new(move |args| {
+            JsValue::Number(f(number(&arg(args, 0))))
+        }))
+    }
+
+    fn binary(f: fn(f64, f64) -> f64) -> JsValue {
+        JsValue::new_function(Box::new(move |args| {
+            let (a, b) = (number(&arg(args, 0)), number(&arg(args, 1)));
+            JsValue::Number(f(a, b))
+        }))
+    }
+
+    /// The largest or the smallest of the arguments, where `NaN` wins and 0 is larger than -0
+    fn extreme(args: &[JsValue], is_beyond: fn(f64, f64) -> bool, empty: f64) -> JsValue {
+        let extreme = args.iter().map(number).fold(empty, |extreme, num| {
+            let is_beyond =
+                is_beyond(num, extreme) || (num == extreme && is_beyond(1.0 / num, 1.0 / extreme));
+            match extreme.is_nan() || !(num.is_nan() || is_beyond) {
+                true => extreme,
+                false => num,
+            }
+        });
+        JsValue::Number(extreme)
+    }
+
+    /// Rounds half-way cases towards positive infinity, and keeps the sign of a zero result
+    fn round(num: f64) -> f64 {
+        if !num.is_finite() {
+            return num;
+        }
+        let floor = num.floor();
+        let rounded = if num - floor >= 0.5 {
+            floor + 1.0
+        } else {
+            floor
+        };
+        match rounded == 0.0 && num < 0.0 {
+            true => -0.0,
+            false => rounded,
+        }
+    }
+
+    fn sign(num: f64) -> f64 {
+        match num == 0.0 || num.is_nan() {
+            true => num,
+            false => num.signum(),
+        }
+    }
+
+    /// Sums the squares scaled by the largest argument, with Kahan summation, like V8 does
+    fn hypot(args: &[JsValue]) -> JsValue {
+        let values: Vec<_> = args.iter().map(|arg| number(arg).abs()).collect();
+        if values.iter().any(|value| value.is_infinite()) {
+            return JsValue::Number(f64::INFINITY);
+        }
+        if values.iter().any(|value| value.is_nan()) {
+            return JsValue::Number(f64::NAN);
+        }
+        let max = values.iter().copied().fold(0.0, f64::max);
+        if max == 0.0 {
+            return JsValue::Number(0.0);
+        }
+        let (mut sum, mut compensation) = (0.0, 0.0);
+        for value in values {
+            let scaled = value / max;
+            let summand = scaled * scaled - compensation;
+            let preliminary = sum + summand;
+            compensation = (preliminary - sum) - summand;
+            sum = preliminary;
+        }
+        JsValue::Number(sum.sqrt() * max)
+    }
+
+    /// A number in [0, 1) from the xorshift64* generator
+    fn random() -> f64 {
+        let mut state = RANDOM_STATE.with(Cell::get);
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        RANDOM_STATE.with(|random_state| random_state.set(state));
+        let bits = state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11;
+        bits as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn new_object() -> JsValue {
+        let members: [(&str, JsValue); 43] = [
+            ("E", JsValue::Number(consts::E)),
+            ("LN10", JsValue::Number(consts::LN_10)),
+            ("LN2", JsValue::Number(consts::LN_2)),
+            ("LOG10E", JsValue::Number(consts::LOG10_E)),
+            ("LOG2E", JsValue::Number(consts::LOG2_E)),
+            ("PI", JsValue::Number(consts::PI)),
+            ("SQRT1_2", JsValue::Number(consts::FRAC_1_SQRT_2)),
+            ("SQRT2", JsValue::Number(consts::SQRT_2)),
+            ("abs", unary(f64::abs)),
+            ("acos", unary(f64::acos)),
+            ("acosh", unary(f64::acosh)),
+            ("asin", unary(f64::asin)),
+            ("asinh", unary(f64::asinh)),
+            ("atan", unary(f64::atan)),
+            ("atanh", unary(f64::atanh)),
+            ("atan2", binary(f64::atan2)),
+            ("cbrt", unary(f64::cbrt)),
+            ("ceil", unary(f64::ceil)),
+            (
+                "clz32",
+                JsValue::new_function(Box::new(|args| {
+                    JsValue::Number(arg(args, 0).to_uint32().leading_zeros() as f64)
+                })),
+            ),
+            ("cos", unary(f64::cos)),
+            ("cosh", unary(f64::cosh)),
+            ("exp", unary(f64::exp)),
+            ("expm1", unary(f64::exp_m1)),
+            ("floor", unary(f64::floor)),
+            ("fround", unary(|num| num as f32 as f64)),
+            ("hypot", JsValue::new_function(Box::new(hypot))),
+            (
+                "imul",
+                JsValue::new_function(Box::new(|args| {
+                    let product = arg(args, 0)
+                        .to_int32()
+                        .wrapping_mul(arg(args, 1).to_int32());
+                    JsValue::Number(product as f64)
+                })),
+            ),
+            ("log", unary(f64::ln)),
+            ("log1p", unary(f64::ln_1p)),
+            ("log10", unary(f64::log10)),
+            ("log2", unary(f64::log2)),
+            (
+                "max",
+                JsValue::new_function(Box::new(|args| {
+                    extreme(args, |a, b| a > b, f64::NEG_INFINITY)
+                })),
+            ),
+            (
+                "min",
+                JsValue::new_function(Box::new(|args| extreme(args, |a, b| a < b, f64::INFINITY))),
+            ),
+            (
+                "pow",
+                JsValue::new_function(Box::new(|args| {
+                    arg(args, 0).to_number().pow(arg(args, 1).to_number())
+                })),
+            ),
+            (
+                "random",
+                JsValue::new_function(Box::new(|_| JsValue::Number(random()))),
+            ),
+            ("round", unary(round)),
+            ("sign", unary(sign)),
+            ("sin", unary(f64::sin)),
+            ("sinh", unary(f64::sinh)),
+            ("sqrt", unary(f64::sqrt)),
+            ("tan", unary(f64::tan)),
+            ("tanh", unary(f64::tanh)),
+            ("trunc", unary(f64::trunc)),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        // Like the globals, the members aren't enumerable
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::RegularObject,
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+}
+
+mod object {
+    //! The `Object` object, whose functions work with the own enumerable properties of objects
+    //! and with their prototypes
+
+    use std::{collections::HashMap, iter, rc::Rc};
+
+    use super::{
+        arg, array_length, type_error, FunctionInfo, JsCell, JsObject, JsObjectContents, JsString,
+        JsValue, Methods, ObjectSubtype, PropertyFlags, PropertyKey, PropertyMap, OBJECT_PROTOTYPE,
+    };
+
+    /// The `Object` function, which returns the object that it's given, or else a new empty
+    /// object, with the functions as its members
+    pub fn new_constructor() -> JsValue {
+        let members: [(&str, JsValue); 15] = [
+            ("keys", JsValue::new_function(Box::new(keys))),
+            ("values", JsValue::new_function(Box::new(values))),
+            ("entries", JsValue::new_function(Box::new(entries))),
+            ("fromEntries", JsValue::new_function(Box::new(from_entries))),
+            ("assign", JsValue::new_function(Box::new(assign))),
+            ("freeze", JsValue::new_function(Box::new(freeze))),
+            ("isFrozen", JsValue::new_function(Box::new(is_frozen))),
+            ("create", JsValue::new_function(Box::new(create))),
+            (
+                "defineProperty",
+                JsValue::new_function(Box::new(define_property)),
+            ),
+            (
+                "defineProperties",
+                JsValue::new_function(Box::new(define_properties)),
+            ),
+            (
+                "getOwnPropertyDescriptor",
+                JsValue::new_function(Box::new(get_own_property_descriptor)),
+            ),
+            (
+                "getOwnPropertySymbols",
+                JsValue::new_function(Box::new(get_own_property_symbols)),
+            ),
+            (
+                "getPrototypeOf",
+                JsValue::new_function(Box::new(get_prototype_of)),
+            ),
+            (
+                "setPrototypeOf",
+                JsValue::new_function(Box::new(set_prototype_of)),
+            ),
+            (
+                "prototype",
+                JsValue::Object(OBJECT_PROTOTYPE.with(Rc::clone)),
+            ),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(convert), FunctionInfo::default()),
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `Object(value)`. Unlike in JS, primitives aren't wrapped in objects.
+    fn convert(args: &[JsValue]) -> JsValue {
+        match arg(args, 0) {
+            value @ JsValue::Object(_) => value,
+            _ => JsValue::from_entries(Vec::new()),
+        }
+    }
+
+    /// The keys from `own_enumerable_keys`, which exist for every value but `null` and
+    /// `undefined`
+    fn own_keys(value: &JsValue) -> Vec<JsValue> {
+        if value.is_nullish() {
+            type_error("Cannot convert undefined or null to object");
+        }
+        value.own_enumerable_keys()
+    }
+
+    fn keys(args: &[JsValue]) -> JsValue {
+        JsValue::new_array(own_keys(&arg(args, 0)))
+    }
+
+    fn values(args: &[JsValue]) -> JsValue {
+        let object = arg(args, 0);
+        let values = own_keys(&object)
+            .into_iter()
+            .map(|key| object.get_prop(key))
+            .collect();
+        JsValue::new_array(values)
+    }
+
+    /// The keys and values, as arrays of two elements
+    fn entries(args: &[JsValue]) -> JsValue {
+        let object = arg(args, 0);
+        let entries = own_keys(&object)
+            .into_iter()
+            .map(|key| {
+                let value = object.get_prop(key.clone());
+                JsValue::new_array(vec![key, value])
+            })
+            .collect();
+        JsValue::new_array(entries)
+    }
+
+    /// A new object from the key and value pairs that the iterable has, like an array from
+    /// `entries`
+    fn from_entries(args: &[JsValue]) -> JsValue {
+        let object = JsValue::from_entries(Vec::new());
+        for entry in arg(args, 0).iter_elements() {
+            if !matches!(entry, JsValue::Object(_)) {
+                type_error(format_args!(
+                    "Iterator value {} is not an entry object",
+                    entry.to_js_string().as_str()
+                ));
+            }
+            let key = entry.get_prop(JsValue::Number(0.0));
+            let value = entry.get_prop(JsValue::Number(1.0));
+            object.set_prop(JsValue::String(key.to_js_string()), value);
+        }
+        object
+    }
+
+    /// Copies the own enumerable properties of each source, in order, to the target, and
+    /// returns the target. Sources that are `null` or `undefined` are skipped.
+    fn assign(args: &[JsValue]) -> JsValue {
+        let target = arg(args, 0);
+        let JsValue::Object(target_obj) = &target else {
+            if target.is_nullish() {
+                type_error("Cannot convert undefined or null to object");
+            }
+            // Unlike in JS, primitives aren't wrapped in objects, so they can't be assigned to
+            return target;
+        };
+        for source in args.iter().skip(1) {
+            for (key, value) in source.own_entries() {
+                // Unlike the `=` operator, `assign` doesn't ignore the write
+                let read_only = target
+                    .lookup_property(&key)
+                    .is_some_and(|(property, flags)| {
+                        !flags.writable && property.accessor().is_none()
+                    });
+                if read_only || target_obj.borrow().frozen {
+                    type_error(format_args!(
+                        "Cannot assign to read only property '{key}' of object"
+                    ));
+                }
+                target.set_prop(key.to_value(), value);
+            }
+        }
+        target
+    }
+
+    /// Makes the properties of the object read-only, and returns it. Primitives are returned
+    /// as they are.
+    fn freeze(args: &[JsValue]) -> JsValue {
+        let value = arg(args, 0);
+        if let JsValue::Object(obj) = &value {
+            let mut obj = obj.borrow_mut();
+            obj.frozen = true;
+            obj.properties.freeze();
+        }
+        value
+    }
+
+    /// Whether the value is frozen, which primitives are
+    fn is_frozen(args: &[JsValue]) -> JsValue {
+        JsValue::Boolean(match arg(args, 0) {
+            JsValue::Object(obj) => obj.borrow().frozen,
+            _ => true,
+        })
+    }
+
+    /// The prototype argument, which is an object or `null`
+    fn prototype_arg(args: &[JsValue], index: usize) -> Option<JsObject> {
+        match arg(args, index) {
+            JsValue::Object(prototype) => Some(prototype),
+            JsValue::Null => None,
+            prototype => type_error(format_args!(
+                "Object prototype may only be an Object or null: {}",
+                prototype.to_js_string().as_str()
+            )),
+        }
+    }
+
+    /// A new object that inherits from the prototype, with the properties that the descriptors
+    /// of the second argument define, like `defineProperties`
+    fn create(args: &[JsValue]) -> JsValue {
+        let object = JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::RegularObject,
+            methods: None,
+            prototype: prototype_arg(args, 0),
+            private: HashMap::new(),
+            frozen: false,
+        })));
+        let descriptors = arg(args, 1);
+        if !descriptors.is_undefined() {
+            define_properties(&[object.clone(), descriptors]);
+        }
+        object
+    }
+
+    /// The object argument of a function that defines properties
+    fn object_arg(args: &[JsValue], function: &str) -> JsObject {
+        match arg(args, 0) {
+            JsValue::Object(obj) => obj,
+            _ => type_error(format_args!("Object.{function} called on non-object")),
+        }
+    }
+
+    /// Adds the property to the object, or changes it, as the descriptor says, and returns the
+    /// object. The attributes that a new property isn't given are `false`.
+    ///
+    /// Unlike in JS, the elements of an array can only be given a value, they always have every
+    /// attribute.
+    fn define_property(args: &[JsValue]) -> JsValue {
+        let obj = object_arg(args, "defineProperty");
+        define(&obj, arg(args, 1), &arg(args, 2));
+        arg(args, 0)
+    }
+
+    /// Defines each property of the second argument, which are descriptors, on the object
+    fn define_properties(args: &[JsValue]) -> JsValue {
+        let obj = object_arg(args, "defineProperties");
+        let descriptors = arg(args, 1);
+        for key in own_keys(&descriptors) {
+            let descriptor = descriptors.get_prop(key.clone());
+            define(&obj, key, &descriptor);
+        }
+        arg(args, 0)
+    }
+
+    /// The fields of a property descriptor, which are `None` where it doesn't have them
+    struct Descriptor {
+        value: Option<JsValue>,
+        writable: Option<bool>,
+        get: Option<JsValue>,
+        set: Option<JsValue>,
+        enumerable: Option<bool>,
+        configurable: Option<bool>,
+    }
+
+    impl Descriptor {
+        fn from_object(descriptor: &JsValue) -> Self {
+            if !matches!(descriptor, JsValue::Object(_)) {
+                type_error(format_args!(
+                    "Property description must be an object: {}",
+                    descriptor.to_js_string().as_str()
+                ));
+            }
+            let field = |name: &str| {
+                let name = JsValue::from(name);
+                let has = name.is_in(descriptor.clone()).truthy();
+                has.then(|| descriptor.get_prop(name))
+            };
+            let flag = |name: &str| field(name).map(|value| value.truthy());
+            let descriptor = Descriptor {
+                value: field("value"),
+                writable: flag("writable"),
+                get: field("get"),
+                set: field("set"),
+                enumerable: flag("enumerable"),
+                configurable: flag("configurable"),
+            };
+            if descriptor.is_accessor()
+                && (descriptor.value.is_some() || descriptor.writable.is_some())
+            {
+                type_error("Invalid property descriptor. Cannot both specify accessors and a value or writable attribute");
+            }
+            for (kind, function) in [("Getter", &descriptor.get), ("Setter", &descriptor.set)] {
+                match function {
+                    Some(function) if !function.is_undefined() && !function.is_callable() => {
+                        type_error(format_args!(
+                            "{kind} must be a function: {}",
+                            function.to_js_string().as_str()
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+            descriptor
+        }
+
+        fn is_accessor(&self) -> bool {
+            self.get.is_some() || self.set.is_some()
+        }
+
+        /// Whether it changes the property, which isn't allowed if the property isn't
+        /// configurable, other than giving a writable property a value or making it read-only
+        fn changes(&self, value: &JsValue, flags: PropertyFlags) -> bool {
+            let differs = |new: &Option<JsValue>, old: &JsValue| {
+                new.as_ref()
+                    .is_some_and(|new| new.strict_equals(old.clone()).falsy())
+            };
+            let changes_flag = |new: Option<bool>, old: bool| new.is_some_and(|new| new != old);
+            let changes_value = match value.accessor() {
+                Some((get, set)) => {
+                    self.value.is_some()
+                        || self.writable.is_some()
+                        || differs(&self.get, &get)
+                        || differs(&self.set, &set)
+                }
+                None => {
+                    self.is_accessor()
+                        || !flags.writable
+                            && (differs(&self.value, value) || self.writable == Some(true))
+                }
+            };
+            changes_value
+                || changes_flag(self.enumerable, flags.enumerable)
+                || changes_flag(self.configurable, flags.configurable)
+        }
+    }
+
+    fn define(obj: &JsObject, key: JsValue, descriptor: &JsValue) {
+        let descriptor = Descriptor::from_object(descriptor);
+        let mut contents = obj.borrow_mut();
+        if let ObjectSubtype::Array(ref mut array) = contents.subtype {
+            if let Some(index) = key.to_array_index() {
+                if index >= array.len() {
+                    array.resize(index + 1, None);
+                }
+                let element = array[index].take().unwrap_or(JsValue::Undefined);
+                array[index] = Some(descriptor.value.unwrap_or(element));
+                return;
+            }
+        }
+        let key = key.to_property_key();
+        let (old_value, old_flags) = match contents.properties.property(&key) {
+            Some((value, flags)) if !flags.configurable && descriptor.changes(value, flags) => {
+                type_error(format_args!("Cannot redefine property: {key}"))
+            }
+            Some((value, flags)) => (value.clone(), flags),
+            None if contents.frozen => type_error(format_args!(
+                "Cannot define property {key}, object is not extensible"
+            )),
+            None => (JsValue::Undefined, PropertyFlags::NONE),
+        };
+        let is_accessor = descriptor.is_accessor();
+        let value = match (is_accessor, old_value.accessor()) {
+            (true, old) => {
+                let (get, set) = old.unwrap_or((JsValue::Undefined, JsValue::Undefined));
+                let get = descriptor.get.unwrap_or(get);
+                JsValue::new_accessor(get, descriptor.set.unwrap_or(set))
+            }
+            // Giving an accessor a value or making it writable turns it into a data property
+            (false, Some(_)) if descriptor.value.is_none() && descriptor.writable.is_none() => {
+                old_value
+            }
+            (false, Some(_)) => descriptor.value.unwrap_or(JsValue::Undefined),
+            (false, None) => descriptor.value.unwrap_or(old_value),
+        };
+        let writable = old_flags.writable && !is_accessor;
+        let flags = PropertyFlags {
+            writable: descriptor.writable.unwrap_or(writable),
+            enumerable: descriptor.enumerable.unwrap_or(old_flags.enumerable),
+            configurable: descriptor.configurable.unwrap_or(old_flags.configurable),
+        };
+        contents.properties.define(key, value, flags);
+    }
+
+    /// The descriptor of the own property of the object, or `undefined` if it doesn't have
+    /// it. Like the elements of arrays, the properties of the other values are left out.
+    fn get_own_property_descriptor(args: &[JsValue]) -> JsValue {
+        let object = arg(args, 0);
+        let key = arg(args, 1);
+        let JsValue::Object(obj) = &object else {
+            if object.is_nullish() {
+                type_error("Cannot convert undefined or null to object");
+            }
+            return JsValue::Undefined;
+        };
+        let contents = obj.borrow();
+        if let ObjectSubtype::Array(ref array) = contents.subtype {
+            // Past the end of the vector, it may be a sparse element
+            if let Some(index) = key.to_array_index().filter(|index| *index < array.len()) {
+                return match array.get(index).cloned().flatten() {
+                    Some(element) => descriptor(element, PropertyFlags::ALL),
+                    None => JsValue::Undefined,
+                };
+            }
+            if key.is_length_key() {
+                let flags = PropertyFlags {
+                    writable: true,
+                    ..PropertyFlags::NONE
+                };
+                let length = array_length(array, &contents.properties);
+                return descriptor(length.into(), flags);
+            }
+        }
+        match contents.properties.property(&key.to_property_key()) {
+            Some((value, flags)) => descriptor(value.clone(), flags),
+            None => JsValue::Undefined,
+        }
+    }
+
+    /// The symbols that are the keys of the own properties of the object, including the ones
+    /// that aren't enumerable
+    fn get_own_property_symbols(args: &[JsValue]) -> JsValue {
+        let object = arg(args, 0);
+        let JsValue::Object(obj) = &object else {
+            if object.is_nullish() {
+                type_error("Cannot convert undefined or null to object");
+            }
+            return JsValue::new_array(Vec::new());
+        };
+        let symbols = (obj.borrow().properties.entries.iter())
+            .filter(|(key, _, _)| matches!(key, PropertyKey::Symbol(_)))
+            .map(|(key, _, _)| key.to_value())
+            .collect();
+        JsValue::new_array(symbols)
+    }
+
+    /// The descriptor object of a property with the value, which may be an accessor
+    fn descriptor(value: JsValue, flags: PropertyFlags) -> JsValue {
+        let mut entries = match value.accessor() {
+            Some((get, set)) => vec![("get", get), ("set", set)],
+            None => vec![
+                ("value", value),
+                ("writable", JsValue::Boolean(flags.writable)),
+            ],
+        };
+        entries.push(("enumerable", JsValue::Boolean(flags.enumerable)));
+        entries.push(("configurable", JsValue::Boolean(flags.configurable)));
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| (PropertyKey::from(key), value));
+        JsValue::from_entries(entries.collect())
+    }
+
+    /// The prototype of the object, or `null` at the end of the prototype chain. Unlike in JS,
+    /// the values whose methods stand in for their prototype, like arrays, primitives and the
+    /// instances of classes, have `null` too.
+    fn get_prototype_of(args: &[JsValue]) -> JsValue {
+        match arg(args, 0) {
+            JsValue::Object(obj) => obj
+                .borrow()
+                .prototype
+                .clone()
+                .map_or(JsValue::Null, JsValue::Object),
+            value if value.is_nullish() => type_error("Cannot convert undefined or null to object"),
+            _ => JsValue::Null,
+        }
+    }
+
+    /// Replaces the prototype of the object, and returns the object. Primitives are returned as
+    /// they are.
+    fn set_prototype_of(args: &[JsValue]) -> JsValue {
+        let value = arg(args, 0);
+        if value.is_nullish() {
+            type_error("Object.setPrototypeOf called on null or undefined");
+        }
+        let prototype = prototype_arg(args, 1);
+        let JsValue::Object(obj) = &value else {
+            return value;
+        };
+        let mut chain = iter::successors(prototype.clone(), |object| {
+            object.borrow().prototype.clone()
+        });
+        if chain.any(|object| Rc::ptr_eq(&object, obj)) {
+            type_error("Cyclic __proto__ value");
+        }
+        if obj.borrow().frozen {
+            type_error("#<Object> is not extensible");
+        }
+        obj.borrow_mut().prototype = prototype;
+        value
+    }
+}
+
+mod number {
+    //! The `Number` object, the methods of numbers, and the global functions that parse and
+    //! check numbers
+
+    use std::{collections::HashMap, rc::Rc};
+
+    use super::{
+        arg, global, is_js_whitespace, number_to_string, range_error, type_error, FunctionInfo,
+        JsCell, JsObject, JsObjectContents, JsString, JsValue, Methods, ObjectSubtype, PropertyMap,
+    };
+
+    /// The `Number` function, which converts its argument to a number, with the constants and
+    /// the functions as its members
+    pub fn new_constructor() -> JsValue {
+        let members: [(&str, JsValue); 14] = [
+            ("EPSILON", JsValue::Number(f64::EPSILON)),
+            ("MAX_SAFE_INTEGER", JsValue::Number(MAX_SAFE_INTEGER)),
+            ("MIN_SAFE_INTEGER", JsValue::Number(-MAX_SAFE_INTEGER)),
+            ("MAX_VALUE", JsValue::Number(f64::MAX)),
+            // The smallest positive number, unlike `f64::MIN_POSITIVE`, which is normal
+            ("MIN_VALUE", JsValue::Number(f64::from_bits(1))),
+            ("NaN", JsValue::Number(f64::NAN)),
+            ("POSITIVE_INFINITY", JsValue::Number(f64::INFINITY)),
+            ("NEGATIVE_INFINITY", JsValue::Number(f64::NEG_INFINITY)),
+            // Unlike the global functions, these don't convert their argument to a number
+            (
+                "isFinite",
+                predicate(|value| value.is_some_and(f64::is_finite)),
+            ),
+            ("isNaN", predicate(|value| value.is_some_and(f64::is_nan))),
+            (
+                "isInteger",
+                predicate(|value| value.is_some_and(is_integer)),
+            ),
+            (
+                "isSafeInteger",
+                predicate(|value| {
+                    value.is_some_and(|num| is_integer(num) && num.abs() <= MAX_SAFE_INTEGER)
+                }),
+            ),
+            // The same functions as the global ones
+            ("parseFloat", global("parseFloat")),
+            ("parseInt", global("parseInt")),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(convert), FunctionInfo::default()),
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `Number(value)`, which is 0 without a value
+    fn convert(args: &[JsValue]) -> JsValue {
+        match args.first() {
+            Some(value) => value.to_number(),
+            None => JsValue::Number(0.0),
+        }
+    }
+
+    /// The methods that `NUMBER_METHODS` holds
+    pub fn methods() -> Methods {
+        let methods: [(&str, JsValue); 5] = [
+            ("toString", JsValue::new_method(Box::new(to_string))),
+            ("toFixed", JsValue::new_method(Box::new(to_fixed))),
+            (
+                "toExponential",
+                JsValue::new_method(Box::new(to_exponential)),
+            ),
+            ("toPrecision", JsValue::new_method(Box::new(to_precision))),
+            (
+                "valueOf",
+                JsValue::new_method(Box::new(|this, _| JsValue::Number(this_number(this)))),
+            ),
+        ];
+        Methods::from(HashMap::from_iter(
+            methods
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        ))
+    }
+
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+    /// A function that checks its argument, which `f` receives as `None` if it isn't a number
+    fn predicate(f: fn(Option<f64>) -> bool) -> JsValue {
+        JsValue::new_function(Box::new(move |args| {
+            let value = match arg(args, 0) {
+                JsValue::Number(num) => Some(num),
+                _ => None,
+            };
+            JsValue::Boolean(f(value))
+        }))
+    }
+
+    fn is_integer(num: f64) -> bool {
+        num.is_finite() && num.trunc() == num
+    }
+
+    fn this_number(this: &JsValue) -> f64 {
+        match this {
+            JsValue::Number(num) => *num,
+            _ => type_error("Number.prototype.valueOf requires that 'this' be a Number"),
+        }
+    }
+
+    /// The number of digits that the methods take, which must be an integer in the range
+    fn digits_argument(
+        value: &JsValue,
+        range: std::ops::RangeInclusive<f64>,
+        message: &str,
+    ) -> usize {
+        let digits = value.to_integer_or_infinity();
+        if !range.contains(&digits) {
+            range_error(format_args!("{message}"));
+        }
+        digits as usize
+    }
+
+    /// Implements `toString`, in the radix from 2 to 36 that the argument is, or 10
+    fn to_string(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let num = this_number(this);
+        let radix = match arg(args, 0) {
+            JsValue::Undefined => 10,
+            radix => digits_argument(
+                &radix,
+                2.0..=36.0,
+                "toString() radix must be between 2 and 36",
+            ),
+        };
+        let text = if radix == 10 || !num.is_finite() {
+            number_to_string(num)
+        } else {
+            to_radix_string(num, radix as u32)
+        };
+        JsValue::String(JsString::from(text))
+    }
+
+    /// The digits of the finite number in the radix, with as many digits after the point as
+    /// it takes to tell the number apart from the next one, like V8 does
+    fn to_radix_string(num: f64, radix: u32) -> String {
+        let radix_f64 = radix as f64;
+        let digit = |value: u32| char::from_digit(value, radix).unwrap();
+        let negative = num < 0.0;
+        let value = num.abs();
+        let mut integer = value.floor();
+        let mut fraction = value - integer;
+        // Half of the distance to the next number, or the smallest number for 0
+        let mut delta =
+            (0.5 * (f64::from_bits(value.to_bits() + 1) - value)).max(f64::from_bits(1));
+        let mut fraction_digits: Vec<u32> = Vec::new();
+        if fraction >= delta {
+            loop {
+                fraction *= radix_f64;
+                delta *= radix_f64;
+                let next = fraction as u32;
+                fraction_digits.push(next);
+                fraction -= next as f64;
+                // Rounds to even
+                let rounds_up = fraction > 0.5 || (fraction == 0.5 && next & 1 == 1);
+                if rounds_up && fraction + delta > 1.0 {
+                    // Carries over the digits that were written already
+                    loop {
+                        match fraction_digits.pop() {
+                            None => {
+                                integer += 1.0;
+                                break;
+                            }
+                            Some(last) if last + 1 < radix => {
+                                fraction_digits.push(last + 1);
+                                break;
+                            }
+                            Some(_) => (),
+                        }
+                    }
+                    break;
+                }
+                if fraction < delta {
+                    break;
+                }
+            }
+        }
+        // The digits beyond the precision of the integer part are zeros
+        let mut integer_digits = Vec::new();
+        while integer / radix_f64 >= 2f64.powi(53) {
+            integer /= radix_f64;
+            integer_digits.push(0);
+        }
+        loop {
+            let remainder = integer % radix_f64;
+            integer_digits.push(remainder as u32);
+            integer = (integer - remainder) / radix_f64;
+            if integer <= 0.0 {
+                break;
+            }
+        }
+        let mut text = String::from(if negative { "-" } else { "" });
+        text.extend(integer_digits.into_iter().rev().map(digit));
+        if !fraction_digits.is_empty() {
+            text.push('.');
+            text.extend(fraction_digits.into_iter().map(digit));
+        }
+        text
+    }
+
+    /// Implements `toFixed`, with the number of digits after the point that the argument is,
+    /// from 0 to 100
+    fn to_fixed(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let num = this_number(this);
+        let fraction_len = digits_argument(
+            &arg(args, 0),
+            0.0..=100.0,
+            "toFixed() digits argument must be between 0 and 100",
+        );
+        if !num.is_finite() || num.abs() >= 1e21 {
+            return JsValue::String(JsString::from(number_to_string(num)));
+        }
+        // All the digits of the number, which has less than 1100 of them after the point
+        let exact = format!("{:.1100}", num.abs());
+        let (integer, fraction) = exact.split_once('.').unwrap();
+        let digits = format!("{integer}{fraction}");
+        let (rounded, _) = round_digits(digits.as_bytes(), integer.len() + fraction_len);
+        let (integer, fraction) = rounded.split_at(rounded.len() - fraction_len);
+        let sign = if num < 0.0 { "-" } else { "" };
+        let point = if fraction_len > 0 { "." } else { "" };
+        JsValue::String(JsString::from(format!("{sign}{integer}{point}{fraction}")))
+    }
+
+    /// Implements `toExponential`, with the number of digits after the point that the argument
+    /// is, from 0 to 100, or as many as it takes to tell the number apart from the others
+    fn to_exponential(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let num = this_number(this);
+        let fraction_len = arg(args, 0);
+        if !num.is_finite() {
+            return JsValue::String(JsString::from(number_to_string(num)));
+        }
+        let (digits, exponent) = match fraction_len {
+            JsValue::Undefined => shortest_digits(num),
+            fraction_len => {
+                let fraction_len = digits_argument(
+                    &fraction_len,
+                    0.0..=100.0,
+                    "toExponential() argument must be between 0 and 100",
+                );
+                significant_digits(num, fraction_len + 1)
+            }
+        };
+        JsValue::String(JsString::from(exponential(num, &digits, exponent)))
+    }
+
+    /// Implements `toPrecision`, with the number of significant digits that the argument is,
+    /// from 1 to 100, in exponential notation if the number is too large or small for them
+    fn to_precision(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let num = this_number(this);
+        let precision = arg(args, 0);
+        if precision.is_undefined() || !num.is_finite() {
+            return JsValue::String(JsString::from(number_to_string(num)));
+        }
+        let precision = digits_argument(
+            &precision,
+            1.0..=100.0,
+            "toPrecision() argument must be between 1 and 100",
+        );
+        let (digits, exponent) = significant_digits(num, precision);
+        let sign = if num < 0.0 { "-" } else { "" };
+        let text = if exponent < -6 || exponent >= precision as i32 {
+            exponential(num, &digits, exponent)
+        } else if exponent >= 0 {
+            let (integer, fraction) = digits.split_at(exponent as usize + 1);
+            let point = if fraction.is_empty() { "" } else { "." };
+            format!("{sign}{integer}{point}{fraction}")
+        } else {
+            let zeros = "0".repeat((-exponent - 1) as usize);
+            format!("{sign}0.{zeros}{digits}")
+        };
+        JsValue::String(JsString::from(text))
+    }
+
+    /// The number in exponential notation, like `-1.25e+3`, from its significant digits and
+    /// the exponent of the first one
+    fn exponential(num: f64, digits: &str, exponent: i32) -> String {
+        let sign = if num < 0.0 { "-" } else { "" };
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        let exponent_sign = if exponent < 0 { "-" } else { "+" };
+        format!(
+            "{sign}{first}{point}{rest}e{exponent_sign}{}",
+            exponent.abs()
+        )
+    }
+
+    /// The shortest digits that tell the number apart from the others, and the exponent of
+    /// the first one
+    fn shortest_digits(num: f64) -> (String, i32) {
+        let text = format!("{:e}", num.abs());
+        let (mantissa, exponent) = text.split_once('e').unwrap();
+        (mantissa.replace('.', ""), exponent.parse().unwrap())
+    }
+
+    /// The number rounded to `len` significant digits, with ties away from zero, and the
+    /// exponent of the first one
+    fn significant_digits(num: f64, len: usize) -> (String, i32) {
+        if num == 0.0 {
+            return ("0".repeat(len), 0);
+        }
+        // All the digits of the number, which has less than 1100 significant ones
+        let text = format!("{:.1100e}", num.abs());
+        let (mantissa, exponent) = text.split_once('e').unwrap();
+        let digits = mantissa.replace('.', "");
+        let (mut rounded, carried) = round_digits(digits.as_bytes(), len);
+        let mut exponent: i32 = exponent.parse().unwrap();
+        if carried {
+            rounded.pop();
+            exponent += 1;
+        }
+        (rounded, exponent)
+    }
+
+    /// The first `len` of the decimal digits, padded with zeros, rounded up if the next digit
+    /// is at least 5. It has an extra digit if the rounding carries over the first digit,
+    /// which is told by the second return value.
+    fn round_digits(digits: &[u8], len: usize) -> (String, bool) {
+        let mut kept: Vec<u8> = digits
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(b'0'))
+            .take(len)
+            .collect();
+        let mut carried = false;
+        if digits.get(len).is_some_and(|next| *next >= b'5') {
+            carried = true;
+            for digit in kept.iter_mut().rev() {
+                if *digit == b'9' {
+                    *digit = b'0';
+                } else {
+                    *digit += 1;
+                    carried = false;
+                    break;
+                }
+            }
+            if carried {
+                kept.insert(0, b'1');
+            }
+        }
+        (String::from_utf8(kept).unwrap(), carried)
+    }
+
+    fn number(value: &JsValue) -> f64 {
+        match value.to_number() {
+            JsValue::Number(num) => num,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parses the integer at the start of the string, after white space, in the radix from 2
+    /// to 36, which is 16 if it's 0 or missing and the string starts with `0x`, or else 10
+    pub fn parse_int(args: &[JsValue]) -> JsValue {
+        let string = arg(args, 0).to_js_string();
+        let text = string.as_str().trim_start_matches(is_js_whitespace);
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(rest) => (-1.0, rest),
+            None => (1.0, text.strip_prefix('+').unwrap_or(text)),
+        };
+        // Only a radix of 16, or none, allows the `0x` prefix
+        let (mut radix, allows_prefix) = match arg(args, 1).to_int32() {
+            0 => (10, true),
+            16 => (16, true),
+            radix @ 2..=36 => (radix as u32, false),
+            _ => return JsValue::Number(f64::NAN),
+        };
+        let mut digits = text;
+        if let Some(rest) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            if allows_prefix {
+                radix = 16;
+                digits = rest;
+            }
+        }
+        let end = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        let digits = &digits[..end];
+        if digits.is_empty() {
+            return JsValue::Number(f64::NAN);
+        }
+        let value = if radix == 10 {
+            // Rust rounds the long ones correctly, unlike adding up the digits
+            digits.parse().unwrap()
+        } else {
+            digits.chars().fold(0.0, |value, c| {
+                value * radix as f64 + c.to_digit(radix).unwrap() as f64
+            })
+        };
+        JsValue::Number(sign * value)
+    }
+
+    /// Parses the decimal number at the start of the string, after white space, ignoring
+    /// whatever follows it
+    pub fn parse_float(args: &[JsValue]) -> JsValue {
+        let string = arg(args, 0).to_js_string();
+        let text = string.as_str().trim_start_matches(is_js_whitespace);
+        let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+        if unsigned.starts_with("Infinity") {
+            let sign = if text.starts_with('-') { -1.0 } else { 1.0 };
+            return JsValue::Number(sign * f64::INFINITY);
+        }
+        let sign_len = text.len() - unsigned.len();
+        let integer_len = digits_len(unsigned);
+        let mut len = integer_len;
+        let mut has_digits = integer_len > 0;
+        if unsigned[len..].starts_with('.') {
+            let fraction_len = digits_len(&unsigned[len + 1..]);
+            has_digits |= fraction_len > 0;
+            len += 1 + fraction_len;
+        }
+        if !has_digits {
+            return JsValue::Number(f64::NAN);
+        }
+        if let Some(exponent) = unsigned[len..].strip_prefix(['e', 'E']) {
+            let exponent_digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            let exponent_len = digits_len(exponent_digits);
+            if exponent_len > 0 {
+                len = unsigned.len() - exponent_digits.len() + exponent_len;
+            }
+        }
+        JsValue::Number(text[..sign_len + len].parse().unwrap())
+    }
+
+    fn digits_len(text: &str) -> usize {
+        text.find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len())
+    }
+
+    pub fn is_nan(args: &[JsValue]) -> JsValue {
+        JsValue::Boolean(number(&arg(args, 0)).is_nan())
+    }
+
+    pub fn is_finite(args: &[JsValue]) -> JsValue {
+        JsValue::Boolean(number(&arg(args, 0)).is_finite())
+    }
+}
+
+mod symbol {
+    //! The `Symbol` function, the registry of the symbols of `Symbol.for`, and the methods of
+    //! symbols
+
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    use super::{
+        arg, type_error, FunctionInfo, JsCell, JsObject, JsObjectContents, JsString, JsSymbol,
+        JsValue, Methods, ObjectSubtype, PropertyMap,
+    };
+
+    thread_local! {
+        /// The symbols that `Symbol.for` created, by their keys
+        static REGISTRY: RefCell<HashMap<JsString, JsSymbol>> = RefCell::new(HashMap::new());
+
+        /// `Symbol.iterator`, the key of the method that returns the iterator of an object
+        pub(super) static ITERATOR: JsSymbol =
+            JsSymbol::new(Some(JsString::from("Symbol.iterator")));
+    }
+
+    /// The `Symbol` function, which creates a new symbol, with `for`, `keyFor` and the
+    /// well-known symbols as its members
+    pub fn new_constructor() -> JsValue {
+        let members: [(&str, JsValue); 3] = [
+            ("for", JsValue::new_function(Box::new(for_key))),
+            ("keyFor", JsValue::new_function(Box::new(key_for))),
+            ("iterator", JsValue::Symbol(ITERATOR.with(JsSymbol::clone))),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(create), FunctionInfo::default()),
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `Symbol(description)`, where the description is converted to a string,
+    /// unless it's `undefined`
+    fn create(args: &[JsValue]) -> JsValue {
+        let description = match arg(args, 0) {
+            JsValue::Undefined => None,
+            description => Some(description.to_js_string()),
+        };
+        JsValue::Symbol(JsSymbol::new(description))
+    }
+
+    /// The symbol of the registry with the key, which is created with the key as its
+    /// description if there isn't one yet
+    fn for_key(args: &[JsValue]) -> JsValue {
+        let key = arg(args, 0).to_js_string();
+        let symbol = REGISTRY.with(|registry| {
+            let mut registry = registry.borrow_mut();
+            let symbol = registry.entry(key.clone());
+            symbol.or_insert_with(|| JsSymbol::new(Some(key))).clone()
+        });
+        JsValue::Symbol(symbol)
+    }
+
+    /// The key of the symbol in the registry, or `undefined` if `Symbol.for` didn't create it
+    fn key_for(args: &[JsValue]) -> JsValue {
+        let symbol = match arg(args, 0) {
+            JsValue::Symbol(symbol) => symbol,
+            value => type_error(format_args!(
+                "{} is not a symbol",
+                value.to_js_string().as_str()
+            )),
+        };
+        REGISTRY.with(|registry| {
+            let registry = registry.borrow();
+            let mut keys = registry.iter().filter(|(_, other)| **other == symbol);
+            keys.next()
+                .map_or(JsValue::Undefined, |(key, _)| JsValue::String(key.clone()))
+        })
+    }
+
+    /// The methods that `SYMBOL_METHODS` holds
+    pub fn methods() -> Methods {
+        let methods: [(&str, JsValue); 2] = [
+            ("toString", JsValue::new_method(Box::new(to_string))),
+            (
+                "valueOf",
+                JsValue::new_method(Box::new(|this, _| this.clone())),
+            ),
+        ];
+        Methods::from(HashMap::from_iter(
+            methods
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        ))
+    }
+
+    /// Implements `toString`, which, unlike converting the symbol implicitly, is allowed
+    fn to_string(this: &JsValue, _: &[JsValue]) -> JsValue {
+        match this {
+            JsValue::Symbol(symbol) => JsValue::from(symbol.to_string().as_str()),
+            _ => type_error("Symbol.prototype.toString requires that 'this' be a Symbol"),
+        }
+    }
+}
+
+mod collection {
+    //! The `Map` and `Set` objects, whose keys are compared like SameValueZero does, so `NaN`
+    //! is equal to itself, and objects are only equal to themselves
+
+    use std::{collections::HashMap, iter, rc::Rc};
+
+    use super::{
+        arg, new_target, type_error, FunctionInfo, JsCell, JsObject, JsObjectContents, JsString,
+        JsSymbol, JsValue, Methods, ObjectSubtype, PropertyMap, MAP_METHODS, SET_METHODS,
+    };
+
+    /// The entries of a map or a set, in the order that they were added in. A set has its
+    /// values as the keys too.
+    #[derive(Default)]
+    pub struct Entries {
+        /// `None` where an entry was deleted, so that the entries after it keep their index
+        /// for the loops that are visiting them
+        entries: Vec<Option<(JsValue, JsValue)>>,
+        /// The index of each key in the entries
+        indices: HashMap<Key, usize>,
+    }
+
+    impl Entries {
+        pub fn len(&self) -> usize {
+            self.indices.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.indices.is_empty()
+        }
+
+        fn get(&self, key: &JsValue) -> Option<&JsValue> {
+            let (_, value) = self.entries[*self.indices.get(&Key::new(key))?].as_ref()?;
+            Some(value)
+        }
+
+        /// Replacing the value of a key keeps its place in the order
+        fn set(&mut self, key: JsValue, value: JsValue) {
+            match self.indices.get(&Key::new(&key)) {
+                Some(&index) => self.entries[index] = Some((key, value)),
+                None => {
+                    self.indices.insert(Key::new(&key), self.entries.len());
+                    // Like in JS, -0 is stored as 0, which adding 0 turns it into
+                    let key = match key {
+                        JsValue::Number(num) => JsValue::Number(num + 0.0),
+                        key => key,
+                    };
+                    self.entries.push(Some((key, value)));
+                }
+            }
+        }
+
+        fn delete(&mut self, key: &JsValue) -> bool {
+            match self.indices.remove(&Key::new(key)) {
+                Some(index) => {
+                    self.entries[index] = None;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn clear(&mut self) {
+            self.entries.iter_mut().for_each(|entry| *entry = None);
+            self.indices.clear();
+        }
+
+        /// The first entry from the index on, with the index after it
+        fn next_from(&self, index: usize) -> Option<(usize, (JsValue, JsValue))> {
+            let entries = self.entries.get(index..)?.iter().enumerate();
+            entries
+                .filter_map(|(offset, entry)| Some((index + offset + 1, entry.clone()?)))
+                .next()
+        }
+
+        /// The entries as they are now
+        pub fn iter(&self) -> impl Iterator<Item = &(JsValue, JsValue)> {
+            self.entries.iter().flatten()
+        }
+    }
+
+    /// The identity of a key, where numbers are compared by their bits after making every
+    /// `NaN` the same and -0 the same as 0, and objects by their address
+    #[derive(Hash, PartialEq, Eq)]
+    enum Key {
+        Undefined,
+        Null,
+        Boolean(bool),
+        Number(u64),
+        String(JsString),
+        Symbol(JsSymbol),
+        Object(*const JsCell<JsObjectContents>),
+    }
+
+    impl Key {
+        fn new(value: &JsValue) -> Key {
+            match value {
+                JsValue::Undefined => Key::Undefined,
+                JsValue::Null => Key::Null,
+                JsValue::Boolean(boolean) => Key::Boolean(*boolean),
+                JsValue::Number(num) if num.is_nan() => Key::Number(f64::NAN.to_bits()),
+                JsValue::Number(num) if *num == 0.0 => Key::Number(0.0f64.to_bits()),
+                JsValue::Number(num) => Key::Number(num.to_bits()),
+                JsValue::String(string) => Key::String(string.clone()),
+                JsValue::Symbol(symbol) => Key::Symbol(symbol.clone()),
+                JsValue::Object(obj) => Key::Object(Rc::as_ptr(obj)),
+            }
+        }
+    }
+
+    /// The `Map` or the `Set` function, which must be called with `new`
+    pub fn new_constructor(is_set: bool) -> JsValue {
+        let name = if is_set { "Set" } else { "Map" };
+        let construct: fn(&[JsValue]) -> JsValue = match is_set {
+            true => construct_set,
+            false => construct_map,
+        };
+        let info = FunctionInfo {
+            name,
+            is_arrow: false,
+            uses_new_target: true,
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(construct), info),
+            methods: None,
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    fn new_collection(is_set: bool, name: &str) -> JsValue {
+        if new_target().is_undefined() {
+            type_error(format_args!("Constructor {name} requires 'new'"));
+        }
+        let (subtype, methods) = match is_set {
+            true => (ObjectSubtype::Set(Entries::default()), &SET_METHODS),
+            false => (ObjectSubtype::Map(Entries::default()), &MAP_METHODS),
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype,
+            methods: Some(methods.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `new Map(entries)`, where the entries are key and value pairs, like an array
+    /// from `Object.entries`
+    fn construct_map(args: &[JsValue]) -> JsValue {
+        let map = new_collection(false, "Map");
+        let iterable = arg(args, 0);
+        if !iterable.is_nullish() {
+            for entry in iterable.iter_elements() {
+                if !matches!(entry, JsValue::Object(_)) {
+                    type_error(format_args!(
+                        "Iterator value {} is not an entry object",
+                        entry.to_js_string().as_str()
+                    ));
+                }
+                let key = entry.get_prop(JsValue::Number(0.0));
+                let value = entry.get_prop(JsValue::Number(1.0));
+                with_entries(&map, false, "set", |entries| entries.set(key, value));
+            }
+        }
+        map
+    }
+
+    /// Implements `new Set(values)`
+    fn construct_set(args: &[JsValue]) -> JsValue {
+        let set = new_collection(true, "Set");
+        let iterable = arg(args, 0);
+        if !iterable.is_nullish() {
+            for value in iterable.iter_elements() {
+                with_entries(&set, true, "add", |entries| {
+                    entries.set(value.clone(), value)
+                });
+            }
+        }
+        set
+    }
+
+    /// The methods that `MAP_METHODS` holds
+    pub fn map_methods() -> Methods {
+        let methods: [(&str, JsValue); 9] = [
+            (
+                "get",
+                JsValue::new_method(Box::new(|this, args| {
+                    with_entries(this, false, "get", |entries| {
+                        let value = entries.get(&arg(args, 0)).cloned();
+                        value.unwrap_or(JsValue::Undefined)
+                    })
+                })),
+            ),
+            (
+                "set",
+                JsValue::new_method(Box::new(|this, args| {
+                    with_entries(this, false, "set", |entries| {
+                        entries.set(arg(args, 0), arg(args, 1))
+                    });
+                    this.clone()
+                })),
+            ),
+            (
+                "has",
+                JsValue::new_method(Box::new(|this, args| has(this, args, false))),
+            ),
+            (
+                "delete",
+                JsValue::new_method(Box::new(|this, args| delete(this, args, false))),
+            ),
+            (
+                "clear",
+                JsValue::new_method(Box::new(|this, _| clear(this, false))),
+            ),
+            (
+                "forEach",
+                JsValue::new_method(Box::new(|this, args| for_each(this, args, false))),
+            ),
+            (
+                "keys",
+                JsValue::new_method(Box::new(|this, _| {
+                    collect(this, false, "keys", |(key, _)| key)
+                })),
+            ),
+            (
+                "values",
+                JsValue::new_method(Box::new(|this, _| {
+                    collect(this, false, "values", |(_, value)| value)
+                })),
+            ),
+            (
+                "entries",
+                JsValue::new_method(Box::new(|this, _| collect(this, false, "entries", entry))),
+            ),
+        ];
+        Methods::from(HashMap::from_iter(
+            methods
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        ))
+    }
+
+    /// The methods that `SET_METHODS` holds, where `keys` is the same as `values`
+    pub fn set_methods() -> Methods {
+        let values = JsValue::new_method(Box::new(|this, _| {
+            collect(this, true, "values", |(_, value)| value)
+        }));
+        let methods: [(&str, JsValue); 8] = [
+            (
+                "add",
+                JsValue::new_method(Box::new(|this, args| {
+                    let value = arg(args, 0);
+                    with_entries(this, true, "add", |entries| {
+                        entries.set(value.clone(), value)
+                    });
+                    this.clone()
+                })),
+            ),
+            (
+                "has",
+                JsValue::new_method(Box::new(|this, args| has(this, args, true))),
+            ),
+            (
+                "delete",
+                JsValue::new_method(Box::new(|this, args| delete(this, args, true))),
+            ),
+            (
+                "clear",
+                JsValue::new_method(Box::new(|this, _| clear(this, true))),
+            ),
+            (
+                "forEach",
+                JsValue::new_method(Box::new(|this, args| for_each(this, args, true))),
+            ),
+            ("keys", values.clone()),
+            ("values", values),
+            (
+                "entries",
+                JsValue::new_method(Box::new(|this, _| collect(this, true, "entries", entry))),
+            ),
+        ];
+        Methods::from(HashMap::from_iter(
+            methods
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        ))
+    }
+
+    /// Calls `f` with the entries of the map, or of the set if `is_set`, which panics if
+    /// `this` is neither
+    fn with_entries<T>(
+        this: &JsValue,
+        is_set: bool,
+        method: &str,
+        f: impl FnOnce(&mut Entries) -> T,
+    ) -> T {
+        if let JsValue::Object(obj) = this {
+            match (&mut obj.borrow_mut().subtype, is_set) {
+                (ObjectSubtype::Map(entries), false) | (ObjectSubtype::Set(entries), true) => {
+                    return f(entries)
+                }
+                _ => {}
+            }
+        }
+        type_error(format_args!(
+            "Method {}.prototype.{method} called on incompatible receiver {}",
+            if is_set { "Set" } else { "Map" },
+            this.to_js_string().as_str()
+        ))
+    }
+
+    fn has(this: &JsValue, args: &[JsValue], is_set: bool) -> JsValue {
+        let key = arg(args, 0);
+        JsValue::Boolean(with_entries(this, is_set, "has", |entries| {
+            entries.get(&key).is_some()
+        }))
+    }
+
+    fn delete(this: &JsValue, args: &[JsValue], is_set: bool) -> JsValue {
+        let key = arg(args, 0);
+        JsValue::Boolean(with_entries(this, is_set, "delete", |entries| {
+            entries.delete(&key)
+        }))
+    }
+
+    fn clear(this: &JsValue, is_set: bool) -> JsValue {
+        with_entries(this, is_set, "clear", Entries::clear);
+        JsValue::Undefined
+    }
+
+    /// Calls the callback with the value, the key and the collection of each entry. Like a
+    /// `for-of` loop, it visits the entries that the callback adds, but not the ones that it
+    /// deletes.
+    fn for_each(this: &JsValue, args: &[JsValue], is_set: bool) -> JsValue {
+        let callback = arg(args, 0);
+        if !callback.is_callable() {
+            type_error(format_args!(
+                "{} is not a function",
+                callback.to_js_string().as_str()
+            ));
+        }
+        let this_arg = arg(args, 1);
+        let mut index = 0;
+        while let Some((next, (key, value))) =
+            with_entries(this, is_set, "forEach", |entries| entries.next_from(index))
+        {
+            index = next;
+            callback.call_with_this(&this_arg, &[value, key, this.clone()]);
+        }
+        JsValue::Undefined
+    }
+
+    /// The key and the value as an array of two elements
+    fn entry((key, value): (JsValue, JsValue)) -> JsValue {
+        JsValue::new_array(vec![key, value])
+    }
+
+    /// Unlike in JS, `keys`, `values` and `entries` return arrays instead of iterators, which
+    /// `for-of` loops and spreading handle the same way
+    fn collect(
+        this: &JsValue,
+        is_set: bool,
+        method: &str,
+        f: fn((JsValue, JsValue)) -> JsValue,
+    ) -> JsValue {
+        let items = with_entries(this, is_set, method, |entries| {
+            entries.iter().cloned().map(f).collect()
+        });
+        JsValue::new_array(items)
+    }
+
+    /// Whether the value is a map or a set
+    pub fn is_collection(value: &JsValue) -> bool {
+        match value {
+            JsValue::Object(obj) => matches!(
+                obj.borrow().subtype,
+                ObjectSubtype::Map(_) | ObjectSubtype::Set(_)
+            ),
+            _ => false,
+        }
+    }
+
+    /// The values that a `for-of` loop visits, which are the entries of a map, as arrays of
+    /// two elements, or the values of a set. They're read one at a time, like `forEach` does.
+    pub fn iter_elements(collection: &JsValue) -> Box<dyn Iterator<Item = JsValue>> {
+        let JsValue::Object(obj) = collection.clone() else {
+            unreachable!()
+        };
+        let mut index = 0;
+        Box::new(iter::from_fn(move || {
+            let (next, entry) = match &obj.borrow().subtype {
+                ObjectSubtype::Map(entries) => {
+                    let (next, pair) = entries.next_from(index)?;
+                    (next, self::entry(pair))
+                }
+                ObjectSubtype::Set(entries) => {
+                    let (next, (_, value)) = entries.next_from(index)?;
+                    (next, value)
+                }
+                _ => unreachable!(),
+            };
+            index = next;
+            Some(entry)
+        }))
+    }
+}
+
+mod typed_array {
+    //! `Float64Array`, `Int32Array` and `Uint8Array`, whose elements are numbers that are
+    //! stored in a buffer of the Rust type, which `subarray` shares
+
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    use super::{
+        arg, new_target, range_error, relative_index, type_error, FunctionInfo, JsCell, JsObject,
+        JsObjectContents, JsString, JsValue, Methods, ObjectSubtype, PropertyMap,
+        TYPED_ARRAY_METHODS,
+    };
+
+    #[derive(Clone, Copy)]
+    pub enum Kind {
+        Float64,
+        Int32,
+        Uint8,
+    }
+
+    impl Kind {
+        pub fn name(self) -> &'static str {
+            match self {
+                Kind::Float64 => "Float64Array",
+                Kind::Int32 => "Int32Array",
+                Kind::Uint8 => "Uint8Array",
+            }
+        }
+
+        fn bytes_per_element(self) -> usize {
+            match self {
+                Kind::Float64 => 8,
+                Kind::Int32 => 4,
+                Kind::Uint8 => 1,
+            }
+        }
+    }
+
+    enum Buffer {
+        Float64(Vec<f64>),
+        Int32(Vec<i32>),
+        Uint8(Vec<u8>),
+    }
+
+    /// A view of `length` elements of the buffer, from the offset on
+    #[derive(Clone)]
+    pub struct TypedArray {
+        buffer: Rc<RefCell<Buffer>>,
+        offset: usize,
+        length: usize,
+    }
+
+    impl TypedArray {
+        /// A new buffer of zeros
+        fn new(kind: Kind, length: usize) -> Self {
+            let buffer = match kind {
+                Kind::Float64 => Buffer::Float64(vec![0.0; length]),
+                Kind::Int32 => Buffer::Int32(vec![0; length]),
+                Kind::Uint8 => Buffer::Uint8(vec![0; length]),
+            };
+            TypedArray {
+                buffer: Rc::new(RefCell::new(buffer)),
+                offset: 0,
+                length,
+            }
+        }
+
+        pub fn kind(&self) -> Kind {
+            match *self.buffer.borrow() {
+                Buffer::Float64(_) => Kind::Float64,
+                Buffer::Int32(_) => Kind::Int32,
+                Buffer::Uint8(_) => Kind::Uint8,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.length
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.length == 0
+        }
+
+        /// The element, or `None` if the index is out of bounds
+        pub fn get(&self, index: usize) -> Option<f64> {
+            if index >= self.length {
+                return None;
+            }
+            let index = self.offset + index;
+            Some(match &*self.buffer.borrow() {
+                Buffer::Float64(buffer) => buffer[index],
+                Buffer::Int32(buffer) => buffer[index] as f64,
+                Buffer::Uint8(buffer) => buffer[index] as f64,
+            })
+        }
+
+        /// Converts the value to the type of the elements, which wraps integers around, and
+        /// stores it, unless the index is out of bounds
+        pub fn set(&self, index: usize, value: &JsValue) {
+            let value = value.to_number();
+            if index >= self.length {
+                return;
+            }
+            let index = self.offset + index;
+            match &mut *self.buffer.borrow_mut() {
+                Buffer::Float64(buffer) => {
+                    let JsValue::Number(num) = value else {
+                        unreachable!()
+                    };
+                    buffer[index] = num;
+                }
+                Buffer::Int32(buffer) => buffer[index] = value.to_int32(),
+                Buffer::Uint8(buffer) => buffer[index] = value.to_uint32() as u8,
+            }
+        }
+
+        pub fn values(&self) -> Vec<f64> {
+            (0..self.length)
+                .filter_map(|index| self.get(index))
+                .collect()
+        }
+    }
+
+    /// The typed array, if the value is one
+    pub fn of(value: &JsValue) -> Option<TypedArray> {
+        match value {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::TypedArray(typed) => Some(typed.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn new_object(typed: TypedArray) -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::TypedArray(typed),
+            methods: Some(TYPED_ARRAY_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// The constructor of the kind, which must be called with `new`, with
+    /// `BYTES_PER_ELEMENT` as its member
+    pub fn new_constructor(kind: Kind) -> JsValue {
+        let construct = move |args: &[JsValue]| {
+            if new_target().is_undefined() {
+                type_error(format_args!("Constructor {} requires 'new'", kind.name()));
+            }
+            construct(kind, &arg(args, 0))
+        };
+        let info = FunctionInfo {
+            name: kind.name(),
+            is_arrow: false,
+            uses_new_target: true,
+        };
+        let bytes = JsValue::from(kind.bytes_per_element());
+        let members = [(JsString::from("BYTES_PER_ELEMENT"), bytes)];
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(construct), info),
+            methods: Some(Rc::new(Methods::from(HashMap::from(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `new Float64Array(argument)` and the others, where the argument is the
+    /// length, or an array or a typed array whose elements are copied, or any other iterable
+    fn construct(kind: Kind, argument: &JsValue) -> JsValue {
+        let values: Vec<JsValue> = match argument {
+            JsValue::Object(_) => match of(argument) {
+                Some(source) => source.values().into_iter().map(JsValue::Number).collect(),
+                None => argument.iter_elements().collect(),
+            },
+            JsValue::Undefined => Vec::new(),
+            _ => {
+                let length = argument.to_integer_or_infinity();
+                if !(0.0..=MAX_LENGTH).contains(&length) {
+                    range_error(format_args!(
+                        "Invalid typed array length: {}",
+                        argument.to_js_string().as_str()
+                    ));
+                }
+                return new_object(TypedArray::new(kind, length as usize));
+            }
+        };
+        let typed = TypedArray::new(kind, values.len());
+        for (index, value) in values.iter().enumerate() {
+            typed.set(index, value);
+        }
+        new_object(typed)
+    }
+
+    /// The length of the longest typed array that can be created, like in V8
+    const MAX_LENGTH: f64 = (1u64 << 32) as f64;
+
+    /// The methods that `TYPED_ARRAY_METHODS` holds
+    pub fn methods() -> Methods {
+        let methods: [(&str, JsValue); 3] = [
+            ("fill", JsValue::new_method(Box::new(fill))),
+            ("set", JsValue::new_method(Box::new(set))),
+            ("subarray", JsValue::new_method(Box::new(subarray))),
+        ];
+        Methods::from(HashMap::from_iter(
+            methods
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        ))
+    }
+
+    fn this_typed_array(this: &JsValue) -> TypedArray {
+        of(this).unwrap_or_else(|| type_error("this is not a typed array."))
+    }
+
+    /// Sets the elements from the start to the end, which are relative indices, to the value,
+    /// and returns the typed array
+    fn fill(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let typed = this_typed_array(this);
+        let value = arg(args, 0).to_number();
+        let start = relative_index(&arg(args, 1), typed.len(), 0);
+        let end = relative_index(&arg(args, 2), typed.len(), typed.len());
+        for index in start..end {
+            typed.set(index, &value);
+        }
+        this.clone()
+    }
+
+    /// Copies the elements of the array or the typed array into the typed array, from the
+    /// offset on
+    fn set(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let typed = this_typed_array(this);
+        let source = arg(args, 0);
+        // The elements are read first, as the source may share the buffer
+        let values: Vec<JsValue> = match of(&source) {
+            Some(source) => source.values().into_iter().map(JsValue::Number).collect(),
+            None => {
+                let length = source
+                    .get_prop(JsValue::from("length"))
+                    .to_integer_or_infinity();
+                (0..length.max(0.0) as usize)
+                    .map(|index| source.get_prop(index.into()))
+                    .collect()
+            }
+        };
+        let offset = arg(args, 1).to_integer_or_infinity();
+        if offset < 0.0 || offset + values.len() as f64 > typed.len() as f64 {
+            range_error("offset is out of bounds");
+        }
+        for (index, value) in values.iter().enumerate() {
+            typed.set(offset as usize + index, value);
+        }
+        JsValue::Undefined
+    }
+
+    /// A typed array of the elements from the start to the end, which are relative indices,
+    /// that shares the buffer, so that changing the elements of one changes the other
+    fn subarray(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let typed = this_typed_array(this);
+        let start = relative_index(&arg(args, 0), typed.len(), 0);
+        let end = relative_index(&arg(args, 1), typed.len(), typed.len());
+        new_object(TypedArray {
+            buffer: typed.buffer.clone(),
+            offset: typed.offset + start,
+            length: end.saturating_sub(start),
+        })
+    }
+}
+
+mod promise {
+    //! `Promise`, whose reactions run as microtasks, see `queue_microtask`
+
+    use std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        rc::Rc,
+    };
+
+    use super::{
+        arg, error, new_target, queue_microtask, throw, try_block, type_error, uncaught_message,
+        Completion, FunctionInfo, JsCell, JsObject, JsObjectContents, JsString, JsValue, Methods,
+        ObjectSubtype, PropertyMap, PROMISE_METHODS,
+    };
+
+    thread_local! {
+        /// The promises that were rejected without a reaction to handle it, which are checked
+        /// once the microtasks ran
+        static UNHANDLED: RefCell<Vec<JsValue>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// The callback of `then`, which receives the value or the reason of the promise
+    type Reaction = Box<dyn FnOnce(Result<JsValue, JsValue>)>;
+
+    pub enum State {
+        Pending,
+        Fulfilled(JsValue),
+        Rejected(JsValue),
+    }
+
+    pub struct Promise {
+        pub state: State,
+        /// The reactions that run once the promise is settled
+        reactions: Vec<Reaction>,
+        /// Whether a reaction was added, so that a rejection isn't reported as unhandled
+        handled: bool,
+    }
+
+    /// The `Promise` function, which must be called with `new`, with `resolve`, `reject`, `all`
+    /// and `race` as its members
+    pub fn new_constructor() -> JsValue {
+        let members: [(&str, JsValue); 4] = [
+            (
+                "resolve",
+                JsValue::new_function(Box::new(|args| promise_resolve(arg(args, 0)))),
+            ),
+            (
+                "reject",
+                JsValue::new_function(Box::new(|args| {
+                    let promise = new_promise();
+                    settle(&promise, Err(arg(args, 0)));
+                    promise
+                })),
+            ),
+            ("all", JsValue::new_function(Box::new(all))),
+            ("race", JsValue::new_function(Box::new(race))),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        let info = FunctionInfo {
+            name: "Promise",
+            is_arrow: false,
+            uses_new_target: true,
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(construct), info),
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Implements `new Promise(executor)`, which calls the executor right away with the
+    /// functions that resolve and reject the promise. The promise is rejected with what the
+    /// executor throws.
+    fn construct(args: &[JsValue]) -> JsValue {
+        if new_target().is_undefined() {
+            type_error("Promise constructor cannot be invoked without 'new'");
+        }
+        let executor = arg(args, 0);
+        if !executor.is_callable() {
+            type_error(format_args!(
+                "Promise resolver {} is not a function",
+                executor.to_js_string().as_str()
+            ));
+        }
+        let (promise, resolve, reject) = with_resolvers();
+        if let Err(reason) = call_catching(|| executor.call(&[resolve, reject.clone()])) {
+            reject.call(&[reason]);
+        }
+        promise
+    }
+
+    /// The methods that `PROMISE_METHODS` holds
+    pub fn methods() -> Methods {
+        let methods: [(&str, JsValue); 3] = [
+            (
+                "then",
+                JsValue::new_method(Box::new(|this, args| {
+                    then(this, arg(args, 0), arg(args, 1))
+                })),
+            ),
+            (
+                "catch",
+                JsValue::new_method(Box::new(|this, args| {
+                    then(this, JsValue::Undefined, arg(args, 0))
+                })),
+            ),
+            ("finally", JsValue::new_method(Box::new(finally))),
+        ];
+        let methods = methods
+            .into_iter()
+            .map(|(name, method)| (JsString::from(name), method));
+        Methods::from(HashMap::from_iter(methods))
+    }
+
+    /// A promise that is pending, with the functions that resolve and reject it
+    pub fn with_resolvers() -> (JsValue, JsValue, JsValue) {
+        let promise = new_promise();
+        let (resolve, reject) = resolving_functions(&promise);
+        (promise, resolve, reject)
+    }
+
+    /// A promise that is pending
+    fn new_promise() -> JsValue {
+        let promise = Promise {
+            state: State::Pending,
+            reactions: Vec::new(),
+            handled: false,
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Promise(promise),
+            methods: Some(PROMISE_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    pub fn is_promise(value: &JsValue) -> bool {
+        match value {
+            JsValue::Object(obj) => matches!(obj.borrow().subtype, ObjectSubtype::Promise(_)),
+            _ => false,
+        }
+    }
+
+    fn with_promise<R>(this: &JsValue, method: &str, f: impl FnOnce(&mut Promise) -> R) -> R {
+        if let JsValue::Object(obj) = this {
+            if let ObjectSubtype::Promise(ref mut promise) = obj.borrow_mut().subtype {
+                return f(promise);
+            }
+        }
+        type_error(format_args!(
+            "Method Promise.prototype.{method} called on incompatible receiver {}",
+            this.to_js_string().as_str()
+        ))
+    }
+
+    /// Runs the function, returning what it throws as the error
+    fn call_catching(f: impl FnOnce() -> JsValue) -> Result<JsValue, JsValue> {
+        match try_block(|| Completion::Return(f()))? {
+            Completion::Return(value) => Ok(value),
+            Completion::Normal => unreachable!(),
+        }
+    }
+
+    /// Fulfills or rejects the promise, unless it's settled already, and queues its reactions
+    fn settle(promise: &JsValue, result: Result<JsValue, JsValue>) {
+        let (reactions, unhandled) = with_promise(promise, "then", |contents| {
+            if !matches!(contents.state, State::Pending) {
+                return (Vec::new(), false);
+            }
+            contents.state = match &result {
+                Ok(value) => State::Fulfilled(value.clone()),
+                Err(reason) => State::Rejected(reason.clone()),
+            };
+            let reactions = std::mem::take(&mut contents.reactions);
+            (reactions, result.is_err() && !contents.handled)
+        });
+        if unhandled {
+            UNHANDLED.with(|unhandled| unhandled.borrow_mut().push(promise.clone()));
+        }
+        for reaction in reactions {
+            let result = result.clone();
+            queue_microtask(move || reaction(result));
+        }
+    }
+
+    /// Resolves the promise with the value. If the value is a thenable, like another promise,
+    /// the promise follows it, by calling its `then` in a microtask.
+    fn resolve(promise: &JsValue, value: JsValue) {
+        if let (JsValue::Object(obj), JsValue::Object(other)) = (promise, &value) {
+            if Rc::ptr_eq(obj, other) {
+                let message = "Chaining cycle detected for promise #<Promise>";
+                return settle(promise, Err(error::create(error::Kind::TypeError, message)));
+            }
+        }
+        if !matches!(value, JsValue::Object(_)) {
+            return settle(promise, Ok(value));
+        }
+        let then = match call_catching(|| value.get_prop(JsValue::from("then"))) {
+            Ok(then) => then,
+            Err(reason) => return settle(promise, Err(reason)),
+        };
+        if !then.is_callable() {
+            return settle(promise, Ok(value));
+        }
+        let promise = promise.clone();
+        queue_microtask(move || {
+            let (resolve, reject) = resolving_functions(&promise);
+            if let Err(reason) =
+                call_catching(|| then.call_with_this(&value, &[resolve, reject.clone()]))
+            {
+                reject.call(&[reason]);
+            }
+        });
+    }
+
+    /// The functions that the executor of a promise receives, which only have an effect the
+    /// first time that either of them is called
+    fn resolving_functions(promise: &JsValue) -> (JsValue, JsValue) {
+        let resolved = Rc::new(Cell::new(false));
+        let resolve = {
+            let (promise, resolved) = (promise.clone(), resolved.clone());
+            JsValue::new_function(Box::new(move |args| {
+                if !resolved.replace(true) {
+                    resolve(&promise, arg(args, 0));
+                }
+                JsValue::Undefined
+            }))
+        };
+        let reject = {
+            let promise = promise.clone();
+            JsValue::new_function(Box::new(move |args| {
+                if !resolved.replace(true) {
+                    settle(&promise, Err(arg(args, 0)));
+                }
+                JsValue::Undefined
+            }))
+        };
+        (resolve, reject)
+    }
+
+    /// Runs the reaction once the promise is settled, in a microtask
+    fn react(promise: &JsValue, method: &str, reaction: Reaction) {
+        let result = with_promise(promise, method, |contents| {
+            contents.handled = true;
+            match &contents.state {
+                State::Pending => {
+                    contents.reactions.push(reaction);
+                    None
+                }
+                State::Fulfilled(value) => Some((Ok(value.clone()), reaction)),
+                State::Rejected(reason) => Some((Err(reason.clone()), reaction)),
+            }
+        });
+        if let Some((result, reaction)) = result {
+            queue_microtask(move || reaction(result));
+        }
+    }
+
+    /// Implements `promise.then(onFulfilled, onRejected)`, which returns a promise that is
+    /// resolved with what the handler returns, or rejected with what it throws. Handlers that
+    /// aren't functions pass the value or the reason on.
+    fn then(this: &JsValue, on_fulfilled: JsValue, on_rejected: JsValue) -> JsValue {
+        let derived = new_promise();
+        let promise = derived.clone();
+        react(
+            this,
+            "then",
+            Box::new(move |result| {
+                let (handler, value) = match result {
+                    Ok(value) if on_fulfilled.is_callable() => (on_fulfilled, value),
+                    Err(reason) if on_rejected.is_callable() => (on_rejected, reason),
+                    Ok(value) => return resolve(&promise, value),
+                    Err(reason) => return settle(&promise, Err(reason)),
+                };
+                match call_catching(|| handler.call(&[value])) {
+                    Ok(value) => resolve(&promise, value),
+                    Err(reason) => settle(&promise, Err(reason)),
+                }
+            }),
+        );
+        derived
+    }
+
+    /// Implements `promise.finally(onFinally)`, which calls the callback without arguments,
+    /// waits for what it returns, then settles the returned promise like this promise is,
+    /// unless the callback throws or returns a promise that is rejected
+    fn finally(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let on_finally = arg(args, 0);
+        if !on_finally.is_callable() {
+            return then(this, on_finally.clone(), on_finally);
+        }
+        let then_finally = {
+            let on_finally = on_finally.clone();
+            JsValue::new_function(Box::new(move |args| {
+                let value = arg(args, 0);
+                let result = promise_resolve(on_finally.call(&[]));
+                let value_thunk = JsValue::new_function(Box::new(move |_| value.clone()));
+                then(&result, value_thunk, JsValue::Undefined)
+            }))
+        };
+        let catch_finally = JsValue::new_function(Box::new(move |args| {
+            let reason = arg(args, 0);
+            let result = promise_resolve(on_finally.call(&[]));
+            let thrower = JsValue::new_function(Box::new(move |_| throw(reason.clone())));
+            then(&result, thrower, JsValue::Undefined)
+        }));
+        then(this, then_finally, catch_finally)
+    }
+
+    /// Implements `Promise.resolve(value)`, which returns promises as they are
+    fn promise_resolve(value: JsValue) -> JsValue {
+        if is_promise(&value) {
+            return value;
+        }
+        let promise = new_promise();
+        resolve(&promise, value);
+        promise
+    }
+
+    /// Implements `Promise.all(iterable)`, which is fulfilled with the values of the promises
+    /// in their order once all of them are, or rejected with the first reason
+    fn all(args: &[JsValue]) -> JsValue {
+        let (promise, resolve, reject) = with_resolvers();
+        let values = Rc::new(RefCell::new(Vec::new()));
+        // One more than the promises that are pending, until every promise is visited
+        let remaining = Rc::new(Cell::new(1));
+        let finish = {
+            let (values, remaining) = (values.clone(), remaining.clone());
+            move || {
+                remaining.set(remaining.get() - 1);
+                if remaining.get() == 0 {
+                    let values = values.take();
+                    resolve.call(&[JsValue::new_array(values)]);
+                }
+            }
+        };
+        let finish = Rc::new(finish);
+        for (index, element) in arg(args, 0).iter_elements().enumerate() {
+            values.borrow_mut().push(JsValue::Undefined);
+            remaining.set(remaining.get() + 1);
+            let on_fulfilled = {
+                let (values, finish) = (values.clone(), finish.clone());
+                JsValue::new_function(Box::new(move |args| {
+                    values.borrow_mut()[index] = arg(args, 0);
+                    finish();
+                    JsValue::Undefined
+                }))
+            };
+            then(&promise_resolve(element), on_fulfilled, reject.clone());
+        }
+        finish();
+        promise
+    }
+
+    /// Implements `Promise.race(iterable)`, which is settled like the first promise that is
+    fn race(args: &[JsValue]) -> JsValue {
+        let (promise, resolve, reject) = with_resolvers();
+        for element in arg(args, 0).iter_elements() {
+            then(&promise_resolve(element), resolve.clone(), reject.clone());
+        }
+        promise
+    }
+
+    /// Ends the program like an uncaught exception if a promise was rejected without a
+    /// reaction to handle it
+    pub fn report_unhandled_rejections() {
+        let rejected = UNHANDLED.with(|unhandled| unhandled.take());
+        for promise in rejected {
+            let JsValue::Object(obj) = promise else {
+                unreachable!()
+            };
+            if let ObjectSubtype::Promise(Promise {
+                state: State::Rejected(reason),
+                handled: false,
+                ..
+            }) = &obj.borrow().subtype
+            {
+                panic!("Uncaught (in promise) {}", uncaught_message(reason));
+            }
+        }
+    }
+}
+
+mod error {
+    //! `Error`, `TypeError`, `RangeError` and `SyntaxError`, which are classes, so that `instanceof` and
+    //! `extends` work on them like on the classes of the program
+
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{
+        arg, error_constructor, range_error_constructor, syntax_error_constructor,
+        type_error_constructor, FunctionInfo, JsString, JsValue, PropertyFlags, PropertyKey,
+        CALL_STACK,
+    };
+
+    /// The flags of `message`, `cause` and `stack`, which aren't enumerable
+    const HIDDEN: PropertyFlags = PropertyFlags {
+        writable: true,
+        enumerable: false,
+        configurable: true,
+    };
+
+    #[derive(Clone, Copy)]
+    pub enum Kind {
+        Error,
+        TypeError,
+        RangeError,
+        SyntaxError,
+    }
+
+    impl Kind {
+        pub fn name(self) -> &'static str {
+            match self {
+                Kind::Error => "Error",
+                Kind::TypeError => "TypeError",
+                Kind::RangeError => "RangeError",
+                Kind::SyntaxError => "SyntaxError",
+            }
+        }
+    }
+
+    /// The class of the kind, where `name` is a method, and `Error` has `message` and
+    /// `toString` too, which the others inherit
+    pub fn new_class(kind: Kind) -> JsValue {
+        let class = match kind {
+            Kind::Error => JsValue::new_class(),
+            _ => JsValue::new_subclass(error_constructor()),
+        };
+        let info = FunctionInfo {
+            name: kind.name(),
+            ..FunctionInfo::default()
+        };
+        let constructor = JsValue::new_method(Box::new(construct)).with_info(info);
+        let name = (JsString::from("name"), JsValue::from(kind.name()));
+        match kind {
+            Kind::Error => {
+                let message = (JsString::from("message"), JsValue::from(""));
+                let to_string = JsValue::new_method(Box::new(|this, _| to_string(this)));
+                let methods = [name, message, (JsString::from("toString"), to_string)];
+                class.define_class(constructor, methods, []);
+            }
+            _ => class.define_class(constructor, [name], []),
+        }
+        class
+    }
+
+    /// The constructor, which gives the error the message, unless it's `undefined`, the
+    /// `cause` of the options, if they have one, and the `stack`
+    fn construct(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let JsValue::Object(obj) = this else {
+            unreachable!()
+        };
+        let message = arg(args, 0);
+        if !message.is_undefined() {
+            let message = JsValue::String(message.to_js_string());
+            let key = PropertyKey::from("message");
+            obj.borrow_mut().properties.define(key, message, HIDDEN);
+        }
+        let options = arg(args, 1);
+        let cause = PropertyKey::from("cause");
+        if matches!(options, JsValue::Object(_)) && options.lookup(&cause).is_some() {
+            let value = options.get_prop(cause.to_value());
+            obj.borrow_mut().properties.define(cause, value, HIDDEN);
+        }
+        let stack = new_stack();
+        obj.borrow_mut()
+            .properties
+            .define(PropertyKey::from("stack"), stack, HIDDEN);
+        JsValue::Undefined
+    }
+
+    /// The `stack` of a new error, which is the string of the error, followed by a line for
+    /// each function that is running, innermost first, like in node. Only the names of the
+    /// functions are known, as the generated program doesn't keep track of the JS source that
+    /// they come from.
+    ///
+    /// Like in node, it's an accessor that creates the string the first time it's read, so
+    /// that it has the `name` that the constructor of a class that extends `Error` gives the
+    /// error.
+    fn new_stack() -> JsValue {
+        let mut frames = String::new();
+        CALL_STACK.with(|stack| {
+            // Without the constructor of the error, which is running
+            for name in stack.borrow().iter().rev().skip(1) {
+                let name = if name.is_empty() { "<anonymous>" } else { name };
+                frames.push_str(&format!("\n    at {name}"));
+            }
+        });
+        let created = Rc::new(RefCell::new(None));
+        let get = JsValue::new_method(Box::new(move |this, _| {
+            if let Some(stack) = created.borrow().clone() {
+                return stack;
+            }
+            let header = to_string(this).to_js_string();
+            let stack = JsValue::from(format!("{}{frames}", header.as_str()).as_str());
+            *created.borrow_mut() = Some(stack.clone());
+            stack
+        }));
+        let set = JsValue::new_method(Box::new(|this, args| {
+            if let JsValue::Object(obj) = this {
+                let key = PropertyKey::from("stack");
+                obj.borrow_mut()
+                    .properties
+                    .define(key, arg(args, 0), HIDDEN);
+            }
+            JsValue::Undefined
+        }));
+        JsValue::new_accessor(get, set)
+    }
+
+    /// Implements `error.toString()`, which is the name and the message, without the colon if
+    /// either of them is empty
+    fn to_string(this: &JsValue) -> JsValue {
+        let name = match this.get_prop(JsValue::from("name")) {
+            JsValue::Undefined => JsString::from("Error"),
+            name => name.to_js_string(),
+        };
+        let message = match this.get_prop(JsValue::from("message")) {
+            JsValue::Undefined => JsString::from(""),
+            message => message.to_js_string(),
+        };
+        JsValue::String(match (name.as_str(), message.as_str()) {
+            ("", _) => message,
+            (_, "") => name,
+            (name, message) => JsString::from(format!("{name}: {message}")),
+        })
+    }
+
+    /// A new error of the kind, as if it was created with `new TypeError(message)` and the
+    /// others
+    pub fn create(kind: Kind, message: &str) -> JsValue {
+        let constructor = match kind {
+            Kind::Error => error_constructor(),
+            Kind::TypeError => type_error_constructor(),
+            Kind::RangeError => range_error_constructor(),
+            Kind::SyntaxError => syntax_error_constructor(),
+        };
+        constructor.construct(&[JsValue::from(message)])
+    }
+
+    /// Whether the value is an instance of `Error`, or of a class that extends it
+    pub fn is_error(value: &JsValue) -> bool {
+        matches!(value, JsValue::Object(_))
+            && matches!(
+                value.instance_of(error_constructor()),
+                JsValue::Boolean(true)
+            )
+    }
+
+    /// How an error is shown when it's logged or uncaught, which is its `stack`, like in
+    /// node, in brackets if it doesn't have any functions in it
+    pub fn inspect(error: &JsValue) -> String {
+        let stack = match error.get_prop(JsValue::from("stack")) {
+            JsValue::String(stack) => stack,
+            _ => to_string(error).to_js_string(),
+        };
+        match stack.as_str().contains("\n    at ") {
+            true => stack.as_str().to_string(),
+            false => format!("[{}]", stack.as_str()),
+        }
+    }
+}
+
+mod date {
+    //! The `Date` object, whose time value is the number of milliseconds since the start of
+    //! 1970 in UTC, or `NaN` for an invalid date.
+    //!
+    //! The local time zone is always UTC, so the local and the UTC methods agree.
+
+    use std::{
+        collections::HashMap,
+        rc::Rc,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use super::{
+        arg, range_error, type_error, FunctionInfo, JsCell, JsObject, JsObjectContents, JsString,
+        JsValue, Methods, ObjectSubtype, PropertyMap, DATE_METHODS,
+    };
+
+    const MS_PER_DAY: f64 = 86_400_000.0;
+
+    /// The year, the month from 0, the day of the month, the hours, the minutes, the seconds
+    /// and the milliseconds of a time
+    type Fields = [f64; 7];
+
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    /// The `Date` function, which has `now`, `UTC` and `parse` as its members
+    pub fn new_constructor() -> JsValue {
+        let members: [(&str, JsValue); 3] = [
+            (
+                "now",
+                JsValue::new_function(Box::new(|_| JsValue::Number(now()))),
+            ),
+            (
+                "UTC",
+                JsValue::new_function(Box::new(|args| {
+                    JsValue::Number(time_clip(from_arguments(args)))
+                })),
+            ),
+            (
+                "parse",
+                JsValue::new_function(Box::new(|args| {
+                    JsValue::Number(parse(arg(args, 0).to_js_string().as_str()))
+                })),
+            ),
+        ];
+        let members = members
+            .into_iter()
+            .map(|(name, member)| (JsString::from(name), member));
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Function(Box::new(construct), FunctionInfo::default()),
+            methods: Some(Rc::new(Methods::from(HashMap::from_iter(members)))),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// The methods that `DATE_METHODS` holds, with the same local and UTC variants
+    pub fn methods() -> Methods {
+        // The names of the fields, with how many fields from it on its setter can set
+        let names = [
+            ("FullYear", 3),
+            ("Month", 2),
+            ("Date", 1),
+            ("Hours", 4),
+            ("Minutes", 3),
+            ("Seconds", 2),
+            ("Milliseconds", 1),
+        ];
+        let mut methods = HashMap::new();
+        for (index, (name, count)) in names.into_iter().enumerate() {
+            let getter = JsValue::new_method(Box::new(move |this, _| {
+                let time = time_value(this);
+                JsValue::Number(if time.is_nan() {
+                    time
+                } else {
+                    fields(time)[index]
+                })
+            }));
+            methods.insert(JsString::from(format!("get{name}")), getter.clone());
+            methods.insert(JsString::from(format!("getUTC{name}")), getter);
+            let setter = JsValue::new_method(Box::new(move |this, args| {
+                set_fields(this, index, &args[..args.len().min(count)])
+            }));
+            methods.insert(JsString::from(format!("set{name}")), setter.clone());
+            methods.insert(JsString::from(format!("setUTC{name}")), setter);
+        }
+        let get_day = JsValue::new_method(Box::new(|this, _| {
+            let days = (time_value(this) / MS_PER_DAY).floor();
+            JsValue::Number((days + 4.0).rem_euclid(7.0))
+        }));
+        methods.insert(JsString::from("getDay"), get_day.clone());
+        methods.insert(JsString::from("getUTCDay"), get_day);
+        let get_time = JsValue::new_method(Box::new(|this, _| JsValue::Number(time_value(this))));
+        methods.insert(JsString::from("getTime"), get_time.clone());
+        methods.insert(JsString::from("valueOf"), get_time);
+        let others: [(&str, JsValue); 7] = [
+            (
+                "setTime",
+                JsValue::new_method(Box::new(|this, args| {
+                    set_time_value(this, time_clip(number(&arg(args, 0))))
+                })),
+            ),
+            (
+                "getTimezoneOffset",
+                JsValue::new_method(Box::new(|this, _| JsValue::Number(time_value(this) * 0.0))),
+            ),
+            ("toISOString", JsValue::new_method(Box::new(to_iso_string))),
+            (
+                "toJSON",
+                JsValue::new_method(Box::new(|this, args| {
+                    if time_value(this).is_finite() {
+                        to_iso_string(this, args)
+                    } else {
+                        JsValue::Null
+                    }
+                })),
+            ),
+            (
+                "toString",
+                JsValue::new_method(Box::new(|this, _| {
+                    JsValue::String(JsString::from(to_string(time_value(this))))
+                })),
+            ),
+            (
+                "toDateString",
+                JsValue::new_method(Box::new(|this, _| {
+                    let text = formatted(time_value(this), |fields, weekday| {
+                        let (year, month, date) = (fields[0], fields[1], fields[2]);
+                        format!(
+                            "{weekday} {} {date:02} {}",
+                            MONTHS[month as usize],
+                            year_string(year)
+                        )
+                    });
+                    JsValue::String(JsString::from(text))
+                })),
+            ),
+            (
+                "toUTCString",
+                JsValue::new_method(Box::new(|this, _| {
+                    let text = formatted(time_value(this), |fields, weekday| {
+                        let [year, month, date, hours, minutes, seconds, _] = *fields;
+                        format!(
+                            "{weekday}, {date:02} {} {} {hours:02}:{minutes:02}:{seconds:02} GMT",
+                            MONTHS[month as usize],
+                            year_string(year)
+                        )
+                    });
+                    JsValue::String(JsString::from(text))
+                })),
+            ),
+        ];
+        methods.extend(
+            others
+                .into_iter()
+                .map(|(name, method)| (JsString::from(name), method)),
+        );
+        Methods::from(methods)
+    }
+
+    /// Creates a date from the arguments of `new Date(...)`. Unlike in JS, calling `Date`
+    /// without `new` creates a date too, instead of returning the current time as a string.
+    fn construct(args: &[JsValue]) -> JsValue {
+        let time = match args {
+            [] => now(),
+            [value] => match time_of(value) {
+                Some(time) => time,
+                None => match value.to_primitive() {
+                    JsValue::String(text) => parse(text.as_str()),
+                    primitive => number(&primitive),
+                },
+            },
+            _ => from_arguments(args),
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Date(time_clip(time)),
+            methods: Some(DATE_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// The time value of the value, if it's a date
+    pub fn time_of(value: &JsValue) -> Option<f64> {
+        match value {
+            JsValue::Object(obj) => match obj.borrow().subtype {
+                ObjectSubtype::Date(time) => Some(time),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn number(value: &JsValue) -> f64 {
+        match value.to_number() {
+            JsValue::Number(num) => num,
+            _ => unreachable!(),
+        }
+    }
+
+    fn time_value(this: &JsValue) -> f64 {
+        time_of(this).unwrap_or_else(|| type_error("this is not a Date object."))
+    }
+
+    fn set_time_value(this: &JsValue, time: f64) -> JsValue {
+        if let JsValue::Object(obj) = this {
+            if let ObjectSubtype::Date(old_time) = &mut obj.borrow_mut().subtype {
+                *old_time = time;
+            }
+        }
+        JsValue::Number(time)
+    }
+
+    /// Replaces the fields from the `first` one on with the arguments. Setting the year of an
+    /// invalid date starts from the beginning of 1970, the other fields leave it invalid.
+    fn set_fields(this: &JsValue, first: usize, values: &[JsValue]) -> JsValue {
+        let time = match time_value(this) {
+            time if time.is_nan() && first == 0 => 0.0,
+            time if time.is_nan() => return JsValue::Number(time),
+            time => time,
+        };
+        let mut fields = fields(time);
+        let values = if values.is_empty() {
+            &[JsValue::Undefined][..]
+        } else {
+            values
+        };
+        for (field, value) in fields[first..].iter_mut().zip(values) {
+            *field = number(value);
+        }
+        set_time_value(this, time_clip(from_fields(&fields)))
+    }
+
+    fn now() -> f64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_millis() as f64)
+    }
+
+    /// Rounds the time towards zero, or makes it `NaN` if it's more than 100 million days away
+    /// from 1970
+    fn time_clip(time: f64) -> f64 {
+        if time.is_finite() && time.abs() <= 8.64e15 {
+            time.trunc() + 0.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// The time from the year, the month, and optionally the day of the month, the hours, the
+    /// minutes, the seconds and the milliseconds, where years from 0 to 99 mean 1900 to 1999
+    fn from_arguments(args: &[JsValue]) -> f64 {
+        let mut fields = [f64::NAN, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+        for (field, value) in fields.iter_mut().zip(args) {
+            *field = number(value);
+        }
+        let year = fields[0].trunc();
+        if (0.0..=99.0).contains(&year) {
+            fields[0] = 1900.0 + year;
+        }
+        from_fields(&fields)
+    }
+
+    /// The fields of the time, which must be valid
+    fn fields(time: f64) -> Fields {
+        let days = (time / MS_PER_DAY).floor();
+        let ms_in_day = time - days * MS_PER_DAY;
+        let (year, month, date) = civil_from_days(days as i64);
+        [
+            year as f64,
+            (month - 1) as f64,
+            date as f64,
+            (ms_in_day / 3_600_000.0).floor(),
+            (ms_in_day / 60_000.0).floor() % 60.0,
+            (ms_in_day / 1000.0).floor() % 60.0,
+            ms_in_day % 1000.0,
+        ]
+    }
+
+    /// The time from the fields that `fields` returns, which may be out of range, like a
+    /// month of 12 being January of the next year
+    fn from_fields(fields: &Fields) -> f64 {
+        if !fields.iter().all(|field| field.is_finite()) {
+            return f64::NAN;
+        }
+        let [year, month, date, hours, minutes, seconds, ms] = fields.map(f64::trunc);
+        let year = year + (month / 12.0).floor();
+        let month = month.rem_euclid(12.0);
+        if year.abs() > 400_000.0 {
+            return f64::NAN;
+        }
+        let days = days_from_civil(year as i64, month as i64 + 1, 1) as f64 + date - 1.0;
+        days * MS_PER_DAY + hours * 3_600_000.0 + minutes * 60_000.0 + seconds * 1000.0 + ms
+    }
+
+    /// The days since 1970 at the date of the proleptic Gregorian calendar, with the month
+    /// from 1
+    fn days_from_civil(year: i64, month: i64, date: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + date - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The inverse of `days_from_civil`
+    fn civil_from_days(days: i64) -> (i64, i64, i64) {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let date = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, date)
+    }
+
+    /// Parses the date time string format of JS, like `2024-02-29T12:30:00.000Z`, where the
+    /// month, the day, the time, its seconds and milliseconds, and the offset may be left out.
+    /// Other formats make the date invalid.
+    fn parse(text: &str) -> f64 {
+        let mut parser = Parser { rest: text };
+        parser.parse().map_or(f64::NAN, time_clip)
+    }
+
+    struct Parser<'a> {
+        rest: &'a str,
+    }
+
+    impl Parser<'_> {
+        fn parse(&mut self) -> Option<f64> {
+            let year = if self.eat('+') {
+                self.digits(6)?
+            } else if self.eat('-') {
+                -self.digits(6)?
+            } else {
+                self.digits(4)?
+            };
+            let (mut month, mut date) = (1.0, 1.0);
+            if self.eat('-') {
+                month = self.digits(2)?;
+                if self.eat('-') {
+                    date = self.digits(2)?;
+                }
+            }
+            let (mut hours, mut minutes, mut seconds, mut ms, mut offset) =
+                (0.0, 0.0, 0.0, 0.0, 0.0);
+            if self.eat('T') {
+                hours = self.digits(2)?;
+                self.eat(':').then_some(())?;
+                minutes = self.digits(2)?;
+                if self.eat(':') {
+                    seconds = self.digits(2)?;
+                    if self.eat('.') {
+                        let len = self
+                            .rest
+                            .find(|c: char| !c.is_ascii_digit())
+                            .unwrap_or(self.rest.len());
+                        let fraction = self.digits(len)?;
+                        ms = (fraction / 10f64.powi(len as i32 - 3)).floor();
+                    }
+                }
+                if !self.eat('Z') {
+                    let sign = if self.eat('+') {
+                        1.0
+                    } else if self.eat('-') {
+                        -1.0
+                    } else {
+                        0.0
+                    };
+                    if sign != 0.0 {
+                        let offset_hours = self.digits(2)?;
+                        self.eat(':').then_some(())?;
+                        offset = sign * (offset_hours * 60.0 + self.digits(2)?) * 60_000.0;
+                    }
+                }
+            }
+            let in_range = self.rest.is_empty()
+                && (1.0..=12.0).contains(&month)
+                && (1.0..=31.0).contains(&date)
+                && minutes < 60.0
+                && seconds < 60.0
+                && (hours < 24.0 || hours == 24.0 && minutes + seconds + ms == 0.0);
+            in_range.then(|| {
+                from_fields(&[year, month - 1.0, date, hours, minutes, seconds, ms]) - offset
+            })
+        }
+
+        fn eat(&mut self, expected: char) -> bool {
+            match self.rest.strip_prefix(expected) {
+                Some(rest) => {
+                    self.rest = rest;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// Exactly `count` digits, as a number
+        fn digits(&mut self, count: usize) -> Option<f64> {
+            let digits = self.rest.get(..count)?;
+            if count == 0 || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            self.rest = &self.rest[count..];
+            digits.parse().ok()
+        }
+    }
+
+    /// The year with at least 4 digits, and a sign if it's negative
+    fn year_string(year: f64) -> String {
+        if year < 0.0 {
+            format!("-{:04}", -year)
+        } else {
+            format!("{year:04}")
+        }
+    }
+
+    /// Formats the fields and the name of the weekday, or is "Invalid Date"
+    fn formatted(time: f64, format: impl FnOnce(&Fields, &str) -> String) -> String {
+        if time.is_nan() {
+            return String::from("Invalid Date");
+        }
+        let weekday = (time / MS_PER_DAY).floor() + 4.0;
+        format(&fields(time), WEEKDAYS[weekday.rem_euclid(7.0) as usize])
+    }
+
+    /// The string of a date, like "Tue Feb 27 2024 09:05:00 GMT+0000 (Coordinated Universal
+    /// Time)"
+    pub fn to_string(time: f64) -> String {
+        formatted(time, |fields, weekday| {
+            let [year, month, date, hours, minutes, seconds, _] = *fields;
+            format!(
+                "{weekday} {} {date:02} {} {hours:02}:{minutes:02}:{seconds:02} GMT+0000 \
+                 (Coordinated Universal Time)",
+                MONTHS[month as usize],
+                year_string(year)
+            )
+        })
+    }
+
+    /// Implements `toISOString`, like "2024-02-27T09:05:00.000Z", with 6 digits and a sign for
+    /// the years before 0 or after 9999
+    pub fn to_iso_string(this: &JsValue, _: &[JsValue]) -> JsValue {
+        let time = time_value(this);
+        if time.is_nan() {
+            range_error("Invalid time value");
+        }
+        let [year, month, date, hours, minutes, seconds, ms] = fields(time);
+        let year = match year {
+            year if (0.0..=9999.0).contains(&year) => format!("{year:04}"),
+            year if year < 0.0 => format!("-{:06}", -year),
+            year => format!("+{year:06}"),
+        };
+        JsValue::String(JsString::from(format!(
+            "{year}-{:02}-{date:02}T{hours:02}:{minutes:02}:{seconds:02}.{ms:03}Z",
+            month + 1.0
+        )))
+    }
+}
+
+mod array {
+    //! The methods of arrays, where holes are `None`
+
+    use super::{arg, relative_index, type_error, JsString, JsValue, ObjectSubtype};
+
+    /// Runs `f` on the elements of the array that the method is called on
+    fn with_elements<T>(this: &JsValue, f: impl FnOnce(&mut Vec<Option<JsValue>>) -> T) -> T {
+        if let JsValue::Object(obj) = this {
+            if let ObjectSubtype::Array(elements) = &mut obj.borrow_mut().subtype {
+                return f(elements);
+            }
+        }
+        type_error(format_args!(
+            "{} is not an array",
+            this.to_js_string().as_str()
+        ))
+    }
+
+    fn element(element: Option<JsValue>) -> JsValue {
+        element.unwrap_or(JsValue::Undefined)
+    }
+
+    fn len(this: &JsValue) -> usize {
+        with_elements(this, |elements| elements.len())
+    }
+
+    /// The element at the index, `None` for a hole or past the end
+    fn get(this: &JsValue, index: usize) -> Option<JsValue> {
+        with_elements(this, |elements| elements.get(index).cloned().flatten())
+    }
+
+    /// The position of the argument, clamped to the array, where negative positions count
+    /// from the end
+    fn position(args: &[JsValue], index: usize, len: usize, default: usize) -> usize {
+        relative_index(&arg(args, index), len, default)
+    }
+
+    /// The strings of the elements joined with the separator, where holes, `null` and
+    /// `undefined` are empty
+    pub(super) fn join_elements(elements: &[Option<JsValue>], separator: &str) -> JsString {
+        let strings = elements
+            .iter()
+            .map(|element| match element {
+                Some(JsValue::Null | JsValue::Undefined) | None => String::new(),
+                Some(element) => element.to_js_string().as_str().to_string(),
+            })
+            .collect::<Vec<_>>();
+        JsString::from(strings.join(separator))
+    }
+
+    fn callable(callback: JsValue) -> JsValue {
+        if !callback.is_callable() {
+            type_error(format_args!(
+                "{} is not a function",
+                callback.to_js_string().as_str()
+            ));
+        }
+        callback
+    }
+
+    /// Calls the callback, the first argument of the method, with each element, its index and
+    /// the array, for as long as `f` returns true for the index, the element and what the
+    /// callback returned. The second argument is `this` for the callback.
+    ///
+    /// Holes are skipped unless `visit_holes`. The elements are read as they are reached, as the
+    /// callback can change the array, but the ones that it adds aren't visited.
+    fn iterate(
+        this: &JsValue,
+        args: &[JsValue],
+        visit_holes: bool,
+        mut f: impl FnMut(usize, JsValue, JsValue) -> bool,
+    ) {
+        let callback = callable(arg(args, 0));
+        let this_arg = arg(args, 1);
+        for index in 0..len(this) {
+            let element = match get(this, index) {
+                Some(element) => element,
+                None if visit_holes => JsValue::Undefined,
+                None => continue,
+            };
+            let arguments = [element.clone(), index.into(), this.clone()];
+            let result = callback.call_with_this(&this_arg, &arguments);
+            if !f(index, element, result) {
+                break;
+            }
+        }
+    }
+
+    pub fn push(this: &JsValue, args: &[JsValue]) -> JsValue {
+        with_elements(this, |elements| {
+            elements.extend(args.iter().cloned().map(Some));
+            elements.len().into()
+        })
+    }
+
+    pub fn pop(this: &JsValue, _: &[JsValue]) -> JsValue {
+        with_elements(this, |elements| element(elements.pop().flatten()))
+    }
+
+    pub fn shift(this: &JsValue, _: &[JsValue]) -> JsValue {
+        with_elements(this, |elements| match elements.is_empty() {
+            true => JsValue::Undefined,
+            false => element(elements.remove(0)),
+        })
+    }
+
+    pub fn unshift(this: &JsValue, args: &[JsValue]) -> JsValue {
+        with_elements(this, |elements| {
+            elements.splice(0..0, args.iter().cloned().map(Some));
+            elements.len().into()
+        })
+    }
+
+    /// Replaces `deleteCount` elements from `start` on with the rest of the arguments, and
+    /// returns the array of the ones it removed. Without a `deleteCount`, it removes every
+    /// element from `start` on.
+    pub fn splice(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let removed = with_elements(this, |elements| {
+            let len = elements.len();
+            let start = relative_index(&arg(args, 0), len, 0);
+            let delete_count = match args.len() {
+                0 => 0,
+                1 => len - start,
+                _ => args[1]
+                    .to_integer_or_infinity()
+                    .clamp(0.0, (len - start) as f64) as usize,
+            };
+            let items = args.iter().skip(2).cloned().map(Some);
+            elements
+                .splice(start..start + delete_count, items)
+                .collect()
+        });
+        JsValue::new_array_with_holes(removed)
+    }
+
+    pub fn join(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let separator = match arg(args, 0) {
+            JsValue::Undefined => JsString::from(","),
+            separator => separator.to_js_string(),
+        };
+        let elements = with_elements(this, |elements| elements.clone());
+        JsValue::String(join_elements(&elements, separator.as_str()))
+    }
+
+    pub fn slice(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let elements = with_elements(this, |elements| {
+            let len = elements.len();
+            let start = position(args, 0, len, 0);
+            let end = position(args, 1, len, len);
+            elements[start..end.max(start)].to_vec()
+        });
+        JsValue::new_array_with_holes(elements)
+    }
+
+    /// The elements of the arrays among the arguments are added one by one, the other
+    /// arguments as they are
+    pub fn concat(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut elements = with_elements(this, |elements| elements.clone());
+        for item in args {
+            match item {
+                JsValue::Object(obj) => match &obj.borrow().subtype {
+                    ObjectSubtype::Array(items) => elements.extend(items.iter().cloned()),
+                    _ => elements.push(Some(item.clone())),
+                },
+                item => elements.push(Some(item.clone())),
+            }
+        }
+        JsValue::new_array_with_holes(elements)
+    }
+
+    /// Compares with `===`, so it never finds `NaN`
+    pub fn index_of(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let search = arg(args, 0);
+        let index = with_elements(this, |elements| {
+            let start = position(args, 1, elements.len(), 0);
+            (start..elements.len()).find(|&index| {
+                let element = elements[index].as_ref();
+                element.is_some_and(|element| element.strict_equals(search.clone()).truthy())
+            })
+        });
+        match index {
+            Some(index) => index.into(),
+            None => JsValue::Number(-1.0),
+        }
+    }
+
+    /// Unlike `indexOf`, it finds `NaN`, and holes are `undefined`
+    pub fn includes(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let search = arg(args, 0);
+        let is_nan = |value: &JsValue| matches!(value, JsValue::Number(num) if num.is_nan());
+        let found = with_elements(this, |elements| {
+            let start = position(args, 1, elements.len(), 0);
+            elements[start..].iter().any(|element| {
+                let element = element.clone().unwrap_or(JsValue::Undefined);
+                (is_nan(&element) && is_nan(&search))
+                    || element.strict_equals(search.clone()).truthy()
+            })
+        });
+        JsValue::Boolean(found)
+    }
+
+    pub fn reverse(this: &JsValue, _: &[JsValue]) -> JsValue {
+        with_elements(this, |elements| elements.reverse());
+        this.clone()
+    }
+
+    /// Sets the elements from `start` to `end` to the value, holes included
+    pub fn fill(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let value = arg(args, 0);
+        with_elements(this, |elements| {
+            let len = elements.len();
+            let start = position(args, 1, len, 0);
+            let end = position(args, 2, len, len);
+            for element in elements.iter_mut().take(end).skip(start) {
+                *element = Some(value.clone());
+            }
+        });
+        this.clone()
+    }
+
+    /// Sorts the array in place, with the comparator if there is one, and by the strings of the
+    /// elements otherwise. The sort is stable, `undefined` goes after every other element, and
+    /// the holes go after that.
+    pub fn sort(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let comparator = match arg(args, 0) {
+            JsValue::Undefined => None,
+            comparator => Some(callable(comparator)),
+        };
+        // The comparator can change the array, so it sorts a copy of the elements
+        let elements = with_elements(this, |elements| elements.clone());
+        let len = elements.len();
+        let (undefined, values): (Vec<_>, Vec<_>) = elements
+            .into_iter()
+            .flatten()
+            .partition(JsValue::is_undefined);
+        let is_after = |a: &JsValue, b: &JsValue| match &comparator {
+            // `NaN` counts as equal
+            Some(comparator) => match comparator.call(&[a.clone(), b.clone()]).to_number() {
+                JsValue::Number(order) => order > 0.0,
+                _ => unreachable!(),
+            },
+            None => {
+                let (a, b) = (a.to_js_string(), b.to_js_string());
+                a.as_str().encode_utf16().gt(b.as_str().encode_utf16())
+            }
+        };
+        let mut sorted: Vec<_> = merge_sort(values, &is_after)
+            .into_iter()
+            .chain(undefined)
+            .map(Some)
+            .collect();
+        sorted.resize(len, None);
+        with_elements(this, |elements| *elements = sorted);
+        this.clone()
+    }
+
+    /// A stable merge sort, which unlike the one of the standard library copes with a
+    /// comparator that isn't consistent
+    fn merge_sort(
+        mut values: Vec<JsValue>,
+        is_after: &impl Fn(&JsValue, &JsValue) -> bool,
+    ) -> Vec<JsValue> {
+        if values.len() <= 1 {
+            return values;
+        }
+        let right = values.split_off(values.len() / 2);
+        let mut left = merge_sort(values, is_after).into_iter().peekable();
+        let mut right = merge_sort(right, is_after).into_iter().peekable();
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            // Equal elements keep their order
+            let next = match is_after(a, b) {
+                true => right.next(),
+                false => left.next(),
+            };
+            merged.extend(next);
+        }
+        merged.extend(left.chain(right));
+        merged
+    }
+
+    pub fn for_each(this: &JsValue, args: &[JsValue]) -> JsValue {
+        iterate(this, args, false, |_, _, _| true);
+        JsValue::Undefined
+    }
+
+    /// The holes stay holes in the new array
+    pub fn map(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut mapped = vec![None; len(this)];
+        iterate(this, args, false, |index, _, result| {
+            mapped[index] = Some(result);
+            true
+        });
+        JsValue::new_array_with_holes(mapped)
+    }
+
+    pub fn filter(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut kept = Vec::new();
+        iterate(this, args, false, |_, element, result| {
+            if result.truthy() {
+                kept.push(element);
+            }
+            true
+        });
+        JsValue::new_array(kept)
+    }
+
+    pub fn some(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut found = false;
+        iterate(this, args, false, |_, _, result| {
+            found = result.truthy();
+            !found
+        });
+        JsValue::Boolean(found)
+    }
+
+    pub fn every(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut all = true;
+        iterate(this, args, false, |_, _, result| {
+            all = result.truthy();
+            all
+        });
+        JsValue::Boolean(all)
+    }
+
+    /// Unlike the other methods, it visits the holes, as `undefined`
+    pub fn find(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let mut found = JsValue::Undefined;
+        iterate(this, args, true, |_, element, result| {
+            let is_match = result.truthy();
+            if is_match {
+                found = element;
+            }
+            !is_match
+        });
+        found
+    }
+
+    /// The callback is called with the value accumulated so far, the element, its index and
+    /// the array. Without an initial value, the first element is the initial value.
+    pub fn reduce(this: &JsValue, args: &[JsValue]) -> JsValue {
+        let callback = callable(arg(args, 0));
+        let mut accumulator = args.get(1).cloned();
+        for index in 0..len(this) {
+            let Some(element) = get(this, index) else {
+                continue;
+            };
+            accumulator = Some(match accumulator {
+                Some(accumulator) => {
+                    callback.call(&[accumulator, element, index.into(), this.clone()])
+                }
+                None => element,
+            });
+        }
+        accumulator.unwrap_or_else(|| type_error("Reduce of empty array with no initial value"))
+    }
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct JsString {
+    // TODO: Replace with something that can be used to represent UTF16 strings efficiently
+    // (strings in JS behave as UTF16 strings)
+    //
+    // An idea that I have is to use SSO, such that the inlined string is UTF8 so that
+    // it fits more chars and the outlined (long) strings are UTF16 so that we save
+    // processing power by avoiding the conversion between UTF16 and UTF8
+    // (because JS string operations all treat the string as if it was UTF16)
+    //
+    value: Rc<str>,
+}
+
+impl<'a> From<&'a str> for JsString {
+    #[inline]
+    fn from(value: &'a str) -> Self {
+        JsString {
+            value: Rc::from(value),
+        }
+    }
+}
+
+impl From<String> for JsString {
+    #[inline]
+    fn from(value: String) -> Self {
+        JsString {
+            value: Rc::from(value),
+        }
+    }
+}
+
+impl JsString {
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+/// A symbol, which is only equal to itself, as each call of `Symbol()` creates a new one. The
+/// ones from `Symbol.for` are kept in a registry, so that the same key gives the same symbol.
+#[derive(Clone)]
+pub struct JsSymbol {
+    /// What `Symbol()` was given, which is only there for the programmer
+    description: Rc<Option<JsString>>,
+}
+
+impl JsSymbol {
+    pub fn new(description: Option<JsString>) -> Self {
+        JsSymbol {
+            description: Rc::new(description),
+        }
+    }
+
+    pub fn description(&self) -> Option<&JsString> {
+        self.description.as_ref().as_ref()
+    }
+}
+
+impl PartialEq for JsSymbol {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.description, &other.description)
+    }
+}
+
+impl Eq for JsSymbol {}
+
+impl Hash for JsSymbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.description).hash(state);
+    }
+}
+
+/// How `String(symbol)` and the inspector show the symbol
+impl fmt::Display for JsSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = self.description().map_or("", JsString::as_str);
+        write!(f, "Symbol({description})")
+    }
+}
+
+/// A variable that closures share with the scope that declares it, rather than each having a
+/// copy of it, as the variable changes after they are made. Clones refer to the same variable.
+#[derive(Clone)]
+pub struct SharedBinding(Rc<JsCell<JsValue>>);
+
+impl SharedBinding {
+    pub fn new(value: JsValue) -> Self {
+        SharedBinding(Rc::new(JsCell::new(value)))
+    }
+
+    pub fn get(&self) -> JsValue {
+        self.0.borrow().clone()
+    }
+
+    pub fn set(&self, value: JsValue) {
+        *self.0.borrow_mut() = value;
+    }
+}
+
+/// What the properties of objects are named by, which is a string, or a symbol for the
+/// properties that are apart from the ones that have strings as keys, like `Symbol.iterator`
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub enum PropertyKey {
+    String(JsString),
+    Symbol(JsSymbol),
+}
+
+impl From<JsString> for PropertyKey {
+    fn from(key: JsString) -> Self {
+        PropertyKey::String(key)
+    }
+}
+
+impl<'a> From<&'a str> for PropertyKey {
+    fn from(key: &'a str) -> Self {
+        PropertyKey::String(JsString::from(key))
+    }
+}
+
+impl From<String> for PropertyKey {
+    fn from(key: String) -> Self {
+        PropertyKey::String(JsString::from(key))
+    }
+}
+
+impl PropertyKey {
+    /// The key as a value of the program, a string or a symbol
+    pub fn to_value(&self) -> JsValue {
+        match self {
+            PropertyKey::String(key) => JsValue::String(key.clone()),
+            PropertyKey::Symbol(symbol) => JsValue::Symbol(symbol.clone()),
+        }
+    }
+
+    fn as_string(&self) -> Option<&JsString> {
+        match self {
+            PropertyKey::String(key) => Some(key),
+            PropertyKey::Symbol(_) => None,
+        }
+    }
+}
+
+/// How error messages show the key
+impl fmt::Display for PropertyKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropertyKey::String(key) => f.write_str(key.as_str()),
+            PropertyKey::Symbol(symbol) => symbol.fmt(f),
+        }
+    }
+}
+
+/// A function, which receives the arguments
+type FunctionFn = dyn Fn(&[JsValue]) -> JsValue;
+
+/// A method of a class, which receives `this` and the arguments
+type MethodFn = dyn Fn(&JsValue, &[JsValue]) -> JsValue;
+
+/// Takes the value that the generator is resumed with
+type GeneratorFn = dyn FnMut(JsValue) -> GeneratorStep;
+
+enum ObjectSubtype {
+    RegularObject,
+    Function(Box<FunctionFn>, FunctionInfo),
+    /// A method of a class, which also receives the object that it's called on as `this`
+    Method(Box<MethodFn>, FunctionInfo),
+    /// A class, which `construct` creates the instances of
+    Class {
+        /// The class or function that the class extends
+        parent: Option<JsValue>,
+        constructor: JsValue,
+        methods: Rc<Methods>,
+        /// The private members that each instance gets, where fields are `undefined` until
+        /// the constructor initializes them
+        private: Rc<HashMap<JsString, JsValue>>,
+    },
+    /// A property with a getter and a setter, which are methods or `undefined`. It never
+    /// reaches the program, as reading and writing the property calls them instead.
+    Accessor {
+        get: JsValue,
+        set: JsValue,
+    },
+    /// `None` elements are holes, e.g. left behind by `delete arr[i]`
+    Array(Vec<Option<JsValue>>),
+    RegExp(Rc<regexp::RegExp>),
+    /// The closure that runs the body of a generator up to its next `yield`, which is `None`
+    /// once the generator is finished, or while it's running
+    Generator(Option<Box<GeneratorFn>>),
+    /// The time value of a date, see `mod date`
+    Date(f64),
+    /// The entries of a map, see `mod collection`
+    Map(collection::Entries),
+    /// The values of a set, see `mod collection`
+    Set(collection::Entries),
+    /// The view of a buffer of numbers, see `mod typed_array`
+    TypedArray(typed_array::TypedArray),
+    /// The state of a promise and its reactions, see `mod promise`
+    Promise(promise::Promise),
+}
+
+/// What the runtime knows about a function of the program besides its closure. The functions
+/// of the runtime have the default, as if they were anonymous function expressions.
+#[derive(Clone, Copy, Default)]
+pub struct FunctionInfo {
+    /// The `name` of the function, which is empty if it's anonymous
+    pub name: &'static str,
+    /// Arrow functions aren't constructors
+    pub is_arrow: bool,
+    /// Whether the function reads `new.target`, which `construct` passes it then, see
+    /// `new_target`
+    pub uses_new_target: bool,
+}
+
+/// The methods of a class, shared by its instances, which stand in for the prototype. The
+/// methods that the class inherits are looked up in those of the class that it extends.
+pub struct Methods {
+    own: HashMap<PropertyKey, JsValue>,
+    parent: Option<Rc<Methods>>,
+}
+
+impl Methods {
+    fn get(&self, name: &PropertyKey) -> Option<&JsValue> {
+        self.own
+            .get(name)
+            .or_else(|| self.parent.as_ref()?.get(name))
+    }
+
+    /// Adds the method that `Symbol.iterator` is the key of, which returns the iterator of the
+    /// object
+    fn with_iterator(mut self, method: fn(&JsValue, &[JsValue]) -> JsValue) -> Self {
+        let key = PropertyKey::Symbol(symbol::ITERATOR.with(JsSymbol::clone));
+        self.own.insert(key, JsValue::new_method(Box::new(method)));
+        self
+    }
+
+    /// Whether these are the methods of the class, or of a class that extends it
+    fn inherit(self: &Rc<Self>, class_methods: &Rc<Methods>) -> bool {
+        iter::successors(Some(self), |methods| methods.parent.as_ref())
+            .any(|methods| Rc::ptr_eq(methods, class_methods))
+    }
+}
+
+impl From<HashMap<JsString, JsValue>> for Methods {
+    fn from(own: HashMap<JsString, JsValue>) -> Self {
+        Methods {
+            own: own.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+            parent: None,
+        }
+    }
+}
+
+/// The attributes of a property besides its value, which `Object.defineProperty` sets. The
+/// properties that are created by writing them have all of them.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct PropertyFlags {
+    /// Whether writing the property changes it, which doesn't matter for an accessor
+    writable: bool,
+    /// Whether `own_enumerable_keys` has it
+    enumerable: bool,
+    /// Whether the property can be deleted or redefined
+    configurable: bool,
+}
+
+impl PropertyFlags {
+    const ALL: PropertyFlags = PropertyFlags {
+        writable: true,
+        enumerable: true,
+        configurable: true,
+    };
+
+    const NONE: PropertyFlags = PropertyFlags {
+        writable: false,
+        enumerable: false,
+        configurable: false,
+    };
+}
+
+/// How many more holes than elements writing past the end of an array may add to it. An
+/// element that is further out is stored as a property of the array instead, a sparse element,
+/// so that `array[1e9] = 1` doesn't allocate a billion holes.
+const MAX_EXTRA_HOLES: usize = 1024;
+
+/// The `length` of an array, which is past its sparse elements too. Unlike in JS, deleting the
+/// last sparse element makes the array shorter again.
+fn array_length(elements: &[Option<JsValue>], properties: &PropertyMap) -> usize {
+    (properties.entries.iter())
+        .filter_map(|(key, _, _)| key.to_value().to_array_index())
+        .map(|index| index + 1)
+        .fold(elements.len(), usize::max)
+}
+
+/// Moves the sparse elements of an array that the vector grew to reach into it
+fn take_sparse_elements(elements: &mut [Option<JsValue>], properties: &mut PropertyMap) {
+    if properties.is_empty() {
+        return;
+    }
+    let reached = (properties.entries.iter())
+        .filter_map(|(key, _, _)| Some((key.clone(), key.to_value().to_array_index()?)))
+        .filter(|(_, index)| *index < elements.len())
+        .collect::<Vec<_>>();
+    for (key, index) in reached {
+        elements[index] = properties.remove(&key);
+    }
+}
+
+/// The properties of an object, in the order that they were added in, which is the order that
+/// they are enumerated in
+#[derive(Clone, Default)]
+struct PropertyMap {
+    entries: Vec<(PropertyKey, JsValue, PropertyFlags)>,
+    /// The index of each key in the entries
+    indices: HashMap<PropertyKey, usize>,
+}
+
+impl PropertyMap {
+    fn new() -> Self {
+        PropertyMap::default()
+    }
+
+    fn get(&self, key: &PropertyKey) -> Option<&JsValue> {
+        self.property(key).map(|(value, _)| value)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The value of the property with its flags
+    fn property(&self, key: &PropertyKey) -> Option<(&JsValue, PropertyFlags)> {
+        let (_, value, flags) = &self.entries[*self.indices.get(key)?];
+        Some((value, *flags))
+    }
+
+    /// Replacing the value of a property keeps its place in the order, and its flags
+    fn insert(&mut self, key: PropertyKey, value: JsValue) -> Option<JsValue> {
+        match self.indices.get(&key) {
+            Some(&index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.define(key, value, PropertyFlags::ALL);
+                None
+            }
+        }
+    }
+
+    /// Sets both the value and the flags of the property
+    fn define(&mut self, key: PropertyKey, value: JsValue, flags: PropertyFlags) {
+        match self.indices.get(&key) {
+            Some(&index) => self.entries[index] = (key, value, flags),
+            None => {
+                self.indices.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value, flags));
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &PropertyKey) -> Option<JsValue> {
+        let index = self.indices.remove(key)?;
+        let (_, value, _) = self.entries.remove(index);
+        for (key, _, _) in &self.entries[index..] {
+            *self.indices.get_mut(key).unwrap() -= 1;
+        }
+        Some(value)
+    }
+
+    fn enumerable_keys(&self) -> impl Iterator<Item = &PropertyKey> {
+        (self.entries.iter())
+            .filter(|(_, _, flags)| flags.enumerable)
+            .map(|(key, _, _)| key)
+    }
+
+    /// Makes every property read-only and non-configurable, for `Object.freeze`
+    fn freeze(&mut self) {
+        for (_, _, flags) in &mut self.entries {
+            flags.writable = false;
+            flags.configurable = false;
+        }
+    }
+
+    fn to_hash_map(&self) -> HashMap<PropertyKey, JsValue> {
+        (self.entries.iter())
+            .map(|(key, value, _)| (key.clone(), value.clone()))
+            .collect()
+    }
+}
+
+impl<K: Into<PropertyKey>> FromIterator<(K, JsValue)> for PropertyMap {
+    fn from_iter<T: IntoIterator<Item = (K, JsValue)>>(iter: T) -> Self {
+        let mut properties = PropertyMap::new();
+        for (key, value) in iter {
+            properties.insert(key.into(), value);
+        }
+        properties
+    }
+}
+
+pub struct JsObjectContents {
+    properties: PropertyMap,
+
+    /// Subtype is a bit of a hack/cheat.
+    /// It is used to help handling callable objects (aka functions) and arrays.
+    ///
+    /// Without this, it would need a lot of extra work to implement arrays through "just" an
+    /// object (in particular because of the length property for example)
+    subtype: ObjectSubtype,
+
+    /// The methods of the class that the object is an instance of, shared by all of its
+    /// instances. This stands in for the prototype, properties that the object doesn't have
+    /// are looked up here.
+    methods: Option<Rc<Methods>>,
+
+    /// The object that the properties that neither the object nor its methods have are looked
+    /// up in next, `None` at the end of the prototype chain
+    prototype: Option<JsObject>,
+
+    /// The private fields and methods of the object, by their names qualified with the class
+    /// that declares them. They aren't properties, so only `get_private` and `set_private` see
+    /// them.
+    private: HashMap<JsString, JsValue>,
+
+    /// Set by `Object.freeze`, after which no properties can be added, and the elements of an
+    /// array can't be changed either
+    frozen: bool,
+}
+
+pub type JsObject = Rc<JsCell<JsObjectContents>>;
+
+#[derive(Clone)]
+pub enum JsValue {
+    Null,
+    Undefined,
+    Boolean(bool),
+    Number(f64),
+    String(JsString),
+    Symbol(JsSymbol),
+    Object(JsObject),
+}
+
+impl From<f64> for JsValue {
+    fn from(value: f64) -> Self {
+        JsValue::Number(value)
+    }
+}
+
+impl From<usize> for JsValue {
+    fn from(value: usize) -> Self {
+        JsValue::Number(value as f64)
+    }
+}
+
+impl JsValue {
+    pub fn from_entries(entries: Vec<(PropertyKey, JsValue)>) -> Self {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: entries.into_iter().collect(),
+            subtype: ObjectSubtype::RegularObject,
+            methods: None,
+            prototype: Some(OBJECT_PROTOTYPE.with(Rc::clone)),
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    pub fn new_array(elements: Vec<JsValue>) -> Self {
+        JsValue::new_array_with_holes(elements.into_iter().map(Some).collect())
+    }
+
+    /// An array where the `None` elements are holes
+    fn new_array_with_holes(elements: Vec<Option<JsValue>>) -> Self {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Array(elements),
+            methods: Some(ARRAY_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    pub fn new_function(func: Box<FunctionFn>) -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: Default::default(),
+            subtype: ObjectSubtype::Function(func, FunctionInfo::default()),
+            methods: None,
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    pub fn new_method(method: Box<MethodFn>) -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: Default::default(),
+            subtype: ObjectSubtype::Method(method, FunctionInfo::default()),
+            methods: None,
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Sets what the runtime knows about the function, which is the default for `new_function`
+    /// and `new_method`
+    pub fn with_info(self, info: FunctionInfo) -> JsValue {
+        if let JsValue::Object(obj) = &self {
+            match &mut obj.borrow_mut().subtype {
+                ObjectSubtype::Function(_, old) | ObjectSubtype::Method(_, old) => *old = info,
+                _ => {}
+            }
+        }
+        self
+    }
+
+    /// The `name` of the function or the class, which is the name of its constructor, or
+    /// `None` if this is neither
+    fn function_name(&self) -> Option<&'static str> {
+        match self {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Class { constructor, .. } => constructor.function_name(),
+                _ => self.function_info().map(|info| info.name),
+            },
+            _ => None,
+        }
+    }
+
+    /// What the runtime knows about the function, or `None` if this isn't a function
+    fn function_info(&self) -> Option<FunctionInfo> {
+        match self {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Function(_, info) | ObjectSubtype::Method(_, info) => Some(*info),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// A regular expression, which panics with a `SyntaxError` if the pattern or the flags are
+    /// invalid
+    pub fn new_regexp(pattern: &str, flags: &str) -> JsValue {
+        let regexp = regexp::RegExp::new(pattern, flags);
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::from_iter([(
+                JsString::from("lastIndex"),
+                JsValue::Number(0.0),
+            )]),
+            subtype: ObjectSubtype::RegExp(Rc::new(regexp)),
+            methods: Some(REGEXP_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// A generator object, which runs the closure each time it's resumed
+    pub fn new_generator(step: Box<GeneratorFn>) -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Generator(Some(step)),
+            methods: Some(GENERATOR_METHODS.with(Rc::clone)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// A class, which `define_class` gives a constructor and methods to
+    pub fn new_class() -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: Default::default(),
+            subtype: ObjectSubtype::Class {
+                parent: None,
+                constructor: JsValue::Undefined,
+                methods: Rc::new(Methods::from(HashMap::new())),
+                private: Rc::default(),
+            },
+            methods: None,
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// A class that extends the parent, which is a class or a function, like `new_class`.
+    ///
+    /// The class inherits the static members that the parent has at this point, which stand
+    /// in for the parent being the prototype of the class.
+    pub fn new_subclass(parent: JsValue) -> JsValue {
+        let JsValue::Object(parent_obj) = &parent else {
+            type_error(format_args!(
+                "Class extends value {} is not a constructor or null",
+                parent.to_js_string().as_str()
+            ));
+        };
+        let statics = {
+            let parent_obj = parent_obj.borrow();
+            if !matches!(
+                parent_obj.subtype,
+                ObjectSubtype::Class { .. }
+                    | ObjectSubtype::Function(..)
+                    | ObjectSubtype::Method(..)
+            ) {
+                type_error("Class extends value [object Object] is not a constructor or null");
+            }
+            Methods {
+                own: parent_obj.properties.to_hash_map(),
+                parent: parent_obj.methods.clone(),
+            }
+        };
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: Default::default(),
+            subtype: ObjectSubtype::Class {
+                parent: Some(parent),
+                constructor: JsValue::Undefined,
+                methods: Rc::new(Methods::from(HashMap::new())),
+                private: Rc::default(),
+            },
+            methods: Some(Rc::new(statics)),
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// Sets the constructor, which is a method, the instance methods of the class, and the
+    /// private members of its instances, whose names are qualified with the class. A subclass
+    /// inherits the methods and private members of its parent.
+    ///
+    /// This is separate from `new_class`, so that the methods can refer to the class.
+    pub fn define_class<const N: usize, const P: usize>(
+        &self,
+        constructor: JsValue,
+        methods: [(JsString, JsValue); N],
+        private: [(JsString, JsValue); P],
+    ) {
+        let JsValue::Object(obj) = self else {
+            unreachable!()
+        };
+        let mut obj = obj.borrow_mut();
+        let ObjectSubtype::Class { parent, .. } = &obj.subtype else {
+            unreachable!()
+        };
+        let parent = parent.clone();
+        let mut all_private = match &parent {
+            Some(JsValue::Object(parent)) => match &parent.borrow().subtype {
+                ObjectSubtype::Class { private, .. } => (**private).clone(),
+                _ => HashMap::new(),
+            },
+            _ => HashMap::new(),
+        };
+        all_private.extend(private);
+        let parent_methods = parent.as_ref().map(JsValue::instance_methods);
+        obj.subtype = ObjectSubtype::Class {
+            parent,
+            constructor,
+            methods: Rc::new(Methods {
+                own: methods.into_iter().map(|(k, v)| (k.into(), v)).collect(),
+                parent: parent_methods,
+            }),
+            private: Rc::new(all_private),
+        };
+    }
+
+    /// A property that calls the getter when it's read and the setter when it's written, see
+    /// `define_class`
+    pub fn new_accessor(get: JsValue, set: JsValue) -> JsValue {
+        JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::Accessor { get, set },
+            methods: None,
+            prototype: None,
+            private: HashMap::new(),
+            frozen: false,
+        })))
+    }
+
+    /// The getter and the setter, if this is an accessor
+    fn accessor(&self) -> Option<(JsValue, JsValue)> {
+        match self {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Accessor { get, set } => Some((get.clone(), set.clone())),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The value of a property that was looked up, which is what the getter returns for
+    /// `this` if the property is an accessor
+    fn read_through(self, this: &JsValue) -> JsValue {
+        match self.accessor() {
+            Some((JsValue::Undefined, _)) => JsValue::Undefined,
+            Some((get, _)) => get.call_with_this(this, &[]),
+            None => self,
+        }
+    }
+
+    /// The methods that the instances of the class get, see `construct`. For a function, which a
+    /// class can extend, they're the properties that its `prototype` has at this point.
+    fn instance_methods(&self) -> Rc<Methods> {
+        let JsValue::Object(obj) = self else {
+            unreachable!()
+        };
+        if let ObjectSubtype::Class { methods, .. } = &obj.borrow().subtype {
+            return methods.clone();
+        }
+        let methods = match self.get_prop(JsValue::from("prototype")) {
+            JsValue::Object(prototype) => prototype.borrow().properties.to_hash_map(),
+            _ => HashMap::new(),
+        };
+        Rc::new(Methods {
+            own: methods,
+            parent: None,
+        })
+    }
+
+    /// The class or function that the class extends
+    fn parent_class(&self) -> JsValue {
+        match self {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Class {
+                    parent: Some(parent),
+                    ..
+                } => parent.clone(),
+                _ => unreachable!("`super` in a class that doesn't extend another"),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Implements `super(args)` in the constructor of the class, which runs the constructor of
+    /// the class that it extends on `this`, with the `new.target` of the constructor that
+    /// calls it
+    pub fn construct_super(&self, this: &JsValue, args: Vec<JsValue>, new_target: &JsValue) {
+        let parent = self.parent_class();
+        let constructor = match &parent {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Class { constructor, .. } => constructor.clone(),
+                _ => parent.clone(),
+            },
+            _ => unreachable!(),
+        };
+        constructor.pass_new_target(new_target);
+        constructor.call_with_this(this, &args);
+    }
+
+    /// Makes the target what `new_target` returns in the constructor, which is about to be
+    /// called, if it reads `new.target`. Other functions don't take it, so it'd be left over.
+    fn pass_new_target(&self, new_target: &JsValue) {
+        if self
+            .function_info()
+            .is_some_and(|info| info.uses_new_target)
+        {
+            NEW_TARGET.with(|target| *target.borrow_mut() = Some(new_target.clone()));
+        }
+    }
+
+    /// Implements `super.key` in a method of the class that is called on `this`. In a static
+    /// method, `super` is the class that it extends, otherwise it's the methods of that class.
+    pub fn get_super_prop(&self, key: JsValue, this: &JsValue, is_static: bool) -> JsValue {
+        let parent = self.parent_class();
+        let key = key.to_property_key();
+        let value = match is_static {
+            true => parent.lookup(&key),
+            false => parent.instance_methods().get(&key).cloned(),
+        };
+        value.map_or(JsValue::Undefined, |value| value.read_through(this))
+    }
+
+    /// The `+` operator, which concatenates the primitive values if either of them is a
+    /// string, and adds them as numbers otherwise
+    pub fn add(&self, other: JsValue) -> JsValue {
+        if let (JsValue::Number(a), JsValue::Number(b)) = (self, &other) {
+            return JsValue::Number(a + b);
+        }
+        match (self.to_default_primitive(), other.to_default_primitive()) {
+            (a @ JsValue::String(_), b) | (a, b @ JsValue::String(_)) => {
+                let (a, b) = (a.to_js_string(), b.to_js_string());
+                JsValue::String(JsString::from(format!("{}{}", a.as_str(), b.as_str())))
+            }
+            (a, b) => a
+                .to_number()
+                .do_binary_operation_nums(b.to_number(), |a, b| a + b),
+        }
+    }
+
+    pub fn sub(&self, other: JsValue) -> JsValue {
+        self.do_binary_operation_nums(other, |a, b| a - b)
+    }
+
+    pub fn mult(&self, other: JsValue) -> JsValue {
+        self.do_binary_operation_nums(other, |a, b| a * b)
+    }
+
+    pub fn divide(&self, other: JsValue) -> JsValue {
+        self.do_binary_operation_nums(other, |a, b| a / b)
+    }
+
+    /// The `%` operator, where the result has the sign of the dividend, like Rust's `%`
+    pub fn rem(&self, other: JsValue) -> JsValue {
+        self.do_binary_operation_nums(other, |a, b| a % b)
+    }
+
+    /// The `**` operator
+    pub fn pow(&self, other: JsValue) -> JsValue {
+        self.do_binary_operation_nums(other, |base, exponent| {
+            // Unlike `powf`, JS doesn't treat these as exact results
+            if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+                f64::NAN
+            } else {
+                base.powf(exponent)
+            }
+        })
+    }
+
+    pub fn bit_or(&self, other: JsValue) -> JsValue {
+        JsValue::Number((self.to_int32() | other.to_int32()) as f64)
+    }
+
+    /// Implements `&`
+    pub fn bit_and(&self, other: JsValue) -> JsValue {
+        JsValue::Number((self.to_int32() & other.to_int32()) as f64)
+    }
+
+    /// Implements `^`
+    pub fn bit_xor(&self, other: JsValue) -> JsValue {
+        JsValue::Number((self.to_int32() ^ other.to_int32()) as f64)
+    }
+
+    /// Implements `<<`. Only the lowest 5 bits of the shift count are used, like for the other
+    /// shifts.
+    pub fn shift_left(&self, other: JsValue) -> JsValue {
+        JsValue::Number(self.to_int32().wrapping_shl(other.to_uint32()) as f64)
+    }
+
+    /// Implements `>>`, which keeps the sign
+    pub fn shift_right(&self, other: JsValue) -> JsValue {
+        JsValue::Number(self.to_int32().wrapping_shr(other.to_uint32()) as f64)
+    }
+
+    /// Implements `>>>`, which fills in zeros, so the result is never negative
+    pub fn shift_right_unsigned(&self, other: JsValue) -> JsValue {
+        JsValue::Number(self.to_uint32().wrapping_shr(other.to_uint32()) as f64)
+    }
+
+    /// The ToInt32 abstract operation, which the bitwise operators apply to their operands.
+    ///
+    /// Fractional numbers are truncated and out of range numbers wrap around modulo 2^32.
+    pub fn to_int32(&self) -> i32 {
+        // The same bits, read as signed
+        self.to_uint32() as i32
+    }
+
+    /// The ToIntegerOrInfinity abstract operation, which truncates the number, where `NaN`
+    /// is 0
+    pub fn to_integer_or_infinity(&self) -> f64 {
+        let JsValue::Number(num) = self.to_number() else {
+            unreachable!()
+        };
+        match num.is_nan() {
+            true => 0.0,
+            // Without -0
+            false => num.trunc() + 0.0,
+        }
+    }
+
+    /// The ToUint32 abstract operation, which is like ToInt32, but wraps around into the
+    /// range of unsigned integers.
+    pub fn to_uint32(&self) -> u32 {
+        let JsValue::Number(num) = self.to_number() else {
+            unreachable!()
+        };
+        if !num.is_finite() {
+            return 0;
+        }
+        num.trunc().rem_euclid(4294967296.0) as u32
+    }
+
+    #[inline]
+    fn do_binary_operation_nums(
+        &self,
+        other: JsValue,
+        operation: impl Fn(f64, f64) -> f64,
+    ) -> JsValue {
+        use JsValue::*;
+        match (self, other) {
+            (Number(self_num), Number(other_num)) => {
+                JsValue::Number(operation(*self_num, other_num))
+            }
+            // The other values are converted to numbers, like dates to their time values
+            (_, other) => self
+                .to_number()
+                .do_binary_operation_nums(other.to_number(), operation),
+        }
+    }
+
+    /// The Abstract Relational Comparison, where strings are compared by their UTF-16 code
+    /// units and everything else as numbers. `None` if either of them is `NaN`.
+    fn compare(&self, other: &JsValue) -> Option<Ordering> {
+        match (self.to_primitive(), other.to_primitive()) {
+            (JsValue::String(a), JsValue::String(b)) => {
+                Some(a.as_str().encode_utf16().cmp(b.as_str().encode_utf16()))
+            }
+            (a, b) => {
+                let (JsValue::Number(a), JsValue::Number(b)) = (a.to_number(), b.to_number())
+                else {
+                    unreachable!()
+                };
+                a.partial_cmp(&b)
+            }
+        }
+    }
+
+    pub fn less(&self, other: JsValue) -> JsValue {
+        JsValue::Boolean(self.compare(&other) == Some(Ordering::Less))
+    }
+
+    /// Implements `>`
+    pub fn greater(&self, other: JsValue) -> JsValue {
+        JsValue::Boolean(self.compare(&other) == Some(Ordering::Greater))
+    }
+
+    /// Implements `<=`
+    pub fn less_equal(&self, other: JsValue) -> JsValue {
+        JsValue::Boolean(matches!(
+            self.compare(&other),
+            Some(Ordering::Less | Ordering::Equal)
+        ))
+    }
+
+    /// Implements `>=`
+    pub fn greater_equal(&self, other: JsValue) -> JsValue {
+        JsValue::Boolean(matches!(
+            self.compare(&other),
+            Some(Ordering::Greater | Ordering::Equal)
+        ))
+    }
+
+    /// Implements `===`, where objects are only equal to themselves
+    pub fn strict_equals(&self, other: JsValue) -> JsValue {
+        use JsValue::*;
+        let equal = match (self, &other) {
+            (Null, Null) | (Undefined, Undefined) => true,
+            (Boolean(a), Boolean(b)) => a == b,
+            // NaN isn't equal to itself, and 0 is equal to -0
+            (Number(a), Number(b)) => a == b,
+            (String(a), String(b)) => a == b,
+            (Symbol(a), Symbol(b)) => a == b,
+            (Object(a), Object(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        };
+        JsValue::Boolean(equal)
+    }
+
+    /// Implements `==`, which converts the operands to the same type before comparing them
+    pub fn loose_equals(&self, other: JsValue) -> JsValue {
+        use JsValue::*;
+        let equal = match (self, &other) {
+            (Null | Undefined, Null | Undefined) => true,
+            (Null | Undefined, _) | (_, Null | Undefined) => false,
+            (Number(_), Number(_))
+            | (String(_), String(_))
+            | (Boolean(_), Boolean(_))
+            | (Symbol(_), Symbol(_))
+            | (Object(_), Object(_)) => return self.strict_equals(other),
+            // Booleans are compared as numbers, objects as their primitive value
+            (Boolean(_), _) => return self.to_number().loose_equals(other),
+            (_, Boolean(_)) => return self.loose_equals(other.to_number()),
+            (Object(_), _) => return self.to_default_primitive().loose_equals(other),
+            (_, Object(_)) => return self.loose_equals(other.to_default_primitive()),
+            // A symbol is only equal to itself
+            (Symbol(_), _) | (_, Symbol(_)) => false,
+            (Number(_), String(_)) => return self.strict_equals(other.to_number()),
+            (String(_), Number(_)) => return self.to_number().strict_equals(other),
+        };
+        JsValue::Boolean(equal)
+    }
+
+    /// Implements `!=`
+    pub fn loose_not_equals(&self, other: JsValue) -> JsValue {
+        not(self.loose_equals(other))
+    }
+
+    /// Implements `!==`
+    pub fn strict_not_equals(&self, other: JsValue) -> JsValue {
+        not(self.strict_equals(other))
+    }
+
+    /// Implements `instanceof`, where an object is an instance of the class whose methods it
+    /// has, or of a class that one extends, as they stand in for the prototype. An object is an
+    /// instance of a function if the `prototype` of the function is in its prototype chain.
+    pub fn instance_of(&self, class: JsValue) -> JsValue {
+        let JsValue::Object(class_obj) = &class else {
+            type_error("Right-hand side of 'instanceof' is not callable");
+        };
+        let class_methods = match &class_obj.borrow().subtype {
+            ObjectSubtype::Class { methods, .. } => Some(methods.clone()),
+            ObjectSubtype::Function(..) | ObjectSubtype::Method(..) => None,
+            _ => type_error("Right-hand side of 'instanceof' is not callable"),
+        };
+        let JsValue::Object(obj) = self else {
+            return JsValue::Boolean(false);
+        };
+        let is_instance = match class_methods {
+            Some(class_methods) => obj
+                .borrow()
+                .methods
+                .as_ref()
+                .is_some_and(|methods| methods.inherit(&class_methods)),
+            None => {
+                let prototype = class.get_prop(JsValue::from("prototype"));
+                let JsValue::Object(prototype) = &prototype else {
+                    type_error(format_args!(
+                        "Function has non-object prototype '{}' in instanceof check",
+                        prototype.to_js_string().as_str()
+                    ));
+                };
+                iter::successors(obj.borrow().prototype.clone(), |object| {
+                    object.borrow().prototype.clone()
+                })
+                .any(|object| Rc::ptr_eq(&object, prototype))
+            }
+        };
+        JsValue::Boolean(is_instance)
+    }
+
+    /// Returns the element index if this value, used as a property key, refers to an array element.
+    ///
+    /// Property keys are strings in JS, so `arr["1"]` is the same element as `arr[1]`.
+    ///
+    /// Like in JS, the indices are below 2^32 - 1, the larger numbers are the keys of regular
+    /// properties.
+    fn to_array_index(&self) -> Option<usize> {
+        const MAX_INDEX: usize = u32::MAX as usize - 1;
+        match self {
+            JsValue::Number(index)
+                if *index >= 0.0 && *index <= MAX_INDEX as f64 && *index == index.trunc() =>
+            {
+                Some(*index as usize)
+            }
+            JsValue::String(key) => {
+                let key = key.as_str();
+                let is_canonical = key == "0" || !key.starts_with('0');
+                if is_canonical && !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit()) {
+                    key.parse().ok().filter(|index| *index <= MAX_INDEX)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn is_length_key(&self) -> bool {
+        matches!(self, JsValue::String(key) if key.as_str() == "length")
+    }
+
+    pub fn get_prop(&self, name: JsValue) -> JsValue {
+        match self {
+            JsValue::Undefined | JsValue::Null => type_error(format_args!(
+                "Cannot read properties of {} (reading '{}')",
+                self.to_js_string().as_str(),
+                name.to_property_key()
+            )),
+            JsValue::Object(obj) => {
+                let obj = obj.borrow();
+                if let ObjectSubtype::Array(ref array) = obj.subtype {
+                    if let Some(index) = name.to_array_index() {
+                        match array.get(index) {
+                            Some(element) => return element.clone().unwrap_or(JsValue::Undefined),
+                            // Past the end of the vector, unless it's a sparse element
+                            None if obj.properties.is_empty() => return JsValue::Undefined,
+                            None => {}
+                        }
+                    }
+                    if name.is_length_key() {
+                        return array_length(array, &obj.properties).into();
+                    }
+                }
+                if let ObjectSubtype::TypedArray(ref typed) = obj.subtype {
+                    if let Some(index) = name.to_array_index() {
+                        return typed.get(index).map_or(JsValue::Undefined, JsValue::Number);
+                    }
+                    if name.is_length_key() {
+                        return typed.len().into();
+                    }
+                }
+                if let ObjectSubtype::RegExp(ref regexp) = obj.subtype {
+                    if let Some(value) = regexp_prop(regexp, &name) {
+                        return value;
+                    }
+                }
+                if let ObjectSubtype::Map(ref entries) | ObjectSubtype::Set(ref entries) =
+                    obj.subtype
+                {
+                    if matches!(&name, JsValue::String(key) if key.as_str() == "size") {
+                        return entries.len().into();
+                    }
+                }
+                let name = name.to_property_key();
+                let name_str = name.as_string().map(JsString::as_str);
+                match self.lookup(&name) {
+                    Some(value) => value.read_through(self),
+                    None if name_str == Some("prototype") => self.create_prototype(),
+                    None if name_str == Some("name") => match self.function_name() {
+                        Some(name) => JsValue::from(name),
+                        None => JsValue::Undefined,
+                    },
+                    None => JsValue::Undefined,
+                }
+            }
+            // The characters are the UTF-16 code units
+            JsValue::String(string) => {
+                if let Some(index) = name.to_array_index() {
+                    let unit = string.as_str().encode_utf16().nth(index);
+                    return unit.map_or(JsValue::Undefined, |unit| {
+                        JsValue::String(JsString::from(String::from_utf16_lossy(&[unit])))
+                    });
+                }
+                if name.is_length_key() {
+                    return string.as_str().encode_utf16().count().into();
+                }
+                let name = name.to_property_key();
+                STRING_METHODS
+                    .with(|methods| methods.get(&name).cloned())
+                    .unwrap_or(JsValue::Undefined)
+            }
+            JsValue::Number(_) => {
+                let name = name.to_property_key();
+                NUMBER_METHODS
+                    .with(|methods| methods.get(&name).cloned())
+                    .unwrap_or(JsValue::Undefined)
+            }
+            JsValue::Symbol(symbol) => {
+                let name = name.to_property_key();
+                if name
+                    .as_string()
+                    .is_some_and(|name| name.as_str() == "description")
+                {
+                    return (symbol.description().cloned())
+                        .map_or(JsValue::Undefined, JsValue::String);
+                }
+                SYMBOL_METHODS
+                    .with(|methods| methods.get(&name).cloned())
+                    .unwrap_or(JsValue::Undefined)
+            }
+            // Booleans don't have any methods
+            JsValue::Boolean(_) => JsValue::Undefined,
+        }
+    }
+
+    /// Gives the function the `prototype` that functions have by default, an object with the
+    /// function as its `constructor`, and returns it, or `undefined` if this isn't a function or
+    /// is an arrow function.
+    ///
+    /// It's created when it's first read, as most functions are never constructors, and it's
+    /// kept with the methods, as neither it nor the `constructor` is enumerable.
+    fn create_prototype(&self) -> JsValue {
+        let JsValue::Object(obj) = self else {
+            return JsValue::Undefined;
+        };
+        if !matches!(self.function_info(), Some(info) if !info.is_arrow) {
+            return JsValue::Undefined;
+        }
+        let constructor = HashMap::from([(JsString::from("constructor"), self.clone())]);
+        let prototype = JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::RegularObject,
+            methods: Some(Rc::new(Methods::from(constructor))),
+            prototype: Some(OBJECT_PROTOTYPE.with(Rc::clone)),
+            private: HashMap::new(),
+            frozen: false,
+        })));
+        let mut obj = obj.borrow_mut();
+        let methods = Methods {
+            own: HashMap::from([(PropertyKey::from("prototype"), prototype.clone())]),
+            parent: obj.methods.take(),
+        };
+        obj.methods = Some(Rc::new(methods));
+        prototype
+    }
+
+    /// The own property or the method of the object, or else the property that it inherits
+    /// through its prototype chain, which may be an accessor
+    fn lookup(&self, name: &PropertyKey) -> Option<JsValue> {
+        self.lookup_property(name).map(|(value, _)| value)
+    }
+
+    /// The property that `lookup` finds, with its flags. Methods have all of them.
+    fn lookup_property(&self, name: &PropertyKey) -> Option<(JsValue, PropertyFlags)> {
+        match self {
+            JsValue::Object(obj) => {
+                let obj = obj.borrow();
+                let own = obj.properties.property(name).or_else(|| {
+                    let method = obj.methods.as_ref()?.get(name)?;
+                    Some((method, PropertyFlags::ALL))
+                });
+                match own {
+                    Some((value, flags)) => Some((value.clone(), flags)),
+                    None => JsValue::Object(obj.prototype.clone()?).lookup_property(name),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn set_prop(&self, name: JsValue, value: JsValue) {
+        match self {
+            JsValue::Object(obj) => {
+                // Out of bounds, the element isn't added, and the write is ignored
+                if let (Some(typed), Some(index)) = (typed_array::of(self), name.to_array_index()) {
+                    typed.set(index, &value);
+                    return;
+                }
+                if let JsValue::String(_) | JsValue::Symbol(_) = &name {
+                    if let Some((property, flags)) = self.lookup_property(&name.to_property_key()) {
+                        if let Some((_, set)) = property.accessor() {
+                            if !set.is_undefined() {
+                                set.call_with_this(self, &[value]);
+                            }
+                            return;
+                        }
+                        // Like in sloppy mode, the write is ignored without an error, which
+                        // is also the case for a read-only property that is inherited
+                        if !flags.writable {
+                            return;
+                        }
+                    }
+                }
+                let mut obj = obj.borrow_mut();
+                // Like in sloppy mode, the write is ignored without an error
+                if obj.frozen {
+                    return;
+                }
+                let contents = &mut *obj;
+                if let ObjectSubtype::Array(array) = &mut contents.subtype {
+                    if let Some(index) = name.to_array_index() {
+                        if index >= array.len() {
+                            // The holes up to the index take up memory, unlike in JS, so an
+                            // element far out is stored as a property instead
+                            if index - array.len() > array.len() + MAX_EXTRA_HOLES {
+                                contents.properties.insert(name.to_property_key(), value);
+                                return;
+                            }
+                            if array.try_reserve(index + 1 - array.len()).is_err() {
+                                range_error("Invalid array length");
+                            }
+                            array.resize(index + 1, None);
+                            take_sparse_elements(array, &mut contents.properties);
+                        }
+                        array[index] = Some(value);
+                        return;
+                    }
+                }
+                contents.properties.insert(name.to_property_key(), value);
+            }
+            JsValue::Undefined | JsValue::Null => type_error(format_args!(
+                "Cannot set properties of {} (setting '{}')",
+                self.to_js_string().as_str(),
+                name.to_property_key()
+            )),
+            // Like in sloppy mode, the write is ignored without an error, as primitive values
+            // don't have properties of their own
+            _ => {}
+        }
+    }
+
+    /// Implements the `delete` operator. Deleting an array element leaves a hole behind,
+    /// so the length of the array stays the same. Properties that aren't configurable, like
+    /// those of a frozen object, can't be deleted, which makes it evaluate to `false`.
+    pub fn delete_prop(&self, name: JsValue) -> JsValue {
+        match self {
+            JsValue::Object(obj) => {
+                let mut obj = obj.borrow_mut();
+                let frozen = obj.frozen;
+                if let ObjectSubtype::Array(ref mut array) = obj.subtype {
+                    // Past the end of the vector, it may be a sparse element
+                    if let Some(element) = name.to_array_index().and_then(|i| array.get_mut(i)) {
+                        if frozen && element.is_some() {
+                            return JsValue::Boolean(false);
+                        }
+                        *element = None;
+                        return JsValue::Boolean(true);
+                    }
+                }
+                let name = name.to_property_key();
+                match obj.properties.property(&name) {
+                    Some((_, flags)) if !flags.configurable => JsValue::Boolean(false),
+                    _ => {
+                        obj.properties.remove(&name);
+                        JsValue::Boolean(true)
+                    }
+                }
+            }
+            JsValue::Undefined | JsValue::Null => {
+                type_error("Cannot convert undefined or null to object")
+            }
+            _ => JsValue::Boolean(true),
+        }
+    }
+
+    /// Implements the `in` operator, where `self` is the property name
+    pub fn is_in(&self, object: JsValue) -> JsValue {
+        match &object {
+            JsValue::Object(obj) => {
+                let obj = obj.borrow();
+                if let ObjectSubtype::Array(ref array) = obj.subtype {
+                    // Past the end of the vector, it may be a sparse element
+                    if let Some(element) = self.to_array_index().and_then(|index| array.get(index))
+                    {
+                        return JsValue::Boolean(element.is_some());
+                    }
+                    if self.is_length_key() {
+                        return JsValue::Boolean(true);
+                    }
+                }
+                JsValue::Boolean(object.lookup(&self.to_property_key()).is_some())
+            }
+            _ => type_error("Cannot use 'in' operator to search for a key in a primitive"),
+        }
+    }
+
+    /// Reads the private member of the object, see `define_class`
+    pub fn get_private(&self, name: &str) -> JsValue {
+        let member = match self {
+            JsValue::Object(obj) => obj.borrow().private.get(&JsString::from(name)).cloned(),
+            _ => None,
+        };
+        member
+            .map(|member| member.read_through(self))
+            .unwrap_or_else(|| {
+                type_error(format_args!(
+                    "Cannot read private member {} from an object whose class did not declare it",
+                    private_name(name)
+                ))
+            })
+    }
+
+    pub fn set_private(&self, name: &str, value: JsValue) {
+        let key = JsString::from(name);
+        let member = match self {
+            JsValue::Object(obj) => obj.borrow().private.get(&key).cloned(),
+            _ => None,
+        };
+        let Some(member) = member else {
+            type_error(format_args!(
+                "Cannot write private member {} to an object whose class did not declare it",
+                private_name(name)
+            ));
+        };
+        match member.accessor() {
+            Some((_, JsValue::Undefined)) => type_error(format_args!(
+                "'{}' was defined without a setter",
+                private_name(name)
+            )),
+            Some((_, set)) => {
+                set.call_with_this(self, &[value]);
+            }
+            None => {
+                if let JsValue::Object(obj) = self {
+                    obj.borrow_mut().private.insert(key, value);
+                }
+            }
+        }
+    }
+
+    /// Implements `#name in object`, where `self` is the object
+    pub fn has_private(&self, name: &str) -> JsValue {
+        match self {
+            JsValue::Object(obj) => {
+                JsValue::Boolean(obj.borrow().private.contains_key(&JsString::from(name)))
+            }
+            _ => type_error(format_args!(
+                "Cannot use 'in' operator to search for '{}' in {}",
+                private_name(name),
+                self.to_js_string().as_str()
+            )),
+        }
+    }
+
+    /// The own keys that a `for-in` loop visits, as strings: the indices of the elements of an
+    /// array or the characters of a string, then the properties of an object.
+    ///
+    /// Like in JS, the properties that are integers come first, in ascending order, and the
+    /// other ones follow in the order that they were added in.
+    pub fn own_enumerable_keys(&self) -> Vec<JsValue> {
+        let index_key = |index: usize| JsValue::String(JsString::from(index.to_string()));
+        match self {
+            JsValue::Object(obj) => {
+                let obj = obj.borrow();
+                let mut keys = Vec::new();
+                if let ObjectSubtype::Array(ref array) = obj.subtype {
+                    let indices = array.iter().enumerate().filter(|(_, e)| e.is_some());
+                    keys.extend(indices.map(|(index, _)| index_key(index)));
+                }
+                if let ObjectSubtype::TypedArray(ref typed) = obj.subtype {
+                    keys.extend((0..typed.len()).map(index_key));
+                }
+                let mut properties: Vec<_> = obj
+                    .properties
+                    .enumerable_keys()
+                    .filter_map(PropertyKey::as_string)
+                    .map(|key| {
+                        let key = JsValue::String(key.clone());
+                        // `Ok` sorts before `Err`, so the integers come first
+                        (key.to_array_index().ok_or(()), key)
+                    })
+                    .collect();
+                // The sort is stable, so the other properties stay in order
+                properties.sort_by_key(|(index, _)| *index);
+                keys.extend(properties.into_iter().map(|(_, key)| key));
+                keys
+            }
+            JsValue::String(string) => (0..string.as_str().encode_utf16().count())
+                .map(index_key)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The keys that a `for-in` loop visits: the ones from `own_enumerable_keys`, followed by
+    /// those of each object in the prototype chain that aren't among them yet
+    pub fn enumerable_keys(&self) -> Vec<JsValue> {
+        let mut keys = self.own_enumerable_keys();
+        let JsValue::Object(obj) = self else {
+            return keys;
+        };
+        let mut seen: HashSet<JsString> = keys.iter().map(JsValue::to_js_string).collect();
+        let prototypes = iter::successors(obj.borrow().prototype.clone(), |object| {
+            object.borrow().prototype.clone()
+        });
+        for prototype in prototypes {
+            for key in JsValue::Object(prototype).own_enumerable_keys() {
+                if seen.insert(key.to_js_string()) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    /// The symbols that are the keys of the enumerable own properties of the object, which
+    /// `own_enumerable_keys` leaves out, in the order that they were added in
+    pub fn own_enumerable_symbols(&self) -> Vec<JsValue> {
+        match self {
+            JsValue::Object(obj) => (obj.borrow().properties.enumerable_keys())
+                .filter(|key| matches!(key, PropertyKey::Symbol(_)))
+                .map(PropertyKey::to_value)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The keys from `own_enumerable_keys` and `own_enumerable_symbols` with their values,
+    /// which spreading the value into an object literal copies
+    pub fn own_entries(&self) -> Vec<(PropertyKey, JsValue)> {
+        (self.own_enumerable_keys().into_iter())
+            .chain(self.own_enumerable_symbols())
+            .map(|key| (key.to_property_key(), self.get_prop(key)))
+            .collect()
+    }
+
+    /// The elements that `iter_elements` visits from the index on, as a new array, for the
+    /// rest of an array pattern
+    pub fn slice_from(&self, start: usize) -> JsValue {
+        JsValue::new_array(self.iter_elements().skip(start).collect())
+    }
+
+    /// The values that a `for-of` loop visits: the elements of an array, with holes read as
+    /// `undefined`, the characters of a string, the values that a generator yields, the
+    /// entries of a map or the values of a set, or the elements of a typed array. These are
+    /// what the iterators of their `[Symbol.iterator]()` methods produce, which other objects
+    /// are iterated with, see `ProtocolIterator`.
+    ///
+    /// An array is read one element at a time, so the loop sees the changes that its body
+    /// makes to the array, like in JS.
+    pub fn iter_elements(&self) -> Box<dyn Iterator<Item = JsValue>> {
+        match self {
+            JsValue::Object(obj) if matches!(obj.borrow().subtype, ObjectSubtype::Array(_)) => {
+                let obj = obj.clone();
+                Box::new((0..).map_while(move |index| match &obj.borrow().subtype {
+                    ObjectSubtype::Array(array) => {
+                        let element = array.get(index)?;
+                        Some(element.clone().unwrap_or(JsValue::Undefined))
+                    }
+                    _ => None,
+                }))
+            }
+            JsValue::Object(_) if self.is_generator() => {
+                let generator = self.clone();
+                Box::new(iter::from_fn(move || generator.next_item()))
+            }
+            JsValue::Object(_) if collection::is_collection(self) => {
+                collection::iter_elements(self)
+            }
+            JsValue::Object(_) if typed_array::of(self).is_some() => {
+                let typed = typed_array::of(self).unwrap();
+                Box::new((0..).map_while(move |index| typed.get(index).map(JsValue::Number)))
+            }
+            JsValue::String(string) => {
+                let chars: Vec<_> = string
+                    .as_str()
+                    .chars()
+                    .map(|c| JsValue::String(JsString::from(c.to_string())))
+                    .collect();
+                Box::new(chars.into_iter())
+            }
+            JsValue::Object(_) => Box::new(ProtocolIterator::new(self)),
+            _ => type_error(format_args!(
+                "{} is not iterable",
+                self.to_js_string().as_str()
+            )),
+        }
+    }
+
+    /// A generator that yields the items that `iter_elements` visits, for a `for-of` loop that
+    /// a generator is suspended in
+    pub fn elements_iterator(&self) -> JsValue {
+        let mut elements = self.iter_elements();
+        JsValue::new_generator(Box::new(move |_| match elements.next() {
+            Some(element) => GeneratorStep::Yield(element),
+            None => GeneratorStep::Return(JsValue::Undefined),
+        }))
+    }
+
+    /// A generator that yields the keys from `enumerable_keys`, for a `for-in` loop that a
+    /// generator is suspended in
+    pub fn keys_iterator(&self) -> JsValue {
+        let mut keys = self.enumerable_keys().into_iter();
+        JsValue::new_generator(Box::new(move |_| match keys.next() {
+            Some(key) => GeneratorStep::Yield(key),
+            None => GeneratorStep::Return(JsValue::Undefined),
+        }))
+    }
+
+    /// Runs the generator up to its next `yield`, with the value as what the `yield` that it's
+    /// suspended at evaluates to.
+    ///
+    /// The closure is taken out of the object while it runs, so that a generator that resumes
+    /// itself finds it finished, and one that throws stays finished.
+    fn resume(&self, value: JsValue) -> GeneratorStep {
+        let step = match self {
+            JsValue::Object(obj) => match &mut obj.borrow_mut().subtype {
+                ObjectSubtype::Generator(step) => step.take(),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(mut step) = step else {
+            return match self.is_generator() {
+                true => GeneratorStep::Return(JsValue::Undefined),
+                false => type_error(format_args!(
+                    "next method called on incompatible receiver {}",
+                    self.to_js_string().as_str()
+                )),
+            };
+        };
+        let result = step(value);
+        if let (GeneratorStep::Yield(_), JsValue::Object(obj)) = (&result, self) {
+            if let ObjectSubtype::Generator(slot) = &mut obj.borrow_mut().subtype {
+                *slot = Some(step);
+            }
+        }
+        result
+    }
+
+    /// Finishes the generator without running the rest of its body
+    fn finish(&self) {
+        if let JsValue::Object(obj) = self {
+            if let ObjectSubtype::Generator(step) = &mut obj.borrow_mut().subtype {
+                *step = None;
+            }
+        }
+    }
+
+    fn is_generator(&self) -> bool {
+        match self {
+            JsValue::Object(obj) => matches!(obj.borrow().subtype, ObjectSubtype::Generator(_)),
+            _ => false,
+        }
+    }
+
+    /// The next value that the generator yields, or `None` once it's finished
+    pub fn next_item(&self) -> Option<JsValue> {
+        match self.resume(JsValue::Undefined) {
+            GeneratorStep::Yield(value) => Some(value),
+            GeneratorStep::Return(_) => None,
+        }
+    }
+
+    /// The ubiquitous `toString` function from JS
+    pub fn to_js_string(&self) -> JsString {
+        match self {
+            JsValue::Null => JsString::from("null"),
+            JsValue::Undefined => JsString::from("undefined"),
+            JsValue::Boolean(val) => JsString {
+                value: Rc::from(format!("{val}")),
+            },
+            JsValue::Number(val) => JsString::from(number_to_string(*val)),
+            JsValue::String(val) => val.clone(),
+            // Unlike `String(symbol)`, a symbol isn't converted implicitly
+            JsValue::Symbol(_) => type_error("Cannot convert a Symbol value to a string"),
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Array(elements) => array::join_elements(elements, ","),
+                ObjectSubtype::TypedArray(typed) => {
+                    let values = typed.values().into_iter().map(number_to_string);
+                    JsString::from(values.collect::<Vec<_>>().join(","))
+                }
+                ObjectSubtype::RegExp(regexp) => {
+                    JsString::from(format!("/{}/{}", regexp.source, regexp.flags))
+                }
+                ObjectSubtype::Generator(_) => JsString::from("[object Generator]"),
+                ObjectSubtype::Date(time) => JsString::from(date::to_string(*time)),
+                ObjectSubtype::Map(_) => JsString::from("[object Map]"),
+                ObjectSubtype::Set(_) => JsString::from("[object Set]"),
+                ObjectSubtype::Promise(_) => JsString::from("[object Promise]"),
+                ObjectSubtype::RegularObject => self.to_string_with_method(),
+                _ => JsString::from("[object Object]"),
+            },
+        }
+    }
+
+    /// The string of an object that its `toString` or else its `valueOf` method returns, like
+    /// for errors, or `[object Object]` if neither returns a primitive
+    fn to_string_with_method(&self) -> JsString {
+        for key in ["toString", "valueOf"] {
+            let Some(method) = self.lookup(&PropertyKey::from(key)) else {
+                continue;
+            };
+            let method = method.read_through(self);
+            if method.is_callable() {
+                let value = method.call_with_this(self, &[]);
+                if !matches!(value, JsValue::Object(_)) {
+                    return value.to_js_string();
+                }
+            }
+        }
+        JsString::from("[object Object]")
+    }
+
+    /// The key of the property that the value names, which is the value itself if it's a
+    /// symbol, or else its string
+    pub fn to_property_key(&self) -> PropertyKey {
+        match self {
+            JsValue::Symbol(symbol) => PropertyKey::Symbol(symbol.clone()),
+            _ => PropertyKey::String(self.to_js_string()),
+        }
+    }
+
+    /// The primitive value of an object is what its `valueOf` or else its `toString` method
+    /// returns, the first one of them that returns a primitive, or else its string
+    pub fn to_primitive(&self) -> JsValue {
+        if !matches!(self, JsValue::Object(_)) {
+            return self.clone();
+        }
+        for key in ["valueOf", "toString"] {
+            let Some(method) = self.lookup(&PropertyKey::from(key)) else {
+                continue;
+            };
+            let method = method.read_through(self);
+            if method.is_callable() {
+                let value = method.call_with_this(self, &[]);
+                if !matches!(value, JsValue::Object(_)) {
+                    return value;
+                }
+            }
+        }
+        JsValue::String(self.to_js_string())
+    }
+
+    /// The primitive value that `+` and `==` convert an object to, which is the string of a
+    /// date, unlike `to_primitive`, which is its time value
+    fn to_default_primitive(&self) -> JsValue {
+        match date::time_of(self) {
+            Some(_) => JsValue::String(self.to_js_string()),
+            None => self.to_primitive(),
+        }
+    }
+
+    /// Whether the value is a function or a class
+    pub fn is_callable(&self) -> bool {
+        match self {
+            JsValue::Object(obj) => matches!(
+                obj.borrow().subtype,
+                ObjectSubtype::Class { .. }
+                    | ObjectSubtype::Function(..)
+                    | ObjectSubtype::Method(..)
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the value is `null` or `undefined`, which `??` replaces
+    pub fn is_nullish(&self) -> bool {
+        matches!(self, JsValue::Null | JsValue::Undefined)
+    }
+
+    /// Whether the value is `undefined`, which the default of a destructured binding replaces
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, JsValue::Undefined)
+    }
+
+    pub fn falsy(&self) -> bool {
+        !self.truthy()
+    }
+
+    pub fn truthy(&self) -> bool {
+        match self {
+            JsValue::Undefined => false,
+            JsValue::Null => false,
+            JsValue::Boolean(boolean) => *boolean,
+            JsValue::Number(number) => *number != 0.0,
+            JsValue::String(string) => !string.as_str().is_empty(),
+            JsValue::Symbol(_) | JsValue::Object(_) => true,
+        }
+    }
+
+    pub fn to_number(&self) -> JsValue {
+        let num = match self {
+            JsValue::Undefined => f64::NAN,
+            JsValue::Null => 0.0,
+            JsValue::Boolean(value) => {
+                if *value {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            JsValue::Number(value) => *value,
+            JsValue::String(js_string) => string_to_number(js_string.as_str()),
+            JsValue::Symbol(_) => type_error("Cannot convert a Symbol value to a number"),
+            JsValue::Object(_) => return self.to_primitive().to_number(),
+        };
+        JsValue::Number(num)
+    }
+
+    pub fn call(&self, args: &[JsValue]) -> JsValue {
+        self.call_with_this(&JsValue::Undefined, args)
+    }
+
+    /// Calls the function with `this` bound to the given value, if it's a method
+    pub fn call_with_this(&self, this: &JsValue, args: &[JsValue]) -> JsValue {
+        match self {
+            JsValue::Object(obj) => {
+                let borrowed = obj.borrow_mut();
+                match &borrowed.subtype {
+                    ObjectSubtype::Function(func, info) => {
+                        let _frame = StackFrame::enter(info);
+                        (func)(args)
+                    }
+                    ObjectSubtype::Method(method, info) => {
+                        let _frame = StackFrame::enter(info);
+                        (method)(this, args)
+                    }
+                    ObjectSubtype::Class { constructor, .. } => type_error(format_args!(
+                        "Class constructor {} cannot be invoked without 'new'",
+                        constructor.function_name().unwrap_or_default()
+                    )),
+                    _ => type_error(format_args!(
+                        "{} is not a function",
+                        self.to_js_string().as_str()
+                    )),
+                }
+            }
+            _ => type_error(format_args!(
+                "{} is not a function",
+                self.to_js_string().as_str()
+            )),
+        }
+    }
+
+    /// Implements `object.key(args)`, where the function is called with the object as `this`
+    pub fn call_method(&self, key: JsValue, args: &[JsValue]) -> JsValue {
+        self.get_prop(key).call_with_this(self, args)
+    }
+
+    /// Implements `new`, which creates an instance of a class and runs the constructor on it.
+    ///
+    /// A function is a constructor too, that runs with the new object as `this`, unless it's
+    /// an arrow function. The instance inherits from the `prototype` of the function, or from
+    /// the prototype of objects if that isn't an object.
+    ///
+    /// Like in JS, a constructor that returns an object makes `new` evaluate to that object
+    /// instead.
+    pub fn construct(&self, args: &[JsValue]) -> JsValue {
+        let (constructor, methods, private) = match self {
+            JsValue::Object(obj) => match &obj.borrow().subtype {
+                ObjectSubtype::Class {
+                    constructor,
+                    methods,
+                    private,
+                    ..
+                } => (
+                    constructor.clone(),
+                    Some(methods.clone()),
+                    (**private).clone(),
+                ),
+                ObjectSubtype::Function(_, info) | ObjectSubtype::Method(_, info) => {
+                    if info.is_arrow {
+                        let name = if info.name.is_empty() {
+                            "anonymous"
+                        } else {
+                            info.name
+                        };
+                        type_error(format_args!("{} is not a constructor", name));
+                    }
+                    (self.clone(), None, HashMap::new())
+                }
+                _ => type_error(format_args!(
+                    "{} is not a constructor",
+                    self.to_js_string().as_str()
+                )),
+            },
+            _ => type_error(format_args!(
+                "{} is not a constructor",
+                self.to_js_string().as_str()
+            )),
+        };
+        let prototype = match methods {
+            Some(_) => None,
+            None => match self.get_prop(JsValue::from("prototype")) {
+                JsValue::Object(prototype) => Some(prototype),
+                _ => Some(OBJECT_PROTOTYPE.with(Rc::clone)),
+            },
+        };
+        let instance = JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+            properties: PropertyMap::new(),
+            subtype: ObjectSubtype::RegularObject,
+            methods,
+            prototype,
+            private,
+            frozen: false,
+        })));
+        constructor.pass_new_target(self);
+        match constructor.call_with_this(&instance, args) {
+            result @ JsValue::Object(_) => result,
+            _ => instance,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for JsValue {
+    fn from(value: &'a str) -> Self {
+        JsValue::String(JsString::from(value))
+    }
+}
+
+/// The name of a private member as it's written in JS, without the class that qualifies it
+fn private_name(name: &str) -> &str {
+    &name[name.find('#').unwrap_or(0)..]
+}
+
+/// The argument at the index, which is `undefined` if the caller didn't pass that many
+#[inline]
+pub fn arg(args: &[JsValue], index: usize) -> JsValue {
+    args.get(index).cloned().unwrap_or(JsValue::Undefined)
+}
+
+thread_local! {
+    /// The `new.target` that `construct` passes to the constructor that it's about to call
+    static NEW_TARGET: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+
+    /// The names of the functions that are running, innermost last, for the `stack` of errors
+    static CALL_STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A function in `CALL_STACK`, which is removed from it when the function returns or throws
+struct StackFrame;
+
+impl StackFrame {
+    fn enter(info: &FunctionInfo) -> StackFrame {
+        CALL_STACK.with(|stack| stack.borrow_mut().push(info.name));
+        StackFrame
+    }
+}
+
+impl Drop for StackFrame {
+    fn drop(&mut self) {
+        CALL_STACK.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+/// Implements `new.target`, which a function that reads it calls first thing. It's the
+/// constructor that `new` was used on, or `undefined` if the function was called without it.
+pub fn new_target() -> JsValue {
+    NEW_TARGET
+        .with(|target| target.borrow_mut().take())
+        .unwrap_or(JsValue::Undefined)
+}
+
+/// The arguments from the index on, as an array, for a rest parameter
+pub fn rest_args(args: &[JsValue], start: usize) -> JsValue {
+    JsValue::new_array(args.get(start..).unwrap_or_default().to_vec())
+}
+
+/// The `arguments` of a function, an object with the arguments at their indices. Its `length`
+/// isn't enumerable, so it's kept with the methods.
+pub fn arguments_object(args: &[JsValue]) -> JsValue {
+    let length = HashMap::from([(JsString::from("length"), args.len().into())]);
+    JsValue::Object(JsObject::new(JsCell::new(JsObjectContents {
+        properties: (args.iter().enumerate())
+            .map(|(index, arg)| (JsString::from(index.to_string()), arg.clone()))
+            .collect(),
+        subtype: ObjectSubtype::RegularObject,
+        methods: Some(Rc::new(Methods::from(length))),
+        prototype: None,
+        private: HashMap::new(),
+        frozen: false,
+    })))
+}
+
+#[inline]
+pub fn negate(value: JsValue) -> JsValue {
+    if let JsValue::Number(num) = value.to_number() {
+        JsValue::Number(-num)
+    } else {
+        unreachable!()
+    }
+}
+
+/// Interleaves the quasis of a template literal with its substitutions.
+///
+/// There is always exactly one more quasi than there are substitutions.
+pub fn template_literal(quasis: &[&str], substitutions: &[JsValue]) -> JsValue {
+    let mut result = String::from(quasis[0]);
+    for (substitution, quasi) in substitutions.iter().zip(&quasis[1..]) {
+        result.push_str(substitution.to_js_string().as_str());
+        result.push_str(quasi);
+    }
+    JsValue::String(JsString::from(result))
+}
+
+/// The Number::toString abstract operation, with the shortest digits that round-trip, and
+/// exponential notation for the numbers from 1e21 on and the ones below 1e-6
+fn number_to_string(num: f64) -> String {
+    if num.is_nan() {
+        return String::from("NaN");
+    }
+    if num == 0.0 {
+        // Including -0
+        return String::from("0");
+    }
+    if num.is_infinite() {
+        return String::from(if num > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+    let sign = if num < 0.0 { "-" } else { "" };
+    // Rust's exponential notation has the shortest digits too, like `1.25e-7`
+    let exponential = format!("{:e}", num.abs());
+    let (mantissa, exponent) = exponential.split_once('e').unwrap();
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // The position of the decimal point relative to the start of the digits
+    let n = exponent.parse::<i32>().unwrap() + 1;
+    let text = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        format!("{integer}.{fraction}")
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        let exponent_sign = if n > 0 { "+" } else { "-" };
+        format!("{first}{point}{rest}e{exponent_sign}{}", (n - 1).abs())
+    };
+    format!("{sign}{text}")
+}
+
+/// The position that the argument of a method of a string or an array refers to, clamped to
+/// its length, where negative positions count from the end
+fn relative_index(position: &JsValue, len: usize, default: usize) -> usize {
+    if position.is_undefined() {
+        return default;
+    }
+    let position = position.to_integer_or_infinity();
+    match position < 0.0 {
+        true => (len as f64 + position).max(0.0) as usize,
+        false => position.min(len as f64) as usize,
+    }
+}
+
+/// Whether the character is white space or a line terminator, which `trim` removes
+fn is_js_whitespace(c: char) -> bool {
+    // Unlike JS, Rust counts the next line character as white space, but not the byte order
+    // mark
+    (c.is_whitespace() && c != '\u{85}') || c == '\u{FEFF}'
+}
+
+/// The StringToNumber abstract operation, where surrounding whitespace is ignored, the empty
+/// string is 0 and anything that isn't a number is `NaN`
+fn string_to_number(text: &str) -> f64 {
+    let text = text.trim_matches(is_js_whitespace);
+    let radix = match text.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &text[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return f64::NAN;
+        }
+        return digits.chars().fold(0.0, |value, c| {
+            value * radix as f64 + c.to_digit(radix).unwrap() as f64
+        });
+    }
+    match text {
+        "" => 0.0,
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // Rust also parses the likes of `inf` and `NaN`, which JS doesn't
+        _ if text
+            .chars()
+            .all(|c| matches!(c, '0'..='9' | '.' | 'e' | 'E' | '+' | '-')) =>
+        {
+            text.parse().unwrap_or(f64::NAN)
+        }
+        _ => f64::NAN,
+    }
+}
+
+/// The properties that every regular expression has, which come from the pattern and the flags
+fn regexp_prop(regexp: &regexp::RegExp, name: &JsValue) -> Option<JsValue> {
+    let JsValue::String(name) = name else {
+        return None;
+    };
+    let flags = &regexp.flags;
+    let value = match name.as_str() {
+        "source" => JsValue::from(regexp.source.as_str()),
+        "flags" => JsValue::String(JsString::from(flags.to_string())),
+        "hasIndices" => JsValue::Boolean(flags.has_indices),
+        "global" => JsValue::Boolean(flags.global),
+        "ignoreCase" => JsValue::Boolean(flags.ignore_case),
+        "multiline" => JsValue::Boolean(flags.multiline),
+        "dotAll" => JsValue::Boolean(flags.dot_all),
+        "unicode" => JsValue::Boolean(flags.unicode),
+        "unicodeSets" => JsValue::Boolean(flags.unicode_sets),
+        "sticky" => JsValue::Boolean(flags.sticky),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Implements `RegExp.prototype.exec`, which returns the array of what the groups captured,
+/// or `null` if there's no match.
+///
+/// A global or sticky regular expression starts at its `lastIndex` and moves it past the
+/// match. Indices are in UTF-16 code units, like in JS.
+fn regexp_exec(this: &JsValue, input: &JsValue) -> JsValue {
+    let regexp = match this {
+        JsValue::Object(obj) => match &obj.borrow().subtype {
+            ObjectSubtype::RegExp(regexp) => regexp.clone(),
+            _ => type_error("RegExp method called on an incompatible receiver"),
+        },
+        _ => type_error("RegExp method called on an incompatible receiver"),
+    };
+    let input = input.to_js_string();
+    let chars = input.as_str().chars().collect::<Vec<_>>();
+    let utf16_index = |index: usize| chars[..index].iter().map(|c| c.len_utf16()).sum::<usize>();
+
+    let uses_last_index = regexp.flags.global || regexp.flags.sticky;
+    let last_index = JsValue::from("lastIndex");
+    let start = match uses_last_index {
+        true => {
+            let JsValue::Number(last_index) = this.get_prop(last_index.clone()).to_number() else {
+                unreachable!()
+            };
+            // `NaN` and negative indices start at the beginning
+            let last_index = last_index.max(0.0);
+            let mut units = 0;
+            let start = chars
+                .iter()
+                .take_while(|c| {
+                    units += c.len_utf16();
+                    units as f64 <= last_index
+                })
+                .count();
+            (last_index <= utf16_index(chars.len()) as f64).then_some(start)
+        }
+        false => Some(0),
+    };
+    let found = start.and_then(|start| match regexp.flags.sticky {
+        true => regexp.match_at(&chars, start),
+        false => (start..=chars.len()).find_map(|start| regexp.match_at(&chars, start)),
+    });
+    let Some(captures) = found else {
+        if uses_last_index {
+            this.set_prop(last_index, JsValue::Number(0.0));
+        }
+        return JsValue::Null;
+    };
+
+    let (start, end) = captures[0].unwrap();
+    if uses_last_index {
+        this.set_prop(last_index, utf16_index(end).into());
+    }
+    let captured = |range: Option<(usize, usize)>| match range {
+        Some((start, end)) => {
+            JsValue::String(JsString::from(chars[start..end].iter().collect::<String>()))
+        }
+        None => JsValue::Undefined,
+    };
+    let result = JsValue::new_array(captures.iter().copied().map(captured).collect());
+    result.set_prop(JsValue::from("index"), utf16_index(start).into());
+    result.set_prop(JsValue::from("input"), JsValue::String(input.clone()));
+    let named_groups = regexp
+        .group_names()
+        .zip(&captures)
+        .filter_map(|(name, range)| Some((JsString::from(name?), captured(*range))))
+        .collect::<Vec<_>>();
+    let groups = match named_groups.is_empty() {
+        true => JsValue::Undefined,
+        false => {
+            let groups = JsValue::from_entries(Vec::new());
+            for (name, value) in named_groups {
+                groups.set_prop(JsValue::String(name), value);
+            }
+            groups
+        }
+    };
+    result.set_prop(JsValue::from("groups"), groups);
+    result
+}
+
+/// The array of the strings of a tagged template, which the tag function receives first.
+///
+/// Its `raw` property is the array of the strings as they are written in the source.
+pub fn template_strings(cooked: &[Option<&str>], raw: &[&str]) -> JsValue {
+    let strings = cooked
+        .iter()
+        .map(|quasi| quasi.map_or(JsValue::Undefined, JsValue::from))
+        .collect();
+    let strings = JsValue::new_array(strings);
+    let raw = raw.iter().map(|quasi| JsValue::from(*quasi)).collect();
+    strings.set_prop(JsValue::from("raw"), JsValue::new_array(raw));
+    strings
+}
+
+#[inline]
+pub fn plus(value: JsValue) -> JsValue {
+    value.to_number()
+}
+
+/// The `!` operator
+#[inline]
+pub fn not(value: JsValue) -> JsValue {
+    JsValue::Boolean(value.falsy())
+}
+
+/// The `~` operator
+#[inline]
+pub fn bit_not(value: JsValue) -> JsValue {
+    JsValue::Number(!value.to_int32() as f64)
+}
+
+/// The `void` operator, the argument is only evaluated for its side effects
+#[inline]
+pub fn void(_value: JsValue) -> JsValue {
+    JsValue::Undefined
+}
+
+thread_local! {
+    /// The value that is being thrown, while the panic that throws it unwinds
+    static THROWN: RefCell<Option<JsValue>> = const { RefCell::new(None) };
+
+    /// The number of `try_block`s that are running, which catch what's thrown
+    static TRY_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The payload of the panic that `throw` unwinds with, the thrown value is in `THROWN`
+struct Exception;
+
+/// How a block of a `try` statement ended, unless it threw
+pub enum Completion {
+    Normal,
+    /// With a `return` of the value
+    Return(JsValue),
+}
+
+/// How a step of a generator ended
+pub enum GeneratorStep {
+    /// At a `yield` of the value, where the generator is suspended
+    Yield(JsValue),
+    /// With a `return` of the value, which finishes the generator
+    Return(JsValue),
+}
+
+/// The object that the `next` method of a generator returns
+fn iterator_result(value: JsValue, done: bool) -> JsValue {
+    JsValue::from_entries(vec![
+        (PropertyKey::from("value"), value),
+        (PropertyKey::from("done"), JsValue::Boolean(done)),
+    ])
+}
+
+/// The values of an object that implements the iterator protocol, which are read by calling
+/// the `next` method of the iterator that its `[Symbol.iterator]()` method returns, until the
+/// result is `done`
+struct ProtocolIterator {
+    iterator: JsValue,
+    next: JsValue,
+    done: bool,
+}
+
+impl ProtocolIterator {
+    fn new(iterable: &JsValue) -> Self {
+        let method = iterable.get_prop(JsValue::Symbol(symbol::ITERATOR.with(JsSymbol::clone)));
+        if !method.is_callable() {
+            type_error(format_args!(
+                "{} is not iterable",
+                iterable.to_js_string().as_str()
+            ));
+        }
+        let iterator = method.call_with_this(iterable, &[]);
+        if !matches!(iterator, JsValue::Object(_)) {
+            type_error("Result of the Symbol.iterator method is not an object");
+        }
+        ProtocolIterator {
+            next: iterator.get_prop(JsValue::from("next")),
+            iterator,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for ProtocolIterator {
+    type Item = JsValue;
+
+    fn next(&mut self) -> Option<JsValue> {
+        if self.done {
+            return None;
+        }
+        // Once `next` throws, the iterator isn't used anymore
+        self.done = true;
+        let result = self.next.call_with_this(&self.iterator, &[]);
+        if !matches!(result, JsValue::Object(_)) {
+            type_error(format_args!(
+                "Iterator result {} is not an object",
+                result.to_js_string().as_str()
+            ));
+        }
+        if result.get_prop(JsValue::from("done")).truthy() {
+            return None;
+        }
+        self.done = false;
+        Some(result.get_prop(JsValue::from("value")))
+    }
+}
+
+impl Drop for ProtocolIterator {
+    /// A loop that stops before the iterator is done, with `break` or `return`, calls its
+    /// `return` method, if it has one, so that it can clean up. This is skipped when the loop
+    /// is left by an exception, as the method can't be called while the exception unwinds.
+    fn drop(&mut self) {
+        if self.done || thread::panicking() {
+            return;
+        }
+        let close = self.iterator.get_prop(JsValue::from("return"));
+        if close.is_callable() {
+            close.call_with_this(&self.iterator, &[]);
+        }
+    }
+}
+
+/// Implements `throw`, by unwinding to the innermost `try_block`
+pub fn throw(value: JsValue) -> ! {
+    if TRY_DEPTH.with(Cell::get) == 0 {
+        panic!("Uncaught {}", uncaught_message(&value));
+    }
+    THROWN.with(|thrown| *thrown.borrow_mut() = Some(value));
+    // Unlike `panic!`, this doesn't print a message, as the exception is going to be caught
+    panic::resume_unwind(Box::new(Exception))
+}
+
+/// Throws a new `TypeError` with the message, like the operations of JS do
+fn type_error(message: impl fmt::Display) -> ! {
+    throw(error::create(error::Kind::TypeError, &message.to_string()))
+}
+
+/// Throws a new `RangeError` with the message, like the operations of JS do
+fn range_error(message: impl fmt::Display) -> ! {
+    throw(error::create(error::Kind::RangeError, &message.to_string()))
+}
+
+/// Throws a new `SyntaxError` with the message, like `JSON.parse` and `RegExp` do
+fn syntax_error(message: impl fmt::Display) -> ! {
+    throw(error::create(
+        error::Kind::SyntaxError,
+        &message.to_string(),
+    ))
+}
+
+/// How the value is shown when it ends the program, with the stack if it's an error
+fn uncaught_message(value: &JsValue) -> String {
+    match error::is_error(value) {
+        true => error::inspect(value),
+        false => value.to_js_string().as_str().to_string(),
+    }
+}
+
+/// Runs a block of a `try` statement, catching the value that it throws.
+///
+/// Panics that aren't JS exceptions, like a `todo!()`, aren't caught.
+pub fn try_block(block: impl FnOnce() -> Completion) -> Result<Completion, JsValue> {
+    TRY_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(block));
+    TRY_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    match result {
+        Ok(completion) => Ok(completion),
+        Err(payload) if payload.is::<Exception>() => {
+            Err(THROWN.with(|thrown| thrown.borrow_mut().take().unwrap()))
+        }
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Finishes a `try` statement once its `catch` and `finally` blocks ran, throwing again what
+/// wasn't caught.
+pub fn complete(result: Result<Completion, JsValue>) -> Completion {
+    match result {
+        Ok(completion) => completion,
+        Err(value) => throw(value),
+    }
+}
+
+thread_local! {
+    /// The jobs that run once the current task is done, like the reactions of promises
+    static MICROTASKS: RefCell<VecDeque<Box<dyn FnOnce()>>> = RefCell::new(VecDeque::new());
+
+    /// The callbacks of `setTimeout` that haven't run yet, in the order they were scheduled in
+    static TIMERS: RefCell<Vec<Timer>> = const { RefCell::new(Vec::new()) };
+
+    /// The id of the last timer, which `clearTimeout` takes
+    static LAST_TIMER_ID: Cell<u32> = const { Cell::new(0) };
+}
+
+/// A callback of `setTimeout`, which is a macrotask
+struct Timer {
+    id: u32,
+    due: Instant,
+    callback: JsValue,
+    args: Vec<JsValue>,
+}
+
+/// Schedules the job to run after the current task, before any timer does
+pub fn queue_microtask(job: impl FnOnce() + 'static) {
+    MICROTASKS.with(|microtasks| microtasks.borrow_mut().push_back(Box::new(job)));
+}
+
+/// Runs the queued jobs, including the ones that they queue
+pub fn run_microtasks() {
+    while let Some(job) = MICROTASKS.with(|microtasks| microtasks.borrow_mut().pop_front()) {
+        job();
+    }
+}
+
+/// Runs the microtasks, then the timers in the order that they're due in, each followed by
+/// the microtasks that it queued, until nothing is scheduled.
+///
+/// The entry function calls it after the statements of the program.
+#[cfg(not(feature = "tokio"))]
+pub fn run_event_loop() {
+    loop {
+        run_microtasks();
+        promise::report_unhandled_rejections();
+        let Some(due) = next_timer_due() else {
+            break;
+        };
+        std::thread::sleep(due.saturating_duration_since(Instant::now()));
+        run_next_timer();
+    }
+}
+
+/// When the timer that runs next is due
+fn next_timer_due() -> Option<Instant> {
+    TIMERS.with(|timers| timers.borrow().iter().map(|timer| timer.due).min())
+}
+
+/// Runs the callback of the timer that is due first, which ones scheduled earlier are if
+/// they're due at the same time
+fn run_next_timer() {
+    let timer = TIMERS.with(|timers| {
+        let mut timers = timers.borrow_mut();
+        let (next, _) = timers
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, timer)| (timer.due, timer.id))?;
+        Some(timers.remove(next))
+    });
+    if let Some(timer) = timer {
+        timer.callback.call(&timer.args);
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use tokio_event_loop::{event_loop, promise_future, run_event_loop, spawn_promise};
+
+#[cfg(feature = "tokio")]
+mod tokio_event_loop {
+    //! The event loop on a tokio runtime, with the `tokio` feature. It waits for the timers
+    //! without blocking the thread, so that the futures of Rust code, like the ones of a
+    //! function that the program calls through an intrinsic, can run in the meantime and
+    //! settle promises, see `spawn_promise`.
+
+    use std::{cell::Cell, future::Future, rc::Rc};
+
+    use tokio::{
+        runtime,
+        sync::{oneshot, Notify},
+        task::LocalSet,
+        time,
+    };
+
+    use super::{
+        arg, next_timer_due, promise, promise_constructor, run_microtasks, run_next_timer, JsValue,
+    };
+
+    thread_local! {
+        /// The tasks of `spawn_promise`, which run while the event loop does
+        static TASKS: Rc<LocalSet> = Rc::new(LocalSet::new());
+
+        /// The number of tasks that haven't finished yet, which keep the event loop running
+        static PENDING_TASKS: Cell<usize> = const { Cell::new(0) };
+
+        /// Wakes up the event loop when a task finished
+        static TASK_FINISHED: Rc<Notify> = Rc::new(Notify::new());
+    }
+
+    /// Runs `event_loop` on a new tokio runtime, blocking the thread until it's done. In
+    /// async code, await `event_loop` instead.
+    ///
+    /// The entry function calls it after the statements of the program.
+    pub fn run_event_loop() {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("failed to start the tokio runtime");
+        runtime.block_on(event_loop());
+    }
+
+    /// Runs the microtasks, and the timers and the tasks of `spawn_promise` as they finish,
+    /// each followed by the microtasks that it queued, until nothing is scheduled.
+    pub async fn event_loop() {
+        let tasks = TASKS.with(Rc::clone);
+        let task_finished = TASK_FINISHED.with(Rc::clone);
+        tasks
+            .run_until(async {
+                loop {
+                    run_microtasks();
+                    promise::report_unhandled_rejections();
+                    let due = next_timer_due();
+                    if due.is_none() && PENDING_TASKS.with(Cell::get) == 0 {
+                        break;
+                    }
+                    let task_finished = task_finished.notified();
+                    match due {
+                        Some(due) => {
+                            let due = time::Instant::from_std(due);
+                            if time::timeout_at(due, task_finished).await.is_err() {
+                                run_next_timer();
+                            }
+                        }
+                        None => task_finished.await,
+                    }
+                }
+            })
+            .await
+    }
+
+    /// A promise that is settled with the result of the future, which runs as a task of the
+    /// event loop. The promise is fulfilled with the `Ok` value, or rejected with the `Err`
+    /// reason.
+    ///
+    /// The event loop keeps running until the future is done, even if it waits for a promise
+    /// that is never settled.
+    pub fn spawn_promise(
+        future: impl Future<Output = Result<JsValue, JsValue>> + 'static,
+    ) -> JsValue {
+        let (promise, resolve, reject) = promise::with_resolvers();
+        PENDING_TASKS.with(|pending| pending.set(pending.get() + 1));
+        TASKS.with(|tasks| {
+            tasks.spawn_local(async move {
+                match future.await {
+                    Ok(value) => resolve.call(&[value]),
+                    Err(reason) => reject.call(&[reason]),
+                };
+                PENDING_TASKS.with(|pending| pending.set(pending.get() - 1));
+                TASK_FINISHED.with(|task_finished| task_finished.notify_one());
+            })
+        });
+        promise
+    }
+
+    /// Waits for the value like `await` does, returning the value that the promise is
+    /// fulfilled with as `Ok`, or the reason it's rejected with as `Err`. Values that aren't
+    /// promises are returned as they are.
+    ///
+    /// The microtasks of the promise only run while the event loop does, so a task of
+    /// `spawn_promise` can wait for a promise, but a future that blocks on the event loop
+    /// can't.
+    pub async fn promise_future(value: JsValue) -> Result<JsValue, JsValue> {
+        let (sender, receiver) = oneshot::channel();
+        let sender = Rc::new(Cell::new(Some(sender)));
+        let settle = |result: fn(JsValue) -> Result<JsValue, JsValue>| {
+            let sender = sender.clone();
+            JsValue::new_function(Box::new(move |args| {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(result(arg(args, 0)));
+                }
+                JsValue::Undefined
+            }))
+        };
+        let (on_fulfilled, on_rejected) = (settle(Ok), settle(Err));
+        let promise = promise_constructor().call_method(JsValue::from("resolve"), &[value]);
+        promise.call_method(JsValue::from("then"), &[on_fulfilled, on_rejected]);
+        receiver
+            .await
+            .expect("the promise is dropped before it's settled")
+    }
+}
+
+/// Implements `setTimeout(callback, delay, ...args)`, where the delay is in milliseconds, and
+/// at least 1 like in node
+fn set_timeout(args: &[JsValue]) -> JsValue {
+    let callback = arg(args, 0);
+    if !callback.is_callable() {
+        type_error(format_args!(
+            "The \"callback\" argument must be of type function, not {}",
+            callback.to_js_string().as_str()
+        ));
+    }
+    let JsValue::Number(delay) = arg(args, 1).to_number() else {
+        unreachable!()
+    };
+    let delay = match delay >= 1.0 && delay <= i32::MAX as f64 {
+        true => delay,
+        false => 1.0,
+    };
+    let id = LAST_TIMER_ID.with(|id| {
+        id.set(id.get() + 1);
+        id.get()
+    });
+    let timer = Timer {
+        id,
+        due: Instant::now() + Duration::from_secs_f64(delay / 1000.0),
+        callback,
+        args: args.get(2..).unwrap_or_default().to_vec(),
+    };
+    TIMERS.with(|timers| timers.borrow_mut().push(timer));
+    JsValue::from(id as f64)
+}
+
+/// Implements `clearTimeout(id)`, which ignores the ids of the timers that already ran
+fn clear_timeout(args: &[JsValue]) -> JsValue {
+    let JsValue::Number(id) = arg(args, 0).to_number() else {
+        unreachable!()
+    };
+    TIMERS.with(|timers| timers.borrow_mut().retain(|timer| timer.id as f64 != id));
+    JsValue::Undefined
+}
+
+/// Implements `queueMicrotask(callback)`
+fn queue_microtask_callback(args: &[JsValue]) -> JsValue {
+    let callback = arg(args, 0);
+    if !callback.is_callable() {
+        type_error(format_args!(
+            "The \"callback\" argument must be of type function, not {}",
+            callback.to_js_string().as_str()
+        ));
+    }
+    queue_microtask(move || {
+        callback.call(&[]);
+    });
+    JsValue::Undefined
+}
+
+// ----------------------------------------------------------
+// END OF PRELUDE
+// ----------------------------------------------------------
@@ -0,0 +1,25 @@
+//! The helpers that the tests share. Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use jsrs_runtime::*;
+
+pub fn text(value: &JsValue) -> String {
+    value.to_js_string().as_str().to_owned()
+}
+
+pub fn number(value: &JsValue) -> f64 {
+    match value {
+        JsValue::Number(number) => *number,
+        _ => panic!("{} is not a number", text(value)),
+    }
+}
+
+/// What the action throws, which it must
+pub fn thrown(action: impl FnOnce()) -> JsValue {
+    try_block(|| {
+        action();
+        Completion::Normal
+    })
+    .err()
+    .expect("nothing was thrown")
+}
@@ -0,0 +1,101 @@
+//! `Date`, only through its UTC methods, so that the tests don't depend on the time zone.
+
+use jsrs_runtime::*;
+
+use common::text;
+
+mod common;
+
+fn date(args: &[JsValue]) -> JsValue {
+    date_constructor().construct(args)
+}
+
+fn call(date: &JsValue, method: &str, args: &[JsValue]) -> JsValue {
+    date.call_method(method.into(), args)
+}
+
+#[test]
+fn from_time() {
+    let epoch = date(&[0.0.into()]);
+    assert_eq!(
+        text(&call(&epoch, "toISOString", &[])),
+        "1970-01-01T00:00:00.000Z"
+    );
+    assert_eq!(
+        text(&call(&epoch, "toUTCString", &[])),
+        "Thu, 01 Jan 1970 00:00:00 GMT"
+    );
+    let before = date(&[(-1.0).into()]);
+    assert_eq!(
+        text(&call(&before, "toISOString", &[])),
+        "1969-12-31T23:59:59.999Z"
+    );
+}
+
+#[test]
+fn utc_fields() {
+    let utc = date_constructor().get_prop("UTC".into());
+    let time = utc.call(&[
+        2024.0.into(),
+        1.0.into(),
+        29.0.into(),
+        13.0.into(),
+        5.0.into(),
+    ]);
+    assert_eq!(text(&time), "1709211900000");
+
+    let leap_day = date(&[time]);
+    let field = |method| text(&call(&leap_day, method, &[]));
+    assert_eq!(field("getUTCFullYear"), "2024");
+    assert_eq!(field("getUTCMonth"), "1");
+    assert_eq!(field("getUTCDate"), "29");
+    assert_eq!(field("getUTCDay"), "4");
+    assert_eq!(field("getUTCHours"), "13");
+    assert_eq!(field("getUTCMinutes"), "5");
+
+    // Setting a field past its range carries over into the next one
+    call(&leap_day, "setUTCDate", &[31.0.into()]);
+    assert_eq!(
+        text(&call(&leap_day, "toISOString", &[])),
+        "2024-03-02T13:05:00.000Z"
+    );
+}
+
+#[test]
+fn parse_strings() {
+    let parse = date_constructor().get_prop("parse".into());
+    let cases = [
+        ("2020-02-29T12:34:56.789Z", "1582979696789"),
+        ("2020-02-29", "1582934400000"),
+        ("not a date", "NaN"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(text(&parse.call(&[source.into()])), expected, "{source:?}");
+    }
+    let parsed = date(&["2020-02-29T12:34:56.789Z".into()]);
+    assert_eq!(text(&call(&parsed, "getUTCMilliseconds", &[])), "789");
+}
+
+#[test]
+fn invalid_date() {
+    let invalid = date(&[f64::NAN.into()]);
+    assert_eq!(text(&call(&invalid, "getTime", &[])), "NaN");
+    assert_eq!(text(&call(&invalid, "toString", &[])), "Invalid Date");
+    let error = try_block(|| {
+        call(&invalid, "toISOString", &[]);
+        Completion::Normal
+    })
+    .err()
+    .unwrap();
+    assert!(error.instance_of(range_error_constructor()).truthy());
+
+    let out_of_range = date(&[8.64e15.into()]);
+    assert_eq!(
+        text(&call(&out_of_range, "getTime", &[])),
+        "8640000000000000"
+    );
+    assert_eq!(
+        text(&call(&date(&[(8.64e15 + 1.0).into()]), "getTime", &[])),
+        "NaN"
+    );
+}
@@ -0,0 +1,89 @@
+//! Throwing and catching, and the errors that the operations throw.
+
+use std::panic;
+
+use jsrs_runtime::*;
+
+use common::{text, thrown};
+
+mod common;
+
+#[test]
+fn catch_thrown_value() {
+    assert_eq!(text(&thrown(|| throw("plain".into()))), "plain");
+    let result = try_block(|| Completion::Return(1.0.into()));
+    assert!(matches!(result, Ok(Completion::Return(JsValue::Number(number))) if number == 1.0));
+}
+
+#[test]
+fn rethrow_from_nested_try() {
+    let error = thrown(|| {
+        let inner = try_block(|| throw(2.0.into()));
+        complete(inner);
+    });
+    assert_eq!(text(&error), "2");
+
+    // The outer block still catches after an inner one caught its own exception
+    let error = thrown(|| {
+        let _ = try_block(|| throw("inner".into()));
+        throw("outer".into());
+    });
+    assert_eq!(text(&error), "outer");
+}
+
+#[test]
+fn errors_of_operations() {
+    let error = thrown(|| {
+        JsValue::Undefined.get_prop("length".into());
+    });
+    assert!(error.instance_of(type_error_constructor()).truthy());
+    assert_eq!(
+        text(&error),
+        "TypeError: Cannot read properties of undefined (reading 'length')"
+    );
+
+    let error = thrown(|| {
+        JsValue::Null.set_prop("name".into(), 1.0.into());
+    });
+    assert_eq!(
+        text(&error),
+        "TypeError: Cannot set properties of null (setting 'name')"
+    );
+
+    let error = thrown(|| {
+        JsValue::from(5.0).call(&[]);
+    });
+    assert_eq!(text(&error), "TypeError: 5 is not a function");
+
+    let error = thrown(|| {
+        JsValue::from("ab").call_method("repeat".into(), &[(-1.0).into()]);
+    });
+    assert!(error.instance_of(range_error_constructor()).truthy());
+    assert!(error.instance_of(error_constructor()).truthy());
+    assert!(!error.instance_of(type_error_constructor()).truthy());
+}
+
+#[test]
+fn error_objects() {
+    let error = type_error_constructor().construct(&["bad value".into()]);
+    assert_eq!(text(&error.get_prop("name".into())), "TypeError");
+    assert_eq!(text(&error.get_prop("message".into())), "bad value");
+    assert!(text(&error.get_prop("stack".into())).starts_with("TypeError: bad value"));
+    assert_eq!(
+        text(&thrown(|| throw(error.clone()))),
+        "TypeError: bad value"
+    );
+}
+
+#[test]
+fn other_panics_are_not_caught() {
+    let result = panic::catch_unwind(|| try_block(|| panic!("not an exception")));
+    let payload = result.err().unwrap();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"not an exception"));
+}
+
+#[test]
+#[should_panic(expected = "Uncaught [RangeError: out of range]")]
+fn uncaught_exception() {
+    throw(range_error_constructor().construct(&["out of range".into()]));
+}
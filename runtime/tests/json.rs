@@ -0,0 +1,110 @@
+//! `JSON.parse` and `JSON.stringify`.
+
+use jsrs_runtime::*;
+
+use common::text;
+
+mod common;
+
+fn parse(text: &str) -> JsValue {
+    json().parse.call(&[text.into()])
+}
+
+fn stringify(value: JsValue) -> JsValue {
+    json().stringify.call(&[value])
+}
+
+#[test]
+fn round_trip() {
+    let source = r#" { "a": [1, 2.5, { "b": null }], "c": "A\n", "d": true, "e": -1e3 } "#;
+    assert_eq!(
+        text(&stringify(parse(source))),
+        r#"{"a":[1,2.5,{"b":null}],"c":"A\n","d":true,"e":-1000}"#
+    );
+}
+
+#[test]
+fn parse_values() {
+    let value = parse(r#"{"list": [10, "x"], "nested": {"deep": false}}"#);
+    let list = value.get_prop("list".into());
+    assert_eq!(text(&list.get_prop("length".into())), "2");
+    assert_eq!(text(&list.get_prop(1.0.into())), "x");
+    assert!(value
+        .get_prop("nested".into())
+        .get_prop("deep".into())
+        .falsy());
+    assert_eq!(text(&parse("\"\\ud83d\\ude00\"")), "😀");
+}
+
+#[test]
+fn parse_reviver() {
+    let double = JsValue::new_function(Box::new(|args| match arg(args, 1) {
+        JsValue::Number(number) => JsValue::Number(number * 2.0),
+        value => value,
+    }));
+    let value = json().parse.call(&["[1, [2]]".into(), double]);
+    assert_eq!(text(&stringify(value)), "[2,[4]]");
+}
+
+#[test]
+fn stringify_values() {
+    let object = JsValue::from_entries(vec![
+        ("skipped".into(), JsValue::Undefined),
+        (
+            "method".into(),
+            JsValue::new_function(Box::new(|_| JsValue::Undefined)),
+        ),
+        ("nan".into(), f64::NAN.into()),
+        ("quote".into(), "say \"hi\"\t".into()),
+    ]);
+    assert_eq!(
+        text(&stringify(object)),
+        r#"{"nan":null,"quote":"say \"hi\"\t"}"#
+    );
+    assert!(stringify(JsValue::Undefined).is_undefined());
+    assert_eq!(
+        text(&stringify(JsValue::new_array(vec![JsValue::Undefined]))),
+        "[null]"
+    );
+}
+
+#[test]
+fn stringify_indented() {
+    let value = parse(r#"{"a": [1, {}], "b": []}"#);
+    let indented = json()
+        .stringify
+        .call(&[value, JsValue::Undefined, 2.0.into()]);
+    assert_eq!(
+        text(&indented),
+        "{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": []\n}"
+    );
+}
+
+#[test]
+fn invalid_text() {
+    for source in ["{ broken", "[1,]", "'single'", ""] {
+        let error = try_block(|| {
+            parse(source);
+            Completion::Normal
+        })
+        .err()
+        .unwrap_or_else(|| panic!("{source:?} was parsed"));
+        assert!(
+            error.instance_of(syntax_error_constructor()).truthy(),
+            "{source:?}"
+        );
+    }
+}
+
+#[test]
+fn cyclic_value() {
+    let object = JsValue::from_entries(Vec::new());
+    object.set_prop("itself".into(), object.clone());
+    let error = try_block(|| {
+        stringify(object.clone());
+        Completion::Normal
+    })
+    .err()
+    .unwrap();
+    assert!(error.instance_of(type_error_constructor()).truthy());
+}
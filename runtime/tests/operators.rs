@@ -0,0 +1,129 @@
+//! The JS operators and how numbers are turned into strings.
+
+use jsrs_runtime::*;
+
+use common::{number, text};
+
+mod common;
+
+fn boolean(value: &JsValue) -> bool {
+    match value {
+        JsValue::Boolean(boolean) => *boolean,
+        _ => panic!("{} is not a boolean", text(value)),
+    }
+}
+
+fn array(elements: &[f64]) -> JsValue {
+    JsValue::new_array(elements.iter().map(|&element| element.into()).collect())
+}
+
+#[test]
+fn add() {
+    assert_eq!(number(&JsValue::from(1.0).add(2.0.into())), 3.0);
+    assert_eq!(text(&JsValue::from("a").add(1.0.into())), "a1");
+    assert_eq!(text(&JsValue::from(1.0).add("2".into())), "12");
+    assert_eq!(text(&array(&[1.0, 2.0]).add("".into())), "1,2");
+    assert_eq!(number(&JsValue::Null.add(1.0.into())), 1.0);
+    assert_eq!(number(&JsValue::Boolean(true).add(1.0.into())), 2.0);
+    assert!(number(&JsValue::Undefined.add(1.0.into())).is_nan());
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(number(&JsValue::from("5").sub(2.0.into())), 3.0);
+    assert_eq!(number(&JsValue::from("3").mult("4".into())), 12.0);
+    assert_eq!(
+        number(&JsValue::from(1.0).divide(0.0.into())),
+        f64::INFINITY
+    );
+    assert_eq!(number(&JsValue::from(-5.0).rem(3.0.into())), -2.0);
+    assert_eq!(number(&JsValue::from(5.5).rem(2.0.into())), 1.5);
+    assert_eq!(number(&JsValue::from(2.0).pow(10.0.into())), 1024.0);
+    assert!(number(&JsValue::from(1.0).pow(f64::INFINITY.into())).is_nan());
+    assert_eq!(number(&negate("3".into())), -3.0);
+    assert_eq!(number(&plus("".into())), 0.0);
+    assert!(number(&plus("1x".into())).is_nan());
+}
+
+#[test]
+fn bitwise() {
+    assert_eq!(number(&JsValue::from(5.0).bit_xor(3.0.into())), 6.0);
+    assert_eq!(number(&JsValue::from(6.0).bit_and(3.0.into())), 2.0);
+    assert_eq!(number(&JsValue::from(4.0).bit_or(1.0.into())), 5.0);
+    assert_eq!(number(&bit_not(0.0.into())), -1.0);
+    assert_eq!(
+        number(&JsValue::from(1.0).shift_left(31.0.into())),
+        -2147483648.0
+    );
+    assert_eq!(number(&JsValue::from(1.0).shift_left(33.0.into())), 2.0);
+    assert_eq!(number(&JsValue::from(-16.0).shift_right(2.0.into())), -4.0);
+    assert_eq!(
+        number(&JsValue::from(-1.0).shift_right_unsigned(0.0.into())),
+        4294967295.0
+    );
+    assert_eq!(number(&JsValue::from(4294967296.5).bit_or(0.0.into())), 0.0);
+}
+
+#[test]
+fn equality() {
+    assert!(boolean(&JsValue::Null.loose_equals(JsValue::Undefined)));
+    assert!(boolean(&JsValue::from("1").loose_equals(1.0.into())));
+    assert!(boolean(&JsValue::Boolean(true).loose_equals("1".into())));
+    assert!(!boolean(&JsValue::Null.loose_equals(0.0.into())));
+    assert!(!boolean(&JsValue::from("1").strict_equals(1.0.into())));
+    assert!(!boolean(
+        &JsValue::from(f64::NAN).strict_equals(f64::NAN.into())
+    ));
+    assert!(boolean(&JsValue::from(0.0).strict_equals((-0.0).into())));
+
+    let elements = array(&[]);
+    assert!(boolean(&elements.strict_equals(elements.clone())));
+    assert!(!boolean(&elements.strict_equals(array(&[]))));
+    assert!(boolean(&elements.loose_equals("".into())));
+}
+
+#[test]
+fn relational() {
+    assert!(boolean(&JsValue::from("10").less("9".into())));
+    assert!(!boolean(&JsValue::from("10").less(9.0.into())));
+    assert!(boolean(&JsValue::from("b").greater("a".into())));
+    assert!(boolean(&JsValue::Null.greater_equal(0.0.into())));
+    assert!(!boolean(&JsValue::Undefined.less_equal(0.0.into())));
+    assert!(!boolean(
+        &JsValue::from(f64::NAN).greater_equal(f64::NAN.into())
+    ));
+}
+
+#[test]
+fn number_to_string() {
+    let cases = [
+        (0.1 + 0.2, "0.30000000000000004"),
+        (-0.0, "0"),
+        (100.0, "100"),
+        (1e21, "1e+21"),
+        (123456789012345680000.0, "123456789012345680000"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (-1.5e-10, "-1.5e-10"),
+        (2f64.powi(53), "9007199254740992"),
+        (f64::NAN, "NaN"),
+        (f64::NEG_INFINITY, "-Infinity"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(text(&value.into()), expected, "{value:?}");
+    }
+}
+
+#[test]
+fn number_methods() {
+    let call = |value: f64, method: &str, args: &[JsValue]| {
+        text(&JsValue::from(value).call_method(method.into(), args))
+    };
+    assert_eq!(call(1234.5678, "toFixed", &[2.0.into()]), "1234.57");
+    assert_eq!(call(1.005, "toFixed", &[2.0.into()]), "1.00");
+    assert_eq!(call(-1.5, "toFixed", &[]), "-2");
+    assert_eq!(call(1e21, "toFixed", &[2.0.into()]), "1e+21");
+    assert_eq!(call(255.0, "toString", &[2.0.into()]), "11111111");
+    assert_eq!(call(255.0, "toString", &[16.0.into()]), "ff");
+    assert_eq!(call(-0.5, "toString", &[16.0.into()]), "-0.8");
+}
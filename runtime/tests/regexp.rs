@@ -0,0 +1,159 @@
+//! Matching with the regular expression engine of the runtime, and how `exec` and `lastIndex`
+//! step through the matches of the global ones.
+
+use jsrs_runtime::*;
+
+use common::{number, text, thrown};
+
+mod common;
+
+/// The match and its groups joined by commas, or `null` if there's no match
+fn exec(regexp: &JsValue, input: &str) -> String {
+    text(&regexp.call_method("exec".into(), &[input.into()]))
+}
+
+fn matches(pattern: &str, flags: &str, input: &str) -> String {
+    exec(&JsValue::new_regexp(pattern, flags), input)
+}
+
+/// What creating the regular expression throws, which it must
+fn invalid(pattern: &str, flags: &str) -> JsValue {
+    thrown(|| {
+        JsValue::new_regexp(pattern, flags);
+    })
+}
+
+fn last_index(regexp: &JsValue) -> f64 {
+    number(&regexp.get_prop("lastIndex".into()))
+}
+
+#[test]
+fn backtracking() {
+    assert_eq!(matches("(a+)+b", "", "aaab"), "aaab,aaa");
+    assert_eq!(matches("a*ab", "", "aaab"), "aaab");
+    assert_eq!(matches("(ab|a)bc", "", "abc"), "abc,a");
+    assert_eq!(matches("(a|ab)(c|bcd)(d*)", "", "abcd"), "abcd,a,bcd,");
+    assert_eq!(matches("<.+>", "", "<a><b>"), "<a><b>");
+    assert_eq!(matches("<.+?>", "", "<a><b>"), "<a>");
+    assert_eq!(
+        matches("^(\\w+)\\s(\\w+)$", "", "hello world"),
+        "hello world,hello,world"
+    );
+    assert_eq!(matches("x{2,3}y", "", "xxxxy"), "xxxy");
+    assert_eq!(matches("(a+)+b", "", "aaaa"), "null");
+}
+
+#[test]
+fn backreferences_and_lookahead() {
+    assert_eq!(matches("(\\w)\\1", "", "hello"), "ll,l");
+    assert_eq!(
+        matches("(?<quote>['\"]).*?\\k<quote>", "", "say 'hi\" there'"),
+        "'hi\" there','"
+    );
+    assert_eq!(matches("\\d+(?=px)", "", "10em 20px"), "20");
+    assert_eq!(matches("\\d+(?!\\d|em)", "", "10em 20px"), "20");
+}
+
+#[test]
+fn unsupported_and_invalid_patterns() {
+    // Lookbehind isn't supported, which is a SyntaxError rather than a wrong match
+    let error = invalid("(?<=\\$)\\d+", "");
+    assert_eq!(text(&error.get_prop("name".into())), "SyntaxError");
+    assert_eq!(
+        text(&invalid("(a", "").get_prop("name".into())),
+        "SyntaxError"
+    );
+    assert_eq!(
+        text(&invalid("a", "gg").get_prop("name".into())),
+        "SyntaxError"
+    );
+}
+
+#[test]
+fn character_classes() {
+    assert_eq!(matches("[a-c]+", "", "xxabcabd"), "abcab");
+    assert_eq!(matches("[^\\d\\s]+", "", "12 ab3"), "ab");
+    assert_eq!(matches("[\\w-]+", "", "  foo-bar baz"), "foo-bar");
+    assert_eq!(matches("[.]", "", "a.b"), ".");
+    assert_eq!(matches("\\bcat\\b", "", "concat cat"), "cat");
+    assert_eq!(matches("\\D\\W\\S", "", "1a!b"), "a!b");
+    assert_eq!(matches("[\\]a]+", "", "x]a]y"), "]a]");
+    assert_eq!(matches("a.c", "", "a\nc"), "null");
+    assert_eq!(matches("a.c", "s", "a\nc"), "a\nc");
+}
+
+#[test]
+fn ignore_case() {
+    assert_eq!(matches("HELLO", "i", "say hello"), "hello");
+    assert_eq!(matches("[a-z]+", "i", "ABC def"), "ABC");
+    assert_eq!(matches("(a)\\1", "i", "aA"), "aA,a");
+    assert_eq!(matches("HELLO", "", "say hello"), "null");
+}
+
+#[test]
+fn multiline() {
+    assert_eq!(matches("^b$", "m", "a\nb\nc"), "b");
+    assert_eq!(matches("^b$", "", "a\nb\nc"), "null");
+    assert_eq!(matches("c$", "", "a\nb\nc"), "c");
+}
+
+#[test]
+fn global_exec_steps_through_matches() {
+    let regexp = JsValue::new_regexp("o+", "g");
+    let mut found = Vec::new();
+    loop {
+        let result = regexp.call_method("exec".into(), &["foo boo o".into()]);
+        if matches!(result, JsValue::Null) {
+            break;
+        }
+        let index = number(&result.get_prop("index".into()));
+        found.push((text(&result), index, last_index(&regexp)));
+    }
+    assert_eq!(
+        found,
+        [
+            ("oo".to_owned(), 1.0, 3.0),
+            ("oo".to_owned(), 5.0, 7.0),
+            ("o".to_owned(), 8.0, 9.0),
+        ]
+    );
+    // The failed match starts it over
+    assert_eq!(last_index(&regexp), 0.0);
+    assert_eq!(exec(&regexp, "foo boo o"), "oo");
+}
+
+#[test]
+fn last_index_is_where_the_search_starts() {
+    let regexp = JsValue::new_regexp("\\d", "g");
+    regexp.set_prop("lastIndex".into(), 3.0.into());
+    assert_eq!(exec(&regexp, "1a2b3c"), "3");
+    assert_eq!(last_index(&regexp), 5.0);
+    regexp.set_prop("lastIndex".into(), 10.0.into());
+    assert_eq!(exec(&regexp, "1a2b3c"), "null");
+    assert_eq!(last_index(&regexp), 0.0);
+
+    // Without the global flag it's ignored and left alone
+    let regexp = JsValue::new_regexp("\\d", "");
+    regexp.set_prop("lastIndex".into(), 3.0.into());
+    assert_eq!(exec(&regexp, "1a2b3c"), "1");
+    assert_eq!(last_index(&regexp), 3.0);
+
+    // Sticky matches only right at it
+    let regexp = JsValue::new_regexp("\\d", "y");
+    regexp.set_prop("lastIndex".into(), 1.0.into());
+    assert_eq!(exec(&regexp, "1a2b3c"), "null");
+    regexp.set_prop("lastIndex".into(), 2.0.into());
+    assert_eq!(exec(&regexp, "1a2b3c"), "2");
+    assert_eq!(last_index(&regexp), 3.0);
+}
+
+#[test]
+fn test_advances_global_regexps() {
+    let regexp = JsValue::new_regexp("a", "gi");
+    let test = |input: &str| regexp.call_method("test".into(), &[input.into()]);
+    assert!(matches!(test("bAa"), JsValue::Boolean(true)));
+    assert_eq!(last_index(&regexp), 2.0);
+    assert!(matches!(test("bAa"), JsValue::Boolean(true)));
+    assert!(matches!(test("bAa"), JsValue::Boolean(false)));
+    assert_eq!(last_index(&regexp), 0.0);
+}
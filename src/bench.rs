@@ -0,0 +1,139 @@
+//! The `bench` subcommand, comparing the compiled benchmark scripts against node.
+
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::Path,
+    process::{self, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use jsrs::Transpiler;
+
+use crate::{native, transpile_to_rust};
+
+struct Benchmark {
+    name: &'static str,
+    /// The script, relative to the directory of the jsrs crate, so that it's found wherever
+    /// jsrs is run from
+    path: &'static str,
+    args: &'static [&'static str],
+}
+
+/// The benchmark scripts bundled in `misc`, with the arguments they're run with.
+const BENCHMARKS: &[Benchmark] = &[Benchmark {
+    name: "n-body",
+    path: "misc/n-body.js",
+    args: &["50000"],
+}];
+
+/// Runs the benchmarks whose name is in `names`, or all of them if it's empty, and prints
+/// a table of the timings.
+///
+/// Returns whether all the benchmarks ran successfully.
+pub fn run(transpiler: &Transpiler, names: &[String]) -> bool {
+    if let Some(name) = names
+        .iter()
+        .find(|name| !BENCHMARKS.iter().any(|bench| bench.name == name.as_str()))
+    {
+        eprintln!("error: unknown benchmark `{name}`");
+        return false;
+    }
+
+    let dir = env::temp_dir().join(format!("jsrs-bench-{}", process::id()));
+    let mut rows = Vec::new();
+    let mut success = true;
+    for bench in BENCHMARKS {
+        if !names.is_empty() && !names.iter().any(|name| name == bench.name) {
+            continue;
+        }
+
+        eprintln!("[bench] {}", bench.name);
+        match run_benchmark(transpiler, bench, &dir.join(bench.name)) {
+            Ok(row) => rows.push(row),
+            Err(error) => {
+                eprintln!("error: {} failed: {error}", bench.name);
+                success = false;
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+
+    print_table(&rows);
+    success
+}
+
+struct Row {
+    name: &'static str,
+    args: String,
+    jsrs: Duration,
+    /// `None` if node isn't installed
+    node: Option<Duration>,
+    /// Whether node printed something different
+    mismatch: bool,
+}
+
+fn run_benchmark(transpiler: &Transpiler, bench: &Benchmark, dir: &Path) -> Result<Row, String> {
+    let script_path = Path::new(env!("CARGO_MANIFEST_DIR")).join(bench.path);
+    let rust_text = transpile_to_rust(transpiler, &script_path)?;
+    let executable_path = native::compile(&rust_text, dir, true)?;
+
+    let (jsrs, jsrs_output) = time_command(Command::new(&executable_path).args(bench.args))?
+        .ok_or("the compiled program could not be started")?;
+    let node = time_command(Command::new("node").arg(&script_path).args(bench.args))?;
+
+    Ok(Row {
+        name: bench.name,
+        args: bench.args.join(" "),
+        jsrs,
+        node: node.as_ref().map(|(duration, _)| *duration),
+        mismatch: node.is_some_and(|(_, node_output)| node_output != jsrs_output),
+    })
+}
+
+/// Runs the command to completion, returning how long it took and what it printed.
+///
+/// Returns `None` if the program doesn't exist.
+fn time_command(command: &mut Command) -> Result<Option<(Duration, Vec<u8>)>, String> {
+    let start = Instant::now();
+    let output = match command.stderr(Stdio::inherit()).output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("failed to start {command:?}: {err}")),
+    };
+    let duration = start.elapsed();
+
+    if !output.status.success() {
+        return Err(format!("{command:?} failed ({})", output.status));
+    }
+    Ok(Some((duration, output.stdout)))
+}
+
+fn print_table(rows: &[Row]) {
+    println!(
+        "{:<12} {:<10} {:>10} {:>10} {:>8}",
+        "benchmark", "args", "jsrs", "node", "ratio"
+    );
+    for row in rows {
+        let (node, ratio) = match row.node {
+            Some(node) => (
+                format!("{:.3}s", node.as_secs_f64()),
+                format!("{:.2}x", row.jsrs.as_secs_f64() / node.as_secs_f64()),
+            ),
+            None => (String::from("n/a"), String::from("n/a")),
+        };
+        println!(
+            "{:<12} {:<10} {:>10} {:>10} {:>8}{}",
+            row.name,
+            row.args,
+            format!("{:.3}s", row.jsrs.as_secs_f64()),
+            node,
+            ratio,
+            if row.mismatch {
+                "  (output differs from node)"
+            } else {
+                ""
+            }
+        );
+    }
+}
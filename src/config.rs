@@ -0,0 +1,250 @@
+//! Loading of `jsrs.toml` configuration files.
+//!
+//! Only the subset of TOML that the configuration needs is supported: `key = value` pairs
+//! with string, boolean and string array values, `[table]` headers and comments.
+//!
+//! ```toml
+//! entry = ["src/main.js", "src/worker.js"]
+//! output = "out"
+//!
+//! [runtime]
+//! # "panic" aborts the transpilation, "todo" emits `todo!()` in place of the unsupported code
+//! on_unsupported = "todo"
+//! # Whether `--run` compiles the generated code with `rustc -O`. The generated code is the
+//! # same either way, its IR is always optimized
+//! optimize = true
+//! # "crate" imports the `jsrs-runtime` crate, "inline" places the runtime in the generated code
+//! mode = "inline"
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use jsrs::{OnUnsupported, Runtime};
+
+pub const FILE_NAME: &str = "jsrs.toml";
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub entry: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub on_unsupported: Option<OnUnsupported>,
+    /// Whether `--run` compiles with optimizations, which doesn't change the generated code
+    pub optimize: Option<bool>,
+    pub runtime: Option<Runtime>,
+}
+
+impl Config {
+    /// Loads the configuration file. Relative paths in it are resolved relative to
+    /// the directory of the file.
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Config::parse(&text, base_dir).map_err(|err| format!("{}: {err}", path.display()))
+    }
+
+    fn parse(text: &str, base_dir: &Path) -> Result<Config, String> {
+        let mut config = Config::default();
+        for (key, value) in parse_toml(text)? {
+            match (key.as_str(), value) {
+                ("entry", Value::String(entry)) => config.entry = vec![base_dir.join(entry)],
+                ("entry", Value::Array(entries)) => {
+                    config.entry = entries.iter().map(|entry| base_dir.join(entry)).collect()
+                }
+                ("output", Value::String(output)) => config.output = Some(base_dir.join(output)),
+                ("runtime.on_unsupported", Value::String(mode)) => {
+                    config.on_unsupported = Some(mode.parse()?)
+                }
+                ("runtime.optimize", Value::Boolean(optimize)) => config.optimize = Some(optimize),
+                ("runtime.mode", Value::String(mode)) => config.runtime = Some(mode.parse()?),
+                (
+                    "entry"
+                    | "output"
+                    | "runtime.on_unsupported"
+                    | "runtime.optimize"
+                    | "runtime.mode",
+                    value,
+                ) => return Err(format!("unexpected value for `{key}`: {value:?}")),
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[derive(Debug)]
+enum Value {
+    String(String),
+    Boolean(bool),
+    Array(Vec<String>),
+}
+
+/// Parses the supported subset of TOML into a map from dotted keys (`table.key`) to values.
+fn parse_toml(text: &str) -> Result<HashMap<String, Value>, String> {
+    let mut values = HashMap::new();
+    let mut table = String::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {line_number}: expected `key = value`"));
+        };
+        let key = if table.is_empty() {
+            key.trim().to_string()
+        } else {
+            format!("{table}.{}", key.trim())
+        };
+        let value =
+            parse_value(value.trim()).map_err(|err| format!("line {line_number}: {err}"))?;
+        if values.insert(key.clone(), value).is_some() {
+            return Err(format!("line {line_number}: duplicate key `{key}`"));
+        }
+    }
+    Ok(values)
+}
+
+fn strip_comment(line: &str) -> &str {
+    match find_outside_strings(line, '#').next() {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+/// The indices of the `target` characters in `text` that aren't part of a string
+fn find_outside_strings(text: &str, target: char) -> impl Iterator<Item = usize> + '_ {
+    let mut in_string = false;
+    let mut escaped = false;
+    text.char_indices().filter_map(move |(index, c)| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if c == target && !in_string => return Some(index),
+            _ => (),
+        }
+        None
+    })
+}
+
+/// Splits the items of an array at the commas between them, leaving the ones in strings alone
+fn split_items(items: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    for index in find_outside_strings(items, ',') {
+        result.push(&items[start..index]);
+        start = index + 1;
+    }
+    result.push(&items[start..]);
+    result
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    match text {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => (),
+    }
+    if text.starts_with('"') {
+        return parse_string(text).map(Value::String);
+    }
+    if let Some(items) = text
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return split_items(items)
+            .into_iter()
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(parse_string)
+            .collect::<Result<_, _>>()
+            .map(Value::Array);
+    }
+    Err(format!("unsupported value `{text}`"))
+}
+
+fn parse_string(text: &str) -> Result<String, String> {
+    let Some(contents) = text
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return Err(format!("expected a string, found `{text}`"));
+    };
+
+    let mut result = String::with_capacity(contents.len());
+    let mut chars = contents.chars();
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            return Err(format!("expected a single string, found `{text}`"));
+        }
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            other => {
+                return Err(format!(
+                    "unsupported escape sequence `\\{}`",
+                    other.unwrap_or(' ')
+                ))
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(text: &str) -> Vec<PathBuf> {
+        Config::parse(text, Path::new("")).unwrap().entry
+    }
+
+    #[test]
+    fn array_items_with_commas() {
+        assert_eq!(
+            entries(r#"entry = ["a,b.js", "c.js",]"#),
+            [PathBuf::from("a,b.js"), PathBuf::from("c.js")]
+        );
+        assert_eq!(
+            entries(r#"entry = ["say \"hi, there\".js", "d.js"]"#),
+            [PathBuf::from("say \"hi, there\".js"), PathBuf::from("d.js")]
+        );
+        assert_eq!(entries("entry = []"), Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn comments_after_strings() {
+        let text = "entry = [\"#1.js\", \"a\\\\\"] # the \"entries\", for now\noutput = \"out#\"";
+        let config = Config::parse(text, Path::new("")).unwrap();
+        assert_eq!(config.entry, [PathBuf::from("#1.js"), PathBuf::from("a\\")]);
+        assert_eq!(config.output, Some(PathBuf::from("out#")));
+    }
+
+    #[test]
+    fn unterminated_items() {
+        assert!(Config::parse(r#"entry = ["a.js", "b.js]"#, Path::new("")).is_err());
+        assert!(Config::parse(r#"entry = ["a.js" "b.js"]"#, Path::new("")).is_err());
+    }
+}
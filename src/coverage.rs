@@ -0,0 +1,229 @@
+//! Reports of how much of a program the converter is able to handle, answering whether
+//! jsrs can transpile it without actually doing so.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use oxc::{
+    semantic::Semantic,
+    span::{GetSpan, Span},
+};
+
+use crate::{emit::node_kind_name, rust};
+
+/// How many spans of a node kind are listed by the `Display` of the report
+const MAX_LISTED_SPANS: usize = 10;
+
+/// All the nodes of one kind in the program
+#[derive(Debug, Clone)]
+pub struct NodeKindCoverage {
+    pub kind: Cow<'static, str>,
+    /// The spans of the nodes in the order of the source
+    pub spans: Vec<Span>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    /// The node kinds that the converter handles, most frequent first
+    pub supported: Vec<NodeKindCoverage>,
+    /// The node kinds that the converter doesn't handle yet, most frequent first
+    pub unsupported: Vec<NodeKindCoverage>,
+}
+
+impl CoverageReport {
+    /// Checks every node of the program against the converter.
+    ///
+    /// This only looks at the kind of each node, without converting anything, so the whole
+    /// program is covered even where an unsupported node contains further ones.
+    pub fn new(semantic: &Semantic) -> Self {
+        let mut supported = BTreeMap::<Cow<str>, Vec<Span>>::new();
+        let mut unsupported = BTreeMap::<Cow<str>, Vec<Span>>::new();
+
+        for node in semantic.nodes().iter() {
+            let kind = node.kind();
+            let kinds = if rust::is_supported_node_kind(&kind) {
+                &mut supported
+            } else {
+                &mut unsupported
+            };
+            kinds
+                .entry(node_kind_name(&kind))
+                .or_default()
+                .push(kind.span());
+        }
+
+        CoverageReport {
+            supported: by_frequency(supported),
+            unsupported: by_frequency(unsupported),
+        }
+    }
+
+    /// Whether jsrs can transpile the program
+    pub fn is_fully_supported(&self) -> bool {
+        self.unsupported.is_empty()
+    }
+
+    pub fn supported_node_count(&self) -> usize {
+        self.supported.iter().map(|kind| kind.spans.len()).sum()
+    }
+
+    pub fn unsupported_node_count(&self) -> usize {
+        self.unsupported.iter().map(|kind| kind.spans.len()).sum()
+    }
+}
+
+/// Most frequent first, ties are broken by name to keep the order stable
+fn by_frequency(kinds: BTreeMap<Cow<'static, str>, Vec<Span>>) -> Vec<NodeKindCoverage> {
+    let mut kinds = kinds
+        .into_iter()
+        .map(|(kind, mut spans)| {
+            spans.sort_by_key(|span| span.start);
+            NodeKindCoverage { kind, spans }
+        })
+        .collect::<Vec<_>>();
+    kinds.sort_by(|a, b| b.spans.len().cmp(&a.spans.len()).then(a.kind.cmp(&b.kind)));
+    kinds
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let supported_count = self.supported_node_count();
+        let total_count = supported_count + self.unsupported_node_count();
+        let kind_count = self.supported.len() + self.unsupported.len();
+        let percentage = |count: usize, total: usize| {
+            if total == 0 {
+                100.0
+            } else {
+                count as f64 * 100.0 / total as f64
+            }
+        };
+
+        writeln!(
+            f,
+            "Nodes supported: {supported_count} of {total_count} ({:.1}%)",
+            percentage(supported_count, total_count)
+        )?;
+        writeln!(
+            f,
+            "Node kinds supported: {} of {kind_count} ({:.1}%)",
+            self.supported.len(),
+            percentage(self.supported.len(), kind_count)
+        )?;
+
+        writeln!(f)?;
+        writeln!(f, "Unsupported node kinds:")?;
+        for kind in &self.unsupported {
+            let spans = kind
+                .spans
+                .iter()
+                .take(MAX_LISTED_SPANS)
+                .map(|span| format!("{}..{}", span.start, span.end))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, "{:>8} {} at {spans}", kind.spans.len(), kind.kind)?;
+            if kind.spans.len() > MAX_LISTED_SPANS {
+                write!(f, " and {} more", kind.spans.len() - MAX_LISTED_SPANS)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Supported node kinds:")?;
+        for kind in &self.supported {
+            writeln!(f, "{:>8} {}", kind.spans.len(), kind.kind)?;
+        }
+
+        writeln!(f)?;
+        if self.is_fully_supported() {
+            write!(f, "jsrs can transpile this file")
+        } else {
+            write!(
+                f,
+                "jsrs can't transpile this file yet, {} nodes are unsupported",
+                self.unsupported_node_count()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SourceType, TranspileOptions, Transpiler};
+
+    fn coverage(source_text: &str) -> CoverageReport {
+        Transpiler::new(TranspileOptions::default())
+            .coverage_str(source_text, SourceType::mjs())
+            .unwrap()
+    }
+
+    fn kinds(kinds: &[NodeKindCoverage]) -> Vec<(&str, Vec<Span>)> {
+        kinds
+            .iter()
+            .map(|kind| (kind.kind.as_ref(), kind.spans.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn mixed_program() {
+        let report = coverage("var a = 1;\nasync function f() {}\nf(a);\n");
+
+        assert_eq!(
+            kinds(&report.unsupported),
+            [
+                ("Function", vec![Span::new(11, 32)]),
+                ("VariableDeclaration", vec![Span::new(0, 10)]),
+            ]
+        );
+        assert_eq!(report.unsupported_node_count(), 2);
+        assert!(!report.is_fully_supported());
+
+        let supported = kinds(&report.supported);
+        assert!(supported.contains(&(
+            "IdentifierReference",
+            vec![Span::new(33, 34), Span::new(35, 36)]
+        )));
+        assert!(supported.contains(&("CallExpression", vec![Span::new(33, 37)])));
+        assert!(supported.iter().all(|(kind, _)| *kind != "Unknown"));
+        assert!(report
+            .to_string()
+            .ends_with("jsrs can't transpile this file yet, 2 nodes are unsupported"));
+    }
+
+    #[test]
+    fn spread_everywhere() {
+        // Spread in arguments is reported as supported because the lowerer handles it
+        let source_text = "const a = [1];\nconst o = { a };\nMath.max(...a);\nnew Set(...a);\n\
+            console.log?.(0, ...a);\nconsole.log([...a], { ...o });\n";
+        let report = coverage(source_text);
+
+        assert!(report.is_fully_supported(), "{report}");
+        let supported = kinds(&report.supported);
+        assert!(supported
+            .iter()
+            .any(|(kind, spans)| *kind == "SpreadElement" && spans.len() == 5));
+        Transpiler::new(TranspileOptions::default())
+            .transpile_str(source_text, SourceType::mjs())
+            .unwrap();
+    }
+
+    #[test]
+    fn new_target() {
+        let report = coverage("function F() {\n  console.log(new.target === F);\n}\nnew F();\n");
+
+        assert!(report.is_fully_supported(), "{report}");
+        assert!(kinds(&report.supported).contains(&("MetaProperty", vec![Span::new(29, 39)])));
+    }
+
+    #[test]
+    fn supported_program() {
+        let report = coverage("let total = 1 + 2;\nconsole.log(total);\n");
+
+        assert!(report.is_fully_supported());
+        assert_eq!(report.unsupported_node_count(), 0);
+        assert!(report.to_string().ends_with("jsrs can transpile this file"));
+    }
+}
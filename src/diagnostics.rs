@@ -0,0 +1,22 @@
+//! Reporting of parser, semantic and transpiler errors, rendered together with the
+//! offending part of the JS source.
+
+use std::{path::Path, sync::Arc};
+
+use oxc::diagnostics::{GraphicalReportHandler, NamedSource, OxcDiagnostic};
+
+/// Renders the errors with labeled snippets of the source they point into.
+pub fn render(errors: Vec<OxcDiagnostic>, source_path: &Path, source_text: &str) -> String {
+    let handler = GraphicalReportHandler::new();
+    let source = Arc::new(NamedSource::new(
+        source_path.to_string_lossy(),
+        source_text.to_owned(),
+    ));
+
+    let mut output = String::new();
+    for error in errors {
+        let error = error.with_source_code(Arc::clone(&source));
+        handler.render_report(&mut output, error.as_ref()).unwrap();
+    }
+    output
+}
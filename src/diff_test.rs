@@ -0,0 +1,124 @@
+//! The `diff-test` subcommand, checking that the compiled program behaves like the JS
+//! program does under node.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+
+use jsrs::Transpiler;
+
+use crate::{native, transpile_to_rust};
+
+/// What a program printed to stdout and how it exited.
+#[derive(Debug, PartialEq)]
+struct Outcome {
+    stdout: String,
+    /// `None` if the program was killed by a signal
+    exit_code: Option<i32>,
+}
+
+/// Runs every file under node and as a compiled program, both with `args`, and reports
+/// where their outputs diverge.
+///
+/// Returns whether all files behaved the same.
+pub fn run(transpiler: &Transpiler, source_paths: &[PathBuf], args: &[String]) -> bool {
+    let mut failed_count = 0;
+    for (index, source_path) in source_paths.iter().enumerate() {
+        let dir = env::temp_dir().join(format!("jsrs-diff-test-{}-{index}", process::id()));
+        let result = diff_test(transpiler, source_path, args, &dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        match result {
+            Ok(None) => eprintln!("ok   {}", source_path.display()),
+            Ok(Some(mismatch)) => {
+                failed_count += 1;
+                eprintln!("FAIL {}", source_path.display());
+                eprintln!("{mismatch}");
+            }
+            Err(error) => {
+                failed_count += 1;
+                eprintln!("FAIL {}", source_path.display());
+                eprintln!("error: {error}");
+            }
+        }
+    }
+
+    eprintln!();
+    eprintln!(
+        "{} of {} files behave like under node",
+        source_paths.len() - failed_count,
+        source_paths.len()
+    );
+    failed_count == 0
+}
+
+/// Returns a description of the difference, if there was any.
+fn diff_test(
+    transpiler: &Transpiler,
+    source_path: &Path,
+    args: &[String],
+    dir: &Path,
+) -> Result<Option<String>, String> {
+    let expected = run_program(Command::new("node").arg(source_path).args(args))?;
+
+    let rust_text = transpile_to_rust(transpiler, source_path)?;
+    let executable_path = native::compile(&rust_text, dir, false)?;
+    let actual = run_program(Command::new(&executable_path).args(args))?;
+
+    if actual == expected {
+        return Ok(None);
+    }
+    if actual.stdout != expected.stdout {
+        return Ok(Some(first_diverging_line(&expected.stdout, &actual.stdout)));
+    }
+    Ok(Some(format!(
+        "  exit code: node {}, jsrs {}",
+        describe_exit_code(expected.exit_code),
+        describe_exit_code(actual.exit_code)
+    )))
+}
+
+fn run_program(command: &mut Command) -> Result<Outcome, String> {
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("failed to start {command:?}: {err}"))?;
+    Ok(Outcome {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        exit_code: output.status.code(),
+    })
+}
+
+fn first_diverging_line(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line_number = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected_line), Some(actual_line)) if expected_line == actual_line => {}
+            // Only the trailing newline differs
+            (None, None) => return String::from("  stdout differs in its trailing newline"),
+            (expected_line, actual_line) => {
+                let describe = |line: Option<&str>| match line {
+                    Some(line) => format!("{line:?}"),
+                    None => String::from("<end of output>"),
+                };
+                return format!(
+                    "  stdout diverges at line {line_number}\n    node: {}\n    jsrs: {}",
+                    describe(expected_line),
+                    describe(actual_line)
+                );
+            }
+        }
+        line_number += 1;
+    }
+}
+
+fn describe_exit_code(exit_code: Option<i32>) -> String {
+    match exit_code {
+        Some(code) => code.to_string(),
+        None => String::from("killed by a signal"),
+    }
+}
@@ -0,0 +1,263 @@
+//! The different stages of the transpilation that can be written out with `--emit`.
+
+use std::{borrow::Cow, fmt::Write, io, str::FromStr};
+
+use oxc::{
+    ast::AstKind,
+    semantic::{
+        dot::{DebugDot, DebugDotContext},
+        Semantic,
+    },
+    span::GetSpan,
+};
+
+use crate::{rust, TranspileError, TranspileOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Emit {
+    /// The tree of AST nodes, with their spans
+    Ast,
+    /// The symbol table built by the semantic analysis
+    Symbols,
+    /// The control flow graph, in Graphviz dot format
+    Cfg,
+    /// The generated Rust program
+    #[default]
+    Rust,
+}
+
+impl Emit {
+    /// The extension of the files the stage is written to when transpiling multiple files.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Emit::Ast => "ast",
+            Emit::Symbols => "symbols",
+            Emit::Cfg => "dot",
+            Emit::Rust => "rs",
+        }
+    }
+
+    pub fn render(
+        self,
+        semantic: &Semantic,
+        options: &TranspileOptions,
+    ) -> Result<String, TranspileError> {
+        match self {
+            Emit::Rust => {
+                let root = semantic.nodes().root_node().unwrap();
+                rust::node_to_rust_text(&root.kind(), options).map(|rust_text| rust_text.text)
+            }
+            _ => Ok(self.render_debug(semantic)),
+        }
+    }
+
+    /// Writes the stage into `out`, like a file. The Rust program is streamed into it as it's
+    /// generated, so on error part of it may already have been written.
+    pub fn write_to(
+        self,
+        semantic: &Semantic,
+        options: &TranspileOptions,
+        out: &mut impl io::Write,
+    ) -> Result<(), TranspileError> {
+        rust::write_io(out, |out| match self {
+            Emit::Rust => {
+                let root = semantic.nodes().root_node().unwrap();
+                rust::write_node(&root.kind(), options, out).map(|_| ())
+            }
+            _ => {
+                // A failed write is reported by `write_io`
+                let _ = out.write_str(&self.render_debug(semantic));
+                Ok(())
+            }
+        })
+    }
+
+    /// The stages other than the Rust program, which can't fail
+    fn render_debug(self, semantic: &Semantic) -> String {
+        match self {
+            Emit::Ast => ast_to_text(semantic),
+            Emit::Symbols => symbols_to_text(semantic),
+            Emit::Cfg => semantic
+                .cfg()
+                .expect("the control flow graph is always built")
+                .debug_dot(DebugDotContext::new(semantic.nodes(), false)),
+            Emit::Rust => unreachable!("the Rust program is generated by the converter"),
+        }
+    }
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ast" => Ok(Emit::Ast),
+            "symbols" => Ok(Emit::Symbols),
+            "cfg" => Ok(Emit::Cfg),
+            "rust" => Ok(Emit::Rust),
+            _ => Err(format!(
+                "unknown emit stage `{s}`, expected `ast`, `symbols`, `cfg` or `rust`"
+            )),
+        }
+    }
+}
+
+/// One line per node, indented by its depth in the tree.
+fn ast_to_text(semantic: &Semantic) -> String {
+    let nodes = semantic.nodes();
+    let mut text = String::new();
+    // Nodes are stored in the order they were visited, so parents always come before
+    // their children
+    for node in nodes.iter() {
+        let depth = nodes.ancestors(node.id()).count() - 1;
+        let kind = node.kind();
+        let span = kind.span();
+        write!(
+            text,
+            "{:indent$}{} {}..{}",
+            "",
+            node_kind_name(&kind),
+            span.start,
+            span.end,
+            indent = depth * 2
+        )
+        .unwrap();
+        match kind {
+            AstKind::IdentifierName(ident) => write!(text, " `{}`", ident.name).unwrap(),
+            AstKind::IdentifierReference(ident) => write!(text, " `{}`", ident.name).unwrap(),
+            AstKind::BindingIdentifier(ident) => write!(text, " `{}`", ident.name).unwrap(),
+            AstKind::NumericLiteral(literal) => write!(text, " {}", literal.raw).unwrap(),
+            AstKind::StringLiteral(literal) => write!(text, " {:?}", literal.value).unwrap(),
+            _ => {}
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn symbols_to_text(semantic: &Semantic) -> String {
+    let symbols = semantic.symbols();
+    let mut text = String::new();
+    for symbol_id in symbols.symbol_ids() {
+        let span = symbols.get_span(symbol_id);
+        writeln!(
+            text,
+            "{symbol_id:?} `{}` {:?} in {:?} at {}..{}, {} references",
+            symbols.get_name(symbol_id),
+            symbols.get_flags(symbol_id),
+            symbols.get_scope_id(symbol_id),
+            span.start,
+            span.end,
+            symbols.get_resolved_reference_ids(symbol_id).len()
+        )
+        .unwrap();
+    }
+    text
+}
+
+/// The name of the node's kind, without any of its data.
+///
+/// The kinds without an arm here get the name of their variant from their `Debug` output.
+pub fn node_kind_name(kind: &AstKind) -> Cow<'static, str> {
+    let name = match kind {
+        AstKind::BooleanLiteral(_) => "BooleanLiteral",
+        AstKind::NullLiteral(_) => "NullLiteral",
+        AstKind::NumericLiteral(_) => "NumericLiteral",
+        AstKind::BigIntLiteral(_) => "BigIntLiteral",
+        AstKind::RegExpLiteral(_) => "RegExpLiteral",
+        AstKind::StringLiteral(_) => "StringLiteral",
+        AstKind::Program(_) => "Program",
+        AstKind::IdentifierName(_) => "IdentifierName",
+        AstKind::IdentifierReference(_) => "IdentifierReference",
+        AstKind::BindingIdentifier(_) => "BindingIdentifier",
+        AstKind::LabelIdentifier(_) => "LabelIdentifier",
+        AstKind::ThisExpression(_) => "ThisExpression",
+        AstKind::ArrayExpression(_) => "ArrayExpression",
+        AstKind::ArrayExpressionElement(_) => "ArrayExpressionElement",
+        AstKind::Elision(_) => "Elision",
+        AstKind::ObjectExpression(_) => "ObjectExpression",
+        AstKind::ObjectProperty(_) => "ObjectProperty",
+        AstKind::PropertyKey(_) => "PropertyKey",
+        AstKind::TemplateLiteral(_) => "TemplateLiteral",
+        AstKind::TaggedTemplateExpression(_) => "TaggedTemplateExpression",
+        AstKind::MemberExpression(_) => "MemberExpression",
+        AstKind::CallExpression(_) => "CallExpression",
+        AstKind::NewExpression(_) => "NewExpression",
+        AstKind::MetaProperty(_) => "MetaProperty",
+        AstKind::SpreadElement(_) => "SpreadElement",
+        AstKind::Argument(_) => "Argument",
+        AstKind::UpdateExpression(_) => "UpdateExpression",
+        AstKind::UnaryExpression(_) => "UnaryExpression",
+        AstKind::BinaryExpression(_) => "BinaryExpression",
+        AstKind::PrivateInExpression(_) => "PrivateInExpression",
+        AstKind::LogicalExpression(_) => "LogicalExpression",
+        AstKind::ConditionalExpression(_) => "ConditionalExpression",
+        AstKind::AssignmentExpression(_) => "AssignmentExpression",
+        AstKind::AssignmentTarget(_) => "AssignmentTarget",
+        AstKind::SimpleAssignmentTarget(_) => "SimpleAssignmentTarget",
+        AstKind::AssignmentTargetPattern(_) => "AssignmentTargetPattern",
+        AstKind::ArrayAssignmentTarget(_) => "ArrayAssignmentTarget",
+        AstKind::ObjectAssignmentTarget(_) => "ObjectAssignmentTarget",
+        AstKind::AssignmentTargetWithDefault(_) => "AssignmentTargetWithDefault",
+        AstKind::SequenceExpression(_) => "SequenceExpression",
+        AstKind::Super(_) => "Super",
+        AstKind::AwaitExpression(_) => "AwaitExpression",
+        AstKind::ChainExpression(_) => "ChainExpression",
+        AstKind::ParenthesizedExpression(_) => "ParenthesizedExpression",
+        AstKind::Directive(_) => "Directive",
+        AstKind::Hashbang(_) => "Hashbang",
+        AstKind::BlockStatement(_) => "BlockStatement",
+        AstKind::VariableDeclaration(_) => "VariableDeclaration",
+        AstKind::VariableDeclarator(_) => "VariableDeclarator",
+        AstKind::EmptyStatement(_) => "EmptyStatement",
+        AstKind::ExpressionStatement(_) => "ExpressionStatement",
+        AstKind::IfStatement(_) => "IfStatement",
+        AstKind::DoWhileStatement(_) => "DoWhileStatement",
+        AstKind::WhileStatement(_) => "WhileStatement",
+        AstKind::ForStatement(_) => "ForStatement",
+        AstKind::ForStatementInit(_) => "ForStatementInit",
+        AstKind::ForInStatement(_) => "ForInStatement",
+        AstKind::ForOfStatement(_) => "ForOfStatement",
+        AstKind::ContinueStatement(_) => "ContinueStatement",
+        AstKind::BreakStatement(_) => "BreakStatement",
+        AstKind::ReturnStatement(_) => "ReturnStatement",
+        AstKind::WithStatement(_) => "WithStatement",
+        AstKind::SwitchStatement(_) => "SwitchStatement",
+        AstKind::SwitchCase(_) => "SwitchCase",
+        AstKind::LabeledStatement(_) => "LabeledStatement",
+        AstKind::ThrowStatement(_) => "ThrowStatement",
+        AstKind::TryStatement(_) => "TryStatement",
+        AstKind::CatchClause(_) => "CatchClause",
+        AstKind::CatchParameter(_) => "CatchParameter",
+        AstKind::DebuggerStatement(_) => "DebuggerStatement",
+        AstKind::AssignmentPattern(_) => "AssignmentPattern",
+        AstKind::ObjectPattern(_) => "ObjectPattern",
+        AstKind::ArrayPattern(_) => "ArrayPattern",
+        AstKind::BindingRestElement(_) => "BindingRestElement",
+        AstKind::Function(_) => "Function",
+        AstKind::FormalParameters(_) => "FormalParameters",
+        AstKind::FormalParameter(_) => "FormalParameter",
+        AstKind::FunctionBody(_) => "FunctionBody",
+        AstKind::ArrowFunctionExpression(_) => "ArrowFunctionExpression",
+        AstKind::YieldExpression(_) => "YieldExpression",
+        AstKind::Class(_) => "Class",
+        AstKind::ClassBody(_) => "ClassBody",
+        AstKind::MethodDefinition(_) => "MethodDefinition",
+        AstKind::PropertyDefinition(_) => "PropertyDefinition",
+        AstKind::PrivateIdentifier(_) => "PrivateIdentifier",
+        AstKind::StaticBlock(_) => "StaticBlock",
+        AstKind::ModuleDeclaration(_) => "ModuleDeclaration",
+        AstKind::ImportExpression(_) => "ImportExpression",
+        AstKind::ImportDeclaration(_) => "ImportDeclaration",
+        AstKind::ImportSpecifier(_) => "ImportSpecifier",
+        AstKind::ImportDefaultSpecifier(_) => "ImportDefaultSpecifier",
+        AstKind::ImportNamespaceSpecifier(_) => "ImportNamespaceSpecifier",
+        AstKind::ExportNamedDeclaration(_) => "ExportNamedDeclaration",
+        AstKind::ExportDefaultDeclaration(_) => "ExportDefaultDeclaration",
+        AstKind::ExportAllDeclaration(_) => "ExportAllDeclaration",
+        AstKind::ExportSpecifier(_) => "ExportSpecifier",
+        AstKind::Decorator(_) => "Decorator",
+        _ => return Cow::Owned(rust::variant_name(kind)),
+    };
+    Cow::Borrowed(name)
+}
@@ -0,0 +1,94 @@
+//! The error type of the transpilation.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    path::Path,
+};
+
+use oxc::{diagnostics::OxcDiagnostic, span::Span};
+
+use crate::diagnostics;
+
+/// A node of the source that the converter can't handle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedNode {
+    /// The name of the `AstKind` of the node
+    pub kind: String,
+    pub span: Span,
+}
+
+/// The parser, semantic and converter errors of a source.
+///
+/// Displaying it renders every error together with the part of the source it points at.
+#[derive(Debug, Clone)]
+pub struct TranspileError {
+    source_name: String,
+    source_text: String,
+    errors: Vec<OxcDiagnostic>,
+    unsupported_nodes: Vec<UnsupportedNode>,
+}
+
+impl TranspileError {
+    pub(crate) fn new(errors: Vec<OxcDiagnostic>) -> Self {
+        TranspileError {
+            source_name: String::new(),
+            source_text: String::new(),
+            errors,
+            unsupported_nodes: Vec::new(),
+        }
+    }
+
+    /// The error of a node that the converter can't handle. The `description` says what
+    /// exactly about the node isn't supported.
+    pub(crate) fn unsupported(kind: &str, description: &str, span: Span) -> Self {
+        let diagnostic = OxcDiagnostic::error(format!("unsupported {description}"))
+            .with_label(span.label("jsrs can't transpile this yet"))
+            .with_help(
+                "use the `todo` unsupported-node mode to generate the rest of the program anyway",
+            );
+        TranspileError {
+            unsupported_nodes: vec![UnsupportedNode {
+                kind: kind.to_owned(),
+                span,
+            }],
+            ..TranspileError::new(vec![diagnostic])
+        }
+    }
+
+    /// Sets the source that the spans of the errors point into.
+    pub(crate) fn with_source(mut self, source_name: &str, source_text: &str) -> Self {
+        self.source_name = source_name.to_owned();
+        self.source_text = source_text.to_owned();
+        self
+    }
+
+    /// Appends the errors of `other`, which must be about the same source.
+    pub(crate) fn merge(mut self, other: TranspileError) -> Self {
+        self.errors.extend(other.errors);
+        self.unsupported_nodes.extend(other.unsupported_nodes);
+        self
+    }
+
+    pub fn errors(&self) -> &[OxcDiagnostic] {
+        &self.errors
+    }
+
+    /// The nodes that the converter couldn't handle, in the order of the source.
+    pub fn unsupported_nodes(&self) -> &[UnsupportedNode] {
+        &self.unsupported_nodes
+    }
+}
+
+impl Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rendered = diagnostics::render(
+            self.errors.clone(),
+            Path::new(&self.source_name),
+            &self.source_text,
+        );
+        f.write_str(rendered.trim_end())
+    }
+}
+
+impl Error for TranspileError {}
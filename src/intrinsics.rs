@@ -0,0 +1,101 @@
+//! The mapping of JS globals and their members to Rust expressions.
+
+use std::collections::HashMap;
+
+/// Maps accesses of JS globals, like `Math.PI` or `myLib.foo`, to Rust expressions.
+///
+/// The Rust expression must evaluate to a `JsValue`. To make a Rust function callable from
+/// JS, wrap it with `JsValue::new_function`.
+///
+/// ```
+/// use jsrs::{IntrinsicMap, TranspileOptions};
+///
+/// let intrinsics = IntrinsicMap::default()
+///     .with("myLib.foo", "my_lib::foo()")
+///     .with("VERSION", "JsValue::from(\"1.0\")");
+/// let options = TranspileOptions::default().with_intrinsics(intrinsics);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrinsicMap {
+    /// From the dotted path of the access to the Rust expression
+    entries: HashMap<String, String>,
+}
+
+impl Default for IntrinsicMap {
+    /// The parts of the JS standard library that the runtime implements
+    fn default() -> Self {
+        IntrinsicMap::empty()
+            .with("Math", "math()")
+            .with("Object", "object_constructor()")
+            .with("Number", "number_constructor()")
+            .with("Symbol", "symbol_constructor()")
+            .with("Date", "date_constructor()")
+            .with("Map", "map_constructor()")
+            .with("Set", "set_constructor()")
+            .with("Float64Array", "float64_array_constructor()")
+            .with("Int32Array", "int32_array_constructor()")
+            .with("Uint8Array", "uint8_array_constructor()")
+            .with("Promise", "promise_constructor()")
+            .with("Error", "error_constructor()")
+            .with("TypeError", "type_error_constructor()")
+            .with("RangeError", "range_error_constructor()")
+            .with("SyntaxError", "syntax_error_constructor()")
+            .with("process.argv", "process().argv")
+            .with("process.env", "process().env")
+            .with("process.exit", "process().exit")
+            .with("process.platform", "process().platform")
+            .with("process.stdout", "process().stdout")
+            .with("process.stderr", "process().stderr")
+            .with("JSON.parse", "json().parse")
+            .with("JSON.stringify", "json().stringify")
+            .with("console.log", "console().log")
+            .with("console.error", "console().error")
+            .with("console.warn", "console().warn")
+            .with("console.info", "console().info")
+            .with("console.debug", "console().debug")
+            .with("console.assert", "console().assert")
+            .with("console.time", "console().time")
+            .with("console.timeEnd", "console().time_end")
+            .with("console.timeLog", "console().time_log")
+            .with("globalThis", "global_this()")
+            .with("parseInt", "global(\"parseInt\")")
+            .with("parseFloat", "global(\"parseFloat\")")
+            .with("isNaN", "global(\"isNaN\")")
+            .with("isFinite", "global(\"isFinite\")")
+            .with("setTimeout", "global(\"setTimeout\")")
+            .with("clearTimeout", "global(\"clearTimeout\")")
+            .with("queueMicrotask", "global(\"queueMicrotask\")")
+    }
+}
+
+impl IntrinsicMap {
+    /// A map without even the standard library of the runtime
+    pub fn empty() -> Self {
+        IntrinsicMap {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Maps the access of `path`, a global name followed by any number of `.member`s, to
+    /// the `rust_expression`. Returns the expression it was mapped to before, if any.
+    pub fn insert(
+        &mut self,
+        path: impl Into<String>,
+        rust_expression: impl Into<String>,
+    ) -> Option<String> {
+        self.entries.insert(path.into(), rust_expression.into())
+    }
+
+    pub fn with(mut self, path: impl Into<String>, rust_expression: impl Into<String>) -> Self {
+        self.insert(path, rust_expression);
+        self
+    }
+
+    pub fn remove(&mut self, path: &str) -> Option<String> {
+        self.entries.remove(path)
+    }
+
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.entries.get(path).map(String::as_str)
+    }
+}
@@ -0,0 +1,320 @@
+//! jsrs transpiles JavaScript programs into Rust programs.
+//!
+//! ```no_run
+//! use jsrs::{SourceType, TranspileOptions, Transpiler};
+//!
+//! let transpiler = Transpiler::new(TranspileOptions::default());
+//! let output = transpiler
+//!     .transpile_str("console.log(1 + 2);", SourceType::mjs())
+//!     .unwrap();
+//! std::fs::write("main.rs", output.rust_text).unwrap();
+//! ```
+
+use std::{fmt, fs, io, path::Path};
+
+use oxc::{
+    allocator::Allocator,
+    diagnostics::OxcDiagnostic,
+    parser::{Parser, ParserReturn},
+    semantic::{Semantic, SemanticBuilder, SemanticBuilderReturn},
+};
+
+pub use oxc::span::{SourceType, Span};
+
+pub mod coverage;
+mod diagnostics;
+pub mod emit;
+mod error;
+mod intrinsics;
+pub mod rust;
+
+pub use coverage::CoverageReport;
+pub use emit::Emit;
+pub use error::{TranspileError, UnsupportedNode};
+pub use intrinsics::IntrinsicMap;
+pub use rust::{OnUnsupported, Runtime, RustText, SpanMapping};
+
+/// How the JS source is transpiled.
+///
+/// ```
+/// use jsrs::{OnUnsupported, Runtime, TranspileOptions};
+///
+/// // Generates `fn run_script()` without the runtime, for a crate that provides it
+/// let options = TranspileOptions::default()
+///     .with_entry_name("run_script")
+///     .with_runtime(Runtime::InScope)
+///     .with_on_unsupported(OnUnsupported::Todo);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TranspileOptions {
+    entry_name: String,
+    wrap_in_fn: bool,
+    runtime: Runtime,
+    on_unsupported: OnUnsupported,
+    identifier_prefix: String,
+    intrinsics: IntrinsicMap,
+    debugger_hook: Option<String>,
+}
+
+impl Default for TranspileOptions {
+    fn default() -> Self {
+        TranspileOptions {
+            entry_name: String::from("main"),
+            wrap_in_fn: true,
+            runtime: Runtime::default(),
+            on_unsupported: OnUnsupported::default(),
+            identifier_prefix: String::new(),
+            intrinsics: IntrinsicMap::default(),
+            debugger_hook: None,
+        }
+    }
+}
+
+impl TranspileOptions {
+    /// The name of the function that the top level statements are placed in, `main` by default.
+    pub fn with_entry_name(mut self, entry_name: impl Into<String>) -> Self {
+        self.entry_name = entry_name.into();
+        self
+    }
+
+    /// Whether the top level statements are placed in the entry function. Without it, the
+    /// output is a sequence of statements to be included into a function body, after which
+    /// the function has to call `run_event_loop` for the callbacks of promises and timers.
+    pub fn with_wrap_in_fn(mut self, wrap_in_fn: bool) -> Self {
+        self.wrap_in_fn = wrap_in_fn;
+        self
+    }
+
+    /// How the generated code gets access to the runtime, by importing the `jsrs-runtime`
+    /// crate by default.
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    pub fn with_on_unsupported(mut self, on_unsupported: OnUnsupported) -> Self {
+        self.on_unsupported = on_unsupported;
+        self
+    }
+
+    /// Replaces the mapping of JS globals to Rust expressions, which covers the standard
+    /// library of the runtime by default.
+    pub fn with_intrinsics(mut self, intrinsics: IntrinsicMap) -> Self {
+        self.intrinsics = intrinsics;
+        self
+    }
+
+    /// Maps one more JS global or member access to a Rust expression, see `IntrinsicMap::insert`.
+    pub fn with_intrinsic(
+        mut self,
+        path: impl Into<String>,
+        rust_expression: impl Into<String>,
+    ) -> Self {
+        self.intrinsics.insert(path, rust_expression);
+        self
+    }
+
+    /// A prefix for the names of the Rust variables of JS identifiers, to keep them from
+    /// colliding with Rust keywords or the items of the runtime.
+    pub fn with_identifier_prefix(mut self, identifier_prefix: impl Into<String>) -> Self {
+        self.identifier_prefix = identifier_prefix.into();
+        self
+    }
+
+    /// A Rust expression that a `debugger` statement runs, like a call to a function of the
+    /// program that it's included into. Without it, `debugger` does nothing.
+    pub fn with_debugger_hook(mut self, rust_expression: impl Into<String>) -> Self {
+        self.debugger_hook = Some(rust_expression.into());
+        self
+    }
+}
+
+/// The generated Rust program
+#[derive(Debug, Clone)]
+pub struct TranspileOutput {
+    pub rust_text: String,
+    /// Where the statements of the JS source ended up in `rust_text`, with outer statements
+    /// before the statements they contain
+    pub mappings: Vec<SpanMapping>,
+}
+
+impl TranspileOutput {
+    /// The span of the innermost JS statement that the byte at `rust_offset` was generated from.
+    pub fn js_span_at(&self, rust_offset: usize) -> Option<Span> {
+        self.mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.rust_range.contains(&rust_offset))
+            .map(|mapping| mapping.js_span)
+    }
+}
+
+impl From<RustText> for TranspileOutput {
+    fn from(rust_text: RustText) -> Self {
+        TranspileOutput {
+            rust_text: rust_text.text,
+            mappings: rust_text.mappings,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Transpiler {
+    options: TranspileOptions,
+}
+
+impl Transpiler {
+    pub fn new(options: TranspileOptions) -> Self {
+        Transpiler { options }
+    }
+
+    pub fn options(&self) -> &TranspileOptions {
+        &self.options
+    }
+
+    /// Transpiles the source into a Rust program.
+    pub fn transpile_str(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+    ) -> Result<TranspileOutput, TranspileError> {
+        self.analyze("<input>", source_text, source_type, |semantic| {
+            let root = semantic.nodes().root_node().unwrap();
+            rust::node_to_rust_text(&root.kind(), &self.options)
+        })
+        .map(TranspileOutput::from)
+    }
+
+    /// Streams the Rust program for the source into `out`, returning where the statements of
+    /// the source ended up in it.
+    ///
+    /// On error, part of the program may already have been written to `out`.
+    pub fn transpile_str_to(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+        out: &mut impl fmt::Write,
+    ) -> Result<Vec<SpanMapping>, TranspileError> {
+        self.analyze("<input>", source_text, source_type, |semantic| {
+            let root = semantic.nodes().root_node().unwrap();
+            rust::write_node(&root.kind(), &self.options, out)
+        })
+    }
+
+    /// Reports which nodes of the source the converter can't handle yet, instead of failing
+    /// on them. Only parser and semantic errors are returned as errors.
+    pub fn coverage_str(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+    ) -> Result<CoverageReport, TranspileError> {
+        self.analyze("<input>", source_text, source_type, |semantic| {
+            Ok(CoverageReport::new(semantic))
+        })
+    }
+
+    /// Reads a script and transpiles it into a Rust program.
+    pub fn transpile_file(&self, source_path: &Path) -> Result<TranspileOutput, TranspileError> {
+        let source_text = read_source(source_path)?;
+        self.with_semantic(source_path, &source_text, |semantic| {
+            let root = semantic.nodes().root_node().unwrap();
+            rust::node_to_rust_text(&root.kind(), &self.options)
+        })
+        .map(TranspileOutput::from)
+    }
+
+    /// Reads a script and streams the Rust program for it into `out`, like a file, returning
+    /// where the statements of the script ended up in it.
+    ///
+    /// On error, part of the program may already have been written to `out`.
+    pub fn transpile_file_to(
+        &self,
+        source_path: &Path,
+        out: &mut impl io::Write,
+    ) -> Result<Vec<SpanMapping>, TranspileError> {
+        let source_text = read_source(source_path)?;
+        self.with_semantic(source_path, &source_text, |semantic| {
+            let root = semantic.nodes().root_node().unwrap();
+            rust::write_io(out, |out| {
+                rust::write_node(&root.kind(), &self.options, out)
+            })
+        })
+    }
+
+    /// Parses the source and runs semantic analysis on it, then hands the result to `f`.
+    /// The errors returned by `f` are taken to be about this source.
+    ///
+    /// The source type (TS/JS/ESM/JSX/etc) is inferred from the extension of `source_path`.
+    pub fn with_semantic<T>(
+        &self,
+        source_path: &Path,
+        source_text: &str,
+        f: impl FnOnce(&Semantic) -> Result<T, TranspileError>,
+    ) -> Result<T, TranspileError> {
+        let source_name = source_path.to_string_lossy();
+        let source_type = SourceType::from_path(source_path).map_err(|err| {
+            TranspileError::new(vec![OxcDiagnostic::error(err.to_string())])
+                .with_source(&source_name, source_text)
+        })?;
+        self.analyze(&source_name, source_text, source_type, f)
+    }
+
+    fn analyze<T>(
+        &self,
+        source_name: &str,
+        source_text: &str,
+        source_type: SourceType,
+        f: impl FnOnce(&Semantic) -> Result<T, TranspileError>,
+    ) -> Result<T, TranspileError> {
+        // Memory arena where AST nodes are allocated.
+        let allocator = Allocator::default();
+        let mut errors = Vec::new();
+
+        // Step 1: Parsing
+        // Parse the TSX file into an AST. The root AST node is a `Program` struct.
+        let ParserReturn {
+            program,
+            errors: parser_errors,
+            panicked,
+            irregular_whitespaces: _,
+        } = Parser::new(&allocator, source_text, source_type).parse();
+        errors.extend(parser_errors);
+
+        // Parsing failed completely. `program` is empty and `errors` isn't. If the
+        // parser could recover from errors, `program` will be a valid AST and
+        // `errors` will be populated. We can still perform semantic analysis in
+        // such cases (if we want).
+        if panicked {
+            return Err(TranspileError::new(errors).with_source(source_name, source_text));
+        }
+
+        // Step 2: Semantic analysis.
+        // Some of the more expensive syntax checks are deferred to this stage, and are
+        // enabled using `with_check_syntax_error`. You are not required to enable
+        // these, and they are disabled by default.
+        let SemanticBuilderReturn {
+            semantic,
+            errors: semantic_errors,
+        } = SemanticBuilder::new()
+            .with_check_syntax_error(true) // Enable extra syntax error checking
+            .with_build_jsdoc(true) // Enable JSDoc parsing
+            .with_cfg(true) // Build a Control Flow Graph
+            .build(&program); // Produce the `Semantic`
+
+        errors.extend(semantic_errors);
+        if !errors.is_empty() {
+            return Err(TranspileError::new(errors).with_source(source_name, source_text));
+        }
+
+        // Step 3: Whatever the caller does with the result, which may involve the converter
+        f(&semantic).map_err(|error| error.with_source(source_name, source_text))
+    }
+}
+
+fn read_source(source_path: &Path) -> Result<String, TranspileError> {
+    fs::read_to_string(source_path).map_err(|err| {
+        let message = format!("failed to read {}: {err}", source_path.display());
+        TranspileError::new(vec![OxcDiagnostic::error(message)])
+            .with_source(&source_path.to_string_lossy(), "")
+    })
+}
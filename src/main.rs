@@ -1,383 +1,607 @@
-use std::{collections::HashSet, fs, path::Path};
-
-use oxc::{
-    allocator::Allocator,
-    ast::AstKind,
-    parser::{Parser, ParserReturn},
-    semantic::{AstNodes, SemanticBuilder, SemanticBuilderReturn},
-    span::SourceType,
+use std::{
+    any::Any,
+    env,
+    fmt::Display,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::{Duration, SystemTime},
 };
 
-mod rust;
+use config::Config;
+use jsrs::{CoverageReport, Emit, Runtime, TranspileError, TranspileOptions, Transpiler};
+use oxc::semantic::Semantic;
+
+mod bench;
+mod config;
+mod diff_test;
+mod native;
+mod project;
 
 fn main() {
-    // In real code, this will likely come from a file read from disk.
-    let source_path = Path::new("./misc/n-body.js");
-    let source_text = fs::read_to_string(source_path).unwrap();
-
-    // Memory arena where AST nodes are allocated.
-    let allocator = Allocator::default();
-    // Infer source type (TS/JS/ESM/JSX/etc) based on file extension
-    let source_type = SourceType::from_path(source_path).unwrap();
-    let mut errors = Vec::new();
-
-    // Step 1: Parsing
-    // Parse the TSX file into an AST. The root AST node is a `Program` struct.
-    let ParserReturn {
-        program,
-        errors: parser_errors,
-        panicked,
-        irregular_whitespaces: _,
-    } = Parser::new(&allocator, &source_text, source_type).parse();
-    errors.extend(parser_errors);
-
-    // Parsing failed completely. `program` is empty and `errors` isn't. If the
-    // parser could recover from errors, `program` will be a valid AST and
-    // `errors` will be populated. We can still perform semantic analysis in
-    // such cases (if we want).
-    if panicked {
-        for error in &errors {
-            eprintln!("{error:?}");
+    let mut dry_run = false;
+    let mut check_only = false;
+    let mut watch = false;
+    let mut run_program = false;
+    let mut emit = Emit::default();
+    let mut program_args = Vec::new();
+    let mut output_path = None;
+    let mut args = env::args().skip(1).peekable();
+
+    if args.peek().map(String::as_str) == Some("bench") {
+        args.next();
+        let names = args.collect::<Vec<_>>();
+        if !bench::run(&native_transpiler(TranspileOptions::default()), &names) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.peek().map(String::as_str) == Some("diff-test") {
+        args.next();
+        // Everything after `--` is passed to both programs
+        let source_paths = args
+            .by_ref()
+            .take_while(|arg| arg != "--")
+            .map(PathBuf::from)
+            .collect::<Vec<_>>();
+        let program_args = args.collect::<Vec<_>>();
+        if source_paths.is_empty() {
+            eprintln!("usage: jsrs diff-test <input.js>... [-- <args>...]");
+            process::exit(1);
+        }
+        let transpiler = native_transpiler(TranspileOptions::default());
+        if !diff_test::run(&transpiler, &source_paths, &program_args) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if args.peek().map(String::as_str) == Some("new-project") {
+        args.next();
+        let mut runtime = Runtime::default();
+        let mut positional = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runtime" => {
+                    let mode = flag_value(&arg, &mut args);
+                    runtime = mode.parse().unwrap_or_else(|err| exit_with_error(err));
+                }
+                _ => positional.push(arg),
+            }
+        }
+        let [source_path, project_dir] = positional.as_slice() else {
+            eprintln!("usage: jsrs new-project [--runtime crate|inline] <input.js> <out-dir>");
+            process::exit(1);
+        };
+        // An inlined runtime becomes a module of the project
+        let options = match runtime {
+            Runtime::Inline => TranspileOptions::default().with_runtime(Runtime::InScope),
+            runtime => TranspileOptions::default().with_runtime(runtime),
+        };
+        let result = transpile_to_rust(&Transpiler::new(options), Path::new(source_path))
+            .and_then(|main_fn| project::create(Path::new(project_dir), &main_fn, runtime));
+        if let Err(error) = result {
+            eprintln!("{error}");
+            process::exit(1);
+        }
+        eprintln!("created project in {project_dir}");
+        return;
+    }
+
+    let mut config_path = None;
+    let mut sources = Vec::new();
+    let mut on_unsupported = None;
+    let mut optimize = None;
+    let mut runtime = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Only report which nodes of the input can't be transpiled yet
+            "--coverage" | "--dry-run" => dry_run = true,
+            // Only parse and analyze the input, without generating any code
+            "--check" => check_only = true,
+            // Transpile again whenever the input changes
+            "--watch" => watch = true,
+            // Compile the generated program and run it
+            "--run" => run_program = true,
+            // Whether `--run` compiles the program with optimizations, the generated code is the
+            // same either way
+            "--optimize" => optimize = Some(true),
+            "--no-optimize" => optimize = Some(false),
+            // Everything after this is passed to the program started by `--run`
+            "--" => {
+                program_args.extend(args.by_ref());
+            }
+            // Which stage of the transpilation to write out
+            "--emit" => {
+                emit = flag_value(&arg, &mut args)
+                    .parse()
+                    .unwrap_or_else(|err| exit_with_error(err));
+            }
+            _ if arg.starts_with("--emit=") => {
+                emit = arg["--emit=".len()..]
+                    .parse()
+                    .unwrap_or_else(|err| exit_with_error(err));
+            }
+            "-o" | "--output" => output_path = Some(PathBuf::from(flag_value(&arg, &mut args))),
+            "--config" => config_path = Some(PathBuf::from(flag_value(&arg, &mut args))),
+            // Whether the generated code imports the `jsrs-runtime` crate or contains the runtime
+            "--runtime" => {
+                let mode = flag_value(&arg, &mut args);
+                runtime = Some(mode.parse().unwrap_or_else(|err| exit_with_error(err)));
+            }
+            "--on-unsupported" => {
+                let mode = flag_value(&arg, &mut args);
+                on_unsupported = Some(mode.parse().unwrap_or_else(|err| exit_with_error(err)));
+            }
+            _ => sources.push(PathBuf::from(arg)),
+        }
+    }
+
+    // Values from the config file are only used where the command line doesn't say otherwise
+    let default_config_path = Path::new(config::FILE_NAME);
+    let config = match config_path {
+        Some(config_path) => Config::load(&config_path).unwrap_or_else(|err| exit_with_error(err)),
+        None if default_config_path.is_file() => {
+            Config::load(default_config_path).unwrap_or_else(|err| exit_with_error(err))
+        }
+        None => Config::default(),
+    };
+    if sources.is_empty() {
+        sources = config.entry;
+    }
+    if sources.is_empty() {
+        sources.push(PathBuf::from("./misc/n-body.js"));
+    }
+    let output_path = output_path.or(config.output);
+    let optimize = optimize.or(config.optimize).unwrap_or(true);
+    let options = TranspileOptions::default()
+        .with_on_unsupported(on_unsupported.or(config.on_unsupported).unwrap_or_default())
+        .with_runtime(runtime.or(config.runtime).unwrap_or_default());
+    let transpiler = Transpiler::new(options.clone());
+
+    let multiple_sources = sources.len() > 1;
+    let has_directory = sources.iter().any(|source| source.is_dir());
+    if (multiple_sources || has_directory) && output_path.is_none() && !check_only && !dry_run {
+        exit_with_error("transpiling a directory or multiple files requires --output <dir>");
+    }
+
+    if run_program {
+        let [source_path] = sources.as_slice() else {
+            exit_with_error("--run requires exactly one input file");
+        };
+        let exit_code = match transpile_to_rust(&native_transpiler(options), source_path) {
+            Ok(rust_text) => native::compile_and_run(&rust_text, &program_args, optimize),
+            Err(errors) => {
+                eprintln!("{errors}");
+                1
+            }
+        };
+        process::exit(exit_code);
+    }
+
+    let run = || {
+        if check_only {
+            return check_sources(&transpiler, &sources);
+        }
+
+        // Multiple inputs are transpiled in parallel, each into the output directory
+        if multiple_sources && !dry_run {
+            let output_dir = output_path.as_deref().unwrap();
+            let mut jobs = Vec::new();
+            for source_path in &sources {
+                let output_path = output_dir.join(source_path.file_name().unwrap_or_default());
+                if source_path.is_dir() {
+                    collect_dir_jobs(source_path, &output_path, emit, &mut jobs);
+                } else {
+                    jobs.push((
+                        source_path.clone(),
+                        output_path.with_extension(emit.extension()),
+                    ));
+                }
+            }
+            return transpile_files(&transpiler, &jobs, emit);
+        }
+
+        let mut success = true;
+        for source_path in &sources {
+            // With multiple inputs, the output is a directory containing the result of each
+            let output_path = match &output_path {
+                Some(output_dir) if multiple_sources => {
+                    Some(output_dir.join(source_path.file_name().unwrap_or_default()))
+                }
+                output_path => output_path.clone(),
+            };
+            success &= if source_path.is_dir() && dry_run {
+                report_dir_coverage(&transpiler, source_path)
+            } else if source_path.is_dir() {
+                transpile_dir(
+                    &transpiler,
+                    source_path,
+                    output_path.as_deref().unwrap(),
+                    emit,
+                )
+            } else {
+                transpile_file(
+                    &transpiler,
+                    source_path,
+                    dry_run,
+                    emit,
+                    output_path.as_deref(),
+                )
+            };
+        }
+        success
+    };
+
+    if watch {
+        watch_for_changes(&sources, || {
+            // A bug in the converter shouldn't end the watch, the panic message is
+            // already printed
+            let _ = panic::catch_unwind(AssertUnwindSafe(run));
+        });
+    } else if !run() {
+        process::exit(1);
+    }
+}
+
+/// A transpiler for programs that are compiled with `rustc` directly. Without cargo there
+/// are no dependencies, so the runtime has to be inlined.
+fn native_transpiler(options: TranspileOptions) -> Transpiler {
+    Transpiler::new(options.with_runtime(Runtime::Inline))
+}
+
+/// Returns the value following a command line flag
+fn flag_value(flag: &str, args: &mut impl Iterator<Item = String>) -> String {
+    args.next()
+        .unwrap_or_else(|| exit_with_error(format!("{flag} requires a value")))
+}
+
+fn exit_with_error(error: impl Display) -> ! {
+    eprintln!("error: {error}");
+    process::exit(1);
+}
+
+/// Transpiles a single file up to the `emit` stage, printing the result to stdout unless
+/// an `output_path` is given.
+///
+/// Returns whether the file was transpiled successfully.
+fn transpile_file(
+    transpiler: &Transpiler,
+    source_path: &Path,
+    dry_run: bool,
+    emit: Emit,
+    output_path: Option<&Path>,
+) -> bool {
+    let result = match output_path {
+        Some(output_path) if !dry_run => {
+            write_output(transpiler, source_path, output_path, |semantic, out| {
+                eprintln!("parsing and semantic analysis completed successfully.");
+                emit.write_to(semantic, transpiler.options(), out)
+            })
+            .map(|()| eprintln!("wrote {}", output_path.display()))
+        }
+        _ => transpile_with(transpiler, source_path, |semantic| {
+            eprintln!("parsing and semantic analysis completed successfully.");
+
+            if dry_run {
+                println!("{}", CoverageReport::new(semantic));
+            } else {
+                println!("{}", emit.render(semantic, transpiler.options())?);
+            }
+            Ok(())
+        }),
+    };
+    match result {
+        Ok(()) => true,
+        Err(errors) => {
+            eprintln!("{errors}");
+            false
+        }
+    }
+}
+
+/// Streams what `write` writes for a single file into the file at `output_path`.
+///
+/// The text goes into a temporary file next to it, which only replaces `output_path` once
+/// everything was written, so a failed transpilation doesn't leave half a file behind.
+fn write_output(
+    transpiler: &Transpiler,
+    source_path: &Path,
+    output_path: &Path,
+    write: impl FnOnce(&Semantic, &mut BufWriter<File>) -> Result<(), TranspileError>,
+) -> Result<(), String> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create {}: {err}", parent.display()))?;
+    }
+    let mut temp_name = output_path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = output_path.with_file_name(temp_name);
+    let file = File::create(&temp_path)
+        .map_err(|err| format!("failed to create {}: {err}", temp_path.display()))?;
+
+    let mut out = BufWriter::new(file);
+    let result = transpile_with(transpiler, source_path, |semantic| {
+        write(semantic, &mut out)
+    })
+    .and_then(|()| {
+        out.flush()
+            .map_err(|err| format!("failed to write {}: {err}", temp_path.display()))
+    });
+    drop(out);
+
+    match result {
+        Ok(()) => fs::rename(&temp_path, output_path)
+            .map_err(|err| format!("failed to write {}: {err}", output_path.display())),
+        Err(error) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(error)
+        }
+    }
+}
+
+/// Calls `run` once, then again every time one of the files (or any script in one of the
+/// directories) changes.
+fn watch_for_changes(source_paths: &[PathBuf], mut run: impl FnMut()) -> ! {
+    const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    let mut last_state = None;
+    loop {
+        let state = source_paths
+            .iter()
+            .flat_map(|source_path| watched_state(source_path))
+            .collect::<Vec<_>>();
+        if last_state.as_ref() != Some(&state) {
+            if last_state.is_some() {
+                eprintln!();
+                eprintln!("[watch] change detected, transpiling again");
+            }
+            last_state = Some(state);
+            run();
+            eprintln!("[watch] waiting for changes");
         }
-        panic!("Parsing failed.");
+        thread::sleep(POLL_INTERVAL);
     }
+}
 
-    // Step 2: Semantic analysis.
-    // Some of the more expensive syntax checks are deferred to this stage, and are
-    // enabled using `with_check_syntax_error`. You are not required to enable
-    // these, and they are disabled by default.
-    let SemanticBuilderReturn {
-        semantic,
-        errors: semantic_errors,
-    } = SemanticBuilder::new()
-        .with_check_syntax_error(true) // Enable extra syntax error checking
-        .with_build_jsdoc(true) // Enable JSDoc parsing
-        .with_cfg(true) // Build a Control Flow Graph
-        .build(&program); // Produce the `Semantic`
-
-    errors.extend(semantic_errors);
-    if errors.is_empty() {
-        eprintln!("parsing and semantic analysis completed successfully.");
+/// The modification times of every watched file. Comparing this against the previous
+/// state also catches files that were added or removed.
+fn watched_state(source_path: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut paths = Vec::new();
+    if source_path.is_dir() {
+        collect_script_paths(source_path, &mut paths);
+        paths.sort();
     } else {
-        for error in errors {
-            eprintln!("{error:?}");
+        paths.push(source_path.to_owned());
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Parses and analyzes every input without generating code, reporting all diagnostics.
+///
+/// Returns whether all inputs are free of errors.
+fn check_sources(transpiler: &Transpiler, sources: &[PathBuf]) -> bool {
+    let mut source_paths = Vec::new();
+    for source in sources {
+        if source.is_dir() {
+            let mut dir_paths = Vec::new();
+            collect_script_paths(source, &mut dir_paths);
+            dir_paths.sort();
+            source_paths.extend(dir_paths);
+        } else {
+            source_paths.push(source.clone());
         }
-        panic!("Failed to build Semantic for Counter component.");
     }
 
-    // println!("{:#?}", semantic.nodes().root_node().unwrap());
+    let results = parallel_map(&source_paths, |source_path| {
+        transpile_with(transpiler, source_path, |_| Ok(()))
+    });
 
-    println!();
-    println!();
-    println!();
-    println!();
+    let mut failed_count = 0;
+    for (source_path, result) in source_paths.iter().zip(results) {
+        if let Err(errors) = result {
+            failed_count += 1;
+            eprintln!("error: {} has errors", source_path.display());
+            eprintln!("{errors}");
+        }
+    }
 
-    let root = semantic.nodes().root_node().unwrap();
-    println!("{}", rust::node_to_rust_text(&root.kind()));
+    eprintln!(
+        "checked {} files, {failed_count} with errors",
+        source_paths.len()
+    );
+    failed_count == 0
 }
 
-fn print_nodes(ast_nodes: &AstNodes) {
-    let mut node_kinds = HashSet::new();
+/// Reads and converts a single file, returning the generated Rust program.
+fn transpile_to_rust(transpiler: &Transpiler, source_path: &Path) -> Result<String, String> {
+    transpiler
+        .transpile_file(source_path)
+        .map(|output| output.rust_text)
+        .map_err(|err| err.to_string())
+}
 
-    for node in ast_nodes.iter() {
-        match &node.kind() {
-            AstKind::BooleanLiteral(_) => {
-                node_kinds.insert("BooleanLiteral");
-            }
-            AstKind::NullLiteral(_) => {
-                node_kinds.insert("NullLiteral");
-            }
-            AstKind::NumericLiteral(_) => {
-                node_kinds.insert("NumericLiteral");
-            }
-            AstKind::BigIntLiteral(_) => {
-                node_kinds.insert("BigIntLiteral");
-            }
-            AstKind::RegExpLiteral(_) => {
-                node_kinds.insert("RegExpLiteral");
-            }
-            AstKind::StringLiteral(_) => {
-                node_kinds.insert("StringLiteral");
-            }
-            AstKind::Program(_) => {
-                node_kinds.insert("Program");
-            }
-            AstKind::IdentifierName(_) => {
-                node_kinds.insert("IdentifierName");
-            }
-            AstKind::IdentifierReference(_) => {
-                node_kinds.insert("IdentifierReference");
-            }
-            AstKind::BindingIdentifier(_) => {
-                node_kinds.insert("BindingIdentifier");
-            }
-            AstKind::LabelIdentifier(_) => {
-                node_kinds.insert("LabelIdentifier");
-            }
-            AstKind::ThisExpression(_) => {
-                node_kinds.insert("ThisExpression");
-            }
-            AstKind::ArrayExpression(_) => {
-                node_kinds.insert("ArrayExpression");
-            }
-            AstKind::ArrayExpressionElement(_) => {
-                node_kinds.insert("ArrayExpressionElement");
-            }
-            AstKind::Elision(_) => {
-                node_kinds.insert("Elision");
-            }
-            AstKind::ObjectExpression(_) => {
-                node_kinds.insert("ObjectExpression");
-            }
-            AstKind::ObjectProperty(_) => {
-                node_kinds.insert("ObjectProperty");
-            }
-            AstKind::PropertyKey(_) => {
-                node_kinds.insert("PropertyKey");
-            }
-            AstKind::TemplateLiteral(_) => {
-                node_kinds.insert("TemplateLiteral");
-            }
-            AstKind::TaggedTemplateExpression(_) => {
-                node_kinds.insert("TaggedTemplateExpression");
-            }
-            AstKind::MemberExpression(_) => {
-                node_kinds.insert("MemberExpression");
-            }
-            AstKind::CallExpression(_) => {
-                node_kinds.insert("CallExpression");
-            }
-            AstKind::NewExpression(_) => {
-                node_kinds.insert("NewExpression");
-            }
-            AstKind::MetaProperty(_) => {
-                node_kinds.insert("MetaProperty");
-            }
-            AstKind::SpreadElement(_) => {
-                node_kinds.insert("SpreadElement");
-            }
-            AstKind::Argument(_) => {
-                node_kinds.insert("Argument");
-            }
-            AstKind::UpdateExpression(_) => {
-                node_kinds.insert("UpdateExpression");
-            }
-            AstKind::UnaryExpression(_) => {
-                node_kinds.insert("UnaryExpression");
-            }
-            AstKind::BinaryExpression(_) => {
-                node_kinds.insert("BinaryExpression");
-            }
-            AstKind::PrivateInExpression(_) => {
-                node_kinds.insert("PrivateInExpression");
-            }
-            AstKind::LogicalExpression(_) => {
-                node_kinds.insert("LogicalExpression");
-            }
-            AstKind::ConditionalExpression(_) => {
-                node_kinds.insert("ConditionalExpression");
-            }
-            AstKind::AssignmentExpression(_) => {
-                node_kinds.insert("AssignmentExpression");
-            }
-            AstKind::AssignmentTarget(_) => {
-                node_kinds.insert("AssignmentTarget");
-            }
-            AstKind::SimpleAssignmentTarget(_) => {
-                node_kinds.insert("SimpleAssignmentTarget");
-            }
-            AstKind::AssignmentTargetPattern(_) => {
-                node_kinds.insert("AssignmentTargetPattern");
-            }
-            AstKind::ArrayAssignmentTarget(_) => {
-                node_kinds.insert("ArrayAssignmentTarget");
-            }
-            AstKind::ObjectAssignmentTarget(_) => {
-                node_kinds.insert("ObjectAssignmentTarget");
-            }
-            AstKind::AssignmentTargetWithDefault(_) => {
-                node_kinds.insert("AssignmentTargetWithDefault");
-            }
-            AstKind::SequenceExpression(_) => {
-                node_kinds.insert("SequenceExpression");
-            }
-            AstKind::Super(_) => {
-                node_kinds.insert("Super");
-            }
-            AstKind::AwaitExpression(_) => {
-                node_kinds.insert("AwaitExpression");
-            }
-            AstKind::ChainExpression(_) => {
-                node_kinds.insert("ChainExpression");
-            }
-            AstKind::ParenthesizedExpression(_) => {
-                node_kinds.insert("ParenthesizedExpression");
-            }
-            AstKind::Directive(_) => {
-                node_kinds.insert("Directive");
-            }
-            AstKind::Hashbang(_) => {
-                node_kinds.insert("Hashbang");
-            }
-            AstKind::BlockStatement(_) => {
-                node_kinds.insert("BlockStatement");
-            }
-            AstKind::VariableDeclaration(_) => {
-                node_kinds.insert("VariableDeclaration");
-            }
-            AstKind::VariableDeclarator(_) => {
-                node_kinds.insert("VariableDeclarator");
-            }
-            AstKind::EmptyStatement(_) => {
-                node_kinds.insert("EmptyStatement");
-            }
-            AstKind::ExpressionStatement(_) => {
-                node_kinds.insert("ExpressionStatement");
-            }
-            AstKind::IfStatement(_) => {
-                node_kinds.insert("IfStatement");
-            }
-            AstKind::DoWhileStatement(_) => {
-                node_kinds.insert("DoWhileStatement");
-            }
-            AstKind::WhileStatement(_) => {
-                node_kinds.insert("WhileStatement");
-            }
-            AstKind::ForStatement(_) => {
-                node_kinds.insert("ForStatement");
-            }
-            AstKind::ForStatementInit(_) => {
-                node_kinds.insert("ForStatementInit");
-            }
-            AstKind::ForInStatement(_) => {
-                node_kinds.insert("ForInStatement");
-            }
-            AstKind::ForOfStatement(_) => {
-                node_kinds.insert("ForOfStatement");
-            }
-            AstKind::ContinueStatement(_) => {
-                node_kinds.insert("ContinueStatement");
-            }
-            AstKind::BreakStatement(_) => {
-                node_kinds.insert("BreakStatement");
-            }
-            AstKind::ReturnStatement(_) => {
-                node_kinds.insert("ReturnStatement");
-            }
-            AstKind::WithStatement(_) => {
-                node_kinds.insert("WithStatement");
-            }
-            AstKind::SwitchStatement(_) => {
-                node_kinds.insert("SwitchStatement");
-            }
-            AstKind::SwitchCase(_) => {
-                node_kinds.insert("SwitchCase");
-            }
-            AstKind::LabeledStatement(_) => {
-                node_kinds.insert("LabeledStatement");
-            }
-            AstKind::ThrowStatement(_) => {
-                node_kinds.insert("ThrowStatement");
-            }
-            AstKind::TryStatement(_) => {
-                node_kinds.insert("TryStatement");
-            }
-            AstKind::CatchClause(_) => {
-                node_kinds.insert("CatchClause");
-            }
-            AstKind::CatchParameter(_) => {
-                node_kinds.insert("CatchParameter");
-            }
-            AstKind::DebuggerStatement(_) => {
-                node_kinds.insert("DebuggerStatement");
-            }
-            AstKind::AssignmentPattern(_) => {
-                node_kinds.insert("AssignmentPattern");
-            }
-            AstKind::ObjectPattern(_) => {
-                node_kinds.insert("ObjectPattern");
-            }
-            AstKind::ArrayPattern(_) => {
-                node_kinds.insert("ArrayPattern");
-            }
-            AstKind::BindingRestElement(_) => {
-                node_kinds.insert("BindingRestElement");
-            }
-            AstKind::Function(_) => {
-                node_kinds.insert("Function");
-            }
-            AstKind::FormalParameters(_) => {
-                node_kinds.insert("FormalParameters");
-            }
-            AstKind::FormalParameter(_) => {
-                node_kinds.insert("FormalParameter");
-            }
-            AstKind::FunctionBody(_) => {
-                node_kinds.insert("FunctionBody");
-            }
-            AstKind::ArrowFunctionExpression(_) => {
-                node_kinds.insert("ArrowFunctionExpression");
-            }
-            AstKind::YieldExpression(_) => {
-                node_kinds.insert("YieldExpression");
-            }
-            AstKind::Class(_) => {
-                node_kinds.insert("Class");
-            }
-            AstKind::ClassBody(_) => {
-                node_kinds.insert("ClassBody");
-            }
-            AstKind::MethodDefinition(_) => {
-                node_kinds.insert("MethodDefinition");
-            }
-            AstKind::PropertyDefinition(_) => {
-                node_kinds.insert("PropertyDefinition");
-            }
-            AstKind::PrivateIdentifier(_) => {
-                node_kinds.insert("PrivateIdentifier");
-            }
-            AstKind::StaticBlock(_) => {
-                node_kinds.insert("StaticBlock");
-            }
-            AstKind::ModuleDeclaration(_) => {
-                node_kinds.insert("ModuleDeclaration");
-            }
-            AstKind::ImportExpression(_) => {
-                node_kinds.insert("ImportExpression");
-            }
-            AstKind::ImportDeclaration(_) => {
-                node_kinds.insert("ImportDeclaration");
-            }
-            AstKind::ImportSpecifier(_) => {
-                node_kinds.insert("ImportSpecifier");
-            }
-            AstKind::ImportDefaultSpecifier(_) => {
-                node_kinds.insert("ImportDefaultSpecifier");
-            }
-            AstKind::ImportNamespaceSpecifier(_) => {
-                node_kinds.insert("ImportNamespaceSpecifier");
-            }
-            AstKind::ExportNamedDeclaration(_) => {
-                node_kinds.insert("ExportNamedDeclaration");
-            }
-            AstKind::ExportDefaultDeclaration(_) => {
-                node_kinds.insert("ExportDefaultDeclaration");
-            }
-            AstKind::ExportAllDeclaration(_) => {
-                node_kinds.insert("ExportAllDeclaration");
-            }
-            AstKind::ExportSpecifier(_) => {
-                node_kinds.insert("ExportSpecifier");
-            }
-            AstKind::Decorator(_) => {
-                node_kinds.insert("Decorator");
-            }
-            _ => unimplemented!(),
+/// Reads a single file and hands the result of its semantic analysis to `f`.
+///
+/// Returns the parser, semantic and converter errors rendered with the source, if there were any.
+fn transpile_with<T>(
+    transpiler: &Transpiler,
+    source_path: &Path,
+    f: impl FnOnce(&Semantic) -> Result<T, TranspileError>,
+) -> Result<T, String> {
+    let source_text = fs::read_to_string(source_path)
+        .map_err(|err| format!("failed to read {}: {err}", source_path.display()))?;
+    transpiler
+        .with_semantic(source_path, &source_text, f)
+        .map_err(|err| err.to_string())
+}
+
+/// Transpiles every script in `source_dir` into a mirrored tree of `.rs` files under `output_dir`,
+/// or of the files of another `emit` stage.
+fn transpile_dir(
+    transpiler: &Transpiler,
+    source_dir: &Path,
+    output_dir: &Path,
+    emit: Emit,
+) -> bool {
+    let mut jobs = Vec::new();
+    collect_dir_jobs(source_dir, output_dir, emit, &mut jobs);
+    transpile_files(transpiler, &jobs, emit)
+}
+
+/// Pairs every script in `source_dir` with its path in the mirrored tree under `output_dir`.
+/// Reports which nodes of each script in the directory can't be transpiled yet, without
+/// writing anything.
+fn report_dir_coverage(transpiler: &Transpiler, source_dir: &Path) -> bool {
+    let mut source_paths = Vec::new();
+    collect_script_paths(source_dir, &mut source_paths);
+    source_paths.sort();
+
+    let mut success = true;
+    for source_path in &source_paths {
+        println!("{}", source_path.display());
+        success &= transpile_file(transpiler, source_path, true, Emit::default(), None);
+    }
+    success
+}
+
+fn collect_dir_jobs(
+    source_dir: &Path,
+    output_dir: &Path,
+    emit: Emit,
+    jobs: &mut Vec<(PathBuf, PathBuf)>,
+) {
+    let mut source_paths = Vec::new();
+    collect_script_paths(source_dir, &mut source_paths);
+    source_paths.sort();
+
+    jobs.extend(source_paths.into_iter().map(|source_path| {
+        let relative_path = source_path.strip_prefix(source_dir).unwrap();
+        let output_path = output_dir
+            .join(relative_path)
+            .with_extension(emit.extension());
+        (source_path, output_path)
+    }));
+}
+
+/// Transpiles each source file of `jobs` into its output path, with the files spread over
+/// all cores.
+///
+/// A file that fails to transpile doesn't stop the others, the errors are collected and
+/// reported at the end in the order of `jobs`. Returns whether all files were transpiled
+/// successfully.
+fn transpile_files(transpiler: &Transpiler, jobs: &[(PathBuf, PathBuf)], emit: Emit) -> bool {
+    // Panic messages are reported together with the other errors instead
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let results = parallel_map(jobs, |(source_path, output_path)| {
+        // A bug in the converter shouldn't abort the whole run
+        panic::catch_unwind(|| {
+            write_output(transpiler, source_path, output_path, |semantic, out| {
+                emit.write_to(semantic, transpiler.options(), out)
+            })
+        })
+        .unwrap_or_else(|payload| Err(panic_message(&*payload)))
+    });
+
+    panic::set_hook(default_hook);
+
+    let mut failures = Vec::new();
+    for ((source_path, output_path), result) in jobs.iter().zip(results) {
+        match result {
+            Ok(()) => eprintln!("{} -> {}", source_path.display(), output_path.display()),
+            Err(error) => failures.push((source_path, error)),
         }
     }
 
-    println!("Node kinds: ");
-    for kind in node_kinds {
-        println!("{}", kind);
+    for (source_path, error) in &failures {
+        eprintln!();
+        eprintln!("error: failed to transpile {}", source_path.display());
+        eprintln!("{error}");
+    }
+    eprintln!();
+    eprintln!(
+        "transpiled {} of {} files",
+        jobs.len() - failures.len(),
+        jobs.len()
+    );
+
+    failures.is_empty()
+}
+
+/// Calls `f` on every item using one worker thread per core, and returns the results in the
+/// order of the items.
+///
+/// Each file is parsed into the `Allocator` of its own `Transpiler::with_semantic` call, so nothing is
+/// shared between the workers but the items themselves.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let worker_count = thread::available_parallelism()
+        .map_or(1, |count| count.get())
+        .min(items.len());
+    let next_index = AtomicUsize::new(0);
+
+    let mut results = thread::scope(|scope| {
+        let workers = (0..worker_count)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break results;
+                        };
+                        results.push((index, f(item)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn collect_script_paths(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_script_paths(&path, paths);
+        } else if matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("js" | "mjs" | "ts")
+        ) {
+            paths.push(path);
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("the converter panicked")
     }
 }
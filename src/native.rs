@@ -0,0 +1,64 @@
+//! Compiling the generated Rust programs into native executables and running them.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+/// Compiles the generated program with `rustc`, writing the executable into `dir`.
+///
+/// Returns the path of the executable.
+pub fn compile(rust_text: &str, dir: &Path, optimize: bool) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("failed to create {}: {err}", dir.display()))?;
+
+    let source_path = dir.join("main.rs");
+    fs::write(&source_path, rust_text)
+        .map_err(|err| format!("failed to write {}: {err}", source_path.display()))?;
+
+    let executable_path = dir.join(format!("main{}", env::consts::EXE_SUFFIX));
+    let mut rustc = Command::new("rustc");
+    rustc
+        .args(["--edition", "2021", "--crate-name", "main"])
+        // The prelude is included as a whole, most programs only use a fraction of it
+        .args(["-A", "warnings"])
+        .arg(&source_path)
+        .arg("-o")
+        .arg(&executable_path);
+    if optimize {
+        rustc.arg("-O");
+    }
+
+    let status = rustc
+        .status()
+        .map_err(|err| format!("failed to start rustc: {err}"))?;
+    if !status.success() {
+        return Err(format!(
+            "rustc failed to compile the generated program ({status})"
+        ));
+    }
+    Ok(executable_path)
+}
+
+/// Compiles the generated program in a temporary directory and runs it with `args`.
+///
+/// The program inherits stdout and stderr. Returns the exit code of the program.
+pub fn compile_and_run(rust_text: &str, args: &[String], optimize: bool) -> i32 {
+    let dir = env::temp_dir().join(format!("jsrs-run-{}", process::id()));
+    let result = compile(rust_text, &dir, optimize).and_then(|executable_path| {
+        Command::new(&executable_path)
+            .args(args)
+            .status()
+            .map_err(|err| format!("failed to start {}: {err}", executable_path.display()))
+    });
+    let _ = fs::remove_dir_all(&dir);
+
+    match result {
+        // A missing exit code means the program was killed by a signal
+        Ok(status) => status.code().unwrap_or(1),
+        Err(error) => {
+            eprintln!("error: {error}");
+            1
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Scaffolding for standalone Cargo projects that contain a transpiled program.
+
+use std::{fs, path::Path};
+
+use jsrs::{
+    rust::{OUTPUT_PRELUDE, RUNTIME_DIR},
+    Runtime,
+};
+
+/// Creates a Cargo project in `project_dir` that builds the generated `main_fn`.
+///
+/// With `Runtime::Crate` the project depends on the `jsrs-runtime` crate in the source tree that
+/// jsrs was built from. With `Runtime::Inline` the runtime is written into its own `runtime`
+/// module instead, and `main_fn` is expected to have been generated with the runtime in scope.
+pub fn create(project_dir: &Path, main_fn: &str, runtime: Runtime) -> Result<(), String> {
+    let src_dir = project_dir.join("src");
+    fs::create_dir_all(&src_dir)
+        .map_err(|err| format!("failed to create {}: {err}", src_dir.display()))?;
+
+    let mut files = vec![
+        (
+            project_dir.join("Cargo.toml"),
+            cargo_toml(project_dir, runtime),
+        ),
+        (src_dir.join("main.rs"), main_rs(main_fn, runtime)),
+    ];
+    if runtime != Runtime::Crate {
+        files.push((src_dir.join("runtime.rs"), runtime_rs()));
+    }
+    for (path, contents) in files {
+        fs::write(&path, contents)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn cargo_toml(project_dir: &Path, runtime: Runtime) -> String {
+    let name = package_name(project_dir);
+    let dependencies = match runtime {
+        Runtime::Crate => format!("jsrs-runtime = {{ path = {RUNTIME_DIR:?} }}\n"),
+        Runtime::Inline | Runtime::InScope => String::new(),
+    };
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+{dependencies}"#
+    )
+}
+
+/// Cargo package names may only contain alphanumerics, `-` and `_`
+fn package_name(project_dir: &Path) -> String {
+    let dir_name = project_dir
+        .canonicalize()
+        .ok()
+        .and_then(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_default();
+    let name = dir_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("jsrs_{name}")
+    }
+}
+
+fn main_rs(main_fn: &str, runtime: Runtime) -> String {
+    // The generated code imports the crate itself
+    let runtime_module = match runtime {
+        Runtime::Crate => "",
+        Runtime::Inline | Runtime::InScope => "mod runtime;\n\nuse runtime::*;\n\n",
+    };
+    format!(
+        "// Generated by jsrs\n\
+        // The generated code doesn't try to be idiomatic, so warnings are expected\n\
+        #![allow(warnings)]\n\
+        \n\
+        {runtime_module}\
+        {main_fn}\n"
+    )
+}
+
+fn runtime_rs() -> String {
+    format!("#![allow(warnings)]\n\n{OUTPUT_PRELUDE}")
+}
@@ -0,0 +1,854 @@
+use super::{
+    generators::{RESUME, SENT, STATE},
+    ir::{Entry, Expr, FunctionInfo, ReturnTo, Stmt, StmtKind},
+    RustWriter, SpanMapping,
+};
+use crate::TranspileError;
+
+/// Prints the IR as Rust code, keeping track of the indentation.
+pub(crate) struct Emitter<'w> {
+    w: RustWriter<'w>,
+    /// The nesting depth of the Rust code being written
+    indent: usize,
+}
+
+impl<'w> Emitter<'w> {
+    pub fn new(w: RustWriter<'w>) -> Self {
+        Emitter { w, indent: 0 }
+    }
+
+    /// The span mappings of everything written.
+    pub fn finish(self) -> Result<Vec<SpanMapping>, TranspileError> {
+        self.w.finish()
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.w.push_str(text);
+    }
+
+    /// Lets `write!` be used on the emitter.
+    pub fn write_fmt(&mut self, args: std::fmt::Arguments) {
+        self.w.write_fmt(args);
+    }
+
+    /// Starts a new line at the current indentation.
+    pub fn newline(&mut self) {
+        self.w.push_str("\n");
+        for _ in 0..self.indent {
+            self.w.push_str("    ");
+        }
+    }
+
+    /// Writes what `f` writes one level deeper, which is only visible after the next `newline`.
+    pub fn indented(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indent += 1;
+        f(self);
+        self.indent -= 1;
+    }
+
+    /// Writes every item with `emit_item`, with the `separator` between them.
+    fn emit_separated<T>(
+        &mut self,
+        items: impl IntoIterator<Item = T>,
+        separator: &str,
+        mut emit_item: impl FnMut(&mut Self, T),
+    ) {
+        for (index, item) in items.into_iter().enumerate() {
+            if index > 0 {
+                self.push_str(separator);
+            }
+            emit_item(self, item);
+        }
+    }
+
+    /// Writes the statements in braces, each on a line of its own.
+    fn emit_body(&mut self, body: &[Stmt]) {
+        self.push_str("{");
+        self.indented(|this| {
+            for stmt in body {
+                this.newline();
+                this.emit_stmt(stmt);
+            }
+        });
+        self.newline();
+        self.push_str("}");
+    }
+
+    /// The text of statements that were lowered from a JS statement is mapped to its span.
+    pub fn emit_stmt(&mut self, stmt: &Stmt) {
+        let mapping = stmt.span.map(|span| self.w.start_mapping(span));
+        match &stmt.kind {
+            StmtKind::Let {
+                name,
+                mutable,
+                init,
+            } => {
+                let mutable = if *mutable { "mut " } else { "" };
+                write!(self, "let {mutable}{name}");
+                if let Some(init) = init {
+                    self.push_str(" = ");
+                    self.emit_expr(init);
+                }
+                self.push_str(";");
+            }
+            StmtKind::Expr(expr) => {
+                self.emit_expr(expr);
+                self.push_str(";");
+            }
+            StmtKind::Return(value) => {
+                self.push_str("return");
+                if let Some(value) = value {
+                    self.push_str(" ");
+                    self.emit_expr(value);
+                }
+                self.push_str(";");
+            }
+            StmtKind::ReturnFromTry(value) => {
+                self.push_str("return Completion::Return(");
+                self.emit_expr(value);
+                self.push_str(");");
+            }
+            StmtKind::Throw(value) => {
+                self.push_str("throw(");
+                self.emit_expr(value);
+                self.push_str(");");
+            }
+            StmtKind::Try {
+                result,
+                body,
+                catch,
+                finally,
+                returns,
+            } => {
+                self.push_str("{");
+                self.indented(|this| {
+                    this.newline();
+                    write!(this, "let mut {result} = ");
+                    this.emit_try_block(body);
+                    this.push_str(";");
+                    if let Some(catch) = catch {
+                        this.newline();
+                        match catch.param.as_str() {
+                            "_" => write!(this, "if let Err(_) = {result} {{"),
+                            param => write!(this, "if let Err(mut {param}) = {result} {{"),
+                        }
+                        this.indented(|this| {
+                            this.newline();
+                            write!(this, "{result} = ");
+                            if catch.guarded {
+                                this.emit_try_block(&catch.body);
+                                this.push_str(";");
+                            } else {
+                                this.push_str("Ok(Completion::Normal);");
+                                for stmt in &catch.body {
+                                    this.newline();
+                                    this.emit_stmt(stmt);
+                                }
+                            }
+                        });
+                        this.newline();
+                        this.push_str("}");
+                    }
+                    if let Some(finally) = finally {
+                        this.newline();
+                        this.emit_body(finally);
+                    }
+                    this.newline();
+                    match returns {
+                        ReturnTo::Nothing => write!(this, "complete({result});"),
+                        ReturnTo::Function => write!(
+                            this,
+                            "if let Completion::Return(value) = complete({result}) {{ return value; }}"
+                        ),
+                        ReturnTo::Try => write!(
+                            this,
+                            "if let Completion::Return(value) = complete({result}) {{ return Completion::Return(value); }}"
+                        ),
+                        ReturnTo::Generator => write!(
+                            this,
+                            "if let Completion::Return(value) = complete({result}) {{ return GeneratorStep::Return(value); }}"
+                        ),
+                    }
+                });
+                self.newline();
+                self.push_str("}");
+            }
+            StmtKind::Block(body) => self.emit_body(body),
+            StmtKind::Group(body) => {
+                for (index, stmt) in body.iter().enumerate() {
+                    if index > 0 {
+                        self.newline();
+                    }
+                    self.emit_stmt(stmt);
+                }
+            }
+            StmtKind::Loop { label, body } => {
+                self.emit_label(label.as_deref());
+                self.push_str("loop ");
+                self.emit_body(body);
+            }
+            StmtKind::Labeled { label, body } => {
+                self.emit_label(Some(label));
+                self.emit_body(body);
+            }
+            StmtKind::If {
+                test,
+                then,
+                otherwise,
+            } => {
+                // Only an `else` reads better as a negated test
+                let (method, then, otherwise) = match then.is_empty() {
+                    true => ("falsy", otherwise, then),
+                    false => ("truthy", then, otherwise),
+                };
+                self.push_str("if ");
+                self.emit_receiver(test);
+                write!(self, ".{method}() ");
+                self.emit_body(then);
+                match otherwise.as_slice() {
+                    [] => {}
+                    // An else-if chain
+                    [stmt @ Stmt {
+                        kind: StmtKind::If { .. },
+                        ..
+                    }] => {
+                        self.push_str(" else ");
+                        self.emit_stmt(stmt);
+                    }
+                    otherwise => {
+                        self.push_str(" else ");
+                        self.emit_body(otherwise);
+                    }
+                }
+            }
+            StmtKind::ForEach {
+                label,
+                name,
+                mutable,
+                iteration,
+                object,
+                body,
+            } => {
+                let mutable = if *mutable { "mut " } else { "" };
+                self.emit_label(label.as_deref());
+                write!(self, "for {mutable}{name} in ");
+                self.emit_receiver(object);
+                write!(self, ".{}() ", iteration.method());
+                self.emit_body(body);
+            }
+            StmtKind::Break(label) => self.emit_jump("break", label.as_deref()),
+            StmtKind::Continue(label) => self.emit_jump("continue", label.as_deref()),
+            StmtKind::Yield { value, resume } => {
+                write!(self, "{STATE} = {resume};");
+                self.newline();
+                self.push_str("return GeneratorStep::Yield(");
+                self.emit_expr(value);
+                self.push_str(");");
+            }
+            StmtKind::Goto(state) => self.emit_goto(*state),
+            StmtKind::NextItem {
+                iterator,
+                name,
+                done,
+            } => {
+                write!(self, "{name} = match {iterator}.next_item() {{");
+                self.indented(|this| {
+                    this.newline();
+                    this.push_str("Some(item) => item,");
+                    this.newline();
+                    this.push_str("None => {");
+                    this.indented(|this| {
+                        this.newline();
+                        this.emit_goto(*done);
+                    });
+                    this.newline();
+                    this.push_str("}");
+                });
+                self.newline();
+                self.push_str("};");
+            }
+            StmtKind::ReturnFromGenerator(value) => {
+                self.push_str("return GeneratorStep::Return(");
+                self.emit_expr(value);
+                self.push_str(");");
+            }
+        }
+        if let Some(mapping) = mapping {
+            self.w.end_mapping(mapping);
+        }
+    }
+
+    /// Writes the statements in a closure that `try_block` runs.
+    fn emit_try_block(&mut self, body: &[Stmt]) {
+        self.push_str("try_block(|| {");
+        self.indented(|this| {
+            for stmt in body {
+                this.newline();
+                this.emit_stmt(stmt);
+            }
+            this.newline();
+            this.push_str("Completion::Normal");
+        });
+        self.newline();
+        self.push_str("})");
+    }
+
+    /// Writes the closure of a generator, which runs through the states from the one that it's
+    /// at, in a loop that `Goto` continues.
+    fn emit_generator(
+        &mut self,
+        copies: &[String],
+        locals: &[String],
+        shared: &[String],
+        states: &[Vec<Stmt>],
+    ) {
+        self.push_str("{");
+        self.indented(|this| {
+            for name in copies {
+                this.newline();
+                write!(this, "let {name} = {name}.clone();");
+            }
+            for name in locals {
+                this.newline();
+                write!(this, "let mut {name} = JsValue::Undefined;");
+            }
+            for name in shared {
+                this.newline();
+                write!(
+                    this,
+                    "let mut {name} = SharedBinding::new(JsValue::Undefined);"
+                );
+            }
+            this.newline();
+            write!(this, "let mut {STATE} = 0;");
+            this.newline();
+            write!(
+                this,
+                "JsValue::new_generator(Box::new(move |{SENT}: JsValue| -> GeneratorStep {{"
+            );
+            this.indented(|this| {
+                this.newline();
+                write!(this, "{RESUME}: loop {{");
+                this.indented(|this| {
+                    this.newline();
+                    write!(this, "match {STATE} {{");
+                    this.indented(|this| {
+                        for (index, state) in states.iter().enumerate() {
+                            this.newline();
+                            write!(this, "{index} => ");
+                            this.emit_body(state);
+                        }
+                        this.newline();
+                        this.push_str("_ => unreachable!(),");
+                    });
+                    this.newline();
+                    this.push_str("}");
+                });
+                this.newline();
+                this.push_str("}");
+            });
+            this.newline();
+            this.push_str("}))");
+        });
+        self.newline();
+        self.push_str("}");
+    }
+
+    fn emit_goto(&mut self, state: usize) {
+        write!(self, "{STATE} = {state};");
+        self.newline();
+        write!(self, "continue {RESUME};");
+    }
+
+    fn emit_label(&mut self, label: Option<&str>) {
+        if let Some(label) = label {
+            write!(self, "{label}: ");
+        }
+    }
+
+    fn emit_jump(&mut self, keyword: &str, label: Option<&str>) {
+        match label {
+            Some(label) => write!(self, "{keyword} {label};"),
+            None => write!(self, "{keyword};"),
+        }
+    }
+
+    pub fn emit_expr(&mut self, expr: &Expr) {
+        match expr {
+            // The debug format is always a float literal, so large integers don't overflow
+            Expr::Number(value) if value.is_finite() => write!(self, "JsValue::Number({value:?})"),
+            Expr::Number(value) if value.is_nan() => self.push_str("JsValue::Number(f64::NAN)"),
+            Expr::Number(value) if *value > 0.0 => self.push_str("JsValue::Number(f64::INFINITY)"),
+            Expr::Number(_) => self.push_str("JsValue::Number(f64::NEG_INFINITY)"),
+            Expr::Boolean(value) => write!(self, "JsValue::Boolean({value})"),
+            Expr::Null => self.push_str("JsValue::Null"),
+            Expr::Undefined => self.push_str("JsValue::Undefined"),
+            Expr::String(value) => write!(self, "JsValue::from({value:?})"),
+            Expr::RegExp { pattern, flags } => {
+                write!(self, "JsValue::new_regexp({pattern:?}, {flags:?})")
+            }
+            Expr::Local(name) => self.push_str(name),
+            Expr::Intrinsic(rust) => self.push_str(rust),
+            Expr::Assign { target, value } => {
+                write!(self, "{target} = ");
+                self.emit_expr(value);
+            }
+            Expr::NewShared(value) => {
+                self.push_str("SharedBinding::new(");
+                self.emit_expr(value);
+                self.push_str(")");
+            }
+            Expr::GetShared(name) => write!(self, "{name}.get()"),
+            Expr::SetShared { target, value } => {
+                write!(self, "{target}.set(");
+                self.emit_expr(value);
+                self.push_str(")");
+            }
+            Expr::Binary { op, left, right } => {
+                self.emit_receiver(left);
+                write!(self, ".{}(", op.method());
+                self.emit_expr(right);
+                self.push_str(")");
+            }
+            Expr::Unary { op, argument } => {
+                write!(self, "{}(", op.function());
+                self.emit_expr(argument);
+                self.push_str(")");
+            }
+            Expr::Logical {
+                op,
+                temp,
+                left,
+                right,
+            } => {
+                write!(self, "{{ let {temp} = ");
+                self.emit_expr(left);
+                write!(self, "; if {temp}.{}() {{ ", op.test());
+                self.emit_expr(right);
+                write!(self, " }} else {{ {temp} }} }}");
+            }
+            Expr::AssignIf {
+                op,
+                current,
+                assign,
+            } => {
+                self.push_str("if ");
+                self.emit_receiver(current);
+                write!(self, ".{}() {{ ", op.test());
+                self.emit_expr(assign);
+                self.push_str("; }");
+            }
+            Expr::Conditional {
+                test,
+                consequent,
+                alternate,
+            } => {
+                self.push_str("if ");
+                self.emit_receiver(test);
+                self.push_str(".truthy() { ");
+                self.emit_expr(consequent);
+                self.push_str(" } else { ");
+                self.emit_expr(alternate);
+                self.push_str(" }");
+            }
+            Expr::GetProp { object, key } => self.emit_method(object, "get_prop", &[key]),
+            Expr::SetProp { object, key, value } => {
+                self.emit_method(object, "set_prop", &[key, value])
+            }
+            Expr::DeleteProp { object, key } => self.emit_method(object, "delete_prop", &[key]),
+            Expr::GetPrivate { object, name } => {
+                self.emit_receiver(object);
+                write!(self, ".get_private({name:?})");
+            }
+            Expr::SetPrivate {
+                object,
+                name,
+                value,
+            } => {
+                self.emit_receiver(object);
+                write!(self, ".set_private({name:?}, ");
+                self.emit_expr(value);
+                self.push_str(")");
+            }
+            Expr::HasPrivate { object, name } => {
+                self.emit_receiver(object);
+                write!(self, ".has_private({name:?})");
+            }
+            Expr::SliceFrom { array, start } => {
+                self.emit_receiver(array);
+                write!(self, ".slice_from({start})");
+            }
+            Expr::CallFunction { callee, arguments } => {
+                self.emit_receiver(callee);
+                self.push_str("(&[");
+                self.emit_separated(arguments, ", ", Self::emit_expr);
+                self.push_str("])");
+            }
+            Expr::CallValue { callee, arguments } => {
+                self.emit_receiver(callee);
+                self.push_str(".call(");
+                self.emit_arguments(arguments);
+                self.push_str(")");
+            }
+            Expr::CallMethod {
+                object,
+                key,
+                arguments,
+            } => {
+                self.emit_receiver(object);
+                self.push_str(".call_method(");
+                self.emit_expr(key);
+                self.push_str(", ");
+                self.emit_arguments(arguments);
+                self.push_str(")");
+            }
+            Expr::CallWithThis {
+                callee,
+                this,
+                arguments,
+            } => {
+                self.emit_receiver(callee);
+                self.push_str(".call_with_this(&");
+                self.emit_receiver(this);
+                self.push_str(", ");
+                self.emit_arguments(arguments);
+                self.push_str(")");
+            }
+            Expr::New { callee, arguments } => {
+                self.emit_receiver(callee);
+                self.push_str(".construct(");
+                self.emit_arguments(arguments);
+                self.push_str(")");
+            }
+            Expr::Array(elements) => {
+                self.push_str("JsValue::new_array(");
+                self.emit_entries(elements, "iter_elements", Self::emit_expr);
+                self.push_str(")");
+            }
+            Expr::Object(entries) => {
+                self.push_str("JsValue::from_entries(");
+                self.emit_entries(entries, "own_entries", |this, (key, value)| {
+                    match key {
+                        Expr::String(key) => write!(this, "({key:?}.into(), "),
+                        key => {
+                            this.push_str("(");
+                            this.emit_method(key, "to_property_key", &[]);
+                            this.push_str(", ");
+                        }
+                    }
+                    this.emit_expr(value);
+                    this.push_str(")");
+                });
+                self.push_str(")");
+            }
+            Expr::TemplateLiteral {
+                quasis,
+                substitutions,
+            } => {
+                self.push_str("template_literal(&[");
+                self.emit_separated(quasis, ", ", |this, quasi| write!(this, "{quasi:?}"));
+                self.push_str("], &[");
+                self.emit_separated(substitutions, ", ", Self::emit_expr);
+                self.push_str("])");
+            }
+            Expr::TemplateStrings { cooked, raw } => {
+                self.push_str("template_strings(&[");
+                self.emit_separated(cooked, ", ", |this, quasi| match quasi {
+                    Some(quasi) => write!(this, "Some({quasi:?})"),
+                    None => this.push_str("None"),
+                });
+                self.push_str("], &[");
+                self.emit_separated(raw, ", ", |this, quasi| write!(this, "{quasi:?}"));
+                self.push_str("])");
+            }
+            Expr::Closure { params, rest, body } => self.emit_function(
+                "|__jsrs_args: &[JsValue]| -> JsValue {",
+                "}",
+                params,
+                rest.as_deref(),
+                body,
+            ),
+            Expr::Function {
+                params,
+                rest,
+                body,
+                info,
+            } => {
+                self.emit_function(
+                    "JsValue::new_function(Box::new(move |__jsrs_args: &[JsValue]| -> JsValue {",
+                    "}))",
+                    params,
+                    rest.as_deref(),
+                    body,
+                );
+                self.emit_function_info(info, true);
+            }
+            Expr::Method {
+                params,
+                rest,
+                body,
+                info,
+            } => {
+                self.emit_function(
+                    "JsValue::new_method(Box::new(move |this: &JsValue, __jsrs_args: &[JsValue]| -> JsValue {",
+                    "}))",
+                    params,
+                    rest.as_deref(),
+                    body,
+                );
+                if *info != FunctionInfo::default() {
+                    self.emit_function_info(info, false);
+                }
+            }
+            Expr::Arguments => self.push_str("arguments_object(__jsrs_args)"),
+            Expr::NewTarget => self.push_str("new_target()"),
+            Expr::Yield { .. } => unreachable!("`yield` outside of the states of a generator"),
+            Expr::Iterator { iteration, object } => {
+                self.emit_method(object, iteration.iterator_method(), &[])
+            }
+            Expr::Generator {
+                copies,
+                locals,
+                shared,
+                states,
+            } => self.emit_generator(copies, locals, shared, states),
+            Expr::NewClass { parent: None } => self.push_str("JsValue::new_class()"),
+            Expr::NewClass {
+                parent: Some(parent),
+            } => {
+                self.push_str("JsValue::new_subclass(");
+                self.emit_expr(parent);
+                self.push_str(")");
+            }
+            Expr::Accessor { get, set } => {
+                self.push_str("JsValue::new_accessor(");
+                self.emit_expr(get);
+                self.push_str(", ");
+                self.emit_expr(set);
+                self.push_str(")");
+            }
+            Expr::SuperCall {
+                class,
+                this,
+                arguments,
+                new_target,
+            } => {
+                self.emit_receiver(class);
+                self.push_str(".construct_super(&");
+                self.emit_receiver(this);
+                self.push_str(", ");
+                self.emit_entries(arguments, "iter_elements", Self::emit_expr);
+                self.push_str(", &");
+                self.emit_receiver(new_target);
+                self.push_str(")");
+            }
+            Expr::GetSuper {
+                class,
+                key,
+                this,
+                is_static,
+            } => {
+                self.emit_receiver(class);
+                self.push_str(".get_super_prop(");
+                self.emit_expr(key);
+                self.push_str(", &");
+                self.emit_receiver(this);
+                write!(self, ", {is_static})");
+            }
+            Expr::DefineClass {
+                class,
+                constructor,
+                methods,
+                private,
+            } => {
+                self.emit_receiver(class);
+                self.push_str(".define_class(");
+                self.emit_expr(constructor);
+                for members in [methods, private] {
+                    self.push_str(", [");
+                    self.emit_separated(members, ", ", |this, (name, member)| {
+                        write!(this, "({name:?}.into(), ");
+                        this.emit_expr(member);
+                        this.push_str(")");
+                    });
+                    self.push_str("]");
+                }
+                self.push_str(")");
+            }
+            Expr::Block { body, value } => {
+                self.push_str("{ ");
+                for stmt in body {
+                    self.emit_stmt(stmt);
+                    self.push_str(" ");
+                }
+                self.emit_expr(value);
+                self.push_str(" }");
+            }
+            Expr::Chain { label, body } => {
+                write!(self, "{label}: {{ ");
+                self.emit_expr(body);
+                self.push_str(" }");
+            }
+            Expr::Optional {
+                label,
+                temp,
+                object,
+            } => {
+                write!(self, "{{ let {temp} = ");
+                self.emit_expr(object);
+                write!(
+                    self,
+                    "; if {temp}.is_nullish() {{ break {label} JsValue::Undefined; }} {temp} }}"
+                );
+            }
+            Expr::Clone(value) => {
+                self.emit_receiver(value);
+                self.push_str(".clone()");
+            }
+            Expr::Todo(message) => {
+                write!(self, "{{ let value: JsValue = todo!({message:?}); value }}")
+            }
+        }
+    }
+
+    /// Writes a function, whose closure takes the arguments as a slice. The `start` is the text
+    /// up to the body of the closure, where the arguments are bound to the parameters, and the
+    /// `end` is the text after it.
+    fn emit_function(
+        &mut self,
+        start: &str,
+        end: &str,
+        params: &[String],
+        rest: Option<&str>,
+        body: &[Stmt],
+    ) {
+        self.push_str(start);
+        self.indented(|this| {
+            for (index, param) in params.iter().enumerate() {
+                this.newline();
+                write!(this, "let {param} = arg(__jsrs_args, {index});");
+            }
+            if let Some(rest) = rest {
+                this.newline();
+                write!(
+                    this,
+                    "let {rest} = rest_args(__jsrs_args, {});",
+                    params.len()
+                );
+            }
+            for stmt in body {
+                this.newline();
+                this.emit_stmt(stmt);
+            }
+        });
+        self.newline();
+        self.push_str(end);
+    }
+
+    /// Gives the function object that was just written what the runtime needs to know about it
+    /// besides its closure.
+    fn emit_function_info(&mut self, info: &FunctionInfo, is_arrow: bool) {
+        let FunctionInfo {
+            name,
+            uses_new_target,
+        } = info;
+        write!(
+            self,
+            ".with_info(FunctionInfo {{ name: {name:?}, is_arrow: {is_arrow}, "
+        );
+        write!(self, "uses_new_target: {uses_new_target} }})");
+    }
+
+    /// Writes the expression so that a method can be called on it, or it can be called.
+    fn emit_receiver(&mut self, expr: &Expr) {
+        let needs_parens = matches!(
+            expr,
+            Expr::Assign { .. }
+                | Expr::SetShared { .. }
+                | Expr::SetProp { .. }
+                | Expr::SetPrivate { .. }
+                | Expr::Logical { .. }
+                | Expr::AssignIf { .. }
+                | Expr::Chain { .. }
+                | Expr::Optional { .. }
+                | Expr::Conditional { .. }
+                | Expr::Closure { .. }
+                | Expr::Block { .. }
+                | Expr::Todo(_)
+        );
+        if needs_parens {
+            self.push_str("(");
+        }
+        self.emit_expr(expr);
+        if needs_parens {
+            self.push_str(")");
+        }
+    }
+
+    /// Writes a `Vec` of the items. If there are spreads, the runs of items between them are
+    /// chained with the items that the `spread` method of the spread values returns.
+    fn emit_entries<T>(
+        &mut self,
+        entries: &[Entry<T>],
+        spread: &str,
+        mut emit_item: impl FnMut(&mut Self, &T),
+    ) {
+        let mut emit_items = |this: &mut Self, items: &[Entry<T>]| {
+            this.emit_separated(items, ", ", |this, item| match item {
+                Entry::Item(item) => emit_item(this, item),
+                Entry::Spread(_) => unreachable!(),
+            })
+        };
+        if entries.iter().all(|entry| matches!(entry, Entry::Item(_))) {
+            self.push_str("vec![");
+            emit_items(self, entries);
+            self.push_str("]");
+            return;
+        }
+        let runs = entries.chunk_by(|a, b| matches!((a, b), (Entry::Item(_), Entry::Item(_))));
+        for (index, run) in runs.enumerate() {
+            if index > 0 {
+                self.push_str(".chain(");
+            }
+            match run {
+                [Entry::Spread(value)] => self.emit_method(value, spread, &[]),
+                items => {
+                    self.push_str("[");
+                    emit_items(self, items);
+                    self.push_str("]");
+                }
+            }
+            match index {
+                0 => self.push_str(".into_iter()"),
+                _ => self.push_str(")"),
+            }
+        }
+        self.push_str(".collect::<Vec<_>>()");
+    }
+
+    /// Writes the arguments of a call as a slice, which is collected from the spread values and
+    /// the arguments between them if there are spreads.
+    fn emit_arguments(&mut self, arguments: &[Entry<Expr>]) {
+        if arguments
+            .iter()
+            .any(|argument| matches!(argument, Entry::Spread(_)))
+        {
+            self.push_str("&");
+            self.emit_entries(arguments, "iter_elements", Self::emit_expr);
+            return;
+        }
+        self.push_str("&[");
+        self.emit_separated(arguments, ", ", |this, argument| match argument {
+            Entry::Item(argument) => this.emit_expr(argument),
+            Entry::Spread(_) => unreachable!(),
+        });
+        self.push_str("]");
+    }
+
+    fn emit_method(&mut self, object: &Expr, method: &str, arguments: &[&Expr]) {
+        self.emit_receiver(object);
+        write!(self, ".{method}(");
+        self.emit_separated(arguments, ", ", |this, argument| this.emit_expr(argument));
+        self.push_str(")");
+    }
+}
@@ -0,0 +1,365 @@
+//! Turns the body of a generator function into a state machine, whose closure runs through
+//! the states from where the generator is suspended each time it's resumed.
+//!
+//! A statement that a `yield` is in is split up into states: its loops, `if`s and labeled
+//! blocks become jumps between them. The other statements are kept as they are, in the state
+//! that they run in. The variables that are declared outside of them live across the steps of
+//! the generator, so they are hoisted to the closure.
+
+use oxc::span::Span;
+
+use super::ir::{Expr, Stmt, StmtKind};
+
+/// The variable of the closure that holds the state that the generator is at
+pub const STATE: &str = "__jsrs_state";
+
+/// The parameter of the closure that holds the value that the generator is resumed with
+pub const SENT: &str = "__jsrs_sent";
+
+/// The label of the loop in the closure that a `Goto` continues
+pub const RESUME: &str = "'__jsrs_resume";
+
+/// A statement that was split up into states, which `break` and `continue` can jump to
+struct Target {
+    label: Option<String>,
+    /// The state that a `continue` goes on with, if it's a loop
+    continue_to: Option<usize>,
+    break_to: usize,
+}
+
+struct StateMachine<'a> {
+    states: Vec<Vec<Stmt>>,
+    /// The state that the statements go into
+    current: usize,
+    locals: Vec<String>,
+    /// The hoisted variables that closures share, which are `SharedBinding`s
+    shared: Vec<String>,
+    /// The statements around the current one that were split up, innermost last
+    targets: Vec<Target>,
+    /// Lowers a node that can't be turned into states, given the description of it
+    unsupported: &'a mut dyn FnMut(&str, Span) -> Expr,
+}
+
+/// The hoisted variables, the ones of them that closures share, and the states of the body,
+/// which ends with a `ReturnFromGenerator` of `undefined`.
+///
+/// A `yield` can only suspend the generator at the top of a statement, where it's the
+/// statement, its value, or the value that it assigns to a variable. Others are unsupported.
+pub fn into_states(
+    body: Vec<Stmt>,
+    unsupported: &mut dyn FnMut(&str, Span) -> Expr,
+) -> (Vec<String>, Vec<String>, Vec<Vec<Stmt>>) {
+    let mut machine = StateMachine {
+        states: vec![Vec::new()],
+        current: 0,
+        locals: Vec::new(),
+        shared: Vec::new(),
+        targets: Vec::new(),
+        unsupported,
+    };
+    machine.split_all(body);
+    machine.push(StmtKind::ReturnFromGenerator(Expr::Undefined));
+    (machine.locals, machine.shared, machine.states)
+}
+
+impl StateMachine<'_> {
+    fn new_state(&mut self) -> usize {
+        self.states.push(Vec::new());
+        self.states.len() - 1
+    }
+
+    fn push(&mut self, kind: StmtKind) {
+        self.states[self.current].push(Stmt::new(kind));
+    }
+
+    /// Ends the current state with a jump to the other one.
+    fn goto(&mut self, state: usize) {
+        self.push(StmtKind::Goto(state));
+    }
+
+    fn hoist(&mut self, name: &str) {
+        if !self.locals.iter().any(|local| local == name) {
+            self.locals.push(name.to_owned());
+        }
+    }
+
+    fn hoist_shared(&mut self, name: &str) {
+        if !self.shared.iter().any(|local| local == name) {
+            self.shared.push(name.to_owned());
+        }
+    }
+
+    fn split_all(&mut self, body: Vec<Stmt>) {
+        for stmt in body {
+            self.split(stmt);
+        }
+    }
+
+    fn split(&mut self, mut stmt: Stmt) {
+        // A group doesn't have a scope, so its `let`s are hoisted even if it's kept
+        if let StmtKind::Group(body) = stmt.kind {
+            return self.split_all(body);
+        }
+        if !contains_yield(&mut stmt) {
+            return self.keep(stmt);
+        }
+        match stmt.kind {
+            StmtKind::Expr(expr) if is_yield(&expr) => self.suspend(yielded(expr)),
+            StmtKind::Let {
+                name,
+                init: Some(init),
+                ..
+            } if is_yield(&init) => {
+                self.suspend(yielded(init));
+                self.hoist(&name);
+                self.push(StmtKind::Expr(sent_to(name)));
+            }
+            StmtKind::Expr(Expr::Assign { target, value }) if is_yield(&value) => {
+                self.suspend(yielded(*value));
+                self.push(StmtKind::Expr(sent_to(target)));
+            }
+            StmtKind::Expr(Expr::SetShared { target, value }) if is_yield(&value) => {
+                self.suspend(yielded(*value));
+                self.push(StmtKind::Expr(Expr::SetShared {
+                    target,
+                    value: Expr::Local(String::from(SENT)).cloned().boxed(),
+                }));
+            }
+            StmtKind::Block(body) => self.split_all(body),
+            StmtKind::If {
+                mut test,
+                then,
+                otherwise,
+            } => {
+                self.replace_yields(&mut test, "`yield` in the test of an `if`");
+                let then_state = self.new_state();
+                let otherwise_state = self.new_state();
+                let after = self.new_state();
+                self.push(StmtKind::If {
+                    test,
+                    then: vec![Stmt::new(StmtKind::Goto(then_state))],
+                    otherwise: vec![Stmt::new(StmtKind::Goto(otherwise_state))],
+                });
+                self.current = then_state;
+                self.split_all(then);
+                self.goto(after);
+                self.current = otherwise_state;
+                self.split_all(otherwise);
+                self.goto(after);
+                self.current = after;
+            }
+            StmtKind::Loop { label, body } => {
+                let start = self.new_state();
+                let after = self.new_state();
+                self.goto(start);
+                self.current = start;
+                self.split_target(label, Some(start), after, body);
+                self.goto(start);
+                self.current = after;
+            }
+            StmtKind::Labeled { label, body } => {
+                let after = self.new_state();
+                self.split_target(Some(label), None, after, body);
+                self.goto(after);
+                self.current = after;
+            }
+            StmtKind::ForEach {
+                label,
+                name,
+                iteration,
+                mut object,
+                body,
+                ..
+            } => {
+                self.replace_yields(&mut object, "`yield` in the object of a loop");
+                let iterator = format!("__jsrs_iterator{}", self.states.len());
+                self.hoist(&iterator);
+                self.hoist(&name);
+                self.push(StmtKind::Expr(Expr::Assign {
+                    target: iterator.clone(),
+                    value: Expr::Iterator {
+                        iteration,
+                        object: object.boxed(),
+                    }
+                    .boxed(),
+                }));
+                let next = self.new_state();
+                let after = self.new_state();
+                self.goto(next);
+                self.current = next;
+                self.push(StmtKind::NextItem {
+                    iterator,
+                    name,
+                    done: after,
+                });
+                self.split_target(label, Some(next), after, body);
+                self.goto(next);
+                self.current = after;
+            }
+            kind @ StmtKind::Try { .. } => {
+                let mut stmt = Stmt { kind, ..stmt };
+                stmt.for_each_expr_mut(&mut |expr| {
+                    self.replace_yields(expr, "`yield` in a `try` statement")
+                });
+                self.keep(stmt);
+            }
+            kind => self.keep(Stmt { kind, ..stmt }),
+        }
+    }
+
+    /// Splits up the body of a statement that `break` and `continue` can jump to.
+    fn split_target(
+        &mut self,
+        label: Option<String>,
+        continue_to: Option<usize>,
+        break_to: usize,
+        body: Vec<Stmt>,
+    ) {
+        self.targets.push(Target {
+            label,
+            continue_to,
+            break_to,
+        });
+        self.split_all(body);
+        self.targets.pop();
+    }
+
+    /// Ends the current state with the `yield`, going on with a new one.
+    fn suspend(&mut self, mut value: Expr) {
+        self.replace_yields(&mut value, "`yield` inside of a `yield`");
+        let resume = self.new_state();
+        self.push(StmtKind::Yield { value, resume });
+        self.current = resume;
+    }
+
+    /// Puts the statement into the current state. If it declares a variable, the variable is
+    /// hoisted and the statement assigns to it instead. A variable that closures share gets a
+    /// new binding each time it's declared, like outside of a generator.
+    fn keep(&mut self, mut stmt: Stmt) {
+        stmt.for_each_expr_mut(&mut |expr| {
+            self.replace_yields(expr, "`yield` inside of an expression")
+        });
+        self.redirect_jumps(&mut stmt, 0, &mut Vec::new());
+        if let StmtKind::Let { name, init, .. } = stmt.kind {
+            match init {
+                Some(Expr::NewShared(_)) => self.hoist_shared(&name),
+                _ => self.hoist(&name),
+            }
+            stmt.kind = StmtKind::Expr(Expr::Assign {
+                target: name,
+                value: init.unwrap_or(Expr::Undefined).boxed(),
+            });
+        }
+        self.states[self.current].push(stmt);
+    }
+
+    /// Turns the `yield`s in the expression, which can't suspend the generator, into
+    /// unsupported nodes.
+    fn replace_yields(&mut self, expr: &mut Expr, description: &str) {
+        expr.for_each_child_mut(|child| self.replace_yields(child, description));
+        if let Expr::Yield { span, .. } = expr {
+            *expr = (self.unsupported)(description, *span);
+        }
+    }
+
+    /// Turns the jumps in a kept statement to the statements that were split up into `Goto`s.
+    /// `loops` is the number of Rust loops around the jump in the kept statement, and `labels`
+    /// are the labels of the statements around it there.
+    fn redirect_jumps(&self, stmt: &mut Stmt, loops: usize, labels: &mut Vec<String>) {
+        let (label, is_break) = match &mut stmt.kind {
+            StmtKind::Break(label) => (label.clone(), true),
+            StmtKind::Continue(label) => (label.clone(), false),
+            StmtKind::Block(body) | StmtKind::Group(body) => {
+                return body
+                    .iter_mut()
+                    .for_each(|stmt| self.redirect_jumps(stmt, loops, labels))
+            }
+            StmtKind::If {
+                then, otherwise, ..
+            } => {
+                return then
+                    .iter_mut()
+                    .chain(otherwise)
+                    .for_each(|stmt| self.redirect_jumps(stmt, loops, labels))
+            }
+            StmtKind::Loop { label, body } | StmtKind::ForEach { label, body, .. } => {
+                labels.extend(label.clone());
+                body.iter_mut()
+                    .for_each(|stmt| self.redirect_jumps(stmt, loops + 1, labels));
+                labels.truncate(labels.len() - usize::from(label.is_some()));
+                return;
+            }
+            StmtKind::Labeled { label, body } => {
+                labels.push(label.clone());
+                body.iter_mut()
+                    .for_each(|stmt| self.redirect_jumps(stmt, loops, labels));
+                labels.pop();
+                return;
+            }
+            // The closures of a `try` can't jump out of it, but its other blocks can
+            StmtKind::Try { catch, finally, .. } => {
+                return catch
+                    .iter_mut()
+                    .filter(|catch| !catch.guarded)
+                    .flat_map(|catch| &mut catch.body)
+                    .chain(finally.iter_mut().flatten())
+                    .for_each(|stmt| self.redirect_jumps(stmt, loops, labels))
+            }
+            _ => return,
+        };
+        let target = match &label {
+            Some(label) if labels.contains(label) => return,
+            Some(label) => self
+                .targets
+                .iter()
+                .rfind(|target| target.label.as_ref() == Some(label)),
+            None if loops > 0 => return,
+            None => self
+                .targets
+                .iter()
+                .rfind(|target| target.continue_to.is_some()),
+        };
+        let target = target.expect("a jump to a statement that isn't around it");
+        stmt.kind = match is_break {
+            true => StmtKind::Goto(target.break_to),
+            false => StmtKind::Goto(target.continue_to.unwrap()),
+        };
+    }
+}
+
+/// Whether the expression is a `yield`, or a copy of what it evaluates to
+fn is_yield(expr: &Expr) -> bool {
+    match expr {
+        Expr::Yield { .. } => true,
+        Expr::Clone(value) => is_yield(value),
+        _ => false,
+    }
+}
+
+/// The value of a `yield` that `is_yield`
+fn yielded(expr: Expr) -> Expr {
+    match expr {
+        Expr::Yield { value, .. } => *value,
+        Expr::Clone(value) => yielded(*value),
+        _ => unreachable!(),
+    }
+}
+
+/// `target = value that the generator was resumed with`
+fn sent_to(target: String) -> Expr {
+    Expr::Assign {
+        target,
+        value: Expr::Local(String::from(SENT)).cloned().boxed(),
+    }
+}
+
+fn contains_yield(stmt: &mut Stmt) -> bool {
+    fn in_expr(expr: &mut Expr) -> bool {
+        let mut found = matches!(expr, Expr::Yield { .. });
+        expr.for_each_child_mut(|child| found |= in_expr(child));
+        found
+    }
+    let mut found = false;
+    stmt.for_each_expr_mut(&mut |expr| found |= in_expr(expr));
+    found
+}
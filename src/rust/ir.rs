@@ -0,0 +1,814 @@
+//! The intermediate representation between the JS AST and the generated Rust code.
+//!
+//! The AST is lowered into it by `lower`, where all of the JS semantics are dealt with, so it
+//! only has what the Rust code is made of: typed operations on `JsValue`s, Rust variables and
+//! temporaries, and control flow that is already in the shape of Rust loops and `if`s. The
+//! passes in `passes` work on it before it's printed by the `Emitter`.
+
+use oxc::{
+    ast::ast::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+    span::Span,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    /// The JS statement that this one was lowered from, which the Rust text of the statement
+    /// is mapped to. Statements that are only a part of the lowering of a JS statement don't
+    /// have one.
+    pub span: Option<Span>,
+}
+
+impl Stmt {
+    pub fn new(kind: StmtKind) -> Self {
+        Stmt { kind, span: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// `let name = init;`
+    Let {
+        name: String,
+        mutable: bool,
+        init: Option<Expr>,
+    },
+    Expr(Expr),
+    Return(Option<Expr>),
+    /// A `return` from inside the closure of a `Try`, which makes the `Try` return the value
+    ReturnFromTry(Expr),
+    Throw(Expr),
+    /// Runs the body in a closure that returns a `Completion`, keeping the `Result` of it in the
+    /// `result` variable, which the `catch` and `finally` blocks then work with
+    Try {
+        result: String,
+        body: Vec<Stmt>,
+        catch: Option<Catch>,
+        finally: Option<Vec<Stmt>>,
+        /// Where a `return` from the blocks goes on to
+        returns: ReturnTo,
+    },
+    Block(Vec<Stmt>),
+    /// Statements that belong together, but unlike a block don't have a scope of their own
+    Group(Vec<Stmt>),
+    /// Runs the body until it breaks out of it. The label is only there if a jump needs it.
+    Loop {
+        label: Option<String>,
+        body: Vec<Stmt>,
+    },
+    /// A block that a `Break` with its label leaves
+    Labeled {
+        label: String,
+        body: Vec<Stmt>,
+    },
+    /// Runs `then` if `test` is truthy and `otherwise` if it isn't
+    If {
+        test: Expr,
+        then: Vec<Stmt>,
+        otherwise: Vec<Stmt>,
+    },
+    /// Runs the body with `name` bound to each item of the object
+    ForEach {
+        label: Option<String>,
+        name: String,
+        mutable: bool,
+        iteration: Iteration,
+        object: Expr,
+        body: Vec<Stmt>,
+    },
+    /// Leaves the loop or block with the label, or the innermost loop
+    Break(Option<String>),
+    /// Goes on with the next iteration of the loop with the label, or of the innermost loop
+    Continue(Option<String>),
+    /// Suspends a generator with the value, which goes on with the state when it's resumed
+    Yield {
+        value: Expr,
+        resume: usize,
+    },
+    /// Goes on with the state of a generator right away
+    Goto(usize),
+    /// Binds `name` to the next item of the iterator object, or goes on with the `done` state
+    /// of the generator once there are none left
+    NextItem {
+        iterator: String,
+        name: String,
+        done: usize,
+    },
+    /// A `return` from the body of a generator, which finishes it with the value
+    ReturnFromGenerator(Expr),
+}
+
+/// The `catch` block of a `Try`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catch {
+    /// The variable that the caught value is bound to
+    pub param: String,
+    pub body: Vec<Stmt>,
+    /// Whether the block runs in a closure too, so that the `finally` block runs after it
+    /// whatever it does
+    pub guarded: bool,
+}
+
+/// An element of an `Array`, a property of an `Object` or an argument of a call, or the spread
+/// of a value into it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry<T> {
+    Item(T),
+    /// The elements that `iter_elements` visits, or the properties from `own_entries`
+    Spread(Expr),
+}
+
+/// What the runtime is told about a `Function` or a `Method` besides its closure
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FunctionInfo {
+    /// The `name` of the function, which is empty if it's anonymous
+    pub name: String,
+    /// Whether it binds `new_target()`, which the runtime only passes it then
+    pub uses_new_target: bool,
+}
+
+/// What a `return` at some point of the program returns from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnTo {
+    /// Nothing, at the top level of the program
+    Nothing,
+    Function,
+    /// The closure of a `Try`
+    Try,
+    /// The closure of a `Generator`
+    Generator,
+}
+
+/// An expression of type `JsValue`, unless noted otherwise
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Boolean(bool),
+    Null,
+    Undefined,
+    String(String),
+    /// A new regular expression object, for a regex literal
+    RegExp {
+        pattern: String,
+        flags: String,
+    },
+    /// A variable of the program
+    Local(String),
+    /// A Rust expression from the `IntrinsicMap`
+    Intrinsic(String),
+    /// `target = value`, which is of type `()`
+    Assign {
+        target: String,
+        value: Box<Expr>,
+    },
+    /// A new `SharedBinding` with the value, for a variable that closures share
+    NewShared(Box<Expr>),
+    /// The value of a variable that is a `SharedBinding`
+    GetShared(String),
+    /// Sets the variable that is a `SharedBinding` to the value, which is of type `()`
+    SetShared {
+        target: String,
+        value: Box<Expr>,
+    },
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        argument: Box<Expr>,
+    },
+    /// Evaluates to the `left` value, which it keeps in `temp`, unless the operator evaluates
+    /// the `right` one
+    Logical {
+        op: LogicalOp,
+        temp: String,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    /// Runs `assign` only if the operator would evaluate its right operand for the `current`
+    /// value, for a logical assignment. Of type `()`.
+    AssignIf {
+        op: LogicalOp,
+        current: Box<Expr>,
+        assign: Box<Expr>,
+    },
+    /// `consequent` if `test` is truthy, `alternate` if it isn't
+    Conditional {
+        test: Box<Expr>,
+        consequent: Box<Expr>,
+        alternate: Box<Expr>,
+    },
+    GetProp {
+        object: Box<Expr>,
+        key: Box<Expr>,
+    },
+    /// Of type `()`
+    SetProp {
+        object: Box<Expr>,
+        key: Box<Expr>,
+        value: Box<Expr>,
+    },
+    DeleteProp {
+        object: Box<Expr>,
+        key: Box<Expr>,
+    },
+    /// A private member of the object, whose name is qualified with the class that declares it
+    GetPrivate {
+        object: Box<Expr>,
+        name: String,
+    },
+    /// Of type `()`
+    SetPrivate {
+        object: Box<Expr>,
+        name: String,
+        value: Box<Expr>,
+    },
+    /// `#name in object`
+    HasPrivate {
+        object: Box<Expr>,
+        name: String,
+    },
+    /// The elements of the array from `start` on, as a new array
+    SliceFrom {
+        array: Box<Expr>,
+        start: usize,
+    },
+    /// Calls a function of the program, which is a Rust closure
+    CallFunction {
+        callee: Box<Expr>,
+        arguments: Vec<Expr>,
+    },
+    /// Calls a function object
+    CallValue {
+        callee: Box<Expr>,
+        arguments: Vec<Entry<Expr>>,
+    },
+    /// Calls the function in a property of the object, with the object as `this`
+    CallMethod {
+        object: Box<Expr>,
+        key: Box<Expr>,
+        arguments: Vec<Entry<Expr>>,
+    },
+    /// Calls a function object with `this` bound to the value
+    CallWithThis {
+        callee: Box<Expr>,
+        this: Box<Expr>,
+        arguments: Vec<Entry<Expr>>,
+    },
+    /// The `arguments` of the function, an object with the arguments that it was called with
+    Arguments,
+    /// Creates an instance of a class
+    New {
+        callee: Box<Expr>,
+        arguments: Vec<Entry<Expr>>,
+    },
+    Array(Vec<Entry<Expr>>),
+    /// The keys are converted to strings, unless they are already string literals
+    Object(Vec<Entry<(Expr, Expr)>>),
+    TemplateLiteral {
+        quasis: Vec<String>,
+        substitutions: Vec<Expr>,
+    },
+    /// The array of the strings of a tagged template, with the raw strings in its `raw`
+    /// property. A cooked string is `None` where it has an invalid escape sequence.
+    TemplateStrings {
+        cooked: Vec<Option<String>>,
+        raw: Vec<String>,
+    },
+    /// A Rust closure that takes the arguments, which are bound to the `params` and the `rest`
+    /// like in a `Function`, and returns a `JsValue`
+    Closure {
+        params: Vec<String>,
+        rest: Option<String>,
+        body: Vec<Stmt>,
+    },
+    /// A function object that takes the arguments, which are bound to the `params`, and the
+    /// ones after them to the `rest` as an array. It's an arrow function, so it isn't a
+    /// constructor.
+    Function {
+        params: Vec<String>,
+        rest: Option<String>,
+        body: Vec<Stmt>,
+        info: FunctionInfo,
+    },
+    /// A function object that takes `this` and the arguments, like a `Function`
+    Method {
+        params: Vec<String>,
+        rest: Option<String>,
+        body: Vec<Stmt>,
+        info: FunctionInfo,
+    },
+    /// The `new.target` that the function was called with, which is taken from the runtime
+    /// before anything else in the function runs
+    NewTarget,
+    /// Suspends the generator with the value, evaluating to the value that it's resumed with.
+    /// Only at the top of a statement, until the body of the generator is turned into states.
+    Yield {
+        value: Box<Expr>,
+        span: Span,
+    },
+    /// A generator that yields the items that a `ForEach` visits, for `NextItem`
+    Iterator {
+        iteration: Iteration,
+        object: Box<Expr>,
+    },
+    /// A generator object, whose closure has copies of the variables from outside of it, and
+    /// the `locals` that live across its steps, as well as the `shared` ones, which are
+    /// `SharedBinding`s. Each step goes through the `states`, starting with the first one, up
+    /// to a `Yield` or a `ReturnFromGenerator`.
+    Generator {
+        copies: Vec<String>,
+        locals: Vec<String>,
+        shared: Vec<String>,
+        states: Vec<Vec<Stmt>>,
+    },
+    /// A class that isn't defined yet, which extends the parent if it has one
+    NewClass {
+        parent: Option<Box<Expr>>,
+    },
+    /// Defines the class with the constructor and instance methods, which are `Method`s, and
+    /// the private members of its instances. Of type `()`.
+    DefineClass {
+        class: Box<Expr>,
+        constructor: Box<Expr>,
+        methods: Vec<(String, Expr)>,
+        /// The private fields, which are `undefined`, and the private methods, by their
+        /// qualified names
+        private: Vec<(String, Expr)>,
+    },
+    /// A property with a getter and a setter, which are `Method`s or `Undefined`, for a class
+    Accessor {
+        get: Box<Expr>,
+        set: Box<Expr>,
+    },
+    /// `super(arguments)` in the constructor of the class, which is of type `()`
+    SuperCall {
+        class: Box<Expr>,
+        this: Box<Expr>,
+        arguments: Vec<Entry<Expr>>,
+        /// The `new.target` of the constructor, which the one of the class that it extends gets
+        new_target: Box<Expr>,
+    },
+    /// `super[key]` in a method of the class, which is the class that it extends in a static
+    /// method
+    GetSuper {
+        class: Box<Expr>,
+        key: Box<Expr>,
+        this: Box<Expr>,
+        is_static: bool,
+    },
+    /// Runs the statements, then evaluates to the value, which is where temporaries live
+    Block {
+        body: Vec<Stmt>,
+        value: Box<Expr>,
+    },
+    /// An optional chain, which evaluates to `undefined` once an `Optional` in it breaks out of
+    /// the Rust block with the label
+    Chain {
+        label: String,
+        body: Box<Expr>,
+    },
+    /// The object that an optional member access or call of a `Chain` works with, which it
+    /// keeps in `temp`, unless it's nullish
+    Optional {
+        label: String,
+        temp: String,
+        object: Box<Expr>,
+    },
+    /// A copy of the value, where it's passed on by value but still needed
+    Clone(Box<Expr>),
+    /// Panics with the message when it's reached
+    Todo(String),
+}
+
+impl Expr {
+    pub fn boxed(self) -> Box<Expr> {
+        Box::new(self)
+    }
+
+    pub fn cloned(self) -> Expr {
+        Expr::Clone(self.boxed())
+    }
+
+    /// Calls `f` on every expression directly inside this one.
+    pub fn for_each_child_mut(&mut self, mut f: impl FnMut(&mut Expr)) {
+        match self {
+            Expr::Number(_)
+            | Expr::Boolean(_)
+            | Expr::Null
+            | Expr::Undefined
+            | Expr::String(_)
+            | Expr::RegExp { .. }
+            | Expr::Local(_)
+            | Expr::GetShared(_)
+            | Expr::Intrinsic(_)
+            | Expr::Arguments
+            | Expr::NewTarget
+            | Expr::NewClass { parent: None }
+            | Expr::TemplateStrings { .. }
+            | Expr::Todo(_) => {}
+            Expr::Assign { value, .. } | Expr::SetShared { value, .. } => f(value),
+            Expr::NewShared(value) => f(value),
+            Expr::Binary { left, right, .. } => {
+                f(left);
+                f(right);
+            }
+            Expr::Unary { argument, .. } => f(argument),
+            Expr::NewClass {
+                parent: Some(parent),
+            } => f(parent),
+            Expr::Accessor { get, set } => {
+                f(get);
+                f(set);
+            }
+            Expr::SuperCall {
+                class,
+                this,
+                arguments,
+                new_target,
+            } => {
+                f(class);
+                f(this);
+                arguments.iter_mut().for_each(|argument| match argument {
+                    Entry::Item(argument) | Entry::Spread(argument) => f(argument),
+                });
+                f(new_target);
+            }
+            Expr::GetSuper {
+                class, key, this, ..
+            } => {
+                f(class);
+                f(key);
+                f(this);
+            }
+            Expr::Yield { value, .. } => f(value),
+            Expr::Iterator { object, .. } => f(object),
+            Expr::Generator { states, .. } => states
+                .iter_mut()
+                .flatten()
+                .for_each(|stmt| stmt.for_each_expr_mut(&mut f)),
+            Expr::Logical { left, right, .. } => {
+                f(left);
+                f(right);
+            }
+            Expr::AssignIf {
+                current, assign, ..
+            } => {
+                f(current);
+                f(assign);
+            }
+            Expr::Conditional {
+                test,
+                consequent,
+                alternate,
+            } => {
+                f(test);
+                f(consequent);
+                f(alternate);
+            }
+            Expr::GetProp { object, key } | Expr::DeleteProp { object, key } => {
+                f(object);
+                f(key);
+            }
+            Expr::SetProp { object, key, value } => {
+                f(object);
+                f(key);
+                f(value);
+            }
+            Expr::GetPrivate { object, .. } | Expr::HasPrivate { object, .. } => f(object),
+            Expr::SetPrivate { object, value, .. } => {
+                f(object);
+                f(value);
+            }
+            Expr::CallFunction { callee, arguments } => {
+                f(callee);
+                arguments.iter_mut().for_each(f);
+            }
+            Expr::CallValue { callee, arguments } | Expr::New { callee, arguments } => {
+                f(callee);
+                arguments.iter_mut().for_each(|argument| match argument {
+                    Entry::Item(argument) | Entry::Spread(argument) => f(argument),
+                });
+            }
+            Expr::CallMethod {
+                object,
+                key,
+                arguments,
+            } => {
+                f(object);
+                f(key);
+                arguments.iter_mut().for_each(|argument| match argument {
+                    Entry::Item(argument) | Entry::Spread(argument) => f(argument),
+                });
+            }
+            Expr::CallWithThis {
+                callee,
+                this,
+                arguments,
+            } => {
+                f(callee);
+                f(this);
+                arguments.iter_mut().for_each(|argument| match argument {
+                    Entry::Item(argument) | Entry::Spread(argument) => f(argument),
+                });
+            }
+            Expr::Array(elements) => elements.iter_mut().for_each(|element| match element {
+                Entry::Item(element) | Entry::Spread(element) => f(element),
+            }),
+            Expr::Object(entries) => entries.iter_mut().for_each(|entry| match entry {
+                Entry::Item((key, value)) => {
+                    f(key);
+                    f(value);
+                }
+                Entry::Spread(object) => f(object),
+            }),
+            Expr::TemplateLiteral { substitutions, .. } => substitutions.iter_mut().for_each(f),
+            Expr::Closure { body, .. }
+            | Expr::Function { body, .. }
+            | Expr::Method { body, .. } => body
+                .iter_mut()
+                .for_each(|stmt| stmt.for_each_expr_mut(&mut f)),
+            Expr::DefineClass {
+                class,
+                constructor,
+                methods,
+                private,
+            } => {
+                f(class);
+                f(constructor);
+                methods
+                    .iter_mut()
+                    .chain(private)
+                    .for_each(|(_, member)| f(member));
+            }
+            Expr::Block { body, value } => {
+                body.iter_mut()
+                    .for_each(|stmt| stmt.for_each_expr_mut(&mut f));
+                f(value);
+            }
+            Expr::Chain { body, .. } => f(body),
+            Expr::Optional { object, .. } => f(object),
+            Expr::SliceFrom { array, .. } => f(array),
+            Expr::Clone(value) => f(value),
+        }
+    }
+}
+
+impl Stmt {
+    /// Calls `f` on every expression directly inside this statement or the statements it
+    /// contains.
+    pub fn for_each_expr_mut(&mut self, f: &mut impl FnMut(&mut Expr)) {
+        match &mut self.kind {
+            StmtKind::Let { init, .. } => init.iter_mut().for_each(f),
+            StmtKind::Expr(expr) => f(expr),
+            StmtKind::Return(value) => value.iter_mut().for_each(f),
+            StmtKind::ReturnFromTry(value)
+            | StmtKind::Throw(value)
+            | StmtKind::ReturnFromGenerator(value)
+            | StmtKind::Yield { value, .. } => f(value),
+            StmtKind::Try {
+                body,
+                catch,
+                finally,
+                ..
+            } => body
+                .iter_mut()
+                .chain(catch.iter_mut().flat_map(|catch| &mut catch.body))
+                .chain(finally.iter_mut().flatten())
+                .for_each(|stmt| stmt.for_each_expr_mut(f)),
+            StmtKind::Block(body)
+            | StmtKind::Group(body)
+            | StmtKind::Loop { body, .. }
+            | StmtKind::Labeled { body, .. } => {
+                body.iter_mut().for_each(|stmt| stmt.for_each_expr_mut(f))
+            }
+            StmtKind::If {
+                test,
+                then,
+                otherwise,
+            } => {
+                f(test);
+                then.iter_mut()
+                    .chain(otherwise)
+                    .for_each(|stmt| stmt.for_each_expr_mut(f));
+            }
+            StmtKind::ForEach { object, body, .. } => {
+                f(object);
+                body.iter_mut().for_each(|stmt| stmt.for_each_expr_mut(f));
+            }
+            StmtKind::Break(_)
+            | StmtKind::Continue(_)
+            | StmtKind::Goto(_)
+            | StmtKind::NextItem { .. } => {}
+        }
+    }
+}
+
+/// What a `ForEach` iterates over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Iteration {
+    /// The keys that `for-in` visits
+    Keys,
+    /// The elements that `for-of` visits
+    Elements,
+}
+
+impl Iteration {
+    /// The method of `JsValue` that returns the items
+    pub fn method(self) -> &'static str {
+        match self {
+            Iteration::Keys => "enumerable_keys",
+            Iteration::Elements => "iter_elements",
+        }
+    }
+
+    /// The method of `JsValue` that returns a generator that yields the items
+    pub fn iterator_method(self) -> &'static str {
+        match self {
+            Iteration::Keys => "keys_iterator",
+            Iteration::Elements => "elements_iterator",
+        }
+    }
+}
+
+/// The binary operators of JS that the runtime implements, as methods of `JsValue`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mult,
+    Divide,
+    Rem,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    IsIn,
+    Pow,
+    BitOr,
+    BitAnd,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    ShiftRightUnsigned,
+    StrictEquals,
+    StrictNotEquals,
+    LooseEquals,
+    LooseNotEquals,
+    InstanceOf,
+}
+
+impl BinaryOp {
+    /// Every binary operator of JS has an equivalent.
+    pub fn from_js(operator: BinaryOperator) -> BinaryOp {
+        match operator {
+            BinaryOperator::Addition => BinaryOp::Add,
+            BinaryOperator::Subtraction => BinaryOp::Sub,
+            BinaryOperator::Division => BinaryOp::Divide,
+            BinaryOperator::Remainder => BinaryOp::Rem,
+            BinaryOperator::LessThan => BinaryOp::Less,
+            BinaryOperator::GreaterThan => BinaryOp::Greater,
+            BinaryOperator::LessEqualThan => BinaryOp::LessEqual,
+            BinaryOperator::GreaterEqualThan => BinaryOp::GreaterEqual,
+            BinaryOperator::Multiplication => BinaryOp::Mult,
+            BinaryOperator::In => BinaryOp::IsIn,
+            BinaryOperator::Exponential => BinaryOp::Pow,
+            BinaryOperator::BitwiseOR => BinaryOp::BitOr,
+            BinaryOperator::BitwiseAnd => BinaryOp::BitAnd,
+            BinaryOperator::BitwiseXOR => BinaryOp::BitXor,
+            BinaryOperator::ShiftLeft => BinaryOp::ShiftLeft,
+            BinaryOperator::ShiftRight => BinaryOp::ShiftRight,
+            BinaryOperator::ShiftRightZeroFill => BinaryOp::ShiftRightUnsigned,
+            BinaryOperator::StrictEquality => BinaryOp::StrictEquals,
+            BinaryOperator::StrictInequality => BinaryOp::StrictNotEquals,
+            BinaryOperator::Equality => BinaryOp::LooseEquals,
+            BinaryOperator::Inequality => BinaryOp::LooseNotEquals,
+            BinaryOperator::Instanceof => BinaryOp::InstanceOf,
+        }
+    }
+
+    /// The operator that a compound assignment applies, `None` for `=` and the logical ones
+    pub fn from_assignment(operator: AssignmentOperator) -> Option<BinaryOp> {
+        let op = match operator {
+            AssignmentOperator::Addition => BinaryOp::Add,
+            AssignmentOperator::Subtraction => BinaryOp::Sub,
+            AssignmentOperator::Multiplication => BinaryOp::Mult,
+            AssignmentOperator::Division => BinaryOp::Divide,
+            AssignmentOperator::Remainder => BinaryOp::Rem,
+            AssignmentOperator::Exponential => BinaryOp::Pow,
+            AssignmentOperator::BitwiseOR => BinaryOp::BitOr,
+            AssignmentOperator::BitwiseAnd => BinaryOp::BitAnd,
+            AssignmentOperator::BitwiseXOR => BinaryOp::BitXor,
+            AssignmentOperator::ShiftLeft => BinaryOp::ShiftLeft,
+            AssignmentOperator::ShiftRight => BinaryOp::ShiftRight,
+            AssignmentOperator::ShiftRightZeroFill => BinaryOp::ShiftRightUnsigned,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// This always returns the name of the equivalent function in our custom Rust impl
+    pub fn method(self) -> &'static str {
+        match self {
+            BinaryOp::Add => "add",
+            BinaryOp::Sub => "sub",
+            BinaryOp::Mult => "mult",
+            BinaryOp::Divide => "divide",
+            BinaryOp::Rem => "rem",
+            BinaryOp::Less => "less",
+            BinaryOp::Greater => "greater",
+            BinaryOp::LessEqual => "less_equal",
+            BinaryOp::GreaterEqual => "greater_equal",
+            BinaryOp::IsIn => "is_in",
+            BinaryOp::Pow => "pow",
+            BinaryOp::BitOr => "bit_or",
+            BinaryOp::BitAnd => "bit_and",
+            BinaryOp::BitXor => "bit_xor",
+            BinaryOp::ShiftLeft => "shift_left",
+            BinaryOp::ShiftRight => "shift_right",
+            BinaryOp::ShiftRightUnsigned => "shift_right_unsigned",
+            BinaryOp::StrictEquals => "strict_equals",
+            BinaryOp::StrictNotEquals => "strict_not_equals",
+            BinaryOp::LooseEquals => "loose_equals",
+            BinaryOp::LooseNotEquals => "loose_not_equals",
+            BinaryOp::InstanceOf => "instance_of",
+        }
+    }
+}
+
+/// The logical operators of JS, which only evaluate the right operand if they need to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalOp {
+    And,
+    Or,
+    Coalesce,
+    /// The default value of a destructured binding, which only replaces `undefined`
+    Default,
+}
+
+impl LogicalOp {
+    pub fn from_js(operator: LogicalOperator) -> LogicalOp {
+        match operator {
+            LogicalOperator::And => LogicalOp::And,
+            LogicalOperator::Or => LogicalOp::Or,
+            LogicalOperator::Coalesce => LogicalOp::Coalesce,
+        }
+    }
+
+    /// The operator of a logical assignment, like `||=`
+    pub fn from_assignment(operator: AssignmentOperator) -> Option<LogicalOp> {
+        match operator {
+            AssignmentOperator::LogicalAnd => Some(LogicalOp::And),
+            AssignmentOperator::LogicalOr => Some(LogicalOp::Or),
+            AssignmentOperator::LogicalNullish => Some(LogicalOp::Coalesce),
+            _ => None,
+        }
+    }
+
+    /// The method of `JsValue` that tells from the left value whether the right one is
+    /// evaluated
+    pub fn test(self) -> &'static str {
+        match self {
+            LogicalOp::And => "truthy",
+            LogicalOp::Or => "falsy",
+            LogicalOp::Coalesce => "is_nullish",
+            LogicalOp::Default => "is_undefined",
+        }
+    }
+}
+
+/// The unary operators of JS that the runtime implements, as functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    Plus,
+    Not,
+    BitNot,
+    Void,
+}
+
+impl UnaryOp {
+    pub fn from_js(operator: UnaryOperator) -> Option<UnaryOp> {
+        let op = match operator {
+            UnaryOperator::UnaryNegation => UnaryOp::Negate,
+            UnaryOperator::UnaryPlus => UnaryOp::Plus,
+            UnaryOperator::LogicalNot => UnaryOp::Not,
+            UnaryOperator::BitwiseNot => UnaryOp::BitNot,
+            UnaryOperator::Void => UnaryOp::Void,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    pub fn function(self) -> &'static str {
+        match self {
+            UnaryOp::Negate => "negate",
+            UnaryOp::Plus => "plus",
+            UnaryOp::Not => "not",
+            UnaryOp::BitNot => "bit_not",
+            UnaryOp::Void => "void",
+        }
+    }
+}
@@ -0,0 +1,3068 @@
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+};
+
+use oxc::{
+    ast::ast::{
+        Argument, ArrayExpression, ArrayExpressionElement, ArrowFunctionExpression,
+        AssignmentExpression, AssignmentOperator, AssignmentTarget, AssignmentTargetMaybeDefault,
+        AssignmentTargetProperty, AssignmentTargetPropertyIdentifier, BinaryExpression,
+        BindingPattern, BindingPatternKind, BlockStatement, CallExpression, CatchClause,
+        ChainElement, ChainExpression, Class, ClassElement, ComputedMemberExpression,
+        DoWhileStatement, Expression, ForStatement, ForStatementInit, ForStatementLeft,
+        FormalParameters, Function, FunctionBody, IdentifierReference, LabelIdentifier,
+        MethodDefinitionKind, NewExpression, ObjectExpression, ObjectPropertyKind,
+        PrivateFieldExpression, PropertyDefinition, PropertyKey, PropertyKind, SequenceExpression,
+        SimpleAssignmentTarget, Statement, StaticMemberExpression, SwitchCase, SwitchStatement,
+        TaggedTemplateExpression, TemplateLiteral, TryStatement, UnaryExpression, UnaryOperator,
+        UpdateExpression, UpdateOperator, VariableDeclaration, VariableDeclarationKind,
+        WhileStatement,
+    },
+    ast::{visit::walk, Visit},
+    span::{GetSpan, Span},
+    syntax::scope::ScopeFlags,
+};
+
+use super::{
+    generators,
+    ir::{
+        BinaryOp, Catch, Entry, Expr, FunctionInfo, Iteration, LogicalOp, ReturnTo, Stmt, StmtKind,
+        UnaryOp,
+    },
+    variant_name, OnUnsupported,
+};
+use crate::{TranspileError, TranspileOptions};
+
+/// Lowers the AST of a program into the IR, which is where the JS semantics are dealt with.
+///
+/// `lower_statement` and `lower_expression` dispatch on the kind of the node, every kind that
+/// needs more than a few lines has a `lower_*` method of its own.
+pub(crate) struct Lowerer<'o> {
+    options: &'o TranspileOptions,
+    /// The JS scopes around the current node, innermost last
+    scopes: Vec<Scope>,
+    /// The statements around the current node that `break` and `continue` can jump to,
+    /// innermost last
+    jump_targets: Vec<JumpTarget>,
+    /// What a `return` at the current node returns from
+    return_to: ReturnTo,
+    /// What `this` is at the current node
+    this: This,
+    /// Whether the innermost function around the current node that isn't an arrow function
+    /// uses `new.target`
+    uses_new_target: bool,
+    /// The Rust label of the `Chain` of the innermost optional chain around the current node
+    chain: Option<String>,
+    /// The classes and function expressions around the current node, innermost last
+    captures: Vec<Capture>,
+    /// The private names of the classes around the current node, innermost last, each mapped
+    /// to the name qualified with its class
+    private_names: Vec<HashMap<String, String>>,
+    /// What `super` refers to at the current node
+    home: Option<Home>,
+    /// The number of temporaries, labels and classes declared so far, which keeps their names
+    /// unique
+    temp_count: usize,
+    /// The unsupported nodes found so far, in `Panic` mode
+    errors: Vec<TranspileError>,
+}
+
+/// The class that a member is in, if it extends another one, which `super` refers to
+struct Home {
+    /// The Rust name of the class
+    class: String,
+    /// The scope that declares the class
+    scope: usize,
+    /// Whether the member is static, where `super` is the class that the class extends
+    is_static: bool,
+    /// The statements that set the fields in the constructor, which come right after the
+    /// `super` call
+    fields: Vec<Stmt>,
+}
+
+/// The name of a member of a class
+enum MemberKey {
+    Public(String),
+    /// The name qualified with the class, see `private_key`
+    Private(String),
+}
+
+/// A statement that `break` or `continue` can jump to, which is lowered to a Rust loop or a
+/// labeled block
+struct JumpTarget {
+    /// The JS labels of the statement
+    labels: Vec<String>,
+    kind: JumpTargetKind,
+    /// The label of the Rust loop or block, once a jump needs one
+    rust_label: Option<String>,
+    /// Whether a `continue` jumps to it
+    continued: bool,
+}
+
+/// What `this` is in a function
+#[derive(Debug, Clone, Copy)]
+enum This {
+    /// Outside of every function, where it would be the exports of the module
+    TopLevel,
+    /// In an immediately invoked function, which is a Rust closure that can only be called as a
+    /// plain function. Its `new.target` is `undefined` too.
+    Undefined,
+    /// In a function object, which is passed it by the caller
+    Bound,
+}
+
+/// A JS scope
+struct Scope {
+    declared: HashSet<String>,
+    /// The Rust names of the variables that closures share with it rather than having copies
+    /// of them, which are `SharedBinding`s, see `shared_names`
+    shared: HashSet<String>,
+    /// The Rust names of the shared variables that were declared up front, which their
+    /// declarations set
+    predeclared: HashSet<String>,
+    /// The names declared by the statements that aren't lowered yet, which closures can't have
+    /// a copy of yet
+    uninitialized: HashSet<String>,
+}
+
+/// The kind of a function, which decides how its body is lowered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionKind {
+    Normal,
+    /// A generator function, whose body is the states of the generator that it returns
+    Generator,
+    /// An arrow function, which has no `arguments` of its own. Its body can be an expression,
+    /// which is what it returns.
+    Arrow {
+        expression_body: bool,
+    },
+}
+
+impl FunctionKind {
+    fn of(func: &Function) -> Self {
+        match func.generator {
+            true => FunctionKind::Generator,
+            false => FunctionKind::Normal,
+        }
+    }
+
+    fn has_expression_body(self) -> bool {
+        matches!(
+            self,
+            FunctionKind::Arrow {
+                expression_body: true
+            }
+        )
+    }
+}
+
+/// A function that was lowered, which is yet to be turned into a Rust closure or a function
+/// object
+struct LoweredFunction {
+    params: Vec<String>,
+    /// The name of the rest parameter
+    rest: Option<String>,
+    body: Vec<Stmt>,
+    /// Whether the body starts by binding `new.target`
+    uses_new_target: bool,
+}
+
+/// The variables that a class or a function object uses from outside of it. Their
+/// functions are Rust closures that outlive the scope they are in, so each of them gets a copy
+/// of the variables, made when it's created.
+struct Capture {
+    /// The number of scopes around it, the variables of these scopes are outside of it
+    depth: usize,
+    /// The Rust names of the variables
+    names: Vec<String>,
+    /// Whether it uses the `this` from outside of it, like an arrow function or the closure of
+    /// a generator
+    outer_this: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JumpTargetKind {
+    /// A loop, which `break` and `continue` jump to
+    Loop,
+    /// A `switch`, which `break` jumps to
+    Switch,
+    /// Any other labeled statement, which only `break` with its label jumps to
+    Block,
+}
+
+impl<'o> Lowerer<'o> {
+    pub fn new(options: &'o TranspileOptions) -> Self {
+        Lowerer {
+            options,
+            scopes: Vec::new(),
+            jump_targets: Vec::new(),
+            return_to: ReturnTo::Nothing,
+            this: This::TopLevel,
+            uses_new_target: false,
+            chain: None,
+            captures: Vec::new(),
+            private_names: Vec::new(),
+            home: None,
+            temp_count: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// The errors of every unsupported node of the program, in the order of the source.
+    pub fn finish(self) -> Result<(), TranspileError> {
+        let mut errors = self.errors;
+        // The parts of a node aren't always lowered in the order of the source
+        errors.sort_by_key(|error| {
+            error
+                .unsupported_nodes()
+                .first()
+                .map(|node| node.span.start)
+        });
+        match errors.into_iter().reduce(TranspileError::merge) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Lowers the top level statements of the program, which are a scope of their own, handing
+    /// each of them to `f` as soon as it's lowered.
+    pub fn lower_program(&mut self, statements: &[Statement], f: impl FnMut(Stmt)) {
+        let declared = declared_names(statements);
+        let shared = self.shared_names(&declared, |uses| {
+            statements
+                .iter()
+                .for_each(|statement| uses.visit_statement(statement))
+        });
+        self.scoped(declared, shared, |this| {
+            this.lower_each_statement(statements, f)
+        });
+    }
+
+    /// Runs `f` in a new JS scope with the given names declared in it, of which closures share
+    /// the `shared` ones.
+    fn scoped<T>(
+        &mut self,
+        declared: HashSet<String>,
+        shared: HashSet<String>,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.scopes.push(Scope {
+            declared,
+            shared,
+            predeclared: HashSet::new(),
+            uninitialized: HashSet::new(),
+        });
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    /// The Rust names of the declared variables that closures share with the scope, given the
+    /// code of the scope. A closure has a copy of a variable from outside of it, unless the
+    /// variable is assigned to after it's declared, or a function declaration uses it, which
+    /// can be called before the variable is declared.
+    fn shared_names(
+        &self,
+        declared: &HashSet<String>,
+        visit: impl FnOnce(&mut Uses),
+    ) -> HashSet<String> {
+        let mut uses = Uses::default();
+        visit(&mut uses);
+        declared
+            .iter()
+            .filter(|name| uses.captured.contains(*name))
+            .filter(|name| uses.assigned.contains(*name) || uses.hoisted.contains(*name))
+            .map(|name| self.identifier(name))
+            .collect()
+    }
+
+    /// Whether the name refers to a variable of the program rather than a global.
+    fn is_declared(&self, name: &str) -> bool {
+        self.declaring_scope(name).is_some()
+    }
+
+    /// The index of the innermost scope that declares the name.
+    fn declaring_scope(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rposition(|scope| scope.declared.contains(name))
+    }
+
+    /// Whether the name refers to a variable from outside of the innermost class or function
+    /// expression that the current node is in, which only has a copy of it.
+    fn is_captured(&self, name: &str) -> bool {
+        match (self.declaring_scope(name), self.captures.last()) {
+            (Some(scope), Some(capture)) => scope < capture.depth,
+            _ => false,
+        }
+    }
+
+    /// A name for a Rust temporary that can't clash with any other.
+    fn temp_name(&mut self) -> String {
+        self.temp_count += 1;
+        format!("__jsrs_tmp{}", self.temp_count)
+    }
+
+    /// A Rust label that can't clash with any other.
+    fn label_name(&mut self) -> String {
+        self.temp_count += 1;
+        format!("'__jsrs_label{}", self.temp_count)
+    }
+
+    /// Runs `f` with the statement that it lowers as the innermost jump target, returning the
+    /// target too, which has the Rust label of the statement if a jump needs one.
+    fn jump_target<T>(
+        &mut self,
+        labels: Vec<String>,
+        kind: JumpTargetKind,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> (T, JumpTarget) {
+        self.jump_targets.push(JumpTarget {
+            labels,
+            kind,
+            rust_label: None,
+            continued: false,
+        });
+        let result = f(self);
+        (result, self.jump_targets.pop().unwrap())
+    }
+
+    /// The name of the Rust variable for a JS identifier
+    fn identifier(&self, name: &str) -> String {
+        format!("{}{name}", self.options.identifier_prefix)
+    }
+
+    /// Handles a node that the converter doesn't support, according to the `OnUnsupported`
+    /// mode of the options.
+    ///
+    /// The `kind` is the kind of the node, while the `description` says what exactly about it
+    /// isn't supported. The returned expression panics when it's reached, so it can be used
+    /// both in expression and statement position.
+    fn unsupported(&mut self, kind: &str, description: &str, span: Span) -> Expr {
+        if self.options.on_unsupported == OnUnsupported::Panic {
+            self.errors
+                .push(TranspileError::unsupported(kind, description, span));
+        }
+        Expr::Todo(format!(
+            "unsupported {description} at {}..{}",
+            span.start, span.end
+        ))
+    }
+
+    fn lower_statements(&mut self, statements: &[Statement]) -> Vec<Stmt> {
+        let mut lowered = Vec::new();
+        self.lower_each_statement(statements, |stmt| lowered.push(stmt));
+        lowered
+    }
+
+    /// Lowers the statements of the innermost scope, handing each of them to `f` as soon as
+    /// it's lowered, while keeping track of the variables that they initialize.
+    ///
+    /// The variables that closures share are declared before anything else, then the function
+    /// declarations are hoisted above the other statements, so that they can be called before
+    /// they are declared, like in JS.
+    fn lower_each_statement(&mut self, statements: &[Statement], mut f: impl FnMut(Stmt)) {
+        let mut predeclared = Vec::new();
+        for name in declared_names(statements) {
+            let rust_name = self.identifier(&name);
+            let scope = self.scopes.last_mut().unwrap();
+            match scope.shared.contains(&rust_name) {
+                true => predeclared.push(rust_name),
+                false => {
+                    scope.uninitialized.insert(name);
+                }
+            }
+        }
+        // In the same order every time
+        predeclared.sort();
+        for name in predeclared {
+            self.scopes
+                .last_mut()
+                .unwrap()
+                .predeclared
+                .insert(name.clone());
+            f(Stmt::new(StmtKind::Let {
+                name,
+                mutable: false,
+                init: Some(Expr::NewShared(Expr::Undefined.boxed())),
+            }));
+        }
+        let (functions, others): (Vec<_>, Vec<_>) = statements
+            .iter()
+            .partition(|statement| matches!(statement, Statement::FunctionDeclaration(_)));
+        for statement in functions.into_iter().chain(others) {
+            f(self.lower_statement(statement));
+            let scope = self.scopes.last_mut().unwrap();
+            for name in declared_names(std::slice::from_ref(statement)) {
+                scope.uninitialized.remove(&name);
+            }
+        }
+    }
+
+    /// The statements of a block, which is a scope of its own.
+    fn lower_block(&mut self, block: &BlockStatement) -> Vec<Stmt> {
+        let declared = declared_names(&block.body);
+        let shared = self.shared_names(&declared, |uses| uses.visit_block_statement(block));
+        self.scoped(declared, shared, |this| this.lower_statements(&block.body))
+    }
+
+    /// Declares the variable of the innermost scope, whose Rust name is `name`. A variable that
+    /// closures share is bound to a `SharedBinding`, unless it was declared up front, in which
+    /// case it's set instead.
+    fn declare(&mut self, name: String, mutable: bool, init: Option<Expr>) -> StmtKind {
+        let scope = self.scopes.last().unwrap();
+        if !scope.shared.contains(&name) {
+            return StmtKind::Let {
+                name,
+                mutable,
+                init,
+            };
+        }
+        let value = init.unwrap_or(Expr::Undefined).boxed();
+        match scope.predeclared.contains(&name) {
+            true => StmtKind::Expr(Expr::SetShared {
+                target: name,
+                value,
+            }),
+            false => StmtKind::Let {
+                name,
+                mutable: false,
+                init: Some(Expr::NewShared(value)),
+            },
+        }
+    }
+
+    /// The statements of a block, or the statement itself if it isn't one, for where Rust
+    /// needs a block anyway.
+    fn lower_body(&mut self, statement: &Statement) -> Vec<Stmt> {
+        match statement {
+            Statement::BlockStatement(block) => self.lower_block(block),
+            statement => vec![self.lower_statement(statement)],
+        }
+    }
+
+    /// Runs `f` in a function or a closure of a `try` statement, which `return` returns from
+    /// and jumps can't leave.
+    fn in_closure<T>(&mut self, return_to: ReturnTo, f: impl FnOnce(&mut Self) -> T) -> T {
+        let outer_return_to = std::mem::replace(&mut self.return_to, return_to);
+        let jump_targets = std::mem::take(&mut self.jump_targets);
+        let result = f(self);
+        self.return_to = outer_return_to;
+        self.jump_targets = jump_targets;
+        result
+    }
+
+    /// Runs `f` with `this` being what it is in a function of that kind.
+    fn with_this<T>(&mut self, this: This, f: impl FnOnce(&mut Self) -> T) -> T {
+        let outer_this = std::mem::replace(&mut self.this, this);
+        let result = f(self);
+        self.this = outer_this;
+        result
+    }
+
+    /// The IR of every statement is mapped to the span of the statement.
+    fn lower_statement(&mut self, statement: &Statement) -> Stmt {
+        let kind = match statement {
+            Statement::FunctionDeclaration(func) => self.lower_function_declaration(func),
+            Statement::ClassDeclaration(class) => self.lower_class_declaration(class),
+            Statement::ReturnStatement(statement) => {
+                let value = statement
+                    .argument
+                    .as_ref()
+                    .map(|argument| self.lower_expression(argument).cloned());
+                match self.return_to {
+                    ReturnTo::Try => StmtKind::ReturnFromTry(value.unwrap_or(Expr::Undefined)),
+                    // A function always returns a `JsValue`
+                    ReturnTo::Function => StmtKind::Return(Some(value.unwrap_or(Expr::Undefined))),
+                    ReturnTo::Generator => {
+                        StmtKind::ReturnFromGenerator(value.unwrap_or(Expr::Undefined))
+                    }
+                    ReturnTo::Nothing => StmtKind::Return(value),
+                }
+            }
+            Statement::VariableDeclaration(statement) => {
+                StmtKind::Group(self.lower_variable_declaration(statement))
+            }
+            Statement::IfStatement(statement) => StmtKind::If {
+                test: self.lower_expression(&statement.test),
+                then: self.lower_body(&statement.consequent),
+                otherwise: match &statement.alternate {
+                    Some(alternate) => self.lower_body(alternate),
+                    None => Vec::new(),
+                },
+            },
+            Statement::ForOfStatement(statement) if statement.r#await => {
+                let todo = self.unsupported("ForOfStatement", "`for await` loop", statement.span);
+                StmtKind::Expr(todo)
+            }
+            Statement::LabeledStatement(_)
+            | Statement::ForStatement(_)
+            | Statement::ForInStatement(_)
+            | Statement::ForOfStatement(_)
+            | Statement::WhileStatement(_)
+            | Statement::DoWhileStatement(_)
+            | Statement::SwitchStatement(_) => self.lower_jump_target(statement, Vec::new()),
+            Statement::BreakStatement(statement) => {
+                self.lower_jump(statement.label.as_ref(), false, statement.span)
+            }
+            Statement::ContinueStatement(statement) => {
+                self.lower_jump(statement.label.as_ref(), true, statement.span)
+            }
+            Statement::BlockStatement(statement) => StmtKind::Block(self.lower_block(statement)),
+            Statement::TryStatement(statement) => self.lower_try(statement),
+            Statement::ThrowStatement(statement) => {
+                StmtKind::Throw(self.lower_expression(&statement.argument).cloned())
+            }
+            Statement::ExpressionStatement(statement) => {
+                StmtKind::Expr(self.lower_expression(&statement.expression))
+            }
+            Statement::EmptyStatement(_) => StmtKind::Group(Vec::new()),
+            Statement::DebuggerStatement(_) => match &self.options.debugger_hook {
+                Some(hook) => StmtKind::Expr(Expr::Intrinsic(hook.clone())),
+                None => StmtKind::Group(Vec::new()),
+            },
+            _ => {
+                let kind = variant_name(statement);
+                StmtKind::Expr(self.unsupported(&kind, &kind, statement.span()))
+            }
+        };
+        Stmt {
+            kind,
+            span: Some(statement.span()),
+        }
+    }
+
+    /// A function declaration is a function object like a function expression, so that it can
+    /// be a constructor, and passed around like any other value.
+    fn lower_function_declaration(&mut self, func: &Function) -> StmtKind {
+        let name = func.name().unwrap();
+        let init = match &func.body {
+            _ if !is_supported_function(func) => {
+                self.unsupported("Function", "async function", func.span)
+            }
+            Some(body) => {
+                self.lower_function_object(&func.params, body, FunctionKind::of(func), &name)
+            }
+            None => self.unsupported("Function", "function without a body", func.span),
+        };
+        self.declare(self.identifier(&name), false, Some(init))
+    }
+
+    /// The Rust names of the parameters of the function, and its body, which starts with the
+    /// `prologue` and ends with a `return` for when the end of the function is reached.
+    ///
+    /// The body of an arrow function can be an expression, which is what it returns. The body
+    /// of a generator function returns a generator, which runs the statements.
+    ///
+    /// The `arguments` of a function other than an arrow function is only made if the function
+    /// uses it, and so is its `new.target`. The parameters that closures share are bound to
+    /// `SharedBinding`s of them.
+    fn lower_function(
+        &mut self,
+        params: &FormalParameters,
+        body: Option<&FunctionBody>,
+        this: This,
+        kind: FunctionKind,
+        prologue: Vec<Stmt>,
+    ) -> LoweredFunction {
+        let param_names: Vec<_> = params
+            .items
+            .iter()
+            .map(|param| self.lower_binding_pattern(&param.pattern))
+            .collect();
+        let rest = params
+            .rest
+            .as_ref()
+            .map(|rest| self.lower_binding_pattern(&rest.argument));
+
+        let mut declared = HashSet::new();
+        for param in &params.items {
+            declared.extend(pattern_names(&param.pattern));
+        }
+        if let Some(rest) = &params.rest {
+            declared.extend(pattern_names(&rest.argument));
+        }
+        if let Some(body) = body {
+            declared.extend(declared_names(&body.statements));
+        }
+        let shared = self.shared_names(&declared, |uses| {
+            uses.visit_formal_parameters(params);
+            if let Some(body) = body {
+                uses.visit_function_body(body);
+            }
+        });
+        let arguments = match (kind, body) {
+            (FunctionKind::Arrow { .. }, _) | (_, None) => None,
+            (_, Some(body)) => {
+                (!declared.contains("arguments") && uses_arguments(body)).then(|| {
+                    declared.insert(String::from("arguments"));
+                    Stmt::new(StmtKind::Let {
+                        name: self.identifier("arguments"),
+                        mutable: false,
+                        init: Some(Expr::Arguments),
+                    })
+                })
+            }
+        };
+        let generator = kind == FunctionKind::Generator;
+        let return_to = match generator {
+            true => ReturnTo::Generator,
+            false => ReturnTo::Function,
+        };
+        if generator {
+            // The closure of the generator is moved into it, so it has copies too
+            self.captures.push(Capture {
+                depth: self.scopes.len(),
+                names: Vec::new(),
+                outer_this: true,
+            });
+        }
+        let mut lowered = prologue;
+        let arrow = matches!(kind, FunctionKind::Arrow { .. });
+        let outer_uses_new_target = std::mem::replace(&mut self.uses_new_target, false);
+        let shared_params = self.with_this(this, |this| {
+            this.in_closure(return_to, |this| {
+                this.scoped(declared, shared, |this| {
+                    let mut shared_params = Vec::new();
+                    for name in param_names.iter().chain(&rest) {
+                        if this.scopes.last().unwrap().shared.contains(name) {
+                            let init = Some(Expr::Local(name.clone()));
+                            shared_params.push(Stmt::new(this.declare(name.clone(), false, init)));
+                        }
+                    }
+                    match body.map(|body| body.statements.as_slice()) {
+                        Some([Statement::ExpressionStatement(statement)])
+                            if kind.has_expression_body() =>
+                        {
+                            let value = this.lower_expression(&statement.expression).cloned();
+                            lowered.push(Stmt {
+                                kind: StmtKind::Return(Some(value)),
+                                span: Some(statement.span),
+                            });
+                        }
+                        Some(statements) => lowered.extend(this.lower_statements(statements)),
+                        None => {}
+                    }
+                    shared_params
+                })
+            })
+        });
+        // An arrow function uses the `new.target` from outside of it
+        let uses_new_target = match arrow {
+            true => {
+                self.uses_new_target |= outer_uses_new_target;
+                false
+            }
+            false => std::mem::replace(&mut self.uses_new_target, outer_uses_new_target),
+        };
+        // The `new.target`, the `arguments` and the shared parameters are made right away,
+        // before the generator is created
+        let mut body = Vec::new();
+        if uses_new_target {
+            body.push(new_target_binding());
+        }
+        body.extend(arguments);
+        body.extend(shared_params);
+        match generator {
+            true => {
+                let copies = self.captures.pop().unwrap().names;
+                let (locals, shared, states) =
+                    generators::into_states(lowered, &mut |description, span| {
+                        self.unsupported("YieldExpression", description, span)
+                    });
+                let generator = Expr::Generator {
+                    copies,
+                    locals,
+                    shared,
+                    states,
+                };
+                body.push(Stmt::new(StmtKind::Return(Some(generator))));
+            }
+            false => {
+                body.extend(lowered);
+                body.push(Stmt::new(StmtKind::Return(Some(Expr::Undefined))));
+            }
+        }
+        LoweredFunction {
+            params: param_names,
+            rest,
+            body,
+            uses_new_target,
+        }
+    }
+
+    /// A function declaration or expression, or an arrow function, is a function object, which
+    /// the Rust closure of the function is wrapped in. The `name` is empty for an anonymous function.
+    fn lower_function_object(
+        &mut self,
+        params: &FormalParameters,
+        body: &FunctionBody,
+        kind: FunctionKind,
+        name: &str,
+    ) -> Expr {
+        let arrow = matches!(kind, FunctionKind::Arrow { .. });
+        self.captures.push(Capture {
+            depth: self.scopes.len(),
+            names: Vec::new(),
+            outer_this: arrow,
+        });
+        // Arrow functions use the `this` from outside of them, other functions are passed it
+        let this = match arrow {
+            true => self.this,
+            false => This::Bound,
+        };
+        let LoweredFunction {
+            params,
+            rest,
+            body,
+            uses_new_target,
+        } = self.lower_function(params, Some(body), this, kind, Vec::new());
+        let captured = self.captures.pop().unwrap().names;
+        let info = FunctionInfo {
+            name: name.to_owned(),
+            uses_new_target,
+        };
+        let function = match arrow {
+            true => Expr::Function {
+                params,
+                rest,
+                body,
+                info,
+            },
+            false => Expr::Method {
+                params,
+                rest,
+                body,
+                info,
+            },
+        };
+        with_copies(&captured, function)
+    }
+
+    /// The name of a function expression is only declared in the function itself, where
+    /// it can't be used, as the closure of the function can't have a copy of itself. An
+    /// anonymous function gets the `name` of what it's assigned to, if it's given one.
+    fn lower_function_expression(&mut self, func: &Function, name: &str) -> Expr {
+        if !is_supported_function(func) {
+            return self.unsupported("Function", "async function", func.span);
+        }
+        let name = func.id.as_ref().map_or(name, |id| id.name.as_str());
+        let declared: HashSet<_> = func.id.iter().map(|id| id.name.to_string()).collect();
+        self.scoped(declared.clone(), HashSet::new(), |this| {
+            this.scopes.last_mut().unwrap().uninitialized = declared;
+            match &func.body {
+                Some(body) => {
+                    this.lower_function_object(&func.params, body, FunctionKind::of(func), name)
+                }
+                None => this.unsupported("Function", "function without a body", func.span),
+            }
+        })
+    }
+
+    /// An arrow function is a function object like a function expression, but it has no
+    /// `this` of its own.
+    fn lower_arrow_function(&mut self, arrow: &ArrowFunctionExpression, name: &str) -> Expr {
+        if arrow.r#async {
+            return self.unsupported("ArrowFunctionExpression", "async function", arrow.span);
+        }
+        let kind = FunctionKind::Arrow {
+            expression_body: arrow.expression,
+        };
+        self.lower_function_object(&arrow.params, &arrow.body, kind, name)
+    }
+
+    /// The value of a variable or a property with the name, where an anonymous function gets
+    /// the name.
+    fn lower_named_value(&mut self, value: &Expression, name: &str) -> Expr {
+        match value.without_parentheses() {
+            Expression::FunctionExpression(func) => self.lower_function_expression(func, name),
+            Expression::ArrowFunctionExpression(arrow) => self.lower_arrow_function(arrow, name),
+            _ => self.lower_expression(value),
+        }
+    }
+
+    /// A class is a constructor and a table of methods that its instances share. The fields
+    /// are set by the constructor, before the statements of its body, or right after the
+    /// `super` call in the constructor of a class that extends another one.
+    ///
+    /// The class is created before its methods are, so that they can use it too. A class that
+    /// closures share is created in a temporary, which the variable is set to right away.
+    fn lower_class_declaration(&mut self, class: &Class) -> StmtKind {
+        let js_name = class.id.as_ref().unwrap().name.to_string();
+        let binding = self.identifier(&js_name);
+        let name = match self.scopes.last().unwrap().shared.contains(&binding) {
+            true => self.temp_name(),
+            false => binding.clone(),
+        };
+        let parent = class
+            .super_class
+            .as_ref()
+            .map(|parent| self.lower_expression(parent).cloned().boxed());
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .uninitialized
+            .remove(&js_name);
+
+        let outer_home = self.home.take();
+        let (extends, scope) = (parent.is_some(), self.scopes.len() - 1);
+        let home = |is_static| {
+            extends.then(|| Home {
+                class: name.clone(),
+                scope,
+                is_static,
+                fields: Vec::new(),
+            })
+        };
+        self.captures.push(Capture {
+            depth: self.scopes.len(),
+            names: Vec::new(),
+            outer_this: false,
+        });
+        // The private names of each class are distinct from those of any other class
+        self.temp_count += 1;
+        let qualifier = self.temp_count;
+        let private_names = class
+            .body
+            .body
+            .iter()
+            .filter_map(|element| element.property_key()?.private_name())
+            .map(|name| (name.to_string(), format!("{qualifier}#{name}")))
+            .collect();
+        self.private_names.push(private_names);
+        let mut fields = Vec::new();
+        let mut constructor = None;
+        let mut methods = Vec::new();
+        // The static fields and blocks, which run once the class is defined
+        let mut initializers = Vec::new();
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(method) => {
+                    let key =
+                        self.lower_class_member_key(&method.key, method.computed, method.r#static);
+                    let Some(key) = key else {
+                        continue;
+                    };
+                    match method.kind {
+                        MethodDefinitionKind::Constructor => constructor = Some(&method.value),
+                        kind => methods.push((key, kind, method.r#static, &method.value)),
+                    }
+                }
+                ClassElement::PropertyDefinition(property) => {
+                    let key = self.lower_class_member_key(
+                        &property.key,
+                        property.computed,
+                        property.r#static,
+                    );
+                    let Some(key) = key else {
+                        continue;
+                    };
+                    match property.r#static {
+                        true => initializers.push(element),
+                        false => fields.push((key, &**property)),
+                    }
+                }
+                ClassElement::StaticBlock(_) => initializers.push(element),
+                element => {
+                    let kind = variant_name(element);
+                    self.unsupported(&kind, &kind, element.span());
+                }
+            }
+        }
+
+        let mut private: Vec<_> = fields
+            .iter()
+            .filter_map(|(key, _)| match key {
+                MemberKey::Private(name) => Some((name.clone(), Expr::Undefined)),
+                MemberKey::Public(_) => None,
+            })
+            .collect();
+        self.home = home(false);
+        let fields = self.with_this(This::Bound, |this| this.lower_fields(&fields));
+        // A class that extends another one sets its fields once `super` returns
+        let prologue = match &mut self.home {
+            Some(home) => {
+                home.fields = fields;
+                Vec::new()
+            }
+            None => fields,
+        };
+        // The name of the class is the name of its constructor
+        let constructor = match constructor {
+            Some(func) => {
+                let LoweredFunction {
+                    params,
+                    rest,
+                    body,
+                    uses_new_target,
+                } = self.lower_function(
+                    &func.params,
+                    func.body.as_deref(),
+                    This::Bound,
+                    FunctionKind::Normal,
+                    prologue,
+                );
+                Expr::Method {
+                    params,
+                    rest,
+                    body,
+                    info: FunctionInfo {
+                        name: js_name,
+                        uses_new_target,
+                    },
+                }
+            }
+            None => self.default_constructor(prologue, js_name, class.span),
+        };
+        let mut lowered = Vec::new();
+        for (key, kind, is_static, func) in methods {
+            if !is_supported_function(func) {
+                self.unsupported("Function", "async method", func.span);
+                continue;
+            }
+            self.home = home(is_static);
+            let LoweredFunction {
+                params, rest, body, ..
+            } = self.lower_function(
+                &func.params,
+                func.body.as_deref(),
+                This::Bound,
+                FunctionKind::of(func),
+                Vec::new(),
+            );
+            let name = match &key {
+                MemberKey::Public(name) => name.as_str(),
+                MemberKey::Private(name) => &name[name.find('#').unwrap()..],
+            };
+            let name = match kind {
+                MethodDefinitionKind::Get => format!("get {name}"),
+                MethodDefinitionKind::Set => format!("set {name}"),
+                _ => name.to_owned(),
+            };
+            let info = FunctionInfo {
+                name,
+                uses_new_target: false,
+            };
+            let method = Expr::Method {
+                params,
+                rest,
+                body,
+                info,
+            };
+            lowered.push((key, kind, is_static, method));
+        }
+        let captured = self.captures.pop().unwrap().names;
+
+        // Every method is a closure of its own, which needs its own copies
+        let mut public = Vec::new();
+        let mut statics = Vec::new();
+        for (key, kind, is_static, method) in lowered {
+            let method = with_copies(&captured, method);
+            match (key, is_static) {
+                (MemberKey::Public(name), false) => add_method(&mut public, name, kind, method),
+                (MemberKey::Private(name), false) => add_method(&mut private, name, kind, method),
+                (MemberKey::Public(name), true) => add_method(&mut statics, name, kind, method),
+                (MemberKey::Private(_), true) => {
+                    unreachable!("static private members are unsupported")
+                }
+            }
+        }
+        let define = Expr::DefineClass {
+            class: Expr::Local(name.clone()).boxed(),
+            constructor: with_copies(&captured, constructor).boxed(),
+            methods: public,
+            private,
+        };
+        let mut group = vec![Stmt::new(StmtKind::Let {
+            name: name.clone(),
+            mutable: false,
+            init: Some(Expr::NewClass { parent }),
+        })];
+        if name != binding {
+            let init = Some(Expr::Local(name.clone()).cloned());
+            group.push(Stmt::new(self.declare(binding, false, init)));
+        }
+        group.push(Stmt::new(StmtKind::Expr(define)));
+        if !statics.is_empty() || !initializers.is_empty() {
+            self.home = home(true);
+            let body = self.lower_static_members(&name, statics, &initializers);
+            group.push(Stmt::new(StmtKind::Block(body)));
+        }
+        self.private_names.pop();
+        self.home = outer_home;
+        StmtKind::Group(group)
+    }
+
+    /// The constructor of a class that doesn't have one, which sets the fields. In a class
+    /// that extends another one, it passes its arguments and its `new.target` on to the
+    /// constructor of that class first.
+    fn default_constructor(&mut self, prologue: Vec<Stmt>, name: String, span: Span) -> Expr {
+        let mut body = prologue;
+        let mut rest = None;
+        let extends = self.home.is_some();
+        if extends {
+            let args = self.temp_name();
+            let arguments = vec![Entry::Spread(Expr::Local(args.clone()))];
+            let outer_uses_new_target = self.uses_new_target;
+            let call = self.with_this(This::Bound, |this| this.super_call(arguments, span));
+            self.uses_new_target = outer_uses_new_target;
+            body.insert(0, new_target_binding());
+            body.push(Stmt::new(StmtKind::Expr(call)));
+            rest = Some(args);
+        }
+        body.push(Stmt::new(StmtKind::Return(Some(Expr::Undefined))));
+        Expr::Method {
+            params: Vec::new(),
+            rest,
+            body,
+            info: FunctionInfo {
+                name,
+                uses_new_target: extends,
+            },
+        }
+    }
+
+    /// Calls the constructor of the class that the class of the constructor extends on `this`,
+    /// then sets the fields of the class. Evaluates to `this`.
+    fn super_call(&mut self, arguments: Vec<Entry<Expr>>, span: Span) -> Expr {
+        let Some(Home {
+            class,
+            scope,
+            fields,
+            ..
+        }) = &self.home
+        else {
+            let description = "`super` outside of a class that extends another";
+            return self.unsupported("Super", description, span);
+        };
+        let (class, scope, fields) = (class.clone(), *scope, fields.clone());
+        let this = self.lower_this(span);
+        let mut body = vec![Stmt::new(StmtKind::Expr(Expr::SuperCall {
+            class: self.capture(scope, class).boxed(),
+            this: this.clone().boxed(),
+            arguments,
+            new_target: self.lower_new_target(span).boxed(),
+        }))];
+        body.extend(fields);
+        Expr::Block {
+            body,
+            value: this.cloned().boxed(),
+        }
+    }
+
+    fn lower_super_call(&mut self, exp: &CallExpression) -> Expr {
+        let arguments = self.lower_arguments(&exp.arguments);
+        self.super_call(arguments, exp.span)
+    }
+
+    /// `super[key]`, which is looked up in the class that the class of the method extends
+    fn lower_super_member(&mut self, key: Expr, span: Span) -> Expr {
+        let Some(Home {
+            class,
+            scope,
+            is_static,
+            ..
+        }) = &self.home
+        else {
+            let description = "`super` outside of a class that extends another";
+            return self.unsupported("Super", description, span);
+        };
+        let (class, scope, is_static) = (class.clone(), *scope, *is_static);
+        Expr::GetSuper {
+            class: self.capture(scope, class).boxed(),
+            key: key.boxed(),
+            this: self.lower_this(span).boxed(),
+            is_static,
+        }
+    }
+
+    /// Sets the static methods on the class, then runs the initializers of the static fields
+    /// and the static blocks in the order they are declared, with the class as `this`.
+    fn lower_static_members(
+        &mut self,
+        class: &str,
+        methods: Vec<(String, Expr)>,
+        initializers: &[&ClassElement],
+    ) -> Vec<Stmt> {
+        let this = String::from("this");
+        let mut body = vec![Stmt::new(StmtKind::Let {
+            name: this.clone(),
+            mutable: false,
+            init: Some(Expr::Local(class.to_owned()).cloned()),
+        })];
+        body.extend(methods.into_iter().map(|(key, method)| {
+            Stmt::new(StmtKind::Expr(Expr::SetProp {
+                object: Expr::Local(this.clone()).boxed(),
+                key: Expr::String(key).boxed(),
+                value: method.boxed(),
+            }))
+        }));
+        self.with_this(This::Bound, |this| {
+            for element in initializers {
+                match element {
+                    ClassElement::PropertyDefinition(property) => {
+                        let key = this.lower_class_member_key(&property.key, false, true);
+                        body.extend(this.lower_fields(&[(key.unwrap(), property)]));
+                    }
+                    ClassElement::StaticBlock(block) => {
+                        let declared = declared_names(&block.body);
+                        let shared = this.shared_names(&declared, |uses| {
+                            block
+                                .body
+                                .iter()
+                                .for_each(|statement| uses.visit_statement(statement))
+                        });
+                        let statements = this
+                            .scoped(declared, shared, |this| this.lower_statements(&block.body));
+                        body.push(Stmt {
+                            kind: StmtKind::Block(statements),
+                            span: Some(block.span),
+                        });
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        });
+        body
+    }
+
+    /// The name of a method or field, unless it's a kind of member that isn't supported.
+    fn lower_class_member_key(
+        &mut self,
+        key: &PropertyKey,
+        computed: bool,
+        is_static: bool,
+    ) -> Option<MemberKey> {
+        let description = match key {
+            _ if computed => String::from("computed class member key"),
+            PropertyKey::StaticIdentifier(identifier) => {
+                return Some(MemberKey::Public(identifier.name.to_string()))
+            }
+            PropertyKey::PrivateIdentifier(_) if is_static => {
+                String::from("static private class member")
+            }
+            PropertyKey::PrivateIdentifier(identifier) => {
+                return Some(MemberKey::Private(self.private_key(&identifier.name)))
+            }
+            key => format!("class member key {}", variant_name(key)),
+        };
+        self.unsupported("PropertyKey", &description, key.span());
+        None
+    }
+
+    /// Sets the fields on `this`, in the order they are declared.
+    fn lower_fields(&mut self, fields: &[(MemberKey, &PropertyDefinition)]) -> Vec<Stmt> {
+        fields
+            .iter()
+            .map(|(key, property)| {
+                let value = match &property.value {
+                    Some(value) => self.lower_expression(value).cloned(),
+                    None => Expr::Undefined,
+                };
+                let object = Expr::Local(String::from("this")).boxed();
+                let set = match key {
+                    MemberKey::Public(key) => Expr::SetProp {
+                        object,
+                        key: Expr::String(key.clone()).boxed(),
+                        value: value.boxed(),
+                    },
+                    MemberKey::Private(name) => Expr::SetPrivate {
+                        object,
+                        name: name.clone(),
+                        value: value.boxed(),
+                    },
+                };
+                Stmt {
+                    kind: StmtKind::Expr(set),
+                    span: Some(property.span),
+                }
+            })
+            .collect()
+    }
+
+    /// The private name qualified with the innermost class around that declares it. The
+    /// parser makes sure that there is one.
+    fn private_key(&self, name: &str) -> String {
+        self.private_names
+            .iter()
+            .rev()
+            .find_map(|names| names.get(name))
+            .expect("a private name that no class around declares")
+            .clone()
+    }
+
+    /// Lowers a statement that `break` or `continue` can jump to, which has the given labels.
+    fn lower_jump_target(&mut self, statement: &Statement, mut labels: Vec<String>) -> StmtKind {
+        match statement {
+            Statement::LabeledStatement(statement) => {
+                labels.push(statement.label.name.to_string());
+                self.lower_jump_target(&statement.body, labels)
+            }
+            Statement::ForStatement(statement) => self.lower_for(statement, labels),
+            Statement::ForInStatement(statement) => self.lower_for_each(
+                labels,
+                Iteration::Keys,
+                &statement.left,
+                &statement.right,
+                &statement.body,
+            ),
+            Statement::ForOfStatement(statement) if !statement.r#await => self.lower_for_each(
+                labels,
+                Iteration::Elements,
+                &statement.left,
+                &statement.right,
+                &statement.body,
+            ),
+            Statement::WhileStatement(statement) => self.lower_while(statement, labels),
+            Statement::DoWhileStatement(statement) => self.lower_do_while(statement, labels),
+            Statement::SwitchStatement(statement) => self.lower_switch(statement, labels),
+            // Any other statement can only be left with a `break` of one of its labels
+            statement => {
+                let (body, target) = self.jump_target(labels, JumpTargetKind::Block, |this| {
+                    this.lower_body(statement)
+                });
+                match target.rust_label {
+                    Some(label) => StmtKind::Labeled { label, body },
+                    None => StmtKind::Block(body),
+                }
+            }
+        }
+    }
+
+    /// `break` and `continue` only name the Rust loop or block that they jump to when Rust
+    /// requires it, which is when it isn't the innermost one, or when it's a labeled block.
+    ///
+    /// The parser makes sure that jumps have a target, unless it's outside of the closure of
+    /// a `try` statement that the jump is in.
+    fn lower_jump(
+        &mut self,
+        label: Option<&LabelIdentifier>,
+        is_continue: bool,
+        span: Span,
+    ) -> StmtKind {
+        let index = self.jump_targets.iter().rposition(|target| match label {
+            Some(label) => target.labels.iter().any(|name| name == label.name.as_str()),
+            None if is_continue => target.kind == JumpTargetKind::Loop,
+            None => target.kind != JumpTargetKind::Block,
+        });
+        let Some(index) = index else {
+            let (kind, keyword) = match is_continue {
+                true => ("ContinueStatement", "continue"),
+                false => ("BreakStatement", "break"),
+            };
+            let description = format!("`{keyword}` out of a `try` statement");
+            return StmtKind::Expr(self.unsupported(kind, &description, span));
+        };
+        let needs_label = index + 1 < self.jump_targets.len()
+            || self.jump_targets[index].kind == JumpTargetKind::Block;
+        if needs_label && self.jump_targets[index].rust_label.is_none() {
+            self.jump_targets[index].rust_label = Some(self.label_name());
+        }
+
+        let target = &mut self.jump_targets[index];
+        target.continued |= is_continue;
+        let rust_label = target.rust_label.clone().filter(|_| needs_label);
+        match is_continue {
+            true => StmtKind::Continue(rust_label),
+            false => StmtKind::Break(rust_label),
+        }
+    }
+
+    /// Makes `then` run at the start of every iteration of a loop but the first, so that a
+    /// `continue` in the body of the loop doesn't skip it.
+    ///
+    /// Returns the declaration of the flag that keeps track of the first iteration, which goes
+    /// before the loop, and the statements that the loop has to start with.
+    fn after_first_iteration(&mut self, then: Vec<Stmt>) -> (Stmt, Vec<Stmt>) {
+        let first = self.temp_name();
+        let declaration = Stmt::new(StmtKind::Let {
+            name: first.clone(),
+            mutable: true,
+            init: Some(Expr::Boolean(true)),
+        });
+        let start = vec![
+            Stmt::new(StmtKind::If {
+                test: Expr::Local(first.clone()),
+                then: Vec::new(),
+                otherwise: then,
+            }),
+            Stmt::new(StmtKind::Expr(Expr::Assign {
+                target: first,
+                value: Expr::Boolean(false).boxed(),
+            })),
+        ];
+        (declaration, start)
+    }
+
+    /// `while (test) body` becomes `loop { if test is falsy { break; } body; }`
+    fn lower_while(&mut self, statement: &WhileStatement, labels: Vec<String>) -> StmtKind {
+        let test = self.lower_loop_test(&statement.test);
+        let (body, target) = self.jump_target(labels, JumpTargetKind::Loop, |this| {
+            this.lower_statement(&statement.body)
+        });
+        StmtKind::Loop {
+            label: target.rust_label,
+            body: vec![test, body],
+        }
+    }
+
+    /// `do body while (test)` becomes `loop { body; if test is falsy { break; } }`, unless the
+    /// body continues the loop, in which case the test goes to the start of every iteration but
+    /// the first.
+    fn lower_do_while(&mut self, statement: &DoWhileStatement, labels: Vec<String>) -> StmtKind {
+        let (body, target) = self.jump_target(labels, JumpTargetKind::Loop, |this| {
+            this.lower_statement(&statement.body)
+        });
+        let test = self.lower_loop_test(&statement.test);
+        if !target.continued {
+            return StmtKind::Loop {
+                label: target.rust_label,
+                body: vec![body, test],
+            };
+        }
+        let (first, mut start) = self.after_first_iteration(vec![test]);
+        start.push(body);
+        StmtKind::Block(vec![
+            first,
+            Stmt::new(StmtKind::Loop {
+                label: target.rust_label,
+                body: start,
+            }),
+        ])
+    }
+
+    /// `for (init; test; update) body` becomes
+    /// `{ init; loop { if test is falsy { break; } body; update; } }`, unless the body continues
+    /// the loop, in which case the update goes to the start of every iteration but the first.
+    ///
+    /// Each iteration has a binding of its own of a variable that closures share, which starts
+    /// out with the value from the iteration before, so the update is preceded by a new one.
+    fn lower_for(&mut self, statement: &ForStatement, labels: Vec<String>) -> StmtKind {
+        let declared = match &statement.init {
+            Some(ForStatementInit::VariableDeclaration(var_decl)) => {
+                declaration_names(var_decl).collect()
+            }
+            _ => HashSet::new(),
+        };
+        let shared = self.shared_names(&declared, |uses| uses.visit_for_statement(statement));
+        self.scoped(declared, shared, |this| {
+            let mut block = match &statement.init {
+                Some(ForStatementInit::VariableDeclaration(var_decl)) => {
+                    this.lower_variable_declaration(var_decl)
+                }
+                Some(init) => {
+                    let init = this.lower_expression(init.as_expression().unwrap());
+                    vec![Stmt::new(StmtKind::Expr(init))]
+                }
+                None => Vec::new(),
+            };
+            let mut rebind = Vec::new();
+            for stmt in &mut block {
+                if let StmtKind::Let {
+                    name,
+                    mutable,
+                    init: Some(Expr::NewShared(_)),
+                } = &mut stmt.kind
+                {
+                    *mutable = true;
+                    rebind.push(Stmt::new(StmtKind::Expr(Expr::Assign {
+                        target: name.clone(),
+                        value: Expr::NewShared(Expr::GetShared(name.clone()).boxed()).boxed(),
+                    })));
+                }
+            }
+
+            let test = statement
+                .test
+                .as_ref()
+                .map(|test| this.lower_loop_test(test));
+            let (body, target) = this.jump_target(labels, JumpTargetKind::Loop, |this| {
+                this.lower_statement(&statement.body)
+            });
+            let update = statement
+                .update
+                .as_ref()
+                .map(|update| Stmt::new(StmtKind::Expr(this.lower_expression(update))));
+            let update = match rebind.is_empty() {
+                true => update,
+                false => Some(Stmt::new(StmtKind::Group(
+                    rebind.into_iter().chain(update).collect(),
+                ))),
+            };
+
+            let mut loop_body = Vec::new();
+            match update {
+                Some(update) if target.continued => {
+                    let (first, start) = this.after_first_iteration(vec![update]);
+                    block.push(first);
+                    loop_body.extend(start);
+                    loop_body.extend(test);
+                    loop_body.push(body);
+                }
+                update => {
+                    loop_body.extend(test);
+                    loop_body.push(body);
+                    loop_body.extend(update);
+                }
+            }
+            block.push(Stmt::new(StmtKind::Loop {
+                label: target.rust_label,
+                body: loop_body,
+            }));
+            StmtKind::Block(block)
+        })
+    }
+
+    /// `for (left in right) body` becomes `for key in right.enumerable_keys() { body }`,
+    /// where the keys are collected before the first iteration, and `for (left of right) body`
+    /// becomes `for element in right.iter_elements() { body }`, which reads the values from the
+    /// iterator of `right` as the loop goes.
+    fn lower_for_each(
+        &mut self,
+        labels: Vec<String>,
+        iteration: Iteration,
+        left: &ForStatementLeft,
+        right: &Expression,
+        body: &Statement,
+    ) -> StmtKind {
+        let object = self.lower_expression(right);
+        let declared = match left {
+            ForStatementLeft::VariableDeclaration(var_decl) => {
+                declaration_names(var_decl).collect()
+            }
+            _ => HashSet::new(),
+        };
+        let shared = self.shared_names(&declared, |uses| {
+            uses.visit_for_statement_left(left);
+            uses.visit_statement(body);
+        });
+        self.scoped(declared, shared, |this| {
+            let (name, mutable, mut stmts) = this.lower_for_left(left);
+            let (body, target) =
+                this.jump_target(labels, JumpTargetKind::Loop, |this| this.lower_body(body));
+            stmts.extend(body);
+            StmtKind::ForEach {
+                label: target.rust_label,
+                name,
+                mutable,
+                iteration,
+                object,
+                body: stmts,
+            }
+        })
+    }
+
+    /// The Rust variable that a `for-in` or `for-of` loop binds each item to, whether it's
+    /// mutable, and the statements that the body has to start with to assign the item to the
+    /// JS target, or to bind a variable that closures share to a `SharedBinding` of it.
+    fn lower_for_left(&mut self, left: &ForStatementLeft) -> (String, bool, Vec<Stmt>) {
+        match left {
+            ForStatementLeft::VariableDeclaration(declaration) => {
+                let mutable = match declaration.kind {
+                    VariableDeclarationKind::Const => false,
+                    VariableDeclarationKind::Let => true,
+                    kind => {
+                        let todo = self.unsupported(
+                            "VariableDeclaration",
+                            &format!("`{}` declaration", kind.as_str()),
+                            declaration.span,
+                        );
+                        return (
+                            String::from("_"),
+                            false,
+                            vec![Stmt::new(StmtKind::Expr(todo))],
+                        );
+                    }
+                };
+                // The parser only allows a single declarator here
+                let name = self.lower_binding_pattern(&declaration.declarations[0].id);
+                if self.scopes.last().unwrap().shared.contains(&name) {
+                    let bind = self.declare(name.clone(), false, Some(Expr::Local(name.clone())));
+                    return (name, false, vec![Stmt::new(bind)]);
+                }
+                (name, mutable, Vec::new())
+            }
+            ForStatementLeft::AssignmentTargetIdentifier(identifier) => {
+                let temp = self.temp_name();
+                let value = Expr::Local(temp.clone());
+                let assign =
+                    self.lower_assign_to_variable(&identifier.name, value, identifier.span);
+                (temp, false, vec![Stmt::new(StmtKind::Expr(assign))])
+            }
+            left => {
+                let kind = variant_name(left);
+                let todo = self.unsupported(&kind, &kind, left.span());
+                (
+                    String::from("_"),
+                    false,
+                    vec![Stmt::new(StmtKind::Expr(todo))],
+                )
+            }
+        }
+    }
+
+    /// `switch (discriminant) { case test: body ... }` becomes
+    /// `{ let value = discriminant; let mut matched = false; loop { ... break; } }`, where each
+    /// case is `if matched is falsy { matched = value === test; } if matched is truthy { body }`,
+    /// so that the cases fall through into each other and `break` leaves the loop.
+    ///
+    /// `default` is reached by falling through into it like the other cases. When nothing
+    /// matched, the clauses from `default` on are run again at the end of the loop, unless
+    /// `default` is the last clause, which is reached either way.
+    fn lower_switch(&mut self, statement: &SwitchStatement, labels: Vec<String>) -> StmtKind {
+        let value = self.temp_name();
+        let matched = self.temp_name();
+        let discriminant = self.lower_expression(&statement.discriminant).cloned();
+        let declared = statement
+            .cases
+            .iter()
+            .flat_map(|case| declared_names(&case.consequent))
+            .collect();
+        let cases = &statement.cases;
+        let shared = self.shared_names(&declared, |uses| {
+            cases.iter().for_each(|case| uses.visit_switch_case(case))
+        });
+
+        let (mut body, target) = self.jump_target(labels, JumpTargetKind::Switch, |this| {
+            this.scoped(declared, shared, |this| {
+                this.lower_cases(cases, &value, &matched)
+            })
+        });
+        let diverges = matches!(
+            body.last(),
+            Some(Stmt {
+                kind: StmtKind::Break(_)
+                    | StmtKind::Continue(_)
+                    | StmtKind::Return(_)
+                    | StmtKind::ReturnFromTry(_)
+                    | StmtKind::Throw(_),
+                ..
+            })
+        );
+        if !diverges {
+            body.push(Stmt::new(StmtKind::Break(None)));
+        }
+
+        StmtKind::Block(vec![
+            Stmt::new(StmtKind::Let {
+                name: value,
+                mutable: false,
+                init: Some(discriminant),
+            }),
+            Stmt::new(StmtKind::Let {
+                name: matched,
+                mutable: true,
+                init: Some(Expr::Boolean(false)),
+            }),
+            Stmt::new(StmtKind::Loop {
+                label: target.rust_label,
+                body,
+            }),
+        ])
+    }
+
+    /// The body of the loop that a `switch` is lowered to, without the `break` at its end.
+    fn lower_cases(&mut self, cases: &[SwitchCase], value: &str, matched: &str) -> Vec<Stmt> {
+        let default = cases.iter().position(|case| case.is_default_case());
+        let mut body = Vec::new();
+        let mut rest = Vec::new();
+        for (index, case) in cases.iter().enumerate() {
+            let consequent = self.lower_statements(&case.consequent);
+            if let Some(test) = &case.test {
+                let test = Expr::Binary {
+                    op: BinaryOp::StrictEquals,
+                    left: Expr::Local(value.to_string()).boxed(),
+                    right: self.lower_expression(test).cloned().boxed(),
+                };
+                body.push(Stmt::new(StmtKind::If {
+                    test: Expr::Local(matched.to_string()),
+                    then: Vec::new(),
+                    otherwise: vec![Stmt::new(StmtKind::Expr(Expr::Assign {
+                        target: matched.to_string(),
+                        value: test.boxed(),
+                    }))],
+                }));
+            }
+            let is_last_default = case.test.is_none() && index + 1 == cases.len();
+            if !consequent.is_empty() && !is_last_default {
+                body.push(Stmt::new(StmtKind::If {
+                    test: Expr::Local(matched.to_string()),
+                    then: consequent.clone(),
+                    otherwise: Vec::new(),
+                }));
+            }
+            if default.is_some_and(|default| default <= index) {
+                rest.extend(consequent);
+            }
+        }
+        if default.is_some_and(|default| default + 1 < cases.len()) {
+            body.push(Stmt::new(StmtKind::If {
+                test: Expr::Local(matched.to_string()),
+                then: vec![Stmt::new(StmtKind::Break(None))],
+                otherwise: Vec::new(),
+            }));
+        }
+        body.extend(rest);
+        body
+    }
+
+    /// The `try` block runs in a closure that catches what it throws, and so does the `catch`
+    /// block if there's a `finally` block, which has to run after it whatever it does. The
+    /// `finally` block itself runs in place, then the `try` statement returns or throws again
+    /// as its blocks did.
+    fn lower_try(&mut self, statement: &TryStatement) -> StmtKind {
+        let result = self.temp_name();
+        let body = self.in_closure(ReturnTo::Try, |this| this.lower_block(&statement.block));
+        let guarded = statement.finalizer.is_some();
+        let catch = statement.handler.as_ref().map(|handler| match guarded {
+            true => self.in_closure(ReturnTo::Try, |this| this.lower_catch(handler, guarded)),
+            false => self.lower_catch(handler, guarded),
+        });
+        let finally = statement
+            .finalizer
+            .as_ref()
+            .map(|finalizer| self.lower_block(finalizer));
+        StmtKind::Try {
+            result,
+            body,
+            catch,
+            finally,
+            returns: self.return_to,
+        }
+    }
+
+    fn lower_catch(&mut self, handler: &CatchClause, guarded: bool) -> Catch {
+        let mut declared = declared_names(&handler.body.body);
+        declared.extend(
+            handler
+                .param
+                .iter()
+                .flat_map(|param| pattern_names(&param.pattern)),
+        );
+        let shared = self.shared_names(&declared, |uses| uses.visit_catch_clause(handler));
+        self.scoped(declared, shared, |this| {
+            let param = match &handler.param {
+                Some(param) => this.lower_binding_pattern(&param.pattern),
+                None => String::from("_"),
+            };
+            let mut body = Vec::new();
+            // A caught value that closures share is bound to a `SharedBinding` of it
+            if this.scopes.last().unwrap().shared.contains(&param) {
+                let init = Some(Expr::Local(param.clone()));
+                body.push(Stmt::new(this.declare(param.clone(), false, init)));
+            }
+            body.extend(this.lower_statements(&handler.body.body));
+            Catch {
+                param,
+                body,
+                guarded,
+            }
+        })
+    }
+
+    /// Breaks out of the loop unless the test is truthy.
+    fn lower_loop_test(&mut self, test: &Expression) -> Stmt {
+        Stmt::new(StmtKind::If {
+            test: self.lower_expression(test),
+            then: Vec::new(),
+            otherwise: vec![Stmt::new(StmtKind::Break(None))],
+        })
+    }
+
+    fn lower_variable_declaration(&mut self, declaration: &VariableDeclaration) -> Vec<Stmt> {
+        let mut lets = Vec::new();
+        for declaration in &declaration.declarations {
+            let mutable = match declaration.kind {
+                VariableDeclarationKind::Const => false,
+                VariableDeclarationKind::Let => true,
+                kind => {
+                    let todo = self.unsupported(
+                        "VariableDeclaration",
+                        &format!("`{}` declaration", kind.as_str()),
+                        declaration.span,
+                    );
+                    lets.push(Stmt::new(StmtKind::Expr(todo)));
+                    continue;
+                }
+            };
+            let init = declaration.init.as_ref().map(|init| {
+                match &declaration.id.kind {
+                    BindingPatternKind::BindingIdentifier(id) => {
+                        self.lower_named_value(init, &id.name)
+                    }
+                    _ => self.lower_expression(init),
+                }
+                .cloned()
+            });
+            match init {
+                Some(init) => self.lower_binding(&declaration.id, mutable, init, &mut lets),
+                None => {
+                    let name = self.lower_binding_pattern(&declaration.id);
+                    lets.push(Stmt::new(self.declare(name, mutable, None)));
+                }
+            }
+        }
+        lets
+    }
+
+    /// Binds the names of the pattern to the parts of the value that they destructure. An
+    /// object or an array is bound to a temporary first, then each of its properties or
+    /// elements is read with `get_prop`, and the rest of the array with `slice_from`.
+    fn lower_binding(
+        &mut self,
+        pattern: &BindingPattern,
+        mutable: bool,
+        value: Expr,
+        lets: &mut Vec<Stmt>,
+    ) {
+        use oxc::ast::ast::BindingPatternKind::*;
+        match &pattern.kind {
+            ObjectPattern(object) => {
+                let temp = self.temp_name();
+                lets.push(Stmt::new(StmtKind::Let {
+                    name: temp.clone(),
+                    mutable: false,
+                    init: Some(value),
+                }));
+                for property in &object.properties {
+                    let key = self.lower_property_key(&property.key);
+                    let value = Expr::GetProp {
+                        object: Expr::Local(temp.clone()).boxed(),
+                        key: key.boxed(),
+                    };
+                    self.lower_binding(&property.value, mutable, value, lets);
+                }
+                if let Some(rest) = &object.rest {
+                    let todo = self.unsupported(
+                        "BindingRestElement",
+                        "rest of an object pattern",
+                        rest.span,
+                    );
+                    lets.push(Stmt::new(StmtKind::Expr(todo)));
+                }
+            }
+            ArrayPattern(array) => {
+                let temp = self.temp_name();
+                lets.push(Stmt::new(StmtKind::Let {
+                    name: temp.clone(),
+                    mutable: false,
+                    init: Some(value),
+                }));
+                for (index, element) in array.elements.iter().enumerate() {
+                    // Holes skip an element
+                    if let Some(element) = element {
+                        let value = Expr::GetProp {
+                            object: Expr::Local(temp.clone()).boxed(),
+                            key: Expr::Number(index as f64).boxed(),
+                        };
+                        self.lower_binding(element, mutable, value, lets);
+                    }
+                }
+                if let Some(rest) = &array.rest {
+                    let value = Expr::SliceFrom {
+                        array: Expr::Local(temp).boxed(),
+                        start: array.elements.len(),
+                    };
+                    self.lower_binding(&rest.argument, mutable, value, lets);
+                }
+            }
+            AssignmentPattern(assignment) => {
+                let value = self.lower_default(value, &assignment.right);
+                self.lower_binding(&assignment.left, mutable, value, lets);
+            }
+            _ => {
+                let name = self.lower_binding_pattern(pattern);
+                lets.push(Stmt::new(self.declare(name, mutable, Some(value))));
+            }
+        }
+    }
+
+    /// The name of the Rust variable that the pattern binds
+    fn lower_binding_pattern(&mut self, pattern: &BindingPattern) -> String {
+        use oxc::ast::ast::BindingPatternKind::*;
+        match &pattern.kind {
+            BindingIdentifier(identifier) => self.identifier(&identifier.name),
+            kind => {
+                // The value is never bound, using it panics in `Todo` mode
+                let kind = variant_name(kind);
+                self.unsupported(&kind, &kind, pattern.span());
+                String::from("_")
+            }
+        }
+    }
+
+    fn lower_expression(&mut self, expression: &Expression) -> Expr {
+        match expression {
+            Expression::AssignmentExpression(exp) => self.lower_assignment(exp),
+            Expression::BinaryExpression(exp) => self.lower_binary(exp),
+            Expression::UnaryExpression(exp) => self.lower_unary(exp),
+            Expression::LogicalExpression(exp) => Expr::Logical {
+                op: LogicalOp::from_js(exp.operator),
+                temp: self.temp_name(),
+                left: self.lower_expression(&exp.left).cloned().boxed(),
+                right: self.lower_expression(&exp.right).cloned().boxed(),
+            },
+            Expression::ConditionalExpression(exp) => Expr::Conditional {
+                test: self.lower_expression(&exp.test).boxed(),
+                consequent: self.lower_expression(&exp.consequent).cloned().boxed(),
+                alternate: self.lower_expression(&exp.alternate).cloned().boxed(),
+            },
+            Expression::StaticMemberExpression(exp) => {
+                // NOTE:
+                // The code should only enter this branch if we are _READING_ this member.
+                // This is because StaticMemberExpression is handled as a special case in assignment expressions.
+
+                self.lower_static_member_read(exp)
+            }
+            Expression::ComputedMemberExpression(exp) => {
+                // NOTE:
+                // The code should only enter this branch if we are _READING_ this member.
+                // This is because ComputedMemberExpression is handled as a special case in assignment expressions.
+
+                self.lower_computed_member_read(exp)
+            }
+            Expression::PrivateFieldExpression(exp) => self.lower_private_field_read(exp),
+            Expression::PrivateInExpression(exp) => Expr::HasPrivate {
+                object: self.lower_expression(&exp.right).boxed(),
+                name: self.private_key(&exp.left.name),
+            },
+            Expression::BooleanLiteral(literal) => Expr::Boolean(literal.value),
+            Expression::NullLiteral(_) => Expr::Null,
+            Expression::NumericLiteral(literal) => Expr::Number(literal.value),
+            Expression::StringLiteral(literal) => Expr::String(literal.value.to_string()),
+            Expression::RegExpLiteral(literal) => Expr::RegExp {
+                pattern: literal.regex.pattern.to_string(),
+                flags: literal.regex.flags.to_string(),
+            },
+            Expression::ObjectExpression(exp) => self.lower_object(exp),
+            Expression::CallExpression(exp) => self.lower_call(exp),
+            Expression::ArrayExpression(exp) => self.lower_array(exp),
+            Expression::UpdateExpression(exp) => self.lower_update(exp),
+            Expression::TemplateLiteral(exp) => self.lower_template_literal(exp),
+            Expression::TaggedTemplateExpression(exp) => self.lower_tagged_template(exp),
+            Expression::Identifier(ident) => {
+                // Special cases for the globals of the language, the Javascript standard
+                // "library" and whatever else the embedder maps, unless the program has a
+                // variable of the same name
+                if self.is_declared(&ident.name) {
+                    return self.lower_variable_read(&ident.name, ident.span);
+                }
+                match ident.name.as_str() {
+                    "undefined" => Expr::Undefined,
+                    "NaN" => Expr::Number(f64::NAN),
+                    "Infinity" => Expr::Number(f64::INFINITY),
+                    name => match self.options.intrinsics.get(name) {
+                        Some(intrinsic) => Expr::Intrinsic(intrinsic.to_owned()),
+                        None => self.lower_variable_read(name, ident.span),
+                    },
+                }
+            }
+            Expression::ThisExpression(exp) => self.lower_this(exp.span),
+            Expression::MetaProperty(exp)
+                if exp.meta.name == "new" && exp.property.name == "target" =>
+            {
+                self.lower_new_target(exp.span)
+            }
+            Expression::FunctionExpression(func) => self.lower_function_expression(func, ""),
+            Expression::YieldExpression(exp) if exp.delegate => {
+                self.unsupported("YieldExpression", "`yield*`", exp.span)
+            }
+            Expression::YieldExpression(exp) => Expr::Yield {
+                value: match &exp.argument {
+                    Some(argument) => self.lower_expression(argument).cloned().boxed(),
+                    None => Expr::Undefined.boxed(),
+                },
+                span: exp.span,
+            },
+            Expression::ArrowFunctionExpression(arrow) => self.lower_arrow_function(arrow, ""),
+            Expression::NewExpression(exp) => self.lower_new(exp),
+            Expression::ParenthesizedExpression(exp) => self.lower_expression(&exp.expression),
+            Expression::SequenceExpression(exp) => self.lower_sequence(exp),
+            Expression::ChainExpression(exp) => self.lower_chain(exp),
+            _ => {
+                let kind = variant_name(expression);
+                self.unsupported(&kind, &kind, expression.span())
+            }
+        }
+    }
+
+    /// Variables from outside of the classes and function objects that the read is in are
+    /// copied into their closures. The ones that closures share are read from their binding.
+    fn lower_variable_read(&mut self, name: &str, span: Span) -> Expr {
+        let rust_name = self.identifier(name);
+        let Some(scope) = self.declaring_scope(name) else {
+            return Expr::Local(rust_name);
+        };
+        if self.is_captured(name) {
+            if self.scopes[scope].uninitialized.contains(name) {
+                return self.unsupported(
+                    "IdentifierReference",
+                    "capture of a variable before it's initialized",
+                    span,
+                );
+            }
+            self.capture(scope, rust_name.clone());
+        }
+        match self.scopes[scope].shared.contains(&rust_name) {
+            true => Expr::GetShared(rust_name),
+            false => Expr::Local(rust_name),
+        }
+    }
+
+    /// Gives the closures around the current node that the variable of the scope is outside
+    /// of a copy of it.
+    fn capture(&mut self, scope: usize, rust_name: String) -> Expr {
+        for capture in &mut self.captures {
+            if scope < capture.depth && !capture.names.contains(&rust_name) {
+                capture.names.push(rust_name.clone());
+            }
+        }
+        Expr::Local(rust_name)
+    }
+
+    fn lower_this(&mut self, span: Span) -> Expr {
+        match self.this {
+            This::Bound => self.capture_from_function(String::from("this")),
+            This::Undefined => Expr::Undefined,
+            This::TopLevel => self.unsupported("ThisExpression", "`this` at the top level", span),
+        }
+    }
+
+    /// `new.target`, which is bound at the start of the function that uses it
+    fn lower_new_target(&mut self, span: Span) -> Expr {
+        match self.this {
+            This::Bound => {
+                self.uses_new_target = true;
+                self.capture_from_function(String::from(NEW_TARGET))
+            }
+            This::Undefined => Expr::Undefined,
+            This::TopLevel => {
+                self.unsupported("MetaProperty", "`new.target` at the top level", span)
+            }
+        }
+    }
+
+    /// A local of the innermost function that isn't an arrow function, like `this`. Arrow
+    /// functions get a copy of it, other functions are passed it.
+    fn capture_from_function(&mut self, rust_name: String) -> Expr {
+        let arrows = self.captures.iter_mut().rev();
+        for capture in arrows.take_while(|capture| capture.outer_this) {
+            if !capture.names.contains(&rust_name) {
+                capture.names.push(rust_name.clone());
+            }
+        }
+        Expr::Local(rust_name)
+    }
+
+    fn lower_binary(&mut self, exp: &BinaryExpression) -> Expr {
+        Expr::Binary {
+            op: BinaryOp::from_js(exp.operator),
+            left: self.lower_expression(&exp.left).boxed(),
+            right: self.lower_expression(&exp.right).cloned().boxed(),
+        }
+    }
+
+    fn lower_unary(&mut self, exp: &UnaryExpression) -> Expr {
+        if exp.operator == UnaryOperator::Delete {
+            return self.lower_delete(&exp.argument);
+        }
+        let Some(op) = UnaryOp::from_js(exp.operator) else {
+            return self.unsupported(
+                "UnaryExpression",
+                &format!("operator `{}`", exp.operator.as_str()),
+                exp.span,
+            );
+        };
+        Expr::Unary {
+            op,
+            argument: self.lower_expression(&exp.argument).cloned().boxed(),
+        }
+    }
+
+    /// The value is converted to a number before it's updated, which is what a postfix update
+    /// evaluates to.
+    fn lower_update(&mut self, expression: &UpdateExpression) -> Expr {
+        let mut setup = Vec::new();
+        let Some(place) = self.lower_place(&expression.argument, &mut setup) else {
+            return self.unsupported(
+                "UpdateExpression",
+                &format!("update of {}", variant_name(&expression.argument)),
+                expression.span,
+            );
+        };
+        let op = match expression.operator {
+            UpdateOperator::Decrement => BinaryOp::Sub,
+            UpdateOperator::Increment => BinaryOp::Add,
+        };
+        let step = |value: Expr| Expr::Binary {
+            op,
+            left: value.boxed(),
+            right: Expr::Number(1.0).boxed(),
+        };
+        let old_value = Expr::Unary {
+            op: UnaryOp::Plus,
+            argument: self.read_place(&place, expression.span).cloned().boxed(),
+        };
+        let result = self.temp_name();
+        let (init, new_value) = match expression.prefix {
+            true => (step(old_value), Expr::Local(result.clone()).cloned()),
+            false => (old_value, step(Expr::Local(result.clone()))),
+        };
+        setup.push(Stmt::new(StmtKind::Let {
+            name: result.clone(),
+            mutable: false,
+            init: Some(init),
+        }));
+        let write = self.write_place(place, new_value, expression.span);
+        setup.push(Stmt::new(StmtKind::Expr(write)));
+        Expr::Block {
+            body: setup,
+            value: Expr::Local(result).boxed(),
+        }
+    }
+
+    /// The value of a shorthand property is the identifier that is its key, and the value of a
+    /// method is a function expression, so they need no special treatment. A getter and a
+    /// setter of the same name are one accessor, like in a class.
+    fn lower_object(&mut self, exp: &ObjectExpression) -> Expr {
+        let mut entries = Vec::new();
+        for entry in &exp.properties {
+            let entry = match entry {
+                ObjectPropertyKind::ObjectProperty(property)
+                    if !matches!(property.kind, PropertyKind::Init) =>
+                {
+                    let PropertyKey::StaticIdentifier(name) = &property.key else {
+                        let description = "getter or setter with a computed key";
+                        self.unsupported("ObjectProperty", description, property.span);
+                        continue;
+                    };
+                    let name = name.name.to_string();
+                    let (prefix, is_get) = match property.kind {
+                        PropertyKind::Get => ("get", true),
+                        _ => ("set", false),
+                    };
+                    let function =
+                        self.lower_named_value(&property.value, &format!("{prefix} {name}"));
+                    add_accessor(&mut entries, name, is_get, function);
+                    continue;
+                }
+                ObjectPropertyKind::ObjectProperty(property) => {
+                    let key = self.lower_property_key(&property.key);
+                    let value = match &property.key {
+                        PropertyKey::StaticIdentifier(name) => {
+                            self.lower_named_value(&property.value, &name.name)
+                        }
+                        _ => self.lower_expression(&property.value),
+                    };
+                    let value = value.cloned();
+                    Entry::Item((key, value))
+                }
+                ObjectPropertyKind::SpreadProperty(spread) => {
+                    Entry::Spread(self.lower_expression(&spread.argument))
+                }
+            };
+            entries.push(entry);
+        }
+        Expr::Object(entries)
+    }
+
+    /// A computed key is an arbitrary expression, and so are string and numeric literal keys.
+    fn lower_property_key(&mut self, key: &PropertyKey) -> Expr {
+        match key {
+            PropertyKey::StaticIdentifier(identifier) => Expr::String(identifier.name.to_string()),
+            key => match key.as_expression() {
+                Some(key) => self.lower_expression(key).cloned(),
+                None => self.unsupported(
+                    "PropertyKey",
+                    &format!("property key {}", variant_name(key)),
+                    key.span(),
+                ),
+            },
+        }
+    }
+
+    /// The arguments of a call, where the values that are spread into them are iterated
+    fn lower_arguments(&mut self, arguments: &[Argument]) -> Vec<Entry<Expr>> {
+        arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::SpreadElement(spread) => {
+                    Entry::Spread(self.lower_expression(&spread.argument))
+                }
+                argument => Entry::Item(self.lower_expression(argument.to_expression()).cloned()),
+            })
+            .collect()
+    }
+
+    /// An optional chain is a Rust block, that the nullish base of an optional member access or
+    /// call in it breaks out of, so the rest of the chain is skipped.
+    fn lower_chain(&mut self, exp: &ChainExpression) -> Expr {
+        let label = self.label_name();
+        let outer_chain = self.chain.replace(label.clone());
+        let body = match &exp.expression {
+            ChainElement::CallExpression(exp) => self.lower_call(exp),
+            ChainElement::StaticMemberExpression(exp) => self.lower_static_member_read(exp),
+            ChainElement::ComputedMemberExpression(exp) => self.lower_computed_member_read(exp),
+            ChainElement::PrivateFieldExpression(exp) => self.lower_private_field_read(exp),
+        };
+        self.chain = outer_chain;
+        Expr::Chain {
+            label,
+            body: body.boxed(),
+        }
+    }
+
+    /// The object of a member access or call, which ends the chain if it's nullish and the
+    /// access or call is optional.
+    fn lower_optional(&mut self, object: Expr, optional: bool) -> Expr {
+        match (optional, &self.chain) {
+            (true, Some(label)) => Expr::Optional {
+                label: label.clone(),
+                temp: self.temp_name(),
+                object: object.cloned().boxed(),
+            },
+            _ => object,
+        }
+    }
+
+    fn lower_call(&mut self, exp: &CallExpression) -> Expr {
+        if let Expression::Super(_) = &exp.callee {
+            return self.lower_super_call(exp);
+        }
+        if !exp.optional {
+            if let Some(call) = self.lower_immediate_call(exp) {
+                return call;
+            }
+        }
+        let callee = self.lower_expression(&exp.callee);
+        if exp.optional {
+            return self.lower_optional_call(exp, callee);
+        }
+        let arguments = self.lower_arguments(&exp.arguments);
+        self.call(callee, arguments)
+    }
+
+    /// An immediately invoked function is a Rust closure that is called right away. Like the
+    /// closure of a `try` block, it borrows its surroundings instead of having copies of them, so
+    /// it can assign to the variables outside of it.
+    ///
+    /// The arguments are evaluated before the closure borrows anything. A named function
+    /// expression can call itself, so it's a function object like any other. Spread arguments
+    /// are left to a call of a function object, which iterates them.
+    fn lower_immediate_call(&mut self, exp: &CallExpression) -> Option<Expr> {
+        if exp.arguments.iter().any(Argument::is_spread) {
+            return None;
+        }
+        let (params, body, this, kind) = match exp.callee.without_parentheses() {
+            Expression::FunctionExpression(func)
+                if func.id.is_none() && !func.r#async && !func.generator =>
+            {
+                let body = func.body.as_deref()?;
+                (&func.params, body, This::Undefined, FunctionKind::Normal)
+            }
+            Expression::ArrowFunctionExpression(arrow) if !arrow.r#async => {
+                let kind = FunctionKind::Arrow {
+                    expression_body: arrow.expression,
+                };
+                (&arrow.params, &*arrow.body, self.this, kind)
+            }
+            _ => return None,
+        };
+        let mut setup = Vec::new();
+        let arguments = self
+            .lower_arguments(&exp.arguments)
+            .into_iter()
+            .map(|argument| match argument {
+                Entry::Item(
+                    argument @ (Expr::Number(_)
+                    | Expr::String(_)
+                    | Expr::Boolean(_)
+                    | Expr::Null
+                    | Expr::Undefined),
+                ) => argument,
+                Entry::Item(argument) | Entry::Spread(argument) => {
+                    let temp = self.temp_name();
+                    setup.push(Stmt::new(StmtKind::Let {
+                        name: temp.clone(),
+                        mutable: false,
+                        init: Some(argument),
+                    }));
+                    Expr::Local(temp)
+                }
+            })
+            .collect();
+        let LoweredFunction {
+            params, rest, body, ..
+        } = self.lower_function(params, Some(body), this, kind, Vec::new());
+        let call = Expr::CallFunction {
+            callee: Expr::Closure { params, rest, body }.boxed(),
+            arguments,
+        };
+        Some(match setup.is_empty() {
+            true => call,
+            false => Expr::Block {
+                body: setup,
+                value: call.boxed(),
+            },
+        })
+    }
+
+    /// Calls the lowered `callee` with the arguments.
+    fn call(&mut self, callee: Expr, arguments: Vec<Entry<Expr>>) -> Expr {
+        let callee = callee.boxed();
+        // A function in a property is called with the object as `this`
+        if let Expr::GetProp { object, key } = *callee {
+            return Expr::CallMethod {
+                object,
+                key,
+                arguments,
+            };
+        }
+        // A method of `super` is called with the `this` of the method that calls it
+        if let Expr::GetSuper { this, .. } = &*callee {
+            return Expr::CallWithThis {
+                this: this.clone(),
+                callee,
+                arguments,
+            };
+        }
+        // So is a private method, where the object is kept for both
+        if let Expr::GetPrivate { object, name } = *callee {
+            let mut setup = Vec::new();
+            let object = self.keep_in_temp(*object, &mut setup);
+            let call = Expr::CallWithThis {
+                callee: Expr::GetPrivate {
+                    object: object.clone().boxed(),
+                    name,
+                }
+                .boxed(),
+                this: object.boxed(),
+                arguments,
+            };
+            return match setup.is_empty() {
+                true => call,
+                false => Expr::Block {
+                    body: setup,
+                    value: call.boxed(),
+                },
+            };
+        }
+        Expr::CallValue { callee, arguments }
+    }
+
+    /// The tag is called with the array of the strings of the template, which has the raw
+    /// strings in its `raw` property, followed by the values of the substitutions.
+    fn lower_tagged_template(&mut self, exp: &TaggedTemplateExpression) -> Expr {
+        let tag = self.lower_expression(&exp.tag);
+        let quasis = &exp.quasi.quasis;
+        let strings = Expr::TemplateStrings {
+            // An invalid escape sequence is only allowed in a tagged template, where its
+            // cooked string is `undefined`
+            cooked: quasis
+                .iter()
+                .map(|quasi| quasi.value.cooked.as_ref().map(|text| text.to_string()))
+                .collect(),
+            raw: quasis
+                .iter()
+                .map(|quasi| quasi.value.raw.to_string())
+                .collect(),
+        };
+        let arguments = iter::once(strings)
+            .chain(
+                exp.quasi
+                    .expressions
+                    .iter()
+                    .map(|exp| self.lower_expression(exp).cloned()),
+            )
+            .map(Entry::Item)
+            .collect::<Vec<_>>();
+        self.call(tag, arguments)
+    }
+
+    fn lower_optional_call(&mut self, exp: &CallExpression, callee: Expr) -> Expr {
+        match callee {
+            // The object is kept, as it's `this` for the call
+            Expr::GetProp { object, key } => {
+                let this = self.temp_name();
+                let keep = Stmt::new(StmtKind::Let {
+                    name: this.clone(),
+                    mutable: false,
+                    init: Some(object.cloned()),
+                });
+                let function = Expr::GetProp {
+                    object: Expr::Local(this.clone()).boxed(),
+                    key,
+                };
+                let call = Expr::CallWithThis {
+                    callee: self.lower_optional(function, true).boxed(),
+                    this: Expr::Local(this).boxed(),
+                    arguments: self.lower_arguments(&exp.arguments),
+                };
+                Expr::Block {
+                    body: vec![keep],
+                    value: call.boxed(),
+                }
+            }
+            callee => Expr::CallValue {
+                callee: self.lower_optional(callee, true).boxed(),
+                arguments: self.lower_arguments(&exp.arguments),
+            },
+        }
+    }
+
+    fn lower_new(&mut self, exp: &NewExpression) -> Expr {
+        Expr::New {
+            callee: self.lower_expression(&exp.callee).boxed(),
+            arguments: self.lower_arguments(&exp.arguments),
+        }
+    }
+
+    fn lower_array(&mut self, exp: &ArrayExpression) -> Expr {
+        let elements = exp
+            .elements
+            .iter()
+            .map(|element| match element {
+                ArrayExpressionElement::SpreadElement(spread) => {
+                    Entry::Spread(self.lower_expression(&spread.argument))
+                }
+                element => Entry::Item(match element.as_expression() {
+                    Some(exp) => self.lower_expression(exp).cloned(),
+                    None => {
+                        let kind = variant_name(element);
+                        self.unsupported(&kind, &kind, element.span())
+                    }
+                }),
+            })
+            .collect();
+        Expr::Array(elements)
+    }
+
+    /// Every expression but the last is only evaluated for its side effects.
+    fn lower_sequence(&mut self, exp: &SequenceExpression) -> Expr {
+        let (last, rest) = exp.expressions.split_last().unwrap();
+        let body = rest
+            .iter()
+            .map(|exp| Stmt::new(StmtKind::Expr(self.lower_expression(exp))))
+            .collect();
+        Expr::Block {
+            body,
+            value: self.lower_expression(last).cloned().boxed(),
+        }
+    }
+
+    fn lower_template_literal(&mut self, exp: &TemplateLiteral) -> Expr {
+        // The quasis are always cooked here, `cooked` can only be `None` in tagged templates
+        let quasis = exp
+            .quasis
+            .iter()
+            .map(|quasi| {
+                let text = quasi.value.cooked.as_ref().unwrap_or(&quasi.value.raw);
+                text.to_string()
+            })
+            .collect();
+        // Substitutions go through `lower_expression` like any other expression, so
+        // interpolations that are themselves template literals are lowered recursively.
+        let substitutions = exp
+            .expressions
+            .iter()
+            .map(|exp| self.lower_expression(exp).cloned())
+            .collect();
+        Expr::TemplateLiteral {
+            quasis,
+            substitutions,
+        }
+    }
+
+    fn lower_assignment(&mut self, exp: &AssignmentExpression) -> Expr {
+        let value = match (&exp.left, exp.operator) {
+            // Only `=` can destructure
+            (
+                AssignmentTarget::ArrayAssignmentTarget(_)
+                | AssignmentTarget::ObjectAssignmentTarget(_),
+                _,
+            ) => return self.lower_destructuring_assignment(&exp.left, &exp.right),
+            (_, AssignmentOperator::Assign) => self.lower_expression(&exp.right).cloned(),
+            (target, operator) => {
+                return self.lower_compound_assignment(target, operator, &exp.right, exp.span)
+            }
+        };
+        self.lower_assign_to(&exp.left, value, exp.span)
+    }
+
+    /// `target op= value`, which is of type `()`. A logical operator that short-circuits skips
+    /// both the value and the write.
+    fn lower_compound_assignment(
+        &mut self,
+        target: &AssignmentTarget,
+        operator: AssignmentOperator,
+        right: &Expression,
+        span: Span,
+    ) -> Expr {
+        let binary = BinaryOp::from_assignment(operator);
+        let logical = LogicalOp::from_assignment(operator);
+        if binary.is_none() && logical.is_none() {
+            return self.unsupported(
+                "AssignmentExpression",
+                &format!("operator `{}`", operator.as_str()),
+                span,
+            );
+        }
+        let mut setup = Vec::new();
+        let place = target
+            .as_simple_assignment_target()
+            .and_then(|target| self.lower_place(target, &mut setup));
+        let Some(place) = place else {
+            let kind = variant_name(target);
+            return self.unsupported(&kind, &kind, target.span());
+        };
+        let current = self.read_place(&place, span);
+        let assignment = match (binary, logical) {
+            (Some(op), _) => {
+                let value = Expr::Binary {
+                    op,
+                    left: current.boxed(),
+                    right: self.lower_expression(right).cloned().boxed(),
+                };
+                self.write_place(place, value, span)
+            }
+            (None, Some(op)) => {
+                let value = self.lower_expression(right).cloned();
+                Expr::AssignIf {
+                    op,
+                    current: current.boxed(),
+                    assign: self.write_place(place, value, span).boxed(),
+                }
+            }
+            (None, None) => unreachable!(),
+        };
+        match setup.is_empty() {
+            true => assignment,
+            false => Expr::Block {
+                body: setup,
+                value: assignment.boxed(),
+            },
+        }
+    }
+
+    /// The variable or property that the target refers to, for an expression that both reads
+    /// and writes it. The object and the key of a property are kept in temporaries that are
+    /// declared in `setup`, unless they are locals or literals already, so that they are only
+    /// evaluated once.
+    fn lower_place(
+        &mut self,
+        target: &SimpleAssignmentTarget,
+        setup: &mut Vec<Stmt>,
+    ) -> Option<Place> {
+        let (object, key) = match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+                return Some(Place::Variable(identifier.name.to_string()))
+            }
+            SimpleAssignmentTarget::PrivateFieldExpression(member) => {
+                let object = self.lower_expression(&member.object);
+                return Some(Place::Private {
+                    object: self.keep_in_temp(object, setup),
+                    name: self.private_key(&member.field.name),
+                });
+            }
+            SimpleAssignmentTarget::StaticMemberExpression(member) => (
+                self.lower_expression(&member.object),
+                Expr::String(member.property.name.to_string()),
+            ),
+            SimpleAssignmentTarget::ComputedMemberExpression(member) => (
+                self.lower_expression(&member.object),
+                self.lower_expression(&member.expression).cloned(),
+            ),
+            _ => return None,
+        };
+        let object = self.keep_in_temp(object, setup);
+        let key = self.keep_in_temp(key, setup);
+        Some(Place::Property { object, key })
+    }
+
+    /// The value, which is kept in a temporary that is declared in `setup`, unless it's a
+    /// local or a literal already, so that it's only evaluated once.
+    fn keep_in_temp(&mut self, value: Expr, setup: &mut Vec<Stmt>) -> Expr {
+        match value {
+            Expr::Local(_) | Expr::String(_) | Expr::Number(_) => value,
+            Expr::Clone(value) if matches!(*value, Expr::Local(_)) => *value,
+            value => {
+                let temp = self.temp_name();
+                setup.push(Stmt::new(StmtKind::Let {
+                    name: temp.clone(),
+                    mutable: false,
+                    init: Some(value),
+                }));
+                Expr::Local(temp)
+            }
+        }
+    }
+
+    fn read_place(&mut self, place: &Place, span: Span) -> Expr {
+        match place {
+            Place::Variable(name) => self.lower_variable_read(name, span),
+            Place::Property { object, key } => Expr::GetProp {
+                object: object.clone().boxed(),
+                key: key.clone().cloned().boxed(),
+            },
+            Place::Private { object, name } => Expr::GetPrivate {
+                object: object.clone().boxed(),
+                name: name.clone(),
+            },
+        }
+    }
+
+    /// Of type `()`
+    fn write_place(&mut self, place: Place, value: Expr, span: Span) -> Expr {
+        match place {
+            Place::Variable(name) => self.lower_assign_to_variable(&name, value, span),
+            Place::Property { object, key } => Expr::SetProp {
+                object: object.boxed(),
+                key: key.cloned().boxed(),
+                value: value.boxed(),
+            },
+            Place::Private { object, name } => Expr::SetPrivate {
+                object: object.boxed(),
+                name,
+                value: value.boxed(),
+            },
+        }
+    }
+
+    /// Assigns the value to a variable or a property, which is of type `()`.
+    fn lower_assign_to(&mut self, target: &AssignmentTarget, value: Expr, span: Span) -> Expr {
+        match target {
+            AssignmentTarget::AssignmentTargetIdentifier(identifier) => {
+                self.lower_assign_to_variable(&identifier.name, value, span)
+            }
+            AssignmentTarget::StaticMemberExpression(member) => Expr::SetProp {
+                object: self.lower_expression(&member.object).boxed(),
+                key: Expr::String(member.property.name.to_string()).boxed(),
+                value: value.boxed(),
+            },
+            AssignmentTarget::ComputedMemberExpression(member) => Expr::SetProp {
+                object: self.lower_expression(&member.object).boxed(),
+                key: self.lower_expression(&member.expression).cloned().boxed(),
+                value: value.boxed(),
+            },
+            AssignmentTarget::PrivateFieldExpression(member) => Expr::SetPrivate {
+                object: self.lower_expression(&member.object).boxed(),
+                name: self.private_key(&member.field.name),
+                value: value.boxed(),
+            },
+            target => {
+                let kind = variant_name(target);
+                self.unsupported(&kind, &kind, target.span())
+            }
+        }
+    }
+
+    /// A variable that closures share is set through its binding, which the closures that
+    /// assign to it have a copy of. Others can only be assigned to in place.
+    fn lower_assign_to_variable(&mut self, name: &str, value: Expr, span: Span) -> Expr {
+        let rust_name = self.identifier(name);
+        let scope = self.declaring_scope(name);
+        if let Some(scope) = scope.filter(|&scope| self.scopes[scope].shared.contains(&rust_name)) {
+            self.capture(scope, rust_name.clone());
+            return Expr::SetShared {
+                target: rust_name,
+                value: value.boxed(),
+            };
+        }
+        if self.is_captured(name) {
+            return self.unsupported(
+                "AssignmentExpression",
+                "assignment to a captured variable",
+                span,
+            );
+        }
+        Expr::Assign {
+            target: rust_name,
+            value: value.boxed(),
+        }
+    }
+
+    /// A block that keeps the value in a temporary, assigns the parts of it to the targets in
+    /// the pattern, then evaluates to the value, like in JS.
+    fn lower_destructuring_assignment(
+        &mut self,
+        pattern: &AssignmentTarget,
+        value: &Expression,
+    ) -> Expr {
+        let temp = self.temp_name();
+        let mut body = vec![Stmt::new(StmtKind::Let {
+            name: temp.clone(),
+            mutable: false,
+            init: Some(self.lower_expression(value).cloned()),
+        })];
+        self.lower_destructuring(pattern, &temp, &mut body);
+        Expr::Block {
+            body,
+            value: Expr::Local(temp).boxed(),
+        }
+    }
+
+    /// Assigns the parts of the value in `temp` to the targets in the pattern, the same way
+    /// that `lower_binding` binds them.
+    fn lower_destructuring(
+        &mut self,
+        pattern: &AssignmentTarget,
+        temp: &str,
+        body: &mut Vec<Stmt>,
+    ) {
+        let part = |key: Expr| Expr::GetProp {
+            object: Expr::Local(temp.to_string()).boxed(),
+            key: key.boxed(),
+        };
+        match pattern {
+            AssignmentTarget::ArrayAssignmentTarget(array) => {
+                for (index, element) in array.elements.iter().enumerate() {
+                    // Holes skip an element
+                    if let Some(element) = element {
+                        let value = part(Expr::Number(index as f64));
+                        self.lower_destructuring_target(element, value, body);
+                    }
+                }
+                if let Some(rest) = &array.rest {
+                    let value = Expr::SliceFrom {
+                        array: Expr::Local(temp.to_string()).boxed(),
+                        start: array.elements.len(),
+                    };
+                    self.lower_destructuring_part(&rest.target, value, body);
+                }
+            }
+            AssignmentTarget::ObjectAssignmentTarget(object) => {
+                for property in &object.properties {
+                    match property {
+                        AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(property) => {
+                            let name = &property.binding.name;
+                            let mut value = part(Expr::String(name.to_string()));
+                            if let Some(init) = &property.init {
+                                value = self.lower_default(value, init);
+                            }
+                            let assign = self.lower_assign_to_variable(name, value, property.span);
+                            body.push(Stmt::new(StmtKind::Expr(assign)));
+                        }
+                        AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => {
+                            let key = self.lower_property_key(&property.name);
+                            self.lower_destructuring_target(&property.binding, part(key), body);
+                        }
+                    }
+                }
+                if let Some(rest) = &object.rest {
+                    let todo = self.unsupported(
+                        "AssignmentTargetRest",
+                        "rest of an object pattern",
+                        rest.span,
+                    );
+                    body.push(Stmt::new(StmtKind::Expr(todo)));
+                }
+            }
+            _ => unreachable!("not a destructuring pattern"),
+        }
+    }
+
+    /// Assigns the part of the value to the target, which may have a default.
+    fn lower_destructuring_target(
+        &mut self,
+        target: &AssignmentTargetMaybeDefault,
+        value: Expr,
+        body: &mut Vec<Stmt>,
+    ) {
+        match target {
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+                let value = self.lower_default(value, &target.init);
+                self.lower_destructuring_part(&target.binding, value, body);
+            }
+            target => {
+                let target = target.as_assignment_target().unwrap();
+                self.lower_destructuring_part(target, value, body);
+            }
+        }
+    }
+
+    /// Assigns the part of the value to the target, which may be a pattern itself.
+    fn lower_destructuring_part(
+        &mut self,
+        target: &AssignmentTarget,
+        value: Expr,
+        body: &mut Vec<Stmt>,
+    ) {
+        match target {
+            AssignmentTarget::ArrayAssignmentTarget(_)
+            | AssignmentTarget::ObjectAssignmentTarget(_) => {
+                let temp = self.temp_name();
+                body.push(Stmt::new(StmtKind::Let {
+                    name: temp.clone(),
+                    mutable: false,
+                    init: Some(value),
+                }));
+                self.lower_destructuring(target, &temp, body);
+            }
+            target => {
+                let assign = self.lower_assign_to(target, value, target.span());
+                body.push(Stmt::new(StmtKind::Expr(assign)));
+            }
+        }
+    }
+
+    /// The value, or the default if it's `undefined`.
+    fn lower_default(&mut self, value: Expr, default: &Expression) -> Expr {
+        Expr::Logical {
+            op: LogicalOp::Default,
+            temp: self.temp_name(),
+            left: value.boxed(),
+            right: self.lower_expression(default).cloned().boxed(),
+        }
+    }
+
+    fn lower_computed_member_read(&mut self, exp: &ComputedMemberExpression) -> Expr {
+        if let Expression::Super(_) = &exp.object {
+            let key = self.lower_expression(&exp.expression).cloned();
+            return self.lower_super_member(key, exp.span);
+        }
+        let object = self.lower_expression(&exp.object);
+        Expr::GetProp {
+            object: self.lower_optional(object, exp.optional).boxed(),
+            key: self.lower_expression(&exp.expression).cloned().boxed(),
+        }
+    }
+
+    fn lower_private_field_read(&mut self, exp: &PrivateFieldExpression) -> Expr {
+        let object = self.lower_expression(&exp.object);
+        Expr::GetPrivate {
+            object: self.lower_optional(object, exp.optional).boxed(),
+            name: self.private_key(&exp.field.name),
+        }
+    }
+
+    fn lower_static_member_read(&mut self, exp: &StaticMemberExpression) -> Expr {
+        if let Expression::Super(_) = &exp.object {
+            let key = Expr::String(exp.property.name.to_string());
+            return self.lower_super_member(key, exp.span);
+        }
+        // Special cases for the Javascript standard "library" and whatever else the embedder
+        // maps, unless the object is a variable of the program
+        if let Some((root, path)) = member_path(exp) {
+            if !self.is_declared(root) {
+                if let Some(intrinsic) = self.options.intrinsics.get(&path) {
+                    return Expr::Intrinsic(intrinsic.to_owned());
+                }
+            }
+        }
+
+        let object = self.lower_expression(&exp.object);
+        Expr::GetProp {
+            object: self.lower_optional(object, exp.optional).boxed(),
+            key: Expr::String(exp.property.name.to_string()).boxed(),
+        }
+    }
+
+    fn lower_delete(&mut self, argument: &Expression) -> Expr {
+        match argument {
+            Expression::StaticMemberExpression(exp) => Expr::DeleteProp {
+                object: self.lower_expression(&exp.object).boxed(),
+                key: Expr::String(exp.property.name.to_string()).boxed(),
+            },
+            Expression::ComputedMemberExpression(exp) => Expr::DeleteProp {
+                object: self.lower_expression(&exp.object).boxed(),
+                key: self.lower_expression(&exp.expression).cloned().boxed(),
+            },
+            Expression::ParenthesizedExpression(exp) => self.lower_delete(&exp.expression),
+            // Deleting anything that isn't a property reference has no effect, but the
+            // operand is still evaluated
+            _ => Expr::Block {
+                body: vec![Stmt::new(StmtKind::Expr(self.lower_expression(argument)))],
+                value: Expr::Boolean(true).boxed(),
+            },
+        }
+    }
+}
+
+/// The names that the statements declare in the scope they are in.
+///
+/// These are collected up front, so that a use before the declaration (which is allowed for
+/// functions) already refers to the variable.
+fn declared_names(statements: &[Statement]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for statement in statements {
+        match statement {
+            Statement::VariableDeclaration(declaration) => {
+                names.extend(declaration_names(declaration))
+            }
+            Statement::FunctionDeclaration(func) => {
+                names.extend(func.name().map(|name| name.to_string()))
+            }
+            Statement::ClassDeclaration(class) => {
+                names.extend(class.id.as_ref().map(|id| id.name.to_string()))
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+fn declaration_names<'d>(
+    declaration: &'d VariableDeclaration,
+) -> impl Iterator<Item = String> + 'd {
+    declaration
+        .declarations
+        .iter()
+        .flat_map(|declarator| pattern_names(&declarator.id))
+}
+
+/// The names that the pattern binds, including the ones it destructures
+fn pattern_names(pattern: &BindingPattern) -> Vec<String> {
+    use oxc::ast::ast::BindingPatternKind::*;
+    match &pattern.kind {
+        BindingIdentifier(identifier) => vec![identifier.name.to_string()],
+        ObjectPattern(object) => object
+            .properties
+            .iter()
+            .flat_map(|property| pattern_names(&property.value))
+            .chain(
+                object
+                    .rest
+                    .iter()
+                    .flat_map(|rest| pattern_names(&rest.argument)),
+            )
+            .collect(),
+        ArrayPattern(array) => array
+            .elements
+            .iter()
+            .flatten()
+            .flat_map(pattern_names)
+            .chain(
+                array
+                    .rest
+                    .iter()
+                    .flat_map(|rest| pattern_names(&rest.argument)),
+            )
+            .collect(),
+        AssignmentPattern(assignment) => pattern_names(&assignment.left),
+    }
+}
+
+/// The name of the identifier at the root of a chain of static member accesses, and the
+/// dotted path of the whole chain, like `a` and `a.b.c`
+fn member_path<'e>(exp: &'e StaticMemberExpression) -> Option<(&'e str, String)> {
+    let (root, object) = match &exp.object {
+        Expression::Identifier(ident) => (ident.name.as_str(), ident.name.to_string()),
+        Expression::StaticMemberExpression(object) => member_path(object)?,
+        _ => return None,
+    };
+    Some((root, format!("{object}.{}", exp.property.name)))
+}
+
+/// A variable or a property that an expression both reads and writes, see `lower_place`
+enum Place {
+    /// The JS name of the variable
+    Variable(String),
+    Property {
+        object: Expr,
+        key: Expr,
+    },
+    /// A private member, by its qualified name
+    Private {
+        object: Expr,
+        name: String,
+    },
+}
+
+/// Whether the body of a function uses its `arguments`, which includes the arrow functions in it,
+/// but not the other functions, which have `arguments` of their own
+fn uses_arguments(body: &FunctionBody) -> bool {
+    struct Finder(bool);
+
+    impl<'a> Visit<'a> for Finder {
+        fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+            self.0 |= ident.name == "arguments";
+        }
+
+        fn visit_function(&mut self, _: &Function<'a>, _: ScopeFlags) {}
+    }
+
+    let mut finder = Finder(false);
+    finder.visit_function_body(body);
+    finder.0
+}
+
+/// How the code of a scope uses the names in it, for `shared_names`. Like `uses_arguments`, it
+/// goes by the names alone, so a variable may be shared that didn't have to be.
+#[derive(Default)]
+struct Uses {
+    /// The number of functions and classes around the current node
+    depth: usize,
+    /// Whether the current node is in a function declaration, which is hoisted
+    in_declaration: bool,
+    /// The names that are assigned to, other than by their declarations
+    assigned: HashSet<String>,
+    /// The names that are used in functions and classes
+    captured: HashSet<String>,
+    /// The names of the function declarations, and the names that they use
+    hoisted: HashSet<String>,
+}
+
+impl Uses {
+    /// Visits a function or a class, which makes closures of its own.
+    fn nested(&mut self, in_declaration: bool, f: impl FnOnce(&mut Self)) {
+        let outer_in_declaration = std::mem::replace(&mut self.in_declaration, in_declaration);
+        self.depth += 1;
+        f(self);
+        self.depth -= 1;
+        self.in_declaration = outer_in_declaration;
+    }
+}
+
+impl<'a> Visit<'a> for Uses {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if self.depth > 0 {
+            self.captured.insert(ident.name.to_string());
+        }
+        if self.in_declaration {
+            self.hoisted.insert(ident.name.to_string());
+        }
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        if let SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) = target {
+            self.assigned.insert(ident.name.to_string());
+        }
+        walk::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        property: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.assigned.insert(property.binding.name.to_string());
+        walk::walk_assignment_target_property_identifier(self, property);
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        let declaration = self.depth == 0 && func.is_declaration();
+        if declaration {
+            self.hoisted
+                .extend(func.name().map(|name| name.to_string()));
+        }
+        let in_declaration = self.in_declaration || declaration;
+        self.nested(in_declaration, |this| {
+            walk::walk_function(this, func, flags)
+        });
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        let in_declaration = self.in_declaration;
+        self.nested(in_declaration, |this| {
+            walk::walk_arrow_function_expression(this, arrow)
+        });
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        let in_declaration = self.in_declaration;
+        self.nested(in_declaration, |this| walk::walk_class(this, class));
+    }
+}
+
+/// Whether the function can be lowered, which an async one can't, as there's no event loop to
+/// run it on. `is_supported_node_kind` asks this too, so that the coverage agrees with what's
+/// lowered.
+pub(super) fn is_supported_function(func: &Function) -> bool {
+    !func.r#async
+}
+
+/// The `let` at the start of a function that uses `new.target`
+fn new_target_binding() -> Stmt {
+    Stmt::new(StmtKind::Let {
+        name: String::from(NEW_TARGET),
+        mutable: false,
+        init: Some(Expr::NewTarget),
+    })
+}
+
+/// The name of the Rust variable that `new.target` is bound to
+const NEW_TARGET: &str = "__jsrs_new_target";
+
+/// Adds the method to the members of a class, where a getter and a setter of the same name are
+/// one accessor.
+fn add_method(
+    members: &mut Vec<(String, Expr)>,
+    name: String,
+    kind: MethodDefinitionKind,
+    method: Expr,
+) {
+    let (get, set) = match kind {
+        MethodDefinitionKind::Get => (Some(method), None),
+        MethodDefinitionKind::Set => (None, Some(method)),
+        _ => return members.push((name, method)),
+    };
+    let accessor = members.iter_mut().find_map(|(key, member)| match member {
+        Expr::Accessor { get, set } if *key == name => Some((get, set)),
+        _ => None,
+    });
+    match accessor {
+        Some((accessor_get, accessor_set)) => {
+            if let Some(get) = get {
+                **accessor_get = get;
+            }
+            if let Some(set) = set {
+                **accessor_set = set;
+            }
+        }
+        None => members.push((
+            name,
+            Expr::Accessor {
+                get: get.unwrap_or(Expr::Undefined).boxed(),
+                set: set.unwrap_or(Expr::Undefined).boxed(),
+            },
+        )),
+    }
+}
+
+/// Adds the getter or the setter to the entries of an object literal. It's merged into the
+/// accessor of the same name, unless a later property of that name replaced that.
+fn add_accessor(
+    entries: &mut Vec<Entry<(Expr, Expr)>>,
+    name: String,
+    is_get: bool,
+    function: Expr,
+) {
+    let last = entries.iter_mut().rev().find_map(|entry| match entry {
+        Entry::Item((Expr::String(key), value)) if *key == name => Some(value),
+        _ => None,
+    });
+    if let Some(Expr::Accessor { get, set }) = last {
+        match is_get {
+            true => **get = function,
+            false => **set = function,
+        }
+        return;
+    }
+    let (get, set) = match is_get {
+        true => (function, Expr::Undefined),
+        false => (Expr::Undefined, function),
+    };
+    let accessor = Expr::Accessor {
+        get: get.boxed(),
+        set: set.boxed(),
+    };
+    entries.push(Entry::Item((Expr::String(name), accessor)));
+}
+
+/// Makes copies of the variables for the closure, which is moved into a function object.
+fn with_copies(names: &[String], closure: Expr) -> Expr {
+    if names.is_empty() {
+        return closure;
+    }
+    let copies = names.iter().map(|name| {
+        Stmt::new(StmtKind::Let {
+            name: name.clone(),
+            mutable: false,
+            init: Some(Expr::Local(name.clone()).cloned()),
+        })
+    });
+    Expr::Block {
+        body: copies.collect(),
+        value: closure.boxed(),
+    }
+}
@@ -0,0 +1,26 @@
+//! The optimizations of the IR, which run on every top level statement before it's printed.
+//! They always run, `--optimize` only concerns how `--run` compiles the generated code.
+
+use std::mem;
+
+use super::ir::{Expr, Stmt};
+
+pub fn optimize(stmt: &mut Stmt) {
+    eliminate_clones(stmt);
+}
+
+/// Removes the clones of values that nothing else refers to, like the results of calls and
+/// operators. The lowering clones every value that is passed on, this keeps only the clones
+/// of variables and intrinsics.
+fn eliminate_clones(stmt: &mut Stmt) {
+    stmt.for_each_expr_mut(&mut eliminate_clones_in);
+}
+
+fn eliminate_clones_in(expr: &mut Expr) {
+    expr.for_each_child_mut(eliminate_clones_in);
+    if let Expr::Clone(value) = expr {
+        if !matches!(**value, Expr::Local(_) | Expr::Intrinsic(_)) {
+            *expr = mem::replace(&mut **value, Expr::Undefined);
+        }
+    }
+}
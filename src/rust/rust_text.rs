@@ -0,0 +1,46 @@
+use std::ops::Range;
+
+use oxc::span::Span;
+
+/// A node of the JS source and the part of the generated Rust text that it became
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanMapping {
+    pub js_span: Span,
+    /// The byte range in the Rust text
+    pub rust_range: Range<usize>,
+}
+
+/// Generated Rust text, together with where each of the JS nodes it was generated from
+/// ended up in it.
+#[derive(Debug, Clone, Default)]
+pub struct RustText {
+    pub text: String,
+    /// Ordered by the start of the Rust range, with outer nodes before the nodes they contain
+    pub mappings: Vec<SpanMapping>,
+}
+
+impl RustText {
+    pub fn push_str(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    /// Appends the other text, moving its mappings along.
+    pub fn push(&mut self, other: RustText) {
+        let offset = self.text.len();
+        self.text.push_str(&other.text);
+        self.mappings
+            .extend(other.mappings.into_iter().map(|mapping| SpanMapping {
+                js_span: mapping.js_span,
+                rust_range: mapping.rust_range.start + offset..mapping.rust_range.end + offset,
+            }));
+    }
+}
+
+impl From<String> for RustText {
+    fn from(text: String) -> Self {
+        RustText {
+            text,
+            mappings: Vec::new(),
+        }
+    }
+}
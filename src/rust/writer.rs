@@ -0,0 +1,118 @@
+use std::{fmt, io};
+
+use oxc::{diagnostics::OxcDiagnostic, span::Span};
+
+use super::SpanMapping;
+use crate::TranspileError;
+
+/// Streams the generated Rust text into any `fmt::Write`, keeping track of the span mappings
+/// along the way.
+///
+/// Writing never fails from the point of view of the converter. A failed write is remembered
+/// and reported by `finish`, every write after it is skipped.
+pub(crate) struct RustWriter<'w> {
+    out: &'w mut dyn fmt::Write,
+    /// The number of bytes written so far
+    len: usize,
+    mappings: Vec<SpanMapping>,
+    write_failed: bool,
+}
+
+impl<'w> RustWriter<'w> {
+    pub fn new(out: &'w mut dyn fmt::Write) -> Self {
+        RustWriter {
+            out,
+            len: 0,
+            mappings: Vec::new(),
+            write_failed: false,
+        }
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        if self.write_failed {
+            return;
+        }
+        match self.out.write_str(text) {
+            Ok(()) => self.len += text.len(),
+            Err(fmt::Error) => self.write_failed = true,
+        }
+    }
+
+    /// Lets `write!` be used on the writer without having to handle a result.
+    pub fn write_fmt(&mut self, args: fmt::Arguments) {
+        match args.as_str() {
+            Some(text) => self.push_str(text),
+            None => {
+                // Only fails when the underlying writer does, which `push_str` keeps track of
+                let _ = fmt::write(self, args);
+            }
+        }
+    }
+
+    /// Starts mapping everything written from here on to the JS node it was generated from,
+    /// until `end_mapping` is called with the returned index.
+    pub fn start_mapping(&mut self, js_span: Span) -> usize {
+        // Reserve the place of the mapping, so that it comes before the mappings of the
+        // nodes it contains
+        self.mappings.push(SpanMapping {
+            js_span,
+            rust_range: self.len..self.len,
+        });
+        self.mappings.len() - 1
+    }
+
+    pub fn end_mapping(&mut self, index: usize) {
+        self.mappings[index].rust_range.end = self.len;
+    }
+
+    /// The span mappings of everything written, unless writing failed.
+    pub fn finish(self) -> Result<Vec<SpanMapping>, TranspileError> {
+        if self.write_failed {
+            let message = "failed to write the generated Rust code";
+            return Err(TranspileError::new(vec![OxcDiagnostic::error(message)]));
+        }
+        Ok(self.mappings)
+    }
+}
+
+impl fmt::Write for RustWriter<'_> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.push_str(text);
+        if self.write_failed {
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Adapts an `io::Write` to `fmt::Write`, keeping the io error that `fmt::Error` can't carry.
+struct IoWriter<W> {
+    out: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.out.write_all(text.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Lets `write` stream its text into an `io::Write`, reporting the io error if writing fails.
+pub(crate) fn write_io<T>(
+    out: &mut impl io::Write,
+    write: impl FnOnce(&mut dyn fmt::Write) -> Result<T, TranspileError>,
+) -> Result<T, TranspileError> {
+    let mut writer = IoWriter { out, error: None };
+    let result = write(&mut writer);
+    match writer.error {
+        Some(err) => {
+            let message = format!("failed to write the generated code: {err}");
+            Err(TranspileError::new(vec![OxcDiagnostic::error(message)]))
+        }
+        None => result,
+    }
+}
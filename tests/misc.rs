@@ -0,0 +1,93 @@
+//! Compiles the programs in `misc` that have an expected output, a `.out` file next to them
+//! with what node prints, and checks that they print the same.
+//!
+//! The expected output of a new program is made with `node misc/<name>.js > misc/<name>.out`.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use jsrs::{Runtime, TranspileOptions, Transpiler};
+
+#[test]
+fn misc_programs() {
+    let misc_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("misc");
+    let mut expected_paths = fs::read_dir(&misc_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "out"))
+        .collect::<Vec<_>>();
+    expected_paths.sort();
+    assert!(!expected_paths.is_empty());
+
+    let transpiler = Transpiler::new(TranspileOptions::default().with_runtime(Runtime::Inline));
+    let dir = env::temp_dir().join(format!("jsrs-misc-{}", process::id()));
+    let mut failures = Vec::new();
+    for expected_path in &expected_paths {
+        let source_path = expected_path.with_extension("js");
+        let expected = fs::read_to_string(expected_path).unwrap();
+        match run(&transpiler, &source_path, &dir) {
+            Ok(actual) if actual == expected => {}
+            Ok(actual) => failures.push(format!(
+                "{} printed:\n{actual}\ninstead of:\n{expected}",
+                source_path.display()
+            )),
+            Err(error) => failures.push(format!("{}: {error}", source_path.display())),
+        }
+    }
+    let _ = fs::remove_dir_all(&dir);
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} programs failed\n\n{}",
+        failures.len(),
+        expected_paths.len(),
+        failures.join("\n\n")
+    );
+}
+
+/// Transpiles and compiles the program in `dir`, returning what it printed to stdout.
+fn run(transpiler: &Transpiler, source_path: &Path, dir: &Path) -> Result<String, String> {
+    let output = transpiler
+        .transpile_file(source_path)
+        .map_err(|err| err.to_string())?;
+    let executable_path = compile(&output.rust_text, dir)?;
+    let output = Command::new(&executable_path)
+        .output()
+        .map_err(|err| format!("failed to start {}: {err}", executable_path.display()))?;
+    if !output.status.success() {
+        return Err(format!(
+            "the program failed ({})\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Like `jsrs run` does, without optimizations to keep the compile times short
+fn compile(rust_text: &str, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| format!("failed to create {}: {err}", dir.display()))?;
+    let source_path = dir.join("main.rs");
+    fs::write(&source_path, rust_text)
+        .map_err(|err| format!("failed to write {}: {err}", source_path.display()))?;
+
+    let executable_path = dir.join(format!("main{}", env::consts::EXE_SUFFIX));
+    let output = Command::new("rustc")
+        .args(["--edition", "2021", "--crate-name", "main"])
+        .args(["-A", "warnings"])
+        .arg(&source_path)
+        .arg("-o")
+        .arg(&executable_path)
+        .output()
+        .map_err(|err| format!("failed to start rustc: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "rustc failed to compile the generated program\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok(executable_path)
+}
@@ -0,0 +1,51 @@
+//! Creates projects with `jsrs new-project` and checks that Cargo builds and runs them.
+
+use std::{
+    env, fs,
+    path::Path,
+    process::{self, Command},
+};
+
+#[test]
+fn crate_runtime_project() {
+    run_project("crate");
+}
+
+#[test]
+fn inline_runtime_project() {
+    run_project("inline");
+}
+
+/// Creates a project from `misc/shared-variables.js` with the runtime `mode`, and checks that it prints
+/// what node does.
+fn run_project(mode: &str) {
+    let misc_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("misc");
+    let project_dir = env::temp_dir().join(format!("jsrs-project-{mode}-{}", process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_jsrs"))
+        .args(["new-project", "--runtime", mode])
+        .arg(misc_dir.join("shared-variables.js"))
+        .arg(&project_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = Command::new(env!("CARGO"))
+        .args(["run", "--quiet", "--offline", "--manifest-path"])
+        .arg(project_dir.join("Cargo.toml"))
+        // Not the target directory of jsrs, which the test run may have locked
+        .env("CARGO_TARGET_DIR", project_dir.join("target"))
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&project_dir);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let expected = fs::read_to_string(misc_dir.join("shared-variables.out")).unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expected);
+}